@@ -0,0 +1 @@
+"""A tiny command line todo list."""
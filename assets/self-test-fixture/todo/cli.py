@@ -0,0 +1,24 @@
+import argparse
+
+from todo.store import TodoStore
+
+
+def main(argv=None):
+    parser = argparse.ArgumentParser(prog="todo")
+    commands = parser.add_subparsers(dest="command", required=True)
+    add = commands.add_parser("add", help="add a new item")
+    add.add_argument("title")
+    done = commands.add_parser("done", help="mark an item as done")
+    done.add_argument("index", type=int)
+    commands.add_parser("list", help="list all items")
+    args = parser.parse_args(argv)
+
+    store = TodoStore.load()
+    if args.command == "add":
+        store.add(args.title)
+    elif args.command == "done":
+        store.complete(args.index)
+    for index, item in enumerate(store.items):
+        mark = "x" if item.done else " "
+        print(f"{index}. [{mark}] {item.title}")
+    store.save()
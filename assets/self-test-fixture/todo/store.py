@@ -0,0 +1,33 @@
+import json
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+
+DEFAULT_PATH = Path.home() / ".todo.json"
+
+
+@dataclass
+class TodoItem:
+    title: str
+    done: bool = False
+
+
+@dataclass
+class TodoStore:
+    path: Path = DEFAULT_PATH
+    items: list = field(default_factory=list)
+
+    @classmethod
+    def load(cls, path=DEFAULT_PATH):
+        if not path.exists():
+            return cls(path=path)
+        items = [TodoItem(**item) for item in json.loads(path.read_text())]
+        return cls(path=path, items=items)
+
+    def add(self, title):
+        self.items.append(TodoItem(title=title))
+
+    def complete(self, index):
+        self.items[index].done = True
+
+    def save(self):
+        self.path.write_text(json.dumps([asdict(item) for item in self.items], indent=2))
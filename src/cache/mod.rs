@@ -7,6 +7,7 @@ use tokio::fs;
 
 use crate::config::CacheConfig;
 use crate::llm::client::types::TokenUsage;
+use crate::utils::file_read_limiter::FileReadLimiter;
 
 pub mod performance_monitor;
 pub use performance_monitor::{CachePerformanceMonitor, CachePerformanceReport};
@@ -53,14 +54,30 @@ impl CacheManager {
             .join(format!("{}.json", hash))
     }
 
-    /// 检查缓存是否过期
-    fn is_expired(&self, timestamp: u64) -> bool {
+    /// 检查缓存是否过期，优先使用类别单独配置的过期时间
+    fn is_expired(&self, category: &str, timestamp: u64) -> bool {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        let expire_seconds = self.config.expire_hours * 3600;
-        now - timestamp > expire_seconds
+        now.saturating_sub(timestamp) > self.expire_hours_for(category) * 3600
+    }
+
+    /// 类别的过期时间：取在`/`分隔处匹配的最长配置类别（类别自身或其上级类别），
+    /// 使`studies_research`的配置覆盖`studies_research/<agent>/<fingerprint>`下的缓存
+    fn expire_hours_for(&self, category: &str) -> u64 {
+        self.config
+            .category_expire_hours
+            .iter()
+            .filter(|(configured, _)| {
+                category == configured.as_str()
+                    || category
+                        .strip_prefix(configured.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .max_by_key(|(configured, _)| configured.len())
+            .map(|(_, hours)| *hours)
+            .unwrap_or(self.config.expire_hours)
     }
 
     /// 获取缓存
@@ -80,11 +97,14 @@ impl CacheManager {
             return Ok(None);
         }
 
-        match fs::read_to_string(&cache_path).await {
+        match FileReadLimiter::global()
+            .run(fs::read_to_string(&cache_path))
+            .await
+        {
             Ok(content) => {
                 match serde_json::from_str::<CacheEntry<T>>(&content) {
                     Ok(entry) => {
-                        if self.is_expired(entry.timestamp) {
+                        if self.is_expired(category, entry.timestamp) {
                             // 删除过期缓存
                             let _ = fs::remove_file(&cache_path).await;
                             self.performance_monitor.record_cache_miss(category);
@@ -192,11 +212,19 @@ impl CacheManager {
         original_content: &str,
         content_type: &str,
         compressed_content: String,
+        token_usage: TokenUsage,
     ) -> Result<()> {
         let cache_key = format!("{}_{}", content_type, self.hash_prompt(original_content));
-        self.set("prompt_compression", &cache_key, compressed_content)
-            .await
+        self.set_with_tokens(
+            "prompt_compression",
+            &cache_key,
+            compressed_content,
+            token_usage,
+        )
+        .await
     }
+
+    /// 设置缓存（不含token信息，仅用于非模型推理产生的数据，如项目结构）
     pub async fn set<T>(&self, category: &str, prompt: &str, data: T) -> Result<()>
     where
         T: Serialize,
@@ -246,6 +274,24 @@ impl CacheManager {
         }
     }
 
+    /// 清除指定类别的全部缓存，类别可以是缓存目录下的多级路径，返回是否存在被清除的缓存
+    pub async fn clear_category(&self, category: &str) -> Result<bool> {
+        let category_dir = self.config.cache_dir.join(category);
+        if !category_dir.is_dir() {
+            return Ok(false);
+        }
+        fs::remove_dir_all(&category_dir).await?;
+        Ok(true)
+    }
+
+    /// 清除全部缓存
+    pub async fn clear_all(&self) -> Result<()> {
+        if self.config.cache_dir.is_dir() {
+            fs::remove_dir_all(&self.config.cache_dir).await?;
+        }
+        Ok(())
+    }
+
     /// 估算推理时间（基于内容复杂度）
     fn estimate_inference_time(&self, content: &str) -> Duration {
         // 基于内容长度估算推理时间
@@ -262,3 +308,168 @@ impl CacheManager {
         self.performance_monitor.generate_report()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema, PartialEq)]
+    struct ExtractProbe {
+        summary: String,
+    }
+
+    #[tokio::test]
+    async fn test_cached_extract_entry_has_token_usage() {
+        use crate::config::{Config, LLMProvider};
+        use crate::generator::agent_executor::{self, AgentExecuteParams};
+        use crate::generator::context::GeneratorContext;
+        use crate::llm::client::mock_server::{
+            MOCK_COMPLETION_TOKENS, MOCK_PROMPT_TOKENS, MockServer,
+        };
+
+        let server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.llm.provider = LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = server.api_base_url.clone();
+        config.cache = CacheConfig {
+            enabled: true,
+            cache_dir: temp_dir.path().to_path_buf(),
+            expire_hours: 1,
+            category_expire_hours: HashMap::new(),
+        };
+        let context = GeneratorContext::new(config).unwrap();
+        let params = || AgentExecuteParams {
+            prompt_sys: "system prompt".to_string(),
+            prompt_user: "user prompt".to_string(),
+            cache_scope: "ai_code_insight".to_string(),
+            log_tag: "probe".to_string(),
+        };
+
+        let reply: ExtractProbe = agent_executor::extract(&context, params()).await.unwrap();
+        assert_eq!(reply.summary, "mock");
+        assert_eq!(server.requests().len(), 1);
+
+        // 缓存条目记录的是provider实际上报的token用量
+        let cache_dir = temp_dir.path().join("ai_code_insight");
+        let cache_file = std::fs::read_dir(&cache_dir)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let content = std::fs::read_to_string(cache_file).unwrap();
+        let entry: CacheEntry<serde_json::Value> = serde_json::from_str(&content).unwrap();
+        let token_usage = entry.token_usage.expect("token_usage should be recorded");
+        assert_eq!(token_usage.input_tokens, MOCK_PROMPT_TOKENS);
+        assert_eq!(token_usage.output_tokens, MOCK_COMPLETION_TOKENS);
+        assert_eq!(
+            token_usage.total_tokens,
+            MOCK_PROMPT_TOKENS + MOCK_COMPLETION_TOKENS
+        );
+
+        // 再次提取命中缓存，不再请求模型服务，节约的token即上次的真实用量
+        let cached: ExtractProbe = agent_executor::extract(&context, params()).await.unwrap();
+        assert_eq!(cached, reply);
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(
+            context
+                .cache_manager
+                .read()
+                .await
+                .generate_performance_report()
+                .input_tokens_saved,
+            MOCK_PROMPT_TOKENS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_category_ttl_overrides_global_expiry() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_manager = CacheManager::new(CacheConfig {
+            enabled: true,
+            cache_dir: temp_dir.path().to_path_buf(),
+            expire_hours: 24,
+            category_expire_hours: HashMap::from([
+                ("ai_prompt".to_string(), 1),
+                ("structure".to_string(), 720),
+            ]),
+        });
+
+        // 写入两小时前的缓存条目
+        let two_hours_ago = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - 2 * 3600;
+        for category in ["ai_prompt", "structure"] {
+            let entry = CacheEntry {
+                data: category.to_string(),
+                timestamp: two_hours_ago,
+                prompt_hash: cache_manager.hash_prompt("prompt"),
+                token_usage: None,
+                model_name: None,
+            };
+            let cache_path = cache_manager.get_cache_path(category, &entry.prompt_hash);
+            std::fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+            std::fs::write(&cache_path, serde_json::to_string(&entry).unwrap()).unwrap();
+        }
+
+        let short_ttl: Option<String> = cache_manager.get("ai_prompt", "prompt").await.unwrap();
+        let long_ttl: Option<String> = cache_manager.get("structure", "prompt").await.unwrap();
+
+        assert!(short_ttl.is_none());
+        assert_eq!(long_ttl.as_deref(), Some("structure"));
+    }
+
+    #[tokio::test]
+    async fn test_category_ttl_applies_to_fingerprinted_subcategories() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_manager = CacheManager::new(CacheConfig {
+            enabled: true,
+            cache_dir: temp_dir.path().to_path_buf(),
+            expire_hours: 24,
+            category_expire_hours: HashMap::from([
+                ("studies_research".to_string(), 1),
+                ("documentation".to_string(), 1),
+                ("documentation/概览".to_string(), 720),
+            ]),
+        });
+
+        let two_hours_ago = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - 2 * 3600;
+        let categories = [
+            "studies_research/领域模块调研报告/3f2a9c",
+            "documentation/概览/b71e04",
+            "studies_research_archive",
+        ];
+        for category in categories {
+            let entry = CacheEntry {
+                data: category.to_string(),
+                timestamp: two_hours_ago,
+                prompt_hash: cache_manager.hash_prompt("prompt"),
+                token_usage: None,
+                model_name: None,
+            };
+            let cache_path = cache_manager.get_cache_path(category, &entry.prompt_hash);
+            std::fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+            std::fs::write(&cache_path, serde_json::to_string(&entry).unwrap()).unwrap();
+        }
+
+        // 带提示词指纹的调研缓存使用studies_research的过期时间
+        let research: Option<String> = cache_manager.get(categories[0], "prompt").await.unwrap();
+        assert!(research.is_none());
+        // 更长的匹配类别优先
+        let overview: Option<String> = cache_manager.get(categories[1], "prompt").await.unwrap();
+        assert_eq!(overview.as_deref(), Some(categories[1]));
+        // 只在`/`分隔处匹配，未配置的类别使用全局过期时间
+        let archive: Option<String> = cache_manager.get(categories[2], "prompt").await.unwrap();
+        assert_eq!(archive.as_deref(), Some(categories[2]));
+    }
+}
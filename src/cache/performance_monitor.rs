@@ -5,6 +5,7 @@ use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::time::Duration;
 
 use crate::llm::client::types::TokenUsage;
+use log::{error, info};
 
 /// 缓存性能监控器
 #[derive(Clone)]
@@ -127,7 +128,7 @@ impl CachePerformanceMonitor {
                 .fetch_add(inference_time_saved.as_millis() as u64, Ordering::Relaxed);
         }
 
-        println!(
+        info!(
             "   💰 缓存命中 [{}] - 节省推理时间: {:.2}秒, 节省tokens: {}输入+{}输出, 估算节省成本: ${:.4}",
             category,
             inference_time_saved.as_secs_f64(),
@@ -147,19 +148,19 @@ impl CachePerformanceMonitor {
             category_metrics.misses.fetch_add(1, Ordering::Relaxed);
         }
 
-        println!("   ⌛ 缓存未命中 [{}] - 需要进行AI推理", category);
+        info!("   ⌛ 缓存未命中 [{}] - 需要进行AI推理", category);
     }
 
     /// 记录缓存写入
     pub fn record_cache_write(&self, category: &str) {
         self.metrics.cache_writes.fetch_add(1, Ordering::Relaxed);
-        println!("   💾 缓存写入 [{}] - 结果已缓存", category);
+        info!("   💾 缓存写入 [{}] - 结果已缓存", category);
     }
 
     /// 记录缓存错误
     pub fn record_cache_error(&self, category: &str, error: &str) {
         self.metrics.cache_errors.fetch_add(1, Ordering::Relaxed);
-        eprintln!("   ❌ 缓存错误 [{}]: {}", category, error);
+        error!("   ❌ 缓存错误 [{}]: {}", category, error);
     }
 
     /// 生成性能报告
@@ -0,0 +1,236 @@
+use crate::config::{Config, LLMProvider};
+use crate::generator::outlet::check_output_path_safety;
+use crate::llm::client::LLMClient;
+use crate::llm::client::embedding::default_embedding_model;
+use anyhow::{Result, anyhow};
+use log::{error, info};
+use std::fs;
+
+/// 单项检查结果
+#[derive(Debug)]
+pub struct CheckItem {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// `check` 子命令的检查报告
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    pub items: Vec<CheckItem>,
+}
+
+impl CheckReport {
+    pub(crate) fn push(&mut self, name: &'static str, result: Result<String>) {
+        let item = match result {
+            Ok(detail) => CheckItem {
+                name,
+                passed: true,
+                detail,
+            },
+            Err(e) => CheckItem {
+                name,
+                passed: false,
+                detail: e.to_string(),
+            },
+        };
+        self.items.push(item);
+    }
+
+    /// 所有检查项是否均通过
+    pub fn passed(&self) -> bool {
+        self.items.iter().all(|item| item.passed)
+    }
+
+    /// 进程退出码，存在失败项时返回非零
+    pub fn exit_code(&self) -> i32 {
+        if self.passed() { 0 } else { 1 }
+    }
+
+    /// 输出检查汇总
+    pub fn print_summary(&self) {
+        info!("\n📋 检查结果汇总");
+        for item in &self.items {
+            if item.passed {
+                info!("   ✅ {}: {}", item.name, item.detail);
+            } else {
+                error!("   ❌ {}: {}", item.name, item.detail);
+            }
+        }
+        if self.passed() {
+            info!("✅ 全部检查通过");
+        } else {
+            error!("❌ 检查未通过");
+        }
+    }
+}
+
+/// 校验配置、输出目录可写性与模型连接，不执行任何分析
+pub async fn run(config: &Config) -> CheckReport {
+    let mut report = CheckReport::default();
+
+    let config_result = validate_config(config);
+    let config_valid = config_result.is_ok();
+    report.push("配置校验", config_result);
+    report.push("输出目录", check_output_writable(config));
+
+    // 配置无效时跳过连接检查，避免使用错误的地址发起请求；离线模式不会调用模型
+    let connection_result = if config.llm.offline {
+        Ok("离线模式，跳过模型连接检查".to_string())
+    } else if config_valid {
+        check_connection(config).await
+    } else {
+        Err(anyhow!("配置校验未通过，跳过模型连接检查"))
+    };
+    report.push("模型连接", connection_result);
+
+    report.print_summary();
+    report
+}
+
+fn validate_config(config: &Config) -> Result<String> {
+    if !config.project_path.is_dir() {
+        return Err(anyhow!(
+            "项目路径不存在或不是目录: {}",
+            config.project_path.display()
+        ));
+    }
+
+    if config.timezone.trim().parse::<chrono_tz::Tz>().is_err() {
+        return Err(anyhow!(
+            "时区无效: {}，应为IANA时区名称（如 Asia/Shanghai）",
+            config.timezone
+        ));
+    }
+
+    // 离线模式不会调用模型，无需校验模型相关配置
+    let llm = &config.llm;
+    if llm.offline {
+        return Ok("离线模式".to_string());
+    }
+    if llm.provider != LLMProvider::Ollama && llm.api_key.trim().is_empty() {
+        return Err(anyhow!(
+            "未配置LLM API KEY，请通过 --llm-api-key 或 LITHO_LLM_API_KEY 设置"
+        ));
+    }
+    if !llm.api_base_url.starts_with("http://") && !llm.api_base_url.starts_with("https://") {
+        return Err(anyhow!(
+            "LLM API基地址无效: {}，应以 http:// 或 https:// 开头",
+            llm.api_base_url
+        ));
+    }
+    if llm.model_efficient.trim().is_empty() || llm.model_powerful.trim().is_empty() {
+        return Err(anyhow!("未配置模型名称"));
+    }
+    if llm.max_parallels == 0 {
+        return Err(anyhow!("max_parallels 必须大于0"));
+    }
+    if config.detect_duplicates {
+        if llm.embedding_model.is_none() && default_embedding_model(&llm.provider).is_none() {
+            return Err(anyhow!(
+                "provider {} 不支持向量嵌入，无法启用重复代码检测",
+                llm.provider
+            ));
+        }
+        if !(0.0..=1.0).contains(&config.duplicate_similarity_threshold) {
+            return Err(anyhow!(
+                "重复代码相似度阈值无效: {}，应在0到1之间",
+                config.duplicate_similarity_threshold
+            ));
+        }
+    }
+
+    Ok(format!(
+        "provider={}, model={}",
+        llm.provider, llm.model_efficient
+    ))
+}
+
+fn check_output_writable(config: &Config) -> Result<String> {
+    let output_path = &config.output_path;
+    if !config.allow_unsafe_output_path {
+        check_output_path_safety(output_path, &config.project_path)?;
+    }
+
+    let created = !output_path.exists();
+    fs::create_dir_all(output_path)
+        .map_err(|e| anyhow!("无法创建输出目录 {}: {}", output_path.display(), e))?;
+
+    let probe_path = output_path.join(".litho-check");
+    let write_result = fs::write(&probe_path, b"ok");
+    let _ = fs::remove_file(&probe_path);
+    if created {
+        let _ = fs::remove_dir(output_path);
+    }
+
+    write_result.map_err(|e| anyhow!("输出目录不可写 {}: {}", output_path.display(), e))?;
+    Ok(format!("{} 可写", output_path.display()))
+}
+
+async fn check_connection(config: &Config) -> Result<String> {
+    let llm_client = LLMClient::new(config.clone())?;
+    llm_client.check_connection().await?;
+    Ok(format!("{} 连接正常", config.llm.api_base_url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(temp_dir: &TempDir) -> Config {
+        let mut config = Config {
+            project_path: temp_dir.path().to_path_buf(),
+            output_path: temp_dir.path().join("litho.docs"),
+            internal_path: temp_dir.path().join(".litho"),
+            ..Default::default()
+        };
+        config.llm.api_key = "test-key".to_string();
+        config.llm.retry_attempts = 1;
+        config.llm.retry_delay_ms = 0;
+        config
+    }
+
+    #[tokio::test]
+    async fn test_bad_api_base_url_yields_non_zero_exit() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        config.llm.api_base_url = "not-a-url".to_string();
+
+        let report = run(&config).await;
+
+        assert!(!report.passed());
+        assert_ne!(report.exit_code(), 0);
+        assert!(!config.output_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_api_base_url_yields_non_zero_exit() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        // 本地端口未监听，连接会被立即拒绝
+        config.llm.api_base_url = "http://127.0.0.1:9/v1".to_string();
+
+        let report = run(&config).await;
+
+        let connection = report.items.iter().find(|i| i.name == "模型连接").unwrap();
+        assert!(!connection.passed);
+        assert_ne!(report.exit_code(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_offline_skips_model_checks_but_no_tools_does_not() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        config.llm.api_base_url = "not-a-url".to_string();
+
+        // 仅禁用工具时仍会调用模型，模型配置需要有效
+        config.llm.disable_preset_tools = true;
+        assert!(!run(&config).await.passed());
+
+        config.llm.offline = true;
+        let report = run(&config).await;
+        assert!(report.passed());
+        assert_eq!(report.exit_code(), 0);
+    }
+}
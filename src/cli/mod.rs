@@ -1,7 +1,15 @@
-use crate::config::{Config, LLMProvider};
+use crate::config::{Config, DocumentProfile, LLMProvider, LogLevel};
 use crate::i18n::TargetLanguage;
-use clap::Parser;
-use std::path::PathBuf;
+use crate::utils::status_text;
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+
+pub mod check;
+pub mod progress;
+pub mod self_test;
+
+/// 默认配置文件名
+const DEFAULT_CONFIG_FILE_NAME: &str = "litho.toml";
 
 /// DeepWiki-RS - 由Rust与AI驱动的项目知识库生成引擎
 #[derive(Parser, Debug)]
@@ -12,7 +20,11 @@ use std::path::PathBuf;
 #[command(author = "Sopaco")]
 #[command(version)]
 pub struct Args {
-    /// 项目路径
+    /// 子命令，不指定时执行文档生成
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// 项目路径，也可以是 .zip / .tar.gz 源码归档
     #[arg(short, long, default_value = ".")]
     pub project_path: PathBuf,
 
@@ -40,10 +52,26 @@ pub struct Args {
     #[arg(long)]
     pub skip_documentation: bool,
 
+    /// 文档集预设：full（全部文档）、executive（仅项目概述）、contributor（架构、快速上手与API文档）
+    #[arg(long, value_name = "PROFILE")]
+    pub profile: Option<String>,
+
     /// 是否启用详细日志
     #[arg(short, long)]
     pub verbose: bool,
 
+    /// 静默模式，仅输出警告和错误信息
+    #[arg(short, long, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// 日志级别 (error, warn, info, debug)
+    #[arg(long)]
+    pub log_level: Option<String>,
+
+    /// 状态输出使用纯ASCII标记代替emoji（也可通过NO_COLOR环境变量启用）
+    #[arg(long)]
+    pub no_emoji: bool,
+
     /// 高能效模型，优先用于Litho引擎的常规推理任务
     #[arg(long)]
     pub model_efficient: Option<String>,
@@ -72,6 +100,22 @@ pub struct Args {
     #[arg(long)]
     pub max_parallels: Option<usize>,
 
+    /// LLM调用成本上限（美元），累计估算成本超出后中止运行
+    #[arg(long)]
+    pub max_cost_usd: Option<f64>,
+
+    /// ReAct模式下智能体调用工具的最大迭代次数
+    #[arg(long)]
+    pub react_max_iterations: Option<usize>,
+
+    /// 达到最大迭代次数时不再进行总结推理，直接返回部分结果
+    #[arg(long)]
+    pub disable_summary_reasoning: bool,
+
+    /// 输出ReAct循环的详细日志
+    #[arg(long)]
+    pub react_verbose: bool,
+
     /// LLM Provider (openai, mistral, openrouter, anthropic, deepseek)
     #[arg(long)]
     pub llm_provider: Option<String>,
@@ -80,17 +124,95 @@ pub struct Args {
     #[arg(long)]
     pub target_language: Option<String>,
 
-    /// 生成报告后,自动使用报告助手查看报告
-    #[arg(long, default_value = "false", action = clap::ArgAction::SetTrue)]
+    /// 目标语言缺少章节标题等固定文案的译文时使用的回退语言（默认 en）
+    #[arg(long)]
+    pub output_language_fallback: Option<String>,
+
+    /// 生成文档与时间工具使用的时区，IANA名称（如 Asia/Shanghai，默认 UTC）
+    #[arg(long, value_name = "TZ")]
+    pub timezone: Option<String>,
+
+    /// 禁用ReAct预设工具（文件浏览、文件读取等），LLM仅基于提示词中的上下文生成文档
+    #[arg(long, visible_alias = "no-tools", default_value = "false", action = clap::ArgAction::SetTrue)]
     pub disable_preset_tools: bool,
 
+    /// 离线模式：跳过所有LLM调用，仅执行静态分析
+    #[arg(long, visible_alias = "no-llm")]
+    pub offline: bool,
+
     /// 是否禁用缓存
     #[arg(long)]
     pub no_cache: bool,
 
-    /// 强制重新生成（清除缓存）
+    /// 强制重新生成（清除缓存），可指定范围：all、preprocess、research、compose 或逗号分隔的智能体类型
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "all")]
+    pub force_regenerate: Option<String>,
+
+    /// 源码仓库地址，用于将文档中的文件引用转换为源码链接
     #[arg(long)]
-    pub force_regenerate: bool,
+    pub source_repo_url: Option<String>,
+
+    /// 生成源码链接使用的分支
+    #[arg(long)]
+    pub source_branch: Option<String>,
+
+    /// 单个文档超过该字节数时拆分为多个分页文件
+    #[arg(long)]
+    pub split_document_threshold: Option<usize>,
+
+    /// 额外的脱敏正则表达式（可重复指定），输出文档中匹配的内容替换为[REDACTED]
+    #[arg(long = "redact", value_name = "PATTERN")]
+    pub redact_patterns: Vec<String>,
+
+    /// 不修复输出文档中列数不一致、缺少表头分隔行的Markdown表格
+    #[arg(long)]
+    pub no_table_repair: bool,
+
+    /// 从测试文件中挖掘公共接口的调用示例，附加到API参考文档中
+    #[arg(long)]
+    pub mine_test_examples: bool,
+
+    /// 系统上下文调研完成后，提升被调研结果反复提及的文件的重要性评分
+    #[arg(long)]
+    pub recalibrate_importance: bool,
+
+    /// 基于向量嵌入识别疑似重复的核心文件，在代码索引文档中输出潜在重复章节（会产生额外的嵌入调用成本）
+    #[arg(long)]
+    pub detect_duplicates: bool,
+
+    /// 使用LLM重新生成静态修复后仍无效的Mermaid图表（会产生额外的调用成本）
+    #[arg(long)]
+    pub llm_mermaid_repair: bool,
+
+    /// 确认允许清理危险的输出目录（项目根目录、用户主目录或文件系统根目录）
+    #[arg(long)]
+    pub allow_unsafe_output_path: bool,
+
+    /// 仅为自指定git引用（如上一个发布标签）以来变更的文件生成文档，输出到独立的子目录
+    #[arg(long, value_name = "GIT_REF")]
+    pub since: Option<String>,
+
+    /// 运行结束后将内存中的全部数据（作用域、键、大小与内容）导出为JSON文件，用于调试
+    #[arg(long, value_name = "PATH")]
+    pub dump_memory: Option<PathBuf>,
+
+    /// 从上次（中断的）运行保存的内存快照恢复，跳过已有结果的调研与文档生成步骤
+    #[arg(long)]
+    pub resume: bool,
+
+    /// 可复现模式：温度设为0、固定遍历顺序，时间戳取SOURCE_DATE_EPOCH（未设置时省略），相同输入与缓存的两次运行产生完全相同的文档（用于测试/CI）
+    #[arg(long)]
+    pub deterministic: bool,
+}
+
+/// 子命令
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// 校验配置、输出目录可写性与模型连接，不执行任何分析
+    Check,
+    /// 使用内置的示例项目完整运行一次文档生成流程，校验每个预期文档均已生成
+    #[command(hide = true)]
+    AnalyzeSelf,
 }
 
 impl Args {
@@ -98,17 +220,18 @@ impl Args {
     pub fn into_config(self) -> Config {
         let mut config = if let Some(config_path) = &self.config {
             // 如果显式指定了配置文件路径，从该路径加载
-            return Config::from_file(config_path).unwrap_or_else(|_| {
+            return load_config_file(config_path).unwrap_or_else(|_| {
                 panic!("⚠️ 警告: 无法读取配置文件 {:?}，使用默认配置", config_path)
             });
         } else {
-            // 如果没有显式指定配置文件，尝试从默认位置加载
-            let default_config_path = std::env::current_dir()
-                .unwrap_or_else(|_| std::path::PathBuf::from("."))
-                .join("litho.toml");
+            // 如果没有显式指定配置文件，从当前目录和项目目录向上查找最近的litho.toml
+            let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            let project_dir = current_dir.join(&self.project_path);
 
-            if default_config_path.exists() {
-                return Config::from_file(&default_config_path).unwrap_or_else(|_| {
+            if let Some(default_config_path) =
+                find_config_file(&current_dir).or_else(|| find_config_file(&project_dir))
+            {
+                return load_config_file(&default_config_path).unwrap_or_else(|_| {
                     panic!(
                         "⚠️ 警告: 无法读取默认配置文件 {:?}，使用默认配置",
                         default_config_path
@@ -120,6 +243,9 @@ impl Args {
             }
         };
 
+        // 日志系统尚未初始化，配置警告直接输出到stderr
+        let plain_status = self.no_emoji || config.plain_status_output();
+
         // 覆盖配置文件中的设置
         config.project_path = self.project_path.clone();
         config.output_path = self.output_path;
@@ -135,9 +261,12 @@ impl Args {
             if let Ok(provider) = provider_str.parse::<LLMProvider>() {
                 config.llm.provider = provider;
             } else {
-                eprintln!(
-                    "⚠️ 警告: 未知的provider: {}，使用默认provider",
-                    provider_str
+                print_warning(
+                    &format!(
+                        "⚠️ 警告: 未知的provider: {}，使用默认provider",
+                        provider_str
+                    ),
+                    plain_status,
                 );
             }
         }
@@ -164,16 +293,61 @@ impl Args {
         if let Some(max_parallels) = self.max_parallels {
             config.llm.max_parallels = max_parallels;
         }
-        config.llm.disable_preset_tools = self.disable_preset_tools;
+        if let Some(max_cost_usd) = self.max_cost_usd {
+            config.llm.max_cost_usd = Some(max_cost_usd);
+        }
+        if let Some(react_max_iterations) = self.react_max_iterations {
+            config.llm.react_max_iterations = react_max_iterations;
+        }
+        if self.disable_summary_reasoning {
+            config.llm.enable_summary_reasoning = false;
+        }
+        if self.react_verbose {
+            config.llm.react_verbose = true;
+        }
+        if self.disable_preset_tools {
+            config.llm.disable_preset_tools = true;
+        }
+        if self.offline {
+            config.llm.offline = true;
+        }
 
         // 目标语言配置
         if let Some(target_language_str) = self.target_language {
             if let Ok(target_language) = target_language_str.parse::<TargetLanguage>() {
                 config.target_language = target_language;
             } else {
-                eprintln!(
-                    "⚠️ 警告: 未知的目标语言: {}，使用默认语言 (English)",
-                    target_language_str
+                print_warning(
+                    &format!(
+                        "⚠️ 警告: 未知的目标语言: {}，使用默认语言 (English)",
+                        target_language_str
+                    ),
+                    plain_status,
+                );
+            }
+        }
+
+        if let Some(fallback_str) = self.output_language_fallback {
+            if let Ok(fallback) = fallback_str.parse::<TargetLanguage>() {
+                config.output_language_fallback = fallback;
+            } else {
+                print_warning(
+                    &format!(
+                        "⚠️ 警告: 未知的回退语言: {}，使用默认回退语言 (English)",
+                        fallback_str
+                    ),
+                    plain_status,
+                );
+            }
+        }
+
+        if let Some(timezone) = self.timezone {
+            if timezone.parse::<chrono_tz::Tz>().is_ok() {
+                config.timezone = timezone;
+            } else {
+                print_warning(
+                    &format!("⚠️ 警告: 未知的时区: {}，使用默认时区 (UTC)", timezone),
+                    plain_status,
                 );
             }
         }
@@ -184,16 +358,135 @@ impl Args {
         }
 
         // 其他配置
-        config.force_regenerate = self.force_regenerate;
+        config.force_regenerate = self.force_regenerate.is_some();
+        if let Some(scope) = self.force_regenerate {
+            config.force_regenerate_scope = scope;
+        }
+        config.allow_unsafe_output_path = self.allow_unsafe_output_path;
+        config.mine_test_examples = self.mine_test_examples;
+        if self.recalibrate_importance {
+            config.recalibrate_importance = true;
+        }
+        if self.detect_duplicates {
+            config.detect_duplicates = true;
+        }
+        config.llm_mermaid_repair = self.llm_mermaid_repair;
+        if let Some(source_repo_url) = self.source_repo_url {
+            config.source_repo_url = Some(source_repo_url);
+        }
+        if let Some(source_branch) = self.source_branch {
+            config.source_branch = Some(source_branch);
+        }
+        if let Some(threshold) = self.split_document_threshold {
+            config.split_document_threshold = Some(threshold);
+        }
+        config.redact_patterns.extend(self.redact_patterns);
+        if self.no_table_repair {
+            config.repair_markdown_tables = false;
+        }
         config.skip_preprocessing = self.skip_preprocessing;
         config.skip_research = self.skip_research;
         config.skip_documentation = self.skip_documentation;
+        if let Some(profile_str) = self.profile {
+            if let Ok(profile) = profile_str.parse::<DocumentProfile>() {
+                config.profile = profile;
+            } else {
+                print_warning(
+                    &format!(
+                        "⚠️ 警告: 未知的文档集预设: {}，生成全部文档 (full)",
+                        profile_str
+                    ),
+                    plain_status,
+                );
+            }
+        }
         config.verbose = self.verbose;
 
+        // 日志级别配置，--quiet优先于--log-level
+        if let Some(log_level_str) = self.log_level {
+            if let Ok(log_level) = log_level_str.parse::<LogLevel>() {
+                config.log_level = log_level;
+            } else {
+                print_warning(
+                    &format!(
+                        "⚠️ 警告: 未知的日志级别: {}，使用默认级别 (info)",
+                        log_level_str
+                    ),
+                    plain_status,
+                );
+            }
+        }
+        if self.quiet {
+            config.log_level = LogLevel::Warn;
+        }
+        if self.no_emoji {
+            config.no_emoji = true;
+        }
+
+        // 变更范围文档输出到独立子目录，避免覆盖完整文档
+        if let Some(git_ref) = self.since {
+            config.output_path = config.output_path.join(changes_since_dir_name(&git_ref));
+            config.since_ref = Some(git_ref);
+        }
+        if let Some(path) = self.dump_memory {
+            config.dump_memory_path = Some(path);
+        }
+        if self.deterministic {
+            config.deterministic = true;
+        }
+        if self.resume {
+            config.resume = true;
+        }
+
         config
     }
 }
 
+/// 变更范围文档的输出子目录名，git引用中的路径分隔符等字符替换为`-`
+fn changes_since_dir_name(git_ref: &str) -> String {
+    let sanitized: String = git_ref
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("changes-since-{}", sanitized)
+}
+
+/// 输出配置警告，按需将emoji替换为纯ASCII标记
+fn print_warning(message: &str, plain_status: bool) {
+    eprintln!("{}", status_text::render(message, plain_status));
+}
+
+/// 加载配置文件，配置文件仍按旧版本含义使用`disable_preset_tools`时提示迁移到`offline`
+fn load_config_file(path: &PathBuf) -> anyhow::Result<Config> {
+    let config = Config::from_file(path)?;
+    if std::fs::read_to_string(path)
+        .is_ok_and(|content| Config::has_legacy_disable_preset_tools(&content))
+    {
+        print_warning(
+            &format!(
+                "⚠️ 警告: 配置文件 {:?} 启用了 llm.disable_preset_tools，该选项现在只禁用预设工具，仍会调用LLM；如需跳过全部LLM调用，请改为设置 llm.offline = true",
+                path
+            ),
+            config.plain_status_output(),
+        );
+    }
+    Ok(config)
+}
+
+/// 从指定目录开始逐级向上查找 `litho.toml`，返回距离最近的一个
+pub fn find_config_file(start_dir: &Path) -> Option<PathBuf> {
+    start_dir
+        .ancestors()
+        .map(|dir| dir.join(DEFAULT_CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
 // Include tests
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,60 @@
+use crate::generator::events::{EventListener, GeneratorEvent};
+use log::{debug, info};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// 命令行进度报告器，在每个阶段结束时输出耗时与累计进度
+#[derive(Default)]
+pub struct ProgressReporter {
+    phase_started_at: Mutex<HashMap<String, Instant>>,
+    files_analyzed: AtomicUsize,
+    agents_finished: AtomicUsize,
+    cache_hits: AtomicUsize,
+}
+
+impl EventListener for ProgressReporter {
+    fn on_event(&self, event: &GeneratorEvent) {
+        match event {
+            GeneratorEvent::PhaseStarted { phase } => {
+                self.phase_started_at
+                    .lock()
+                    .unwrap()
+                    .insert(phase.clone(), Instant::now());
+            }
+            GeneratorEvent::PhaseFinished { phase } => {
+                let elapsed = self
+                    .phase_started_at
+                    .lock()
+                    .unwrap()
+                    .remove(phase)
+                    .map(|started_at| started_at.elapsed())
+                    .unwrap_or_default();
+                info!(
+                    "📊 [{}] 阶段完成，耗时 {:.1}秒 | 已分析文件 {} | 已完成智能体 {} | 缓存命中 {}",
+                    phase,
+                    elapsed.as_secs_f64(),
+                    self.files_analyzed.load(Ordering::Relaxed),
+                    self.agents_finished.load(Ordering::Relaxed),
+                    self.cache_hits.load(Ordering::Relaxed)
+                );
+            }
+            GeneratorEvent::AgentStarted { agent } => {
+                debug!("   ▶️ 智能体开始: {}", agent);
+            }
+            GeneratorEvent::AgentFinished { agent } => {
+                let finished = self.agents_finished.fetch_add(1, Ordering::Relaxed) + 1;
+                debug!("   ⏹️ 智能体完成: {} (累计 {})", agent, finished);
+            }
+            GeneratorEvent::FileAnalyzed { file_path } => {
+                let analyzed = self.files_analyzed.fetch_add(1, Ordering::Relaxed) + 1;
+                debug!("   📄 [{}] 已分析: {}", analyzed, file_path.display());
+            }
+            GeneratorEvent::CacheHit { category } => {
+                self.cache_hits.fetch_add(1, Ordering::Relaxed);
+                debug!("   💾 缓存命中: {}", category);
+            }
+        }
+    }
+}
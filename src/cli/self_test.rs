@@ -0,0 +1,150 @@
+use crate::cli::check::CheckReport;
+use crate::config::Config;
+use crate::generator::outlet::CORE_DOCUMENTS;
+use crate::generator::workflow::launch;
+use anyhow::{Result, anyhow};
+use include_dir::{Dir, include_dir};
+use log::info;
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// 内置的示例项目：一个简单的Python命令行待办事项工具
+static FIXTURE: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/assets/self-test-fixture");
+
+/// 自检时使用的项目名称
+const FIXTURE_PROJECT_NAME: &str = "litho-self-test";
+
+/// 使用内置示例项目完整运行一次文档生成流程，校验每个预期文档均已生成
+pub async fn run(config: &Config) -> CheckReport {
+    // 完整流程的future层级很深，装箱后避免外层future的布局计算超出编译器递归上限
+    run_with(
+        config,
+        |config| async move { Box::pin(launch(&config)).await },
+    )
+    .await
+}
+
+/// 使用指定的生成流程执行自检，生成流程接收指向示例项目与临时输出目录的配置
+pub async fn run_with<F, Fut>(config: &Config, generate: F) -> CheckReport
+where
+    F: FnOnce(Config) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut report = CheckReport::default();
+    info!("🧪 使用内置示例项目执行自检...");
+
+    // 工作目录在自检结束时自动清理
+    let workspace = match prepare_workspace() {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            report.push("示例项目", Err(e));
+            report.print_summary();
+            return report;
+        }
+    };
+    report.push(
+        "示例项目",
+        Ok(format!("已解压到 {}", workspace.path().display())),
+    );
+
+    let config = self_test_config(config, workspace.path());
+    let generated = generate(config.clone()).await;
+    let generated_ok = generated.is_ok();
+    report.push("文档生成", generated.map(|_| "流程执行完成".to_string()));
+
+    if generated_ok {
+        for (agent_type, doc_type) in CORE_DOCUMENTS {
+            if !config.profile.includes(&agent_type) {
+                continue;
+            }
+            let path = config
+                .output_path
+                .join(config.target_language.get_doc_filename(doc_type));
+            report.push(doc_type, check_document(&path));
+        }
+    }
+
+    report.print_summary();
+    report
+}
+
+/// 将示例项目解压到临时目录
+fn prepare_workspace() -> Result<TempDir> {
+    let workspace = TempDir::new()?;
+    let project_path = workspace.path().join("project");
+    fs::create_dir_all(&project_path)?;
+    FIXTURE
+        .extract(&project_path)
+        .map_err(|e| anyhow!("示例项目解压失败: {}", e))?;
+    Ok(workspace)
+}
+
+/// 自检配置：沿用用户的模型配置，项目与输出路径指向临时目录
+fn self_test_config(config: &Config, workspace: &Path) -> Config {
+    let mut config = config.clone();
+    config.project_name = Some(FIXTURE_PROJECT_NAME.to_string());
+    config.project_path = workspace.join("project");
+    config.output_path = workspace.join("docs");
+    config.internal_path = workspace.join(".litho");
+    // 完整执行每个阶段且不使用缓存，确保每一步都真实调用模型服务
+    config.skip_preprocessing = false;
+    config.skip_research = false;
+    config.skip_documentation = false;
+    config.cache.enabled = false;
+    config.since_ref = None;
+    config.incremental_output = false;
+    config.dump_memory_path = None;
+    config
+}
+
+/// 校验文档已生成且内容非空
+fn check_document(path: &Path) -> Result<String> {
+    let metadata = fs::metadata(path).map_err(|_| anyhow!("未生成文档: {}", path.display()))?;
+    if metadata.len() == 0 {
+        return Err(anyhow!("文档内容为空: {}", path.display()));
+    }
+    Ok(format!("{} ({} 字节)", path.display(), metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DocumentProfile, LLMProvider};
+    use crate::llm::client::mock_server::MockServer;
+
+    #[tokio::test]
+    async fn test_self_analysis_produces_overview_document() {
+        let server = MockServer::start().await;
+        let mut config = Config {
+            profile: DocumentProfile::Executive,
+            ..Default::default()
+        };
+        config.llm.provider = LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = server.api_base_url.clone();
+
+        // 对示例项目完整执行一次真实的生成流程，模型调用由模拟服务应答
+        let report = run(&config).await;
+
+        assert!(report.passed());
+        assert!(!server.requests().is_empty());
+        assert!(
+            report
+                .items
+                .iter()
+                .any(|item| item.name == "overview" && item.passed)
+        );
+
+        // 流程结束但缺少预期文档时自检失败
+        let report = run_with(&Config::default(), |_| async { Ok(()) }).await;
+        assert!(!report.passed());
+        assert!(
+            report
+                .items
+                .iter()
+                .any(|item| item.name == "overview" && !item.passed)
+        );
+    }
+}
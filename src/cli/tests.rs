@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
-    use crate::cli::Args;
+    use crate::cli::{Args, find_config_file};
+    use crate::config::DocumentProfile;
     use clap::Parser;
     use std::path::PathBuf;
 
@@ -14,7 +15,7 @@ mod tests {
         assert!(!args.skip_research);
         assert!(!args.skip_documentation);
         assert!(!args.verbose);
-        assert!(!args.force_regenerate);
+        assert!(args.force_regenerate.is_none());
         assert!(!args.no_cache);
     }
 
@@ -59,7 +60,7 @@ mod tests {
         assert!(args.skip_preprocessing);
         assert!(args.skip_research);
         assert!(args.skip_documentation);
-        assert!(args.force_regenerate);
+        assert_eq!(args.force_regenerate.as_deref(), Some("all"));
         assert!(args.verbose);
     }
 
@@ -160,12 +161,121 @@ mod tests {
         assert!(!config.cache.enabled);
     }
 
+    #[test]
+    fn test_into_config_force_regenerate_scope() {
+        let args = Args::try_parse_from(["deepwiki-rs", "--force-regenerate=compose"]).unwrap();
+        assert_eq!(args.force_regenerate.as_deref(), Some("compose"));
+
+        let config = args.into_config();
+        assert!(config.force_regenerate);
+        assert_eq!(config.force_regenerate_scope, "compose");
+    }
+
     #[test]
     fn test_into_config_disable_preset_tools() {
         let args = Args::try_parse_from(&["deepwiki-rs", "--disable-preset-tools"]).unwrap();
 
         let config = args.into_config();
         assert!(config.llm.disable_preset_tools);
+        assert!(!config.llm.offline);
+    }
+
+    #[test]
+    fn test_into_config_no_tools_keeps_llm_enabled() {
+        let args = Args::try_parse_from(["deepwiki-rs", "--no-tools"]).unwrap();
+
+        let config = args.into_config();
+        assert!(config.llm.disable_preset_tools);
+        assert!(!config.llm.offline);
+    }
+
+    #[test]
+    fn test_into_config_offline_disables_all_llm_usage() {
+        for flag in ["--offline", "--no-llm"] {
+            let args = Args::try_parse_from(["deepwiki-rs", flag]).unwrap();
+
+            let config = args.into_config();
+            assert!(config.llm.offline);
+            assert!(!config.llm.disable_preset_tools);
+        }
+    }
+
+    #[test]
+    fn test_into_config_quiet_sets_warn_level() {
+        let args = Args::try_parse_from(["deepwiki-rs", "-q"]).unwrap();
+        assert!(args.quiet);
+
+        let config = args.into_config();
+        assert_eq!(config.log_level, crate::config::LogLevel::Warn);
+        assert_eq!(config.effective_log_level(), crate::config::LogLevel::Warn);
+    }
+
+    #[test]
+    fn test_into_config_verbose_enables_debug_level() {
+        let args = Args::try_parse_from(["deepwiki-rs", "--verbose"]).unwrap();
+
+        let config = args.into_config();
+        assert_eq!(config.effective_log_level(), crate::config::LogLevel::Debug);
+    }
+
+    #[test]
+    fn test_into_config_no_emoji() {
+        let args = Args::try_parse_from(["deepwiki-rs", "--no-emoji"]).unwrap();
+        assert!(args.no_emoji);
+
+        let config = args.into_config();
+        assert!(config.no_emoji);
+        assert!(config.plain_status_output());
+    }
+
+    #[test]
+    fn test_into_config_since_uses_separate_output_dir() {
+        let args =
+            Args::try_parse_from(["deepwiki-rs", "-o", "/test/docs", "--since", "release/v1.2"])
+                .unwrap();
+
+        let config = args.into_config();
+        assert_eq!(config.since_ref.as_deref(), Some("release/v1.2"));
+        assert_eq!(
+            config.output_path,
+            PathBuf::from("/test/docs/changes-since-release-v1.2")
+        );
+    }
+
+    #[test]
+    fn test_quiet_conflicts_with_verbose() {
+        assert!(Args::try_parse_from(["deepwiki-rs", "--quiet", "--verbose"]).is_err());
+    }
+
+    #[test]
+    fn test_check_subcommand() {
+        let args = Args::try_parse_from(["deepwiki-rs", "-p", "/test/project", "check"]).unwrap();
+        assert_eq!(args.command, Some(crate::cli::Command::Check));
+        assert_eq!(args.project_path, PathBuf::from("/test/project"));
+
+        let args = Args::try_parse_from(["deepwiki-rs", "analyze-self"]).unwrap();
+        assert_eq!(args.command, Some(crate::cli::Command::AnalyzeSelf));
+
+        let args = Args::try_parse_from(["deepwiki-rs"]).unwrap();
+        assert!(args.command.is_none());
+    }
+
+    #[test]
+    fn test_config_discovered_from_parent_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let child = root.join("crates").join("core");
+        std::fs::create_dir_all(&child).unwrap();
+        std::fs::write(root.join("litho.toml"), "").unwrap();
+
+        assert_eq!(find_config_file(&child), Some(root.join("litho.toml")));
+
+        // 存在多个配置文件时使用距离最近的一个
+        std::fs::write(root.join("crates").join("litho.toml"), "").unwrap();
+        assert_eq!(
+            find_config_file(&child),
+            Some(root.join("crates").join("litho.toml"))
+        );
     }
 
     #[test]
@@ -215,7 +325,7 @@ mod tests {
         assert_eq!(args.name, Some("Complex Project".to_string()));
         assert!(args.skip_preprocessing);
         assert!(args.skip_research);
-        assert!(args.force_regenerate);
+        assert_eq!(args.force_regenerate.as_deref(), Some("all"));
         assert!(args.verbose);
         assert_eq!(args.model_efficient, Some("gpt-3.5-turbo".to_string()));
         assert_eq!(args.model_powerful, Some("gpt-4".to_string()));
@@ -225,4 +335,18 @@ mod tests {
         assert!(args.disable_preset_tools);
         assert!(args.no_cache);
     }
+
+    #[test]
+    fn test_into_config_profile() {
+        let config = Args::try_parse_from(["deepwiki-rs", "--profile", "contributor"])
+            .unwrap()
+            .into_config();
+        assert_eq!(config.profile, DocumentProfile::Contributor);
+
+        // 未知的预设回退为生成全部文档
+        let config = Args::try_parse_from(["deepwiki-rs", "--profile", "unknown"])
+            .unwrap()
+            .into_config();
+        assert_eq!(config.profile, DocumentProfile::Full);
+    }
 }
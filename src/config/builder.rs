@@ -0,0 +1,124 @@
+use anyhow::{Result, bail};
+use std::path::PathBuf;
+
+use crate::config::{Config, LLMProvider};
+use crate::i18n::TargetLanguage;
+
+/// `Config`的链式构建器，未设置的字段使用`Config::default()`中的默认值
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+    internal_path_set: bool,
+}
+
+#[allow(dead_code)]
+impl ConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 项目名称
+    pub fn project_name(mut self, project_name: impl Into<String>) -> Self {
+        self.config.project_name = Some(project_name.into());
+        self
+    }
+
+    /// 项目路径，未单独设置内部工作目录时使用项目下的`.litho`
+    pub fn project_path(mut self, project_path: impl Into<PathBuf>) -> Self {
+        self.config.project_path = project_path.into();
+        self
+    }
+
+    /// 文档输出路径
+    pub fn output_path(mut self, output_path: impl Into<PathBuf>) -> Self {
+        self.config.output_path = output_path.into();
+        self
+    }
+
+    /// 内部工作目录路径
+    pub fn internal_path(mut self, internal_path: impl Into<PathBuf>) -> Self {
+        self.config.internal_path = internal_path.into();
+        self.internal_path_set = true;
+        self
+    }
+
+    /// LLM Provider
+    pub fn provider(mut self, provider: LLMProvider) -> Self {
+        self.config.llm.provider = provider;
+        self
+    }
+
+    /// LLM API KEY
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.config.llm.api_key = api_key.into();
+        self
+    }
+
+    /// LLM API基地址
+    pub fn api_base_url(mut self, api_base_url: impl Into<String>) -> Self {
+        self.config.llm.api_base_url = api_base_url.into();
+        self
+    }
+
+    /// 高能效模型
+    pub fn model_efficient(mut self, model: impl Into<String>) -> Self {
+        self.config.llm.model_efficient = model.into();
+        self
+    }
+
+    /// 高质量模型
+    pub fn model_powerful(mut self, model: impl Into<String>) -> Self {
+        self.config.llm.model_powerful = model.into();
+        self
+    }
+
+    /// 同时设置高能效模型与高质量模型
+    pub fn models(self, efficient: impl Into<String>, powerful: impl Into<String>) -> Self {
+        self.model_efficient(efficient).model_powerful(powerful)
+    }
+
+    /// 文档目标语言
+    pub fn target_language(mut self, target_language: TargetLanguage) -> Self {
+        self.config.target_language = target_language;
+        self
+    }
+
+    /// LLM最大并发数
+    pub fn max_parallels(mut self, max_parallels: usize) -> Self {
+        self.config.llm.max_parallels = max_parallels;
+        self
+    }
+
+    /// 校验并生成配置
+    pub fn build(self) -> Result<Config> {
+        let mut config = self.config;
+        if !self.internal_path_set {
+            config.internal_path = config.project_path.join(".litho");
+        }
+
+        if config.llm.max_parallels == 0 {
+            bail!("max_parallels 必须大于 0");
+        }
+        if config.llm.model_efficient.trim().is_empty()
+            || config.llm.model_powerful.trim().is_empty()
+        {
+            bail!("模型名称不能为空");
+        }
+        if config.output_path == config.project_path {
+            bail!(
+                "输出路径不能与项目路径相同: {}",
+                config.output_path.display()
+            );
+        }
+        Ok(config)
+    }
+}
+
+impl Config {
+    /// 创建配置构建器
+    #[allow(dead_code)]
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+    }
+}
@@ -1,13 +1,38 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
 
-use crate::i18n::TargetLanguage;
+use crate::generator::outlet::redactor::DEFAULT_REDACT_PATTERNS;
+use crate::i18n::{Localizer, TargetLanguage};
+
+pub mod builder;
+mod profile;
+pub use profile::DocumentProfile;
+
+/// 默认用于推断项目名称的清单文件，按优先级排列
+pub const DEFAULT_PROJECT_NAME_MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "pom.xml",
+    "go.mod",
+    "build.gradle.kts",
+    "build.gradle",
+    "composer.json",
+    "*.csproj",
+    "setup.py",
+];
+
+/// 默认的重要注释标记
+pub const DEFAULT_IMPORTANT_COMMENT_MARKERS: &[&str] = &["TODO", "FIXME", "NOTE", "HACK"];
 
 /// LLM Provider类型
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, Default)]
 pub enum LLMProvider {
     #[serde(rename = "openai")]
     #[default]
@@ -61,12 +86,56 @@ impl std::str::FromStr for LLMProvider {
     }
 }
 
+/// 日志输出级别
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// 仅输出错误
+    Error,
+    /// 输出警告与错误（静默模式）
+    Warn,
+    /// 默认级别，输出常规进度信息
+    #[default]
+    Info,
+    /// 输出调试信息（如工具调用详情）
+    Debug,
+}
+
+impl LogLevel {
+    pub fn to_level_filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+        }
+    }
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(LogLevel::Error),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => Err(format!("Unknown log level: {}", s)),
+        }
+    }
+}
+
 /// 应用程序配置
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
     /// 项目名称
     pub project_name: Option<String>,
 
+    /// 未配置项目名称时，按顺序从这些清单文件推断项目名称（`*.csproj`匹配项目根目录下任意.NET项目文件）
+    #[serde(default = "default_project_name_manifests")]
+    pub project_name_manifests: Vec<String>,
+
     /// 项目路径
     pub project_path: PathBuf,
 
@@ -79,6 +148,14 @@ pub struct Config {
     /// 目标语言
     pub target_language: TargetLanguage,
 
+    /// 目标语言缺少文档固定文案（章节标题等）的译文时使用的回退语言
+    #[serde(default = "default_output_language_fallback")]
+    pub output_language_fallback: TargetLanguage,
+
+    /// 生成文档与时间工具使用的时区（IANA名称，如`Asia/Shanghai`），默认UTC
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+
     /// 是否分析依赖关系
     pub analyze_dependencies: bool,
 
@@ -88,18 +165,72 @@ pub struct Config {
     /// 最大递归深度
     pub max_depth: u8,
 
-    /// 核心组件的百分比
+    /// 核心组件的百分比：参与代码分析的核心文件数量上限，以项目文件总数为基数（至少保留1个）。
+    /// 早期版本读取该配置但并未生效，会分析全部核心文件；需要保持该行为时可将其设为100
     pub core_component_percentage: f64,
 
+    /// 参与代码分析的最低重要性分数（与核心组件百分比共同生效，取更严格者）
+    #[serde(default)]
+    pub min_importance_score: f64,
+
     /// 最大文件大小限制（字节）
     pub max_file_size: u64,
 
+    /// 读取文档类文件（如README）时的最大字节数，超出部分将被截断
+    #[serde(default = "default_max_document_read_bytes")]
+    pub max_document_read_bytes: u64,
+
+    /// 计算复杂度指标的文件大小上限（字节），超过该值则跳过复杂度计算
+    #[serde(default = "default_max_complexity_file_size")]
+    pub max_complexity_file_size: u64,
+
+    /// 单个文件最多保留的接口数量，超出时优先保留公开接口
+    #[serde(default = "default_max_interfaces_per_file")]
+    pub max_interfaces_per_file: usize,
+
+    /// 截取过长源码时优先保留的注释标记（如`SAFETY`、`PERF`、工单号前缀），对所有语言生效
+    #[serde(default = "default_important_comment_markers")]
+    pub important_comment_markers: Vec<String>,
+
+    /// 单个目录直接包含的文件数量上限，超出时视为大型数据目录，仅抽样少量代表性文件参与分析；
+    /// 同时限制file_explorer工具单次返回的条目数量
+    #[serde(default = "default_max_files_per_directory")]
+    pub max_files_per_directory: usize,
+
+    /// 同时进行的文件读取数量上限，避免耗尽文件描述符；未配置时取文件描述符上限（`ulimit -n`）的1/4
+    #[serde(default)]
+    pub max_concurrent_file_reads: Option<usize>,
+
+    /// 是否根据文件内容（空字节）识别并跳过二进制文件，用于捕获无扩展名的编译产物、字体等
+    #[serde(default = "default_skip_binary_content")]
+    pub skip_binary_content: bool,
+
     /// 是否包括测试文件
     pub include_tests: bool,
 
+    /// 是否从测试文件中挖掘公共接口的调用示例（测试文件本身不作为文档组件）
+    #[serde(default)]
+    pub mine_test_examples: bool,
+
+    /// 系统上下文调研完成后，按调研结果对文件的引用次数重新校准代码洞察的重要性评分
+    #[serde(default)]
+    pub recalibrate_importance: bool,
+
+    /// 基于向量嵌入比较核心文件的代码摘要，报告疑似重复的文件组（会产生额外的嵌入调用成本）
+    #[serde(default)]
+    pub detect_duplicates: bool,
+
+    /// 判定两个文件疑似重复的最低余弦相似度
+    #[serde(default = "default_duplicate_similarity_threshold")]
+    pub duplicate_similarity_threshold: f32,
+
     /// 是否包括隐藏文件
     pub include_hidden: bool,
 
+    /// 即使不包括隐藏文件，也需要纳入分析的隐藏路径模式（如`.github/**`）
+    #[serde(default)]
+    pub included_hidden_patterns: Vec<String>,
+
     /// 要排除的目录
     pub excluded_dirs: Vec<String>,
 
@@ -112,6 +243,22 @@ pub struct Config {
     /// 只包含指定的文件扩展名
     pub included_extensions: Vec<String>,
 
+    /// 语言映射：扩展名/文件名（如`mjsx`）或路径glob（如`scripts/*`）到语言名称（如`rust`），优先于按扩展名识别
+    #[serde(default)]
+    pub language_overrides: HashMap<String, String>,
+
+    /// 自定义代码用途分类，用于内置分类无法覆盖的领域概念（如`Saga`、`EventHandler`）
+    #[serde(default)]
+    pub custom_code_purposes: Vec<CustomCodePurpose>,
+
+    /// 边界分析时附带源码摘要的边界文件数量（按重要性取前N个），其余文件只提供描述与接口名称
+    #[serde(default = "default_boundary_full_source_files")]
+    pub boundary_full_source_files: usize,
+
+    /// 边界分析中边界代码与API端点内容的token预算，超出时先将源码降级为摘要，再省略重要性较低的文件与端点；0表示不限制
+    #[serde(default = "default_boundary_prompt_token_budget")]
+    pub boundary_prompt_token_budget: usize,
+
     /// LLM模型配置
     pub llm: LLMConfig,
 
@@ -124,6 +271,71 @@ pub struct Config {
     /// 强制重新生成（清除缓存）
     pub force_regenerate: bool,
 
+    /// 强制重新生成时失效的缓存范围：`all`、`preprocess`、`research`、`compose`，或逗号分隔的智能体类型
+    #[serde(default = "default_force_regenerate_scope")]
+    pub force_regenerate_scope: String,
+
+    /// 源码仓库地址（如`https://github.com/org/repo`），用于生成源码链接；未设置时从git配置中读取origin地址
+    #[serde(default)]
+    pub source_repo_url: Option<String>,
+
+    /// 生成源码链接使用的分支，未设置时使用当前检出的分支
+    #[serde(default)]
+    pub source_branch: Option<String>,
+
+    /// 单个文档超过该字节数时按章节拆分为多个分页文件，未设置时不拆分
+    #[serde(default)]
+    pub split_document_threshold: Option<usize>,
+
+    /// 添加到每个输出文档开头的Markdown内容，支持`{{ project_name }}`、`{{ date }}`占位符
+    #[serde(default)]
+    pub document_preamble: Option<String>,
+
+    /// 添加到每个输出文档末尾的Markdown内容，占位符同`document_preamble`
+    #[serde(default)]
+    pub document_postamble: Option<String>,
+
+    /// 输出文档（含嵌入的源码片段）的脱敏正则表达式，匹配内容替换为`[REDACTED]`；默认覆盖常见的密钥与令牌格式，设置为空列表可关闭
+    #[serde(default = "default_redact_patterns")]
+    pub redact_patterns: Vec<String>,
+
+    /// 保存文档前修复格式错误的Markdown表格（列数不一致、缺少表头分隔行）
+    #[serde(default = "default_repair_markdown_tables")]
+    pub repair_markdown_tables: bool,
+
+    /// 静态修复后仍无效的Mermaid图表，交由LLM结合校验错误重新生成（会产生额外的调用成本）
+    #[serde(default)]
+    pub llm_mermaid_repair: bool,
+
+    /// 增量输出：依据输出清单跳过未变化的文档，并只清理清单中记录过的孤立文件
+    #[serde(default = "default_incremental_output")]
+    pub incremental_output: bool,
+
+    /// 变更范围文档模式：仅分析自该git引用以来变更的文件
+    #[serde(default)]
+    pub since_ref: Option<String>,
+
+    /// 运行结束后将内存中所有作用域的键、数据大小及内容导出为JSON到该路径，用于调试
+    #[serde(default)]
+    pub dump_memory_path: Option<PathBuf>,
+
+    /// 覆盖写入内存数据时每个键保留的历史版本数量，避免重新运行失败时的不完整结果覆盖先前的有效结果，为0时不保留
+    #[serde(default)]
+    pub memory_versions: usize,
+
+    /// 从上次运行在内部目录保存的内存快照恢复，已存储结果的调研与文档智能体不再重新执行
+    #[serde(default)]
+    pub resume: bool,
+
+    /// 可复现模式：温度设为0、按固定顺序遍历集合、时间戳固定为`SOURCE_DATE_EPOCH`（未设置时文档中省略时间戳），
+    /// 使相同输入与缓存的两次运行产生逐字节相同的文档
+    #[serde(default)]
+    pub deterministic: bool,
+
+    /// 允许清理危险的输出目录（如项目根目录、用户主目录或文件系统根目录）
+    #[serde(default)]
+    pub allow_unsafe_output_path: bool,
+
     /// 跳过项目预处理
     pub skip_preprocessing: bool,
 
@@ -133,8 +345,20 @@ pub struct Config {
     /// 跳过最终文档生成
     pub skip_documentation: bool,
 
+    /// 文档集预设（full、executive、contributor），决定执行哪些调研与文档生成智能体
+    #[serde(default)]
+    pub profile: DocumentProfile,
+
     /// 是否启用详细日志
     pub verbose: bool,
+
+    /// 日志输出级别（error、warn、info、debug）
+    #[serde(default)]
+    pub log_level: LogLevel,
+
+    /// 状态输出中不使用emoji，改用纯ASCII标记（设置了`NO_COLOR`环境变量时同样生效）
+    #[serde(default)]
+    pub no_emoji: bool,
 }
 
 /// LLM模型配置
@@ -155,6 +379,11 @@ pub struct LLMConfig {
     /// 高质量模型，优先用于Litho引擎的复杂推理任务，以及作为efficient失效情况下的兜底
     pub model_powerful: String,
 
+    /// 兜底调用（efficient失效后改用model_powerful）所使用的provider，未配置时与`provider`相同；
+    /// 可用于本地Ollama为主、云端模型兜底的组合
+    #[serde(default)]
+    pub fallover_provider: Option<FalloverProviderConfig>,
+
     /// 最大tokens
     pub max_tokens: u32,
 
@@ -167,12 +396,104 @@ pub struct LLMConfig {
     /// 重试间隔（毫秒）
     pub retry_delay_ms: u64,
 
+    /// 模型返回空白内容时视为失败并触发重试
+    #[serde(default = "default_retry_on_empty_response")]
+    pub retry_on_empty_response: bool,
+
     /// 超时时间（秒）
     pub timeout_seconds: u64,
 
+    /// 禁用ReAct预设工具（文件浏览、文件读取、时间查询），LLM仍会基于提示词中的上下文生成内容。
+    /// 迁移说明：旧版本中该项为true时会跳过全部LLM调用，现在需改用`offline = true`
     pub disable_preset_tools: bool,
 
+    /// 离线模式：跳过所有LLM调用，仅执行静态分析，不生成需要LLM编写的文档
+    #[serde(default)]
+    pub offline: bool,
+
     pub max_parallels: usize,
+
+    /// 按provider覆盖的最大并发数（如`ollama = 8`、`openai = 2`），未配置的provider使用`max_parallels`
+    #[serde(default)]
+    pub provider_max_parallels: HashMap<LLMProvider, usize>,
+
+    /// 本次运行允许的LLM调用成本上限（美元），累计估算成本超出后停止发起新的调用
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+
+    /// 单次工具调用返回结果的最大字节数，超出部分会被截断并附带截断说明
+    #[serde(default = "default_tool_output_max_bytes")]
+    pub tool_output_max_bytes: usize,
+
+    /// 单次工具调用的超时时间（秒），避免遍历超大目录时长时间阻塞
+    #[serde(default = "default_tool_timeout_seconds")]
+    pub tool_timeout_seconds: u64,
+
+    /// 结构化抽取时启用provider原生的JSON输出模式（仅对支持的provider生效）
+    #[serde(default = "default_native_json_mode")]
+    pub native_json_mode: bool,
+
+    /// ReAct模式下智能体调用工具的最大迭代次数，越大越倾向于多轮探索代码
+    #[serde(default = "default_react_max_iterations")]
+    pub react_max_iterations: usize,
+
+    /// 达到最大迭代次数时，基于已有的工具调用结果总结后直接推理出最终回复
+    #[serde(default = "default_enable_summary_reasoning")]
+    pub enable_summary_reasoning: bool,
+
+    /// 输出ReAct循环的详细日志（debug构建下始终输出）
+    #[serde(default)]
+    pub react_verbose: bool,
+
+    /// 向量嵌入使用的模型，未配置时使用provider的默认嵌入模型（仅openai、gemini、ollama支持）
+    #[serde(default)]
+    pub embedding_model: Option<String>,
+
+    /// 附加到每个LLM请求上的HTTP请求头，用于企业LLM网关（API管理密钥、租户ID等），对所有provider生效
+    #[serde(default)]
+    pub extra_headers: HashMap<String, String>,
+}
+
+impl LLMConfig {
+    /// 当前provider实际生效的最大并发数，provider单独配置的上限优先于全局`max_parallels`
+    pub fn effective_max_parallels(&self) -> usize {
+        self.max_parallels_for(&self.provider)
+    }
+
+    /// 指定provider的最大并发请求数，未单独配置时使用全局`max_parallels`
+    pub fn max_parallels_for(&self, provider: &LLMProvider) -> usize {
+        self.provider_max_parallels
+            .get(provider)
+            .copied()
+            .unwrap_or(self.max_parallels)
+            .max(1)
+    }
+
+    /// 兜底调用使用的LLM配置：替换为`fallover_provider`的provider、密钥与基地址，未配置时返回None
+    pub fn fallover_config(&self) -> Option<LLMConfig> {
+        let fallover = self.fallover_provider.as_ref()?;
+        Some(LLMConfig {
+            provider: fallover.provider.clone(),
+            api_key: fallover.api_key.clone(),
+            api_base_url: fallover.api_base_url.clone(),
+            fallover_provider: None,
+            ..self.clone()
+        })
+    }
+}
+
+/// 兜底调用使用的provider配置
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FalloverProviderConfig {
+    /// LLM Provider类型
+    pub provider: LLMProvider,
+
+    /// LLM API KEY
+    #[serde(default)]
+    pub api_key: String,
+
+    /// LLM API基地址
+    pub api_base_url: String,
 }
 
 /// 缓存配置
@@ -186,6 +507,41 @@ pub struct CacheConfig {
 
     /// 缓存过期时间（小时）
     pub expire_hours: u64,
+
+    /// 按缓存类别覆盖的过期时间（类别 -> 小时），未配置的类别使用全局过期时间。
+    /// 配置的类别同时覆盖其下级类别（如`studies_research`覆盖各调研智能体的提示词缓存），
+    /// 多个配置都匹配时以最长的类别为准
+    #[serde(default)]
+    pub category_expire_hours: HashMap<String, u64>,
+}
+
+/// 自定义代码用途分类
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CustomCodePurpose {
+    /// 分类名称，同时作为分类结果的标识
+    pub name: String,
+
+    /// 分类说明，提供给LLM作为分类依据
+    #[serde(default)]
+    pub description: String,
+
+    /// 文件路径包含任一片段（不区分大小写）时直接归入该分类，优先于内置规则
+    #[serde(default)]
+    pub path_patterns: Vec<String>,
+
+    /// 是否属于系统边界（如事件处理器），为true时参与边界接口分析
+    #[serde(default)]
+    pub boundary: bool,
+}
+
+impl CustomCodePurpose {
+    /// 文件路径是否匹配该分类的路径片段
+    pub fn matches_path(&self, file_path: &str) -> bool {
+        let path_lower = file_path.replace('\\', "/").to_lowercase();
+        self.path_patterns
+            .iter()
+            .any(|pattern| path_lower.contains(&pattern.to_lowercase()))
+    }
 }
 
 impl Config {
@@ -201,6 +557,29 @@ impl Config {
         Ok(config)
     }
 
+    /// 配置文件是否按旧版本的含义使用`llm.disable_preset_tools`：启用了该项但没有设置`llm.offline`，
+    /// 旧版本中这表示跳过全部LLM调用，现在只禁用预设工具
+    pub fn has_legacy_disable_preset_tools(content: &str) -> bool {
+        let Ok(table) = content.parse::<toml::Table>() else {
+            return false;
+        };
+        let Some(llm) = table.get("llm").and_then(|llm| llm.as_table()) else {
+            return false;
+        };
+        llm.get("disable_preset_tools")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+            && !llm.contains_key("offline")
+    }
+
+    /// 文档固定文案的本地化查询，依次使用目标语言、回退语言和英文
+    pub fn localizer(&self) -> Localizer {
+        Localizer::new(
+            self.target_language.clone(),
+            self.output_language_fallback.clone(),
+        )
+    }
+
     /// 获取项目名称，优先使用配置的project_name，否则自动推断
     pub fn get_project_name(&self) -> String {
         // 优先使用配置的项目名称
@@ -214,6 +593,42 @@ impl Config {
         self.infer_project_name()
     }
 
+    /// 写入文档与prompt的生成时间；可复现模式下取`SOURCE_DATE_EPOCH`，未设置时返回None，文档中省略时间戳
+    pub fn generation_time(&self) -> Option<DateTime<Utc>> {
+        if !self.deterministic {
+            return Some(Utc::now());
+        }
+        std::env::var("SOURCE_DATE_EPOCH")
+            .ok()
+            .and_then(|epoch| epoch.trim().parse::<i64>().ok())
+            .and_then(|epoch| DateTime::from_timestamp(epoch, 0))
+    }
+
+    /// 配置的时区，名称无效时回退到UTC
+    pub fn time_zone(&self) -> Tz {
+        self.timezone.trim().parse().unwrap_or(Tz::UTC)
+    }
+
+    /// 配置时区下的生成时间
+    pub fn local_generation_time(&self) -> Option<DateTime<Tz>> {
+        self.generation_time()
+            .map(|time| time.with_timezone(&self.time_zone()))
+    }
+
+    /// 状态输出是否使用纯ASCII标记代替emoji
+    pub fn plain_status_output(&self) -> bool {
+        self.no_emoji || crate::utils::status_text::no_color_requested()
+    }
+
+    /// 获取实际生效的日志级别，verbose模式下默认级别提升为debug
+    pub fn effective_log_level(&self) -> LogLevel {
+        if self.verbose && self.log_level == LogLevel::Info {
+            LogLevel::Debug
+        } else {
+            self.log_level
+        }
+    }
+
     /// 自动推断项目名称
     fn infer_project_name(&self) -> String {
         // 尝试从项目配置文件中提取项目名称
@@ -231,27 +646,32 @@ impl Config {
 
     /// 从项目配置文件中提取项目名称
     fn extract_project_name_from_config_files(&self) -> Option<String> {
-        // 尝试从 Cargo.toml 提取（Rust项目）
-        if let Some(name) = self.extract_from_cargo_toml() {
-            return Some(name);
-        }
-
-        // 尝试从 package.json 提取（Node.js项目）
-        if let Some(name) = self.extract_from_package_json() {
-            return Some(name);
-        }
-
-        // 尝试从 pyproject.toml 提取（Python项目）
-        if let Some(name) = self.extract_from_pyproject_toml() {
-            return Some(name);
-        }
+        self.project_name_manifests
+            .iter()
+            .find_map(|manifest| self.extract_from_manifest(manifest))
+    }
 
-        // 尝试从 pom.xml 提取（Java Maven项目）
-        if let Some(name) = self.extract_from_pom_xml() {
-            return Some(name);
+    /// 从指定清单文件提取项目名称，不支持的清单文件返回None
+    fn extract_from_manifest(&self, manifest: &str) -> Option<String> {
+        match manifest {
+            // Rust项目
+            "Cargo.toml" => self.extract_from_cargo_toml(),
+            // Node.js项目
+            "package.json" => self.extract_from_package_json(),
+            // Python项目
+            "pyproject.toml" => self.extract_from_pyproject_toml(),
+            "setup.py" => self.extract_from_setup_py(),
+            // Java Maven / Gradle项目
+            "pom.xml" => self.extract_from_pom_xml(),
+            "build.gradle" | "build.gradle.kts" => self.extract_from_gradle(manifest),
+            // Go项目
+            "go.mod" => self.extract_from_go_mod(),
+            // PHP项目
+            "composer.json" => self.extract_from_composer_json(),
+            // .NET项目
+            "*.csproj" => self.extract_from_csproj(),
+            _ => None,
         }
-
-        None
     }
 
     /// 从 Cargo.toml 提取项目名称
@@ -405,23 +825,237 @@ impl Config {
         }
         None
     }
+
+    /// 从 go.mod 提取项目名称，取模块路径的最后一段（忽略`/v2`等主版本后缀）
+    pub fn extract_from_go_mod(&self) -> Option<String> {
+        let content = std::fs::read_to_string(self.project_path.join("go.mod")).ok()?;
+        let module_path = content
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("module "))?
+            .trim()
+            .trim_matches('"');
+
+        let mut segments = module_path.rsplit('/');
+        let last = segments.next()?;
+        let is_major_version = last.len() > 1
+            && last.starts_with('v')
+            && last[1..].chars().all(|c| c.is_ascii_digit());
+        let name = if is_major_version {
+            segments.next().unwrap_or(last)
+        } else {
+            last
+        };
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// 从 Gradle 项目提取项目名称，优先使用 settings.gradle(.kts) 中的`rootProject.name`，
+    /// 其次使用构建脚本中的`archivesBaseName`/`archivesName`
+    fn extract_from_gradle(&self, build_file: &str) -> Option<String> {
+        if !self.project_path.join(build_file).exists() {
+            return None;
+        }
+
+        let assigned_value = |content: &str, keys: &[&str]| {
+            content.lines().find_map(|line| {
+                let line = line.trim();
+                let key = keys.iter().find(|key| line.starts_with(*key))?;
+                let value = line[key.len()..].trim_start();
+                let value = value
+                    .strip_prefix('=')
+                    .or_else(|| value.strip_prefix(".set("))?;
+                let name = value
+                    .trim()
+                    .trim_end_matches(')')
+                    .trim_matches('"')
+                    .trim_matches('\'');
+                (!name.is_empty()).then(|| name.to_string())
+            })
+        };
+
+        ["settings.gradle.kts", "settings.gradle"]
+            .iter()
+            .filter_map(|settings| std::fs::read_to_string(self.project_path.join(settings)).ok())
+            .find_map(|content| assigned_value(&content, &["rootProject.name"]))
+            .or_else(|| {
+                let content = std::fs::read_to_string(self.project_path.join(build_file)).ok()?;
+                assigned_value(&content, &["archivesBaseName", "archivesName"])
+            })
+    }
+
+    /// 从 composer.json 提取项目名称，`vendor/package`格式取包名部分
+    pub fn extract_from_composer_json(&self) -> Option<String> {
+        let content = std::fs::read_to_string(self.project_path.join("composer.json")).ok()?;
+        let composer: serde_json::Value = serde_json::from_str(&content).ok()?;
+        let name = composer.get("name")?.as_str()?.trim();
+        let name = name.rsplit('/').next().unwrap_or(name);
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// 从项目根目录下的 .csproj 文件提取项目名称，优先使用`<AssemblyName>`，否则使用文件名
+    pub fn extract_from_csproj(&self) -> Option<String> {
+        let mut csproj_files: Vec<PathBuf> = std::fs::read_dir(&self.project_path)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "csproj"))
+            .collect();
+        csproj_files.sort();
+        let csproj_path = csproj_files.into_iter().next()?;
+
+        let content = std::fs::read_to_string(&csproj_path).ok()?;
+        let assembly_name = content.lines().find_map(|line| {
+            let name = line
+                .trim()
+                .strip_prefix("<AssemblyName>")?
+                .strip_suffix("</AssemblyName>")?
+                .trim();
+            (!name.is_empty()).then(|| name.to_string())
+        });
+        assembly_name.or_else(|| {
+            csproj_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+    }
+
+    /// 从 setup.py 提取`setup(name=...)`中的项目名称
+    pub fn extract_from_setup_py(&self) -> Option<String> {
+        let content = std::fs::read_to_string(self.project_path.join("setup.py")).ok()?;
+        let pattern = regex::Regex::new(r#"\bname\s*=\s*["']([^"']+)["']"#).ok()?;
+        pattern
+            .captures(&content)
+            .map(|captures| captures[1].trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
+}
+
+fn default_max_document_read_bytes() -> u64 {
+    1024 * 1024 // 1MB
+}
+
+fn default_max_complexity_file_size() -> u64 {
+    512 * 1024 // 512KB
+}
+
+fn default_max_interfaces_per_file() -> usize {
+    200
+}
+
+fn default_project_name_manifests() -> Vec<String> {
+    DEFAULT_PROJECT_NAME_MANIFESTS
+        .iter()
+        .map(|manifest| manifest.to_string())
+        .collect()
+}
+
+fn default_important_comment_markers() -> Vec<String> {
+    DEFAULT_IMPORTANT_COMMENT_MARKERS
+        .iter()
+        .map(|marker| marker.to_string())
+        .collect()
+}
+
+fn default_redact_patterns() -> Vec<String> {
+    DEFAULT_REDACT_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+fn default_max_files_per_directory() -> usize {
+    5000
+}
+
+fn default_skip_binary_content() -> bool {
+    true
+}
+
+fn default_incremental_output() -> bool {
+    false
+}
+
+fn default_repair_markdown_tables() -> bool {
+    true
+}
+
+fn default_duplicate_similarity_threshold() -> f32 {
+    0.95
+}
+
+fn default_boundary_full_source_files() -> usize {
+    10
+}
+
+fn default_boundary_prompt_token_budget() -> usize {
+    32000
+}
+
+fn default_force_regenerate_scope() -> String {
+    "all".to_string()
+}
+
+fn default_retry_on_empty_response() -> bool {
+    true
+}
+
+fn default_tool_output_max_bytes() -> usize {
+    64 * 1024
+}
+
+fn default_tool_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_native_json_mode() -> bool {
+    true
+}
+
+fn default_react_max_iterations() -> usize {
+    10
+}
+
+fn default_enable_summary_reasoning() -> bool {
+    true
+}
+
+fn default_output_language_fallback() -> TargetLanguage {
+    TargetLanguage::English
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             project_name: None,
+            project_name_manifests: default_project_name_manifests(),
             project_path: PathBuf::from("."),
             output_path: PathBuf::from("./litho.docs"),
             internal_path: PathBuf::from("./.litho"),
             target_language: TargetLanguage::default(),
+            output_language_fallback: default_output_language_fallback(),
+            timezone: default_timezone(),
             analyze_dependencies: true,
             identify_components: true,
             max_depth: 10,
             core_component_percentage: 20.0,
+            min_importance_score: 0.0,
             max_file_size: 64 * 1024, // 64KB
+            max_document_read_bytes: default_max_document_read_bytes(),
+            max_complexity_file_size: default_max_complexity_file_size(),
+            max_interfaces_per_file: default_max_interfaces_per_file(),
+            important_comment_markers: default_important_comment_markers(),
+            max_files_per_directory: default_max_files_per_directory(),
+            max_concurrent_file_reads: None,
+            skip_binary_content: default_skip_binary_content(),
             include_tests: false,
+            mine_test_examples: false,
+            recalibrate_importance: false,
+            detect_duplicates: false,
+            duplicate_similarity_threshold: default_duplicate_similarity_threshold(),
             include_hidden: false,
+            included_hidden_patterns: vec![],
             excluded_dirs: vec![
                 ".litho".to_string(),
                 "litho.docs".to_string(),
@@ -472,14 +1106,37 @@ impl Default for Config {
                 "archive".to_string(),
             ],
             included_extensions: vec![],
+            language_overrides: HashMap::new(),
+            custom_code_purposes: Vec::new(),
+            boundary_full_source_files: default_boundary_full_source_files(),
+            boundary_prompt_token_budget: default_boundary_prompt_token_budget(),
             architecture_meta_path: None,
             llm: LLMConfig::default(),
             cache: CacheConfig::default(),
             force_regenerate: false,
+            force_regenerate_scope: default_force_regenerate_scope(),
+            source_repo_url: None,
+            source_branch: None,
+            split_document_threshold: None,
+            document_preamble: None,
+            document_postamble: None,
+            redact_patterns: default_redact_patterns(),
+            repair_markdown_tables: default_repair_markdown_tables(),
+            since_ref: None,
+            dump_memory_path: None,
+            memory_versions: 0,
+            resume: false,
+            deterministic: false,
+            llm_mermaid_repair: false,
+            incremental_output: default_incremental_output(),
+            allow_unsafe_output_path: false,
             skip_preprocessing: false,
+            profile: DocumentProfile::default(),
             skip_research: false,
             skip_documentation: false,
             verbose: false,
+            log_level: LogLevel::default(),
+            no_emoji: false,
         }
     }
 }
@@ -492,13 +1149,26 @@ impl Default for LLMConfig {
             api_base_url: String::from("https://api-inference.modelscope.cn/v1"),
             model_efficient: String::from("Qwen/Qwen3-Next-80B-A3B-Instruct"),
             model_powerful: String::from("Qwen/Qwen3-235B-A22B-Instruct-2507"),
+            fallover_provider: None,
             max_tokens: 131072,
             temperature: 0.1,
             retry_attempts: 5,
             retry_delay_ms: 5000,
+            retry_on_empty_response: default_retry_on_empty_response(),
             timeout_seconds: 300,
             disable_preset_tools: false,
+            offline: false,
             max_parallels: 3,
+            provider_max_parallels: HashMap::new(),
+            max_cost_usd: None,
+            tool_output_max_bytes: default_tool_output_max_bytes(),
+            tool_timeout_seconds: default_tool_timeout_seconds(),
+            native_json_mode: default_native_json_mode(),
+            react_max_iterations: default_react_max_iterations(),
+            enable_summary_reasoning: default_enable_summary_reasoning(),
+            react_verbose: false,
+            embedding_model: None,
+            extra_headers: HashMap::new(),
         }
     }
 }
@@ -509,6 +1179,7 @@ impl Default for CacheConfig {
             enabled: true,
             cache_dir: PathBuf::from(".litho/cache"),
             expire_hours: 8760,
+            category_expire_hours: HashMap::new(),
         }
     }
 }
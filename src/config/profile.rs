@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::generator::compose::types::AgentType;
+
+/// 文档集预设，面向不同读者选择需要调研与生成的文档
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentProfile {
+    /// 生成全部文档
+    #[default]
+    Full,
+    /// 面向管理者：仅生成项目概述
+    Executive,
+    /// 面向贡献者：项目概述（快速上手）、架构说明、边界调用与代码索引（API）、构建与持续集成
+    Contributor,
+}
+
+impl DocumentProfile {
+    /// 文档集是否包含指定的文档
+    pub fn includes(&self, document: &AgentType) -> bool {
+        match self {
+            DocumentProfile::Full => true,
+            DocumentProfile::Executive => *document == AgentType::Overview,
+            DocumentProfile::Contributor => matches!(
+                document,
+                AgentType::Overview
+                    | AgentType::Architecture
+                    | AgentType::Boundary
+                    | AgentType::CodeIndex
+                    | AgentType::BuildSystem
+            ),
+        }
+    }
+
+    /// 文档集是否包含核心模块的深入解析文档
+    pub fn includes_key_modules(&self) -> bool {
+        *self == DocumentProfile::Full
+    }
+}
+
+impl std::fmt::Display for DocumentProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocumentProfile::Full => write!(f, "full"),
+            DocumentProfile::Executive => write!(f, "executive"),
+            DocumentProfile::Contributor => write!(f, "contributor"),
+        }
+    }
+}
+
+impl std::str::FromStr for DocumentProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "full" => Ok(DocumentProfile::Full),
+            "executive" => Ok(DocumentProfile::Executive),
+            "contributor" => Ok(DocumentProfile::Contributor),
+            _ => Err(format!("Unknown profile: {}", s)),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use crate::config::{CacheConfig, Config, LLMConfig, LLMProvider};
+    use crate::config::{
+        CacheConfig, Config, DEFAULT_PROJECT_NAME_MANIFESTS, DocumentProfile, LLMConfig,
+        LLMProvider,
+    };
     use std::path::PathBuf;
     use tempfile::TempDir;
 
@@ -16,13 +19,51 @@ mod tests {
         assert!(config.identify_components);
         assert_eq!(config.max_depth, 10);
         assert_eq!(config.core_component_percentage, 20.0);
+        assert_eq!(config.max_interfaces_per_file, 200);
+        assert_eq!(
+            config.important_comment_markers,
+            vec!["TODO", "FIXME", "NOTE", "HACK"]
+        );
+        assert_eq!(config.max_files_per_directory, 5000);
+        assert!(config.skip_binary_content);
+        assert!(config.document_preamble.is_none());
+        assert!(config.document_postamble.is_none());
+        assert!(!config.redact_patterns.is_empty());
+        assert!(config.repair_markdown_tables);
+        assert!(config.since_ref.is_none());
+        assert!(config.dump_memory_path.is_none());
+        assert_eq!(config.memory_versions, 0);
+        assert_eq!(
+            config.project_name_manifests.len(),
+            DEFAULT_PROJECT_NAME_MANIFESTS.len()
+        );
+        assert!(!config.deterministic);
+        assert!(!config.resume);
+        assert!(!config.incremental_output);
+        assert!(config.max_concurrent_file_reads.is_none());
+        assert_eq!(config.timezone, "UTC");
+        assert_eq!(config.time_zone(), chrono_tz::Tz::UTC);
+        assert_eq!(
+            config.output_language_fallback,
+            crate::i18n::TargetLanguage::English
+        );
+        assert!(config.custom_code_purposes.is_empty());
+        assert_eq!(config.boundary_full_source_files, 10);
+        assert_eq!(config.boundary_prompt_token_budget, 32000);
         assert!(!config.include_tests);
+        assert!(!config.mine_test_examples);
+        assert!(!config.recalibrate_importance);
+        assert!(!config.detect_duplicates);
+        assert_eq!(config.duplicate_similarity_threshold, 0.95);
         assert!(!config.include_hidden);
         assert!(!config.force_regenerate);
+        assert_eq!(config.force_regenerate_scope, "all");
         assert!(!config.skip_preprocessing);
+        assert_eq!(config.profile, DocumentProfile::Full);
         assert!(!config.skip_research);
         assert!(!config.skip_documentation);
         assert!(!config.verbose);
+        assert!(!config.no_emoji);
     }
 
     #[test]
@@ -90,13 +131,25 @@ mod tests {
         assert!(!config.api_base_url.is_empty());
         assert!(!config.model_efficient.is_empty());
         assert!(!config.model_powerful.is_empty());
+        assert!(config.fallover_provider.is_none());
         assert_eq!(config.max_tokens, 131072);
         assert_eq!(config.temperature, 0.1);
         assert_eq!(config.retry_attempts, 5);
         assert_eq!(config.retry_delay_ms, 5000);
+        assert!(config.retry_on_empty_response);
         assert_eq!(config.timeout_seconds, 300);
         assert!(!config.disable_preset_tools);
+        assert!(!config.offline);
         assert_eq!(config.max_parallels, 3);
+        assert!(config.max_cost_usd.is_none());
+        assert_eq!(config.tool_output_max_bytes, 64 * 1024);
+        assert_eq!(config.tool_timeout_seconds, 30);
+        assert!(config.native_json_mode);
+        assert_eq!(config.react_max_iterations, 10);
+        assert!(config.enable_summary_reasoning);
+        assert!(!config.react_verbose);
+        assert!(config.embedding_model.is_none());
+        assert!(config.extra_headers.is_empty());
     }
 
     #[test]
@@ -267,6 +320,55 @@ build-backend = "poetry.core.masonry.api"
         assert!(config.extract_from_package_json().is_none());
         assert!(config.extract_from_pyproject_toml().is_none());
         assert!(config.extract_from_pom_xml().is_none());
+        assert!(config.extract_from_go_mod().is_none());
+        assert!(config.extract_from_csproj().is_none());
+        assert!(config.extract_from_composer_json().is_none());
+        assert!(config.extract_from_setup_py().is_none());
+    }
+
+    #[test]
+    fn test_extract_from_go_mod() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("go.mod"),
+            "module github.com/example/payment-gateway/v2\n\ngo 1.22\n\nrequire github.com/google/uuid v1.6.0\n",
+        )
+        .unwrap();
+
+        let config = Config {
+            project_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.extract_from_go_mod(),
+            Some("payment-gateway".to_string())
+        );
+        assert_eq!(config.get_project_name(), "payment-gateway");
+    }
+
+    #[test]
+    fn test_extract_from_csproj_assembly_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let csproj_content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <AssemblyName>Contoso.Inventory</AssemblyName>
+  </PropertyGroup>
+</Project>
+"#;
+        std::fs::write(temp_dir.path().join("Inventory.csproj"), csproj_content).unwrap();
+
+        let config = Config {
+            project_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.extract_from_csproj(),
+            Some("Contoso.Inventory".to_string())
+        );
+        assert_eq!(config.get_project_name(), "Contoso.Inventory");
     }
 
     #[test]
@@ -322,4 +424,69 @@ build-backend = "poetry.core.masonry.api"
         assert!(config.excluded_extensions.contains(&"png".to_string()));
         assert!(config.excluded_extensions.contains(&"mp3".to_string()));
     }
+
+    #[test]
+    fn test_builder_sets_common_fields_and_keeps_defaults() {
+        let config = Config::builder()
+            .project_path("/work/demo")
+            .output_path("/work/demo-docs")
+            .provider(LLMProvider::DeepSeek)
+            .api_key("sk-test")
+            .models("deepseek-chat", "deepseek-reasoner")
+            .target_language(crate::i18n::TargetLanguage::English)
+            .max_parallels(8)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.project_path, PathBuf::from("/work/demo"));
+        assert_eq!(config.output_path, PathBuf::from("/work/demo-docs"));
+        assert_eq!(config.internal_path, PathBuf::from("/work/demo/.litho"));
+        assert_eq!(config.llm.provider, LLMProvider::DeepSeek);
+        assert_eq!(config.llm.api_key, "sk-test");
+        assert_eq!(config.llm.model_efficient, "deepseek-chat");
+        assert_eq!(config.llm.model_powerful, "deepseek-reasoner");
+        assert_eq!(config.target_language, crate::i18n::TargetLanguage::English);
+        assert_eq!(config.llm.max_parallels, 8);
+
+        // 未设置的字段保持默认值
+        let defaults = Config::default();
+        assert!(config.project_name.is_none());
+        assert_eq!(config.max_depth, defaults.max_depth);
+        assert_eq!(config.max_file_size, defaults.max_file_size);
+        assert_eq!(config.llm.temperature, defaults.llm.temperature);
+        assert_eq!(config.llm.api_base_url, defaults.llm.api_base_url);
+        assert_eq!(config.cache.enabled, defaults.cache.enabled);
+        assert!(!config.force_regenerate);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_config() {
+        assert!(Config::builder().max_parallels(0).build().is_err());
+        assert!(Config::builder().models("", "gpt-4").build().is_err());
+        assert!(
+            Config::builder()
+                .project_path("/work/demo")
+                .output_path("/work/demo")
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_legacy_disable_preset_tools_is_detected() {
+        // 旧版本的配置只设置disable_preset_tools来跳过LLM调用
+        assert!(Config::has_legacy_disable_preset_tools(
+            "[llm]\ndisable_preset_tools = true\n"
+        ));
+        // 已显式设置offline或未启用该项时不需要迁移
+        assert!(!Config::has_legacy_disable_preset_tools(
+            "[llm]\ndisable_preset_tools = true\noffline = false\n"
+        ));
+        assert!(!Config::has_legacy_disable_preset_tools(
+            "[llm]\ndisable_preset_tools = false\n"
+        ));
+        assert!(!Config::has_legacy_disable_preset_tools(
+            "project_name = \"demo\"\n"
+        ));
+    }
 }
@@ -3,7 +3,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::generator::context::GeneratorContext;
-use crate::llm::client::utils::estimate_token_usage;
+use crate::generator::events::GeneratorEvent;
+use log::info;
 
 pub struct AgentExecuteParams {
     pub prompt_sys: String,
@@ -24,26 +25,25 @@ pub async fn prompt(context: &GeneratorContext, params: AgentExecuteParams) -> R
         .cache_manager
         .read()
         .await
-        .get::<serde_json::Value>(cache_scope, &prompt_key)
+        .get::<String>(cache_scope, &prompt_key)
         .await?
     {
-        println!("   ✅ 使用缓存的AI分析结果: {}", log_tag);
-        return Ok(cached_reply.to_string());
+        info!("   ✅ 使用缓存的AI分析结果: {}", log_tag);
+        context.emit(GeneratorEvent::CacheHit {
+            category: cache_scope.to_string(),
+        });
+        return Ok(cached_reply);
     }
 
-    println!("   🤖 正在进行AI分析: {}", log_tag);
+    info!("   🤖 正在进行AI分析: {}", log_tag);
 
-    let reply = context
+    let (reply, token_usage) = context
         .llm_client
         .prompt_without_react(prompt_sys, prompt_user)
         .await
         .map_err(|e| anyhow::anyhow!("AI分析失败: {}", e))?;
 
-    // 估算token使用情况
-    let input_text = format!("{} {}", prompt_sys, prompt_user);
-    let token_usage = estimate_token_usage(&input_text, &reply);
-
-    // 缓存结果 - 使用带token信息的方法
+    // 缓存结果 - 记录模型返回的token使用情况
     context
         .cache_manager
         .write()
@@ -69,27 +69,25 @@ pub async fn prompt_with_tools(
         .cache_manager
         .read()
         .await
-        .get::<serde_json::Value>(cache_scope, &prompt_key)
+        .get::<String>(cache_scope, &prompt_key)
         .await?
     {
-        println!("   ✅ 使用缓存的AI分析结果: {}", log_tag);
-        return Ok(cached_reply.to_string());
+        info!("   ✅ 使用缓存的AI分析结果: {}", log_tag);
+        context.emit(GeneratorEvent::CacheHit {
+            category: cache_scope.to_string(),
+        });
+        return Ok(cached_reply);
     }
 
-    println!("   🤖 正在进行AI分析: {}", log_tag);
+    info!("   🤖 正在进行AI分析: {}", log_tag);
 
-    let reply = context
+    let (reply, token_usage) = context
         .llm_client
         .prompt(prompt_sys, prompt_user)
         .await
         .map_err(|e| anyhow::anyhow!("AI分析失败: {}", e))?;
 
-    // 估算token使用情况
-    let input_text = format!("{} {}", prompt_sys, prompt_user);
-    let output_text = serde_json::to_string(&reply).unwrap_or_default();
-    let token_usage = estimate_token_usage(&input_text, &output_text);
-
-    // 缓存结果 - 使用带token信息的方法
+    // 缓存结果 - 记录模型返回的token使用情况
     context
         .cache_manager
         .write()
@@ -118,24 +116,22 @@ where
         .get::<T>(cache_scope, &prompt_key)
         .await?
     {
-        println!("   ✅ 使用缓存的AI分析结果: {}", log_tag);
+        info!("   ✅ 使用缓存的AI分析结果: {}", log_tag);
+        context.emit(GeneratorEvent::CacheHit {
+            category: cache_scope.to_string(),
+        });
         return Ok(cached_reply);
     }
 
-    println!("   🤖 正在进行AI分析: {}", log_tag);
+    info!("   🤖 正在进行AI分析: {}", log_tag);
 
-    let reply = context
+    let (reply, token_usage) = context
         .llm_client
         .extract::<T>(prompt_sys, prompt_user)
         .await
         .map_err(|e| anyhow::anyhow!("AI分析失败: {}", e))?;
 
-    // 估算token使用情况
-    let input_text = format!("{} {}", prompt_sys, prompt_user);
-    let output_text = serde_json::to_string(&reply).unwrap_or_default();
-    let token_usage = estimate_token_usage(&input_text, &output_text);
-
-    // 缓存结果 - 使用带token信息的方法
+    // 缓存结果 - 记录模型返回的token使用情况
     context
         .cache_manager
         .write()
@@ -148,6 +148,7 @@ pub struct User {
 - 数据关系图
 - 字段详细说明
 - 数据生命周期
+- 数据库迁移脚本（若代码洞察中存在`migration`类型的文件，需引用其路径并说明表结构的演进过程）
 
 ## 6. 部署视图 (Deployment View)
 - 部署架构设计
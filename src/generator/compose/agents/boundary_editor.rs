@@ -1,6 +1,7 @@
 use crate::generator::compose::memory::MemoryScope;
 use crate::generator::compose::types::AgentType;
 use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::memory::{MemoryScope as PreprocessMemoryScope, ScopedKeys};
 use crate::generator::research::memory::MemoryRetriever;
 use crate::generator::research::types::{
     APIBoundary, AgentType as ResearchAgentType, BoundaryAnalysisReport, CLIBoundary,
@@ -9,8 +10,12 @@ use crate::generator::research::types::{
 use crate::generator::step_forward_agent::{
     AgentDataConfig, DataSource, PromptTemplate, StepForwardAgent,
 };
+use crate::i18n::Localizer;
+use crate::types::api_surface::{ApiStability, ApiSurfaceReport};
+use crate::types::feature_flag::FeatureFlagReport;
 use anyhow::Result;
 use async_trait::async_trait;
+use std::collections::BTreeMap;
 
 /// 边界接口文档编辑器 - 将边界分析结果编排为标准化文档
 #[derive(Default)]
@@ -40,6 +45,7 @@ impl StepForwardAgent for BoundaryEditor {
                 DataSource::PROJECT_STRUCTURE,
                 DataSource::CODE_INSIGHTS,
                 DataSource::README_CONTENT,
+                DataSource::FEATURE_FLAGS,
             ],
         }
     }
@@ -88,7 +94,30 @@ impl StepForwardAgent for BoundaryEditor {
         let report: BoundaryAnalysisReport = serde_json::from_value(boundary_analysis)?;
 
         // 生成文档内容
-        let content = self.generate_boundary_documentation(&report);
+        let texts = context.config.localizer();
+        let mut content = self.generate_boundary_documentation(&report, &texts);
+
+        // 追加可配置特性章节
+        if let Some(feature_flags) = context
+            .get_from_memory::<FeatureFlagReport>(
+                PreprocessMemoryScope::PREPROCESS,
+                ScopedKeys::FEATURE_FLAGS,
+            )
+            .await
+        {
+            content.push_str(&self.generate_feature_flag_documentation(&feature_flags, &texts));
+        }
+
+        // 追加公开API稳定性章节
+        if let Some(api_surface) = context
+            .get_from_memory::<ApiSurfaceReport>(
+                PreprocessMemoryScope::PREPROCESS,
+                ScopedKeys::API_SURFACE,
+            )
+            .await
+        {
+            content.push_str(&self.generate_api_surface_documentation(&api_surface, &texts));
+        }
 
         // 存储到内存
         let value = serde_json::to_value(&content)?;
@@ -102,65 +131,84 @@ impl StepForwardAgent for BoundaryEditor {
 
 impl BoundaryEditor {
     /// 生成边界接口文档
-    fn generate_boundary_documentation(&self, report: &BoundaryAnalysisReport) -> String {
+    fn generate_boundary_documentation(
+        &self,
+        report: &BoundaryAnalysisReport,
+        texts: &Localizer,
+    ) -> String {
         let mut content = String::new();
-        content.push_str("# 系统边界接口文档\n\n");
-        content.push_str(
-            "本文档描述了系统的外部调用接口，包括CLI命令、API端点、配置参数等边界机制。\n\n",
-        );
+        content.push_str(&format!("# {}\n\n", texts.text("boundary.title")));
+        content.push_str(&format!("{}\n\n", texts.text("boundary.intro")));
 
         // 生成CLI接口文档
         if !report.cli_boundaries.is_empty() {
-            content.push_str(&self.generate_cli_documentation(&report.cli_boundaries));
+            content.push_str(&self.generate_cli_documentation(&report.cli_boundaries, texts));
         }
 
         // 生成API接口文档
         if !report.api_boundaries.is_empty() {
-            content.push_str(&self.generate_api_documentation(&report.api_boundaries));
+            content.push_str(&self.generate_api_documentation(&report.api_boundaries, texts));
         }
 
         // 生成Router路由文档
         if !report.router_boundaries.is_empty() {
-            content.push_str(&self.generate_router_documentation(&report.router_boundaries));
+            content.push_str(&self.generate_router_documentation(&report.router_boundaries, texts));
         }
 
         // 生成集成建议
         if !report.integration_suggestions.is_empty() {
             content.push_str(
-                &self.generate_integration_documentation(&report.integration_suggestions),
+                &self.generate_integration_documentation(&report.integration_suggestions, texts),
             );
         }
 
         // 添加分析置信度
         content.push_str(&format!(
-            "\n---\n\n**分析置信度**: {:.1}/10\n",
+            "\n---\n\n**{}**: {:.1}/10\n",
+            texts.text("boundary.confidence"),
             report.confidence_score
         ));
 
         content
     }
 
-    fn generate_cli_documentation(&self, cli_boundaries: &[CLIBoundary]) -> String {
+    fn generate_cli_documentation(
+        &self,
+        cli_boundaries: &[CLIBoundary],
+        texts: &Localizer,
+    ) -> String {
         if cli_boundaries.is_empty() {
             return String::new();
         }
 
         let mut content = String::new();
-        content.push_str("## 命令行接口 (CLI)\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("boundary.cli")));
 
         for cli in cli_boundaries {
             content.push_str(&format!("### {}\n\n", cli.command));
-            content.push_str(&format!("**描述**: {}\n\n", cli.description));
-            content.push_str(&format!("**源文件**: `{}`\n\n", cli.source_location));
+            content.push_str(&format!(
+                "**{}**: {}\n\n",
+                texts.text("label.description"),
+                cli.description
+            ));
+            content.push_str(&format!(
+                "**{}**: `{}`\n\n",
+                texts.text("label.source_file"),
+                cli.source_location
+            ));
 
             if !cli.arguments.is_empty() {
-                content.push_str("**参数**:\n\n");
+                content.push_str(&format!("**{}**:\n\n", texts.text("label.arguments")));
                 for arg in &cli.arguments {
-                    let required_text = if arg.required { "必需" } else { "可选" };
+                    let required_text = if arg.required {
+                        texts.text("label.required")
+                    } else {
+                        texts.text("label.optional")
+                    };
                     let default_text = arg
                         .default_value
                         .as_ref()
-                        .map(|v| format!(" (默认: `{}`)", v))
+                        .map(|v| format!(" ({}: `{}`)", texts.text("label.default"), v))
                         .unwrap_or_default();
                     content.push_str(&format!(
                         "- `{}` ({}): {} - {}{}\n",
@@ -171,18 +219,22 @@ impl BoundaryEditor {
             }
 
             if !cli.options.is_empty() {
-                content.push_str("**选项**:\n\n");
+                content.push_str(&format!("**{}**:\n\n", texts.text("label.options")));
                 for option in &cli.options {
                     let short_text = option
                         .short_name
                         .as_ref()
                         .map(|s| format!(", {}", s))
                         .unwrap_or_default();
-                    let required_text = if option.required { "必需" } else { "可选" };
+                    let required_text = if option.required {
+                        texts.text("label.required")
+                    } else {
+                        texts.text("label.optional")
+                    };
                     let default_text = option
                         .default_value
                         .as_ref()
-                        .map(|v| format!(" (默认: `{}`)", v))
+                        .map(|v| format!(" ({}: `{}`)", texts.text("label.default"), v))
                         .unwrap_or_default();
                     content.push_str(&format!(
                         "- `{}{}`({}): {} - {}{}\n",
@@ -198,7 +250,7 @@ impl BoundaryEditor {
             }
 
             if !cli.examples.is_empty() {
-                content.push_str("**使用示例**:\n\n");
+                content.push_str(&format!("**{}**:\n\n", texts.text("label.usage_examples")));
                 for example in &cli.examples {
                     content.push_str(&format!("```bash\n{}\n```\n\n", example));
                 }
@@ -208,33 +260,60 @@ impl BoundaryEditor {
         content
     }
 
-    fn generate_api_documentation(&self, api_boundaries: &[APIBoundary]) -> String {
+    fn generate_api_documentation(
+        &self,
+        api_boundaries: &[APIBoundary],
+        texts: &Localizer,
+    ) -> String {
         if api_boundaries.is_empty() {
             return String::new();
         }
 
         let mut content = String::new();
-        content.push_str("## API接口\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("boundary.api")));
 
         for api in api_boundaries {
             content.push_str(&format!("### {} {}\n\n", api.method, api.endpoint));
-            content.push_str(&format!("**描述**: {}\n\n", api.description));
-            content.push_str(&format!("**源文件**: `{}`\n\n", api.source_location));
+            content.push_str(&format!(
+                "**{}**: {}\n\n",
+                texts.text("label.description"),
+                api.description
+            ));
+            content.push_str(&format!(
+                "**{}**: `{}`\n\n",
+                texts.text("label.source_file"),
+                api.source_location
+            ));
 
             if let Some(request_format) = &api.request_format {
-                content.push_str(&format!("**请求格式**: {}\n\n", request_format));
+                content.push_str(&format!(
+                    "**{}**: {}\n\n",
+                    texts.text("label.request_format"),
+                    request_format
+                ));
             }
 
             if let Some(response_format) = &api.response_format {
-                content.push_str(&format!("**响应格式**: {}\n\n", response_format));
+                content.push_str(&format!(
+                    "**{}**: {}\n\n",
+                    texts.text("label.response_format"),
+                    response_format
+                ));
             }
 
             if let Some(auth) = &api.authentication {
-                content.push_str(&format!("**认证方式**: {}\n\n", auth));
+                content.push_str(&format!(
+                    "**{}**: {}\n\n",
+                    texts.text("label.authentication"),
+                    auth
+                ));
             }
 
             // 🆕 添加 cURL 调用示例
-            content.push_str("**cURL 调用示例**:\n\n```bash\n");
+            content.push_str(&format!(
+                "**{}**:\n\n```bash\n",
+                texts.text("label.curl_example")
+            ));
             match api.method.as_str() {
                 "GET" => {
                     content.push_str(&format!("curl -X GET 'http://localhost:3000{}'\n", api.endpoint));
@@ -262,7 +341,10 @@ impl BoundaryEditor {
 
             // 🆕 添加客户端代码示例（Rust）
             if api.method != "GET" {
-                content.push_str("**Rust 客户端示例**:\n\n```rust\n");
+                content.push_str(&format!(
+                    "**{}**:\n\n```rust\n",
+                    texts.text("label.rust_client_example")
+                ));
                 content.push_str(&format!(
                     "let response = client.{}(\"http://localhost:3000{}\")\n",
                     api.method.to_lowercase(),
@@ -276,30 +358,49 @@ impl BoundaryEditor {
             }
 
             // 🆕 添加响应示例
-            content.push_str("**成功响应示例**:\n\n```json\n{\n  \"status\": \"success\",\n  \"data\": {}\n}\n```\n\n");
+            content.push_str(&format!(
+                "**{}**:\n\n```json\n{{\n  \"status\": \"success\",\n  \"data\": {{}}\n}}\n```\n\n",
+                texts.text("label.success_response")
+            ));
             
             // 🆕 添加错误响应示例
-            content.push_str("**错误响应示例**:\n\n```json\n{\n  \"status\": \"error\",\n  \"message\": \"错误描述\",\n  \"code\": \"ERROR_CODE\"\n}\n```\n\n");
+            content.push_str(&format!(
+                "**{}**:\n\n```json\n{{\n  \"status\": \"error\",\n  \"message\": \"{}\",\n  \"code\": \"ERROR_CODE\"\n}}\n```\n\n",
+                texts.text("label.error_response"),
+                texts.text("label.error_message")
+            ));
         }
 
         content
     }
 
-    fn generate_router_documentation(&self, router_boundaries: &[RouterBoundary]) -> String {
+    fn generate_router_documentation(
+        &self,
+        router_boundaries: &[RouterBoundary],
+        texts: &Localizer,
+    ) -> String {
         if router_boundaries.is_empty() {
             return String::new();
         }
 
         let mut content = String::new();
-        content.push_str("## Router路由\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("boundary.router")));
 
         for router in router_boundaries {
             content.push_str(&format!("### {}\n\n", router.path));
-            content.push_str(&format!("**描述**: {}\n\n", router.description));
-            content.push_str(&format!("**源文件**: `{}`\n\n", router.source_location));
+            content.push_str(&format!(
+                "**{}**: {}\n\n",
+                texts.text("label.description"),
+                router.description
+            ));
+            content.push_str(&format!(
+                "**{}**: `{}`\n\n",
+                texts.text("label.source_file"),
+                router.source_location
+            ));
 
             if !router.params.is_empty() {
-                content.push_str("**参数**:\n\n");
+                content.push_str(&format!("**{}**:\n\n", texts.text("label.arguments")));
                 for param in &router.params {
                     content.push_str(&format!(
                         "- `{}` ({}): {}\n",
@@ -315,25 +416,26 @@ impl BoundaryEditor {
     fn generate_integration_documentation(
         &self,
         integration_suggestions: &[IntegrationSuggestion],
+        texts: &Localizer,
     ) -> String {
         if integration_suggestions.is_empty() {
             return String::new();
         }
 
         let mut content = String::new();
-        content.push_str("## 集成建议\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("boundary.integration")));
 
         for suggestion in integration_suggestions {
             content.push_str(&format!("### {}\n\n", suggestion.integration_type));
             content.push_str(&format!("{}\n\n", suggestion.description));
 
             if !suggestion.example_code.is_empty() {
-                content.push_str("**示例代码**:\n\n");
+                content.push_str(&format!("**{}**:\n\n", texts.text("label.example_code")));
                 content.push_str(&format!("```\n{}\n```\n\n", suggestion.example_code));
             }
 
             if !suggestion.best_practices.is_empty() {
-                content.push_str("**最佳实践**:\n\n");
+                content.push_str(&format!("**{}**:\n\n", texts.text("label.best_practices")));
                 for practice in &suggestion.best_practices {
                     content.push_str(&format!("- {}\n", practice));
                 }
@@ -343,4 +445,237 @@ impl BoundaryEditor {
 
         content
     }
+
+    fn generate_feature_flag_documentation(
+        &self,
+        report: &FeatureFlagReport,
+        texts: &Localizer,
+    ) -> String {
+        if report.is_empty() {
+            return String::new();
+        }
+
+        let mut content = String::new();
+        content.push_str(&format!("\n## {}\n\n", texts.text("boundary.features")));
+        content.push_str(&format!("{}\n\n", texts.text("boundary.features_intro")));
+
+        if !report.cargo_features.is_empty() {
+            content.push_str(&format!(
+                "### {}\n\n",
+                texts.text("boundary.cargo_features")
+            ));
+            content.push_str(&format!("{}\n", texts.text("boundary.features_table")));
+            content.push_str("|------|----------|----------|----------|\n");
+            for feature in &report.cargo_features {
+                let enables = if feature.enables.is_empty() {
+                    "-".to_string()
+                } else {
+                    feature
+                        .enables
+                        .iter()
+                        .map(|item| format!("`{}`", item))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                content.push_str(&format!(
+                    "| `{}` | {} | {} | `{}` |\n",
+                    feature.name,
+                    if feature.is_default {
+                        texts.text("label.yes")
+                    } else {
+                        texts.text("label.no")
+                    },
+                    enables,
+                    feature.manifest_path
+                ));
+            }
+            content.push('\n');
+        }
+
+        let gate_sections = [
+            ("cfg_feature", texts.text("boundary.cfg_gates")),
+            ("env", texts.text("boundary.env_vars")),
+        ];
+        for (gate_type, title) in gate_sections {
+            let mut gates: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+            for gate in report.gates.iter().filter(|g| g.gate_type == gate_type) {
+                gates
+                    .entry(gate.name.as_str())
+                    .or_default()
+                    .push(format!("`{}:{}`", gate.file_path, gate.line_number));
+            }
+            if gates.is_empty() {
+                continue;
+            }
+
+            content.push_str(&format!("### {}\n\n", title));
+            for (name, locations) in gates {
+                content.push_str(&format!("- `{}`: {}\n", name, locations.join(", ")));
+            }
+            content.push('\n');
+        }
+
+        content
+    }
+
+    /// 生成公开API稳定性章节：公开接口统计，以及被标记为弃用、内部或不稳定的接口
+    fn generate_api_surface_documentation(
+        &self,
+        report: &ApiSurfaceReport,
+        texts: &Localizer,
+    ) -> String {
+        if report.is_empty() {
+            return String::new();
+        }
+
+        let stability_text = |stability: ApiStability| match stability {
+            ApiStability::Deprecated => texts.text("stability.deprecated"),
+            ApiStability::Internal => texts.text("stability.internal"),
+            ApiStability::Unstable => texts.text("stability.unstable"),
+        };
+
+        let mut content = String::new();
+        content.push_str(&format!("\n## {}\n\n", texts.text("boundary.api_surface")));
+        content.push_str(&format!("{}\n\n", texts.text("boundary.api_surface_intro")));
+        content.push_str(&format!(
+            "**{}**: {} ({}: {}, {}: {}, {}: {})\n\n",
+            texts.text("label.public_items"),
+            report.public_items,
+            stability_text(ApiStability::Deprecated),
+            report.count(ApiStability::Deprecated),
+            stability_text(ApiStability::Internal),
+            report.count(ApiStability::Internal),
+            stability_text(ApiStability::Unstable),
+            report.count(ApiStability::Unstable)
+        ));
+
+        if !report.flagged.is_empty() {
+            content.push_str(&format!("{}\n", texts.text("boundary.api_surface_table")));
+            content.push_str("|------|------|--------|------|------|\n");
+            for item in &report.flagged {
+                let location = match item.line_number {
+                    Some(line_number) => format!("{}:{}", item.file_path, line_number),
+                    None => item.file_path.clone(),
+                };
+                content.push_str(&format!(
+                    "| `{}` | {} | {} | `{}` | `{}` |\n",
+                    item.name,
+                    item.interface_type,
+                    stability_text(item.stability),
+                    location,
+                    item.marker.replace('|', "\\|")
+                ));
+            }
+            content.push('\n');
+        }
+
+        content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::research::types::{CLIArgument, CLIOption, RouterParam};
+    use crate::i18n::TargetLanguage;
+    use crate::types::api_surface::ApiSurfaceItem;
+    use crate::types::feature_flag::{CargoFeature, FeatureGate};
+
+    fn contains_chinese(text: &str) -> bool {
+        text.chars().any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c))
+    }
+
+    #[test]
+    fn test_english_output_has_no_chinese_section_headers() {
+        let report = BoundaryAnalysisReport {
+            cli_boundaries: vec![CLIBoundary {
+                command: "demo run".to_string(),
+                description: "Run the demo".to_string(),
+                arguments: vec![CLIArgument {
+                    name: "path".to_string(),
+                    description: "Project path".to_string(),
+                    required: true,
+                    default_value: None,
+                    value_type: "string".to_string(),
+                }],
+                options: vec![CLIOption {
+                    name: "--verbose".to_string(),
+                    short_name: Some("-v".to_string()),
+                    description: "Verbose output".to_string(),
+                    required: false,
+                    default_value: Some("false".to_string()),
+                    value_type: "bool".to_string(),
+                }],
+                examples: vec!["demo run .".to_string()],
+                source_location: "src/main.rs".to_string(),
+            }],
+            api_boundaries: vec![APIBoundary {
+                endpoint: "/users".to_string(),
+                method: "POST".to_string(),
+                description: "Create a user".to_string(),
+                request_format: Some("JSON".to_string()),
+                response_format: Some("JSON".to_string()),
+                authentication: Some("Bearer".to_string()),
+                source_location: "src/api.rs".to_string(),
+            }],
+            router_boundaries: vec![RouterBoundary {
+                path: "/home".to_string(),
+                description: "Home page".to_string(),
+                source_location: "src/router.ts".to_string(),
+                params: vec![RouterParam {
+                    key: "id".to_string(),
+                    value_type: "string".to_string(),
+                    description: "Item id".to_string(),
+                }],
+            }],
+            integration_suggestions: vec![IntegrationSuggestion {
+                integration_type: "SDK".to_string(),
+                description: "Use the SDK".to_string(),
+                example_code: "demo::run()".to_string(),
+                best_practices: vec!["Retry on failure".to_string()],
+            }],
+            confidence_score: 8.0,
+        };
+        let feature_flags = FeatureFlagReport {
+            cargo_features: vec![CargoFeature {
+                name: "tls".to_string(),
+                enables: vec![],
+                is_default: true,
+                manifest_path: "Cargo.toml".to_string(),
+            }],
+            gates: vec![FeatureGate {
+                name: "DEMO_MODE".to_string(),
+                gate_type: "env".to_string(),
+                file_path: "src/main.rs".to_string(),
+                line_number: 3,
+            }],
+        };
+        let api_surface = ApiSurfaceReport {
+            public_items: 4,
+            flagged: vec![ApiSurfaceItem {
+                name: "old_run".to_string(),
+                interface_type: "function".to_string(),
+                file_path: "src/lib.rs".to_string(),
+                line_number: Some(12),
+                stability: ApiStability::Deprecated,
+                marker: "#[deprecated]".to_string(),
+            }],
+        };
+
+        let texts = Localizer::new(TargetLanguage::English, TargetLanguage::English);
+        let mut content = BoundaryEditor.generate_boundary_documentation(&report, &texts);
+        content
+            .push_str(&BoundaryEditor.generate_feature_flag_documentation(&feature_flags, &texts));
+        content.push_str(&BoundaryEditor.generate_api_surface_documentation(&api_surface, &texts));
+
+        assert!(content.contains("# System Boundary Interfaces"));
+        assert!(content.contains("## Command-Line Interface (CLI)"));
+        assert!(content.contains("### Environment Variables"));
+        assert!(content.contains("| `old_run` | function | Deprecated | `src/lib.rs:12` |"));
+        assert!(!contains_chinese(&content), "{}", content);
+
+        let texts = Localizer::new(TargetLanguage::Chinese, TargetLanguage::English);
+        let content = BoundaryEditor.generate_boundary_documentation(&report, &texts);
+        assert!(content.contains("## 命令行接口 (CLI)"));
+    }
 }
@@ -0,0 +1,174 @@
+use crate::generator::compose::memory::MemoryScope;
+use crate::generator::compose::types::AgentType;
+use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::memory::{MemoryScope as PreprocessMemoryScope, ScopedKeys};
+use crate::generator::step_forward_agent::{
+    AgentDataConfig, DataSource, FormatterConfig, LLMCallMode, PromptTemplate, StepForwardAgent,
+};
+use crate::i18n::Localizer;
+use crate::types::build_system::{BuildConfigFile, BuildSystemReport};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// 构建与CI文档编辑器 - 将识别到的构建系统与CI配置编排为文档，不调用LLM
+#[derive(Default)]
+pub struct BuildSystemEditor;
+
+#[async_trait]
+impl StepForwardAgent for BuildSystemEditor {
+    type Output = String;
+
+    fn agent_type(&self) -> String {
+        AgentType::BuildSystem.to_string()
+    }
+
+    fn memory_scope_key(&self) -> String {
+        MemoryScope::DOCUMENTATION.to_string()
+    }
+
+    fn data_config(&self) -> AgentDataConfig {
+        AgentDataConfig {
+            required_sources: vec![DataSource::BUILD_SYSTEM],
+            optional_sources: vec![],
+        }
+    }
+
+    fn prompt_template(&self) -> PromptTemplate {
+        PromptTemplate {
+            system_prompt: "你是一个专业的软件工程文档编写专家，负责整理项目的构建与持续集成流程"
+                .to_string(),
+            opening_instruction: "基于以下构建与CI配置，生成构建与持续集成文档：".to_string(),
+            closing_instruction: String::new(),
+            llm_call_mode: LLMCallMode::Prompt,
+            formatter_config: FormatterConfig::default(),
+        }
+    }
+
+    /// 自定义execute实现，直接生成文档而不使用LLM
+    async fn execute(&self, context: &GeneratorContext) -> Result<Self::Output> {
+        let report = context
+            .get_from_memory::<BuildSystemReport>(
+                PreprocessMemoryScope::PREPROCESS,
+                ScopedKeys::BUILD_SYSTEM,
+            )
+            .await
+            .unwrap_or_default();
+
+        let texts = context.config.localizer();
+        let content = self.generate_build_documentation(&report, &texts);
+        context
+            .store_to_memory(&self.memory_scope_key(), &self.agent_type(), &content)
+            .await?;
+
+        Ok(content)
+    }
+}
+
+impl BuildSystemEditor {
+    /// 生成构建与CI文档，未识别到任何配置时返回空字符串
+    pub fn generate_build_documentation(
+        &self,
+        report: &BuildSystemReport,
+        texts: &Localizer,
+    ) -> String {
+        if report.is_empty() {
+            return String::new();
+        }
+
+        let mut content = String::new();
+        content.push_str(&format!("# {}\n\n", texts.text("build.title")));
+        content.push_str(&format!("{}\n\n", texts.text("build.intro")));
+
+        if !report.ci.is_empty() {
+            content.push_str(&format!("## {}\n\n", texts.text("build.ci")));
+            for file in &report.ci {
+                content.push_str(&Self::generate_config_file_section(file, texts));
+            }
+        }
+
+        if !report.build_tools.is_empty() {
+            content.push_str(&format!("## {}\n\n", texts.text("build.tools")));
+            for file in &report.build_tools {
+                content.push_str(&Self::generate_config_file_section(file, texts));
+            }
+        }
+
+        if !report.package_scripts.is_empty() {
+            content.push_str(&format!("## {}\n\n", texts.text("build.scripts")));
+            content.push_str(&format!("{}\n", texts.text("build.scripts_table")));
+            content.push_str("|------|------|------|\n");
+            for script in &report.package_scripts {
+                content.push_str(&format!(
+                    "| `{}` | `{}` | `{}` |\n",
+                    script.name,
+                    script.command.replace('|', "\\|"),
+                    script.manifest_path
+                ));
+            }
+            content.push('\n');
+        }
+
+        content
+    }
+
+    fn generate_config_file_section(file: &BuildConfigFile, texts: &Localizer) -> String {
+        let mut content = format!(
+            "### {}\n\n**{}**: `{}`\n\n",
+            file.tool,
+            texts.text("label.config_file"),
+            file.file_path
+        );
+        for detail in &file.details {
+            content.push_str(&format!("- {}\n", detail));
+        }
+        if !file.details.is_empty() {
+            content.push('\n');
+        }
+        content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::preprocess::extractors::build_system_extractor;
+    use crate::i18n::TargetLanguage;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_github_actions_and_dockerfile_yield_build_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let workflows_dir = temp_dir.path().join(".github/workflows");
+        std::fs::create_dir_all(&workflows_dir).unwrap();
+        std::fs::write(
+            workflows_dir.join("ci.yml"),
+            "name: CI\non: [push, pull_request]\njobs:\n  test:\n    runs-on: ubuntu-latest\n    steps:\n      - run: cargo test\n  release:\n    runs-on: ubuntu-latest\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("Dockerfile"),
+            "FROM rust:1.85 AS builder\nRUN cargo build --release\nFROM debian:bookworm-slim\n",
+        )
+        .unwrap();
+
+        let report = build_system_extractor::extract(temp_dir.path(), 1024 * 1024).await;
+        let texts = Localizer::new(TargetLanguage::Chinese, TargetLanguage::English);
+        let content = BuildSystemEditor.generate_build_documentation(&report, &texts);
+
+        assert!(!content.is_empty());
+        assert!(content.contains("## 持续集成"));
+        assert!(content.contains("`.github/workflows/ci.yml`"));
+        assert!(content.contains("- 触发条件: push, pull_request"));
+        assert!(content.contains("- 任务: release"));
+        assert!(content.contains("## 构建工具"));
+        assert!(content.contains("- 基础镜像: rust:1.85（阶段 builder）"));
+
+        let empty_dir = TempDir::new().unwrap();
+        let report = build_system_extractor::extract(empty_dir.path(), 1024 * 1024).await;
+        assert!(
+            BuildSystemEditor
+                .generate_build_documentation(&report, &texts)
+                .is_empty()
+        );
+    }
+}
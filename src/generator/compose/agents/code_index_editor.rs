@@ -3,6 +3,8 @@ use crate::generator::compose::types::AgentType;
 use crate::generator::step_forward_agent::{
     AgentDataConfig, DataSource, FormatterConfig, LLMCallMode, PromptTemplate, StepForwardAgent,
 };
+use crate::i18n::Localizer;
+use crate::types::duplication::DuplicationReport;
 
 #[derive(Default)]
 pub struct CodeIndexEditor;
@@ -76,6 +78,7 @@ impl StepForwardAgent for CodeIndexEditor {
 - **接口索引**：包含所有接口的定义和实现
 - **数据结构索引**：包含所有数据结构定义
 - **常量索引**：包含重要的常量和配置项
+- **使用示例**：源码洞察中提供了接口使用示例（来自测试代码）时，在对应接口下附上示例代码及其来源位置
 
 ### 3. 索引格式要求
 - 使用表格形式展示，便于快速浏览
@@ -242,3 +245,61 @@ let instance = StructName {
         }
     }
 }
+
+/// 生成潜在重复代码章节，未发现疑似重复的文件时返回None
+pub fn render_duplication_section(report: &DuplicationReport, texts: &Localizer) -> Option<String> {
+    if report.is_empty() {
+        return None;
+    }
+
+    let mut section = format!("\n## {}\n\n", texts.text("code_index.duplication"));
+    section.push_str(&format!(
+        "{}\n\n",
+        texts.text("code_index.duplication_intro")
+    ));
+    section.push_str(&format!("{}\n", texts.text("code_index.duplication_table")));
+    section.push_str("|------|--------|\n");
+    for group in &report.groups {
+        let files: Vec<String> = group
+            .files
+            .iter()
+            .map(|file| format!("`{}`", file))
+            .collect();
+        section.push_str(&format!(
+            "| {} | {:.1}% |\n",
+            files.join("<br>"),
+            group.similarity * 100.0
+        ));
+    }
+    Some(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::TargetLanguage;
+    use crate::types::duplication::DuplicateGroup;
+
+    #[test]
+    fn test_duplication_section_lists_groups() {
+        let texts = Localizer::new(TargetLanguage::English, TargetLanguage::English);
+        let report = DuplicationReport {
+            threshold: 0.95,
+            groups: vec![DuplicateGroup {
+                files: vec![
+                    "src/config/loader.rs".to_string(),
+                    "src/legacy/config_loader.rs".to_string(),
+                ],
+                similarity: 0.987,
+            }],
+        };
+
+        let section = render_duplication_section(&report, &texts).unwrap();
+
+        assert!(section.contains("## Potential Duplication"));
+        assert!(
+            section.contains("| `src/config/loader.rs`<br>`src/legacy/config_loader.rs` | 98.7% |")
+        );
+        assert!(render_duplication_section(&DuplicationReport::default(), &texts).is_none());
+    }
+}
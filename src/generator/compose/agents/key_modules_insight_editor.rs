@@ -1,13 +1,20 @@
 use crate::generator::compose::memory::MemoryScope;
 use crate::generator::context::GeneratorContext;
 use crate::generator::outlet::DocTree;
+use crate::generator::preprocess::memory::{MemoryScope as PreprocessMemoryScope, ScopedKeys};
 use crate::generator::research::memory::MemoryRetriever;
 use crate::generator::research::types::{AgentType as ResearchAgentType, KeyModuleReport};
 use crate::generator::step_forward_agent::{
     AgentDataConfig, DataSource, FormatterConfig, LLMCallMode, PromptTemplate, StepForwardAgent,
 };
+use crate::i18n::Localizer;
+use crate::types::code::{CodeInsight, CodePurpose};
+use crate::types::code_releationship::RelationshipAnalysis;
 use crate::utils::threads::do_parallel_with_limit;
 use anyhow::Result;
+use log::info;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
 
 #[derive(Default)]
 pub struct KeyModulesInsightEditor {}
@@ -19,9 +26,36 @@ impl KeyModulesInsightEditor {
             .await
         {
             let insight_reports: Vec<KeyModuleReport> = serde_json::from_value(value)?;
-            let max_parallels = context.config.llm.max_parallels;
+            let code_insights = context
+                .get_from_memory::<Vec<CodeInsight>>(
+                    PreprocessMemoryScope::PREPROCESS,
+                    ScopedKeys::CODE_INSIGHTS,
+                )
+                .await
+                .unwrap_or_default();
+            let insight_reports = sort_reports_by_purpose(insight_reports, &code_insights);
+            let relationships = context
+                .get_from_memory::<RelationshipAnalysis>(
+                    PreprocessMemoryScope::PREPROCESS,
+                    ScopedKeys::RELATIONSHIPS,
+                )
+                .await
+                .unwrap_or_default();
+            let related_modules = find_related_modules(&insight_reports, &relationships);
+            let module_links: Vec<(String, String)> = insight_reports
+                .iter()
+                .enumerate()
+                .map(|(index, report)| {
+                    (
+                        report.domain_name.clone(),
+                        module_file_name(index, &report.domain_name),
+                    )
+                })
+                .collect();
+            let texts = context.config.localizer();
+            let max_parallels = context.config.llm.effective_max_parallels();
 
-            println!(
+            info!(
                 "🚀 启动并发分析insight reports，最大并发数：{}",
                 max_parallels
             );
@@ -29,19 +63,19 @@ impl KeyModulesInsightEditor {
             // 创建并发任务
             let analysis_futures: Vec<_> = insight_reports
                 .into_iter()
-                .map(|insight_report| {
+                .enumerate()
+                .map(|(index, insight_report)| {
                     let insight_key = format!(
                         "{}_{}",
                         ResearchAgentType::KeyModulesInsight,
                         &insight_report.domain_name
                     );
-                    let domain_name = insight_report.domain_name.clone();
                     let kmie = KeyModuleInsightEditor::new(insight_key.clone(), insight_report);
                     let context_clone = context.clone();
 
                     Box::pin(async move {
                         let result = kmie.execute(&context_clone).await;
-                        (insight_key, domain_name, result)
+                        (index, insight_key, result)
                     })
                 })
                 .collect();
@@ -50,18 +84,32 @@ impl KeyModulesInsightEditor {
             let analysis_results = do_parallel_with_limit(analysis_futures, max_parallels).await;
 
             // 处理结果并更新doc_tree
-            for (insight_key, domain_name, result) in analysis_results {
+            for (index, insight_key, result) in analysis_results {
                 result?; // 检查是否有错误
 
+                // 基于依赖关系追加相关模块章节，恢复运行时已追加过的文档不再重复追加
+                if let Some(section) =
+                    render_related_modules(&related_modules[index], &module_links, &texts)
+                    && let Some(mut document) = context
+                        .get_from_memory::<String>(MemoryScope::DOCUMENTATION, &insight_key)
+                        .await
+                    && !document.contains(section.trim_start())
+                {
+                    document.push_str(&section);
+                    context
+                        .store_to_memory(MemoryScope::DOCUMENTATION, &insight_key, document)
+                        .await?;
+                }
+
                 doc_tree.insert(
                     &insight_key,
                     format!(
-                        "{}/{}.md",
+                        "{}/{}",
                         context
                             .config
                             .target_language
                             .get_directory_name("deep_exploration"),
-                        &domain_name
+                        &module_links[index].1
                     )
                     .as_str(),
                 );
@@ -72,6 +120,143 @@ impl KeyModulesInsightEditor {
     }
 }
 
+/// 按照模块关联代码的用途优先级排序，同一优先级内按重要性降序排列，
+/// 使入口模块排在最前，其次是服务/接口类模块，工具类模块靠后
+fn sort_reports_by_purpose(
+    reports: Vec<KeyModuleReport>,
+    code_insights: &[CodeInsight],
+) -> Vec<KeyModuleReport> {
+    let mut ranked: Vec<((u8, f64), KeyModuleReport)> = reports
+        .into_iter()
+        .map(|report| (rank_report(&report, code_insights), report))
+        .collect();
+
+    // 稳定排序，优先级和重要性都相同的模块保持调研输出的原有顺序
+    ranked.sort_by(
+        |((a_priority, a_importance), _), ((b_priority, b_importance), _)| {
+            a_priority.cmp(b_priority).then(
+                b_importance
+                    .partial_cmp(a_importance)
+                    .unwrap_or(Ordering::Equal),
+            )
+        },
+    );
+
+    ranked.into_iter().map(|(_, report)| report).collect()
+}
+
+/// 计算模块的排序依据：关联文件中最高的用途优先级，以及最高的重要性分数
+fn rank_report(report: &KeyModuleReport, code_insights: &[CodeInsight]) -> (u8, f64) {
+    let mut priority = CodePurpose::Other.compose_priority();
+    let mut importance = 0.0_f64;
+
+    for insight in code_insights {
+        let file_path = insight.code_dossier.file_path.to_string_lossy();
+        if is_associated(report, &file_path) {
+            priority = priority.min(insight.code_dossier.code_purpose.compose_priority());
+            importance = importance.max(insight.code_dossier.importance_score);
+        }
+    }
+
+    (priority, importance)
+}
+
+/// 文件是否属于该模块（路径互为后缀即视为匹配，兼容相对路径与`./`前缀的差异）
+fn is_associated(report: &KeyModuleReport, file_path: &str) -> bool {
+    let file_path = file_path.replace('\\', "/");
+    let file_path = file_path.strip_prefix("./").unwrap_or(&file_path);
+    if file_path.is_empty() {
+        return false;
+    }
+    report.associated_files.iter().any(|path| {
+        let path = path.replace('\\', "/");
+        let path = path.strip_prefix("./").unwrap_or(&path);
+        !path.is_empty() && (ends_with_path(file_path, path) || ends_with_path(path, file_path))
+    })
+}
+
+/// 路径是否以另一路径结尾，只在`/`分隔处匹配，避免`src/domain.rs`匹配到`main.rs`
+fn ends_with_path(path: &str, suffix: &str) -> bool {
+    path == suffix || path.ends_with(&format!("/{}", suffix))
+}
+
+/// 模块文档的文件名，如`01-用户管理域.md`，序号前缀使输出目录按排序后的模块顺序列出文档
+fn module_file_name(index: usize, domain_name: &str) -> String {
+    format!("{:02}-{}.md", index + 1, domain_name)
+}
+
+/// 单个模块依赖的模块与依赖它的模块（均为模块列表中的下标）
+#[derive(Debug, Default, PartialEq)]
+struct RelatedModules {
+    depends_on: BTreeSet<usize>,
+    depended_by: BTreeSet<usize>,
+}
+
+/// 将文件级依赖边映射到模块，计算各模块的出向与入向相关模块，模块内部的依赖不计入
+fn find_related_modules(
+    reports: &[KeyModuleReport],
+    relationships: &RelationshipAnalysis,
+) -> Vec<RelatedModules> {
+    let mut related: Vec<RelatedModules> =
+        reports.iter().map(|_| RelatedModules::default()).collect();
+    let owners = |file_path: &str| -> Vec<usize> {
+        reports
+            .iter()
+            .enumerate()
+            .filter(|(_, report)| is_associated(report, file_path))
+            .map(|(index, _)| index)
+            .collect()
+    };
+
+    for dependency in &relationships.core_dependencies {
+        let from_modules = owners(&dependency.from);
+        let to_modules = owners(&dependency.to);
+        for &from in &from_modules {
+            for &to in to_modules.iter().filter(|&&to| to != from) {
+                related[from].depends_on.insert(to);
+                related[to].depended_by.insert(from);
+            }
+        }
+    }
+    related
+}
+
+/// 渲染相关模块章节，链接到同目录下的模块文档；没有任何相关模块时返回None
+fn render_related_modules(
+    related: &RelatedModules,
+    module_links: &[(String, String)],
+    texts: &Localizer,
+) -> Option<String> {
+    if related.depends_on.is_empty() && related.depended_by.is_empty() {
+        return None;
+    }
+    let links = |indices: &BTreeSet<usize>| {
+        indices
+            .iter()
+            .map(|&index| {
+                let (name, file_name) = &module_links[index];
+                format!("[{}]({})", name, file_name.replace(' ', "%20"))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut section = format!("\n\n## {}\n\n", texts.text("module.related"));
+    for (label, indices) in [
+        ("module.depends_on", &related.depends_on),
+        ("module.depended_by", &related.depended_by),
+    ] {
+        if !indices.is_empty() {
+            section.push_str(&format!(
+                "- **{}**: {}\n",
+                texts.text(label),
+                links(indices)
+            ));
+        }
+    }
+    Some(section)
+}
+
 struct KeyModuleInsightEditor {
     insight_key: String,
     report: KeyModuleReport,
@@ -255,3 +440,230 @@ let instance = StructName {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::outlet::{DiskOutlet, Outlet};
+    use crate::i18n::TargetLanguage;
+    use crate::types::code::{CodeComplexity, CodeDossier};
+    use crate::types::code_releationship::{CoreDependency, DependencyType};
+    use std::path::PathBuf;
+
+    fn report(domain_name: &str, associated_files: &[&str]) -> KeyModuleReport {
+        KeyModuleReport {
+            domain_name: domain_name.to_string(),
+            module_name: domain_name.to_string(),
+            module_description: String::new(),
+            interaction: String::new(),
+            implementation: String::new(),
+            associated_files: associated_files.iter().map(|f| f.to_string()).collect(),
+            flowchart_mermaid: String::new(),
+            sequence_diagram_mermaid: String::new(),
+        }
+    }
+
+    fn insight(file_path: &str, code_purpose: CodePurpose, importance_score: f64) -> CodeInsight {
+        CodeInsight {
+            code_dossier: CodeDossier {
+                name: file_path.to_string(),
+                file_path: PathBuf::from(file_path),
+                source_summary: String::new(),
+                code_purpose,
+                importance_score,
+                description: None,
+                functions: vec![],
+                interfaces: vec![],
+            },
+            detailed_description: String::new(),
+            responsibilities: vec![],
+            interfaces: vec![],
+            dependencies: vec![],
+            complexity_metrics: Some(CodeComplexity {
+                cyclomatic_complexity: 0.0,
+                lines_of_code: 0,
+                number_of_functions: 0,
+                number_of_classes: 0,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_saved_module_documents_follow_purpose_order() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = crate::config::Config {
+            output_path: temp_dir.path().join("docs"),
+            internal_path: temp_dir.path().join(".litho"),
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config.clone()).unwrap();
+
+        let reports = vec![
+            report("工具域", &["src/utils/strings.rs"]),
+            report("次要服务", &["src/service/a.rs"]),
+            report("核心服务", &["src/service/b.rs"]),
+            report("入口域", &["src/main.rs"]),
+        ];
+        let insights = vec![
+            insight("src/utils/strings.rs", CodePurpose::Util, 0.9),
+            insight("src/service/a.rs", CodePurpose::Service, 0.4),
+            insight("src/service/b.rs", CodePurpose::Service, 0.8),
+            insight("src/main.rs", CodePurpose::Entry, 0.3),
+        ];
+        context
+            .store_research(
+                &ResearchAgentType::KeyModulesInsight.to_string(),
+                serde_json::to_value(&reports).unwrap(),
+            )
+            .await
+            .unwrap();
+        context
+            .store_to_memory(
+                PreprocessMemoryScope::PREPROCESS,
+                ScopedKeys::CODE_INSIGHTS,
+                &insights,
+            )
+            .await
+            .unwrap();
+        // 模块文档已在内存中，编辑器直接复用，无需调用模型
+        for report in &reports {
+            context
+                .store_to_memory(
+                    MemoryScope::DOCUMENTATION,
+                    &format!(
+                        "{}_{}",
+                        ResearchAgentType::KeyModulesInsight,
+                        report.domain_name
+                    ),
+                    format!("# {}", report.domain_name),
+                )
+                .await
+                .unwrap();
+        }
+
+        let mut doc_tree = DocTree::default();
+        KeyModulesInsightEditor::default()
+            .execute(&context, &mut doc_tree)
+            .await
+            .unwrap();
+        DiskOutlet::new(doc_tree).save(&context).await.unwrap();
+
+        let module_dir = config.output_path.join(
+            config
+                .target_language
+                .get_directory_name("deep_exploration"),
+        );
+        let mut file_names: Vec<String> = std::fs::read_dir(module_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        file_names.sort();
+
+        // 入口模块排在最前，同为服务模块时重要性高的在前，工具模块排在最后
+        assert_eq!(
+            file_names,
+            vec![
+                "01-入口域.md",
+                "02-核心服务.md",
+                "03-次要服务.md",
+                "04-工具域.md"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suffix_match_respects_path_boundary() {
+        let reports = vec![
+            report("领域域", &["src/domain.rs"]),
+            report("入口域", &["main.rs"]),
+        ];
+        let insights = vec![
+            insight("src/main.rs", CodePurpose::Entry, 0.9),
+            insight("src/domain.rs", CodePurpose::Util, 0.2),
+        ];
+
+        // main.rs只与src/main.rs关联，不会因字符后缀匹配到src/domain.rs
+        assert!(!is_associated(&reports[0], "src/main.rs"));
+        assert!(is_associated(&reports[1], "./src/main.rs"));
+        assert_eq!(rank_report(&reports[0], &insights), (3, 0.2));
+
+        let sorted = sort_reports_by_purpose(reports, &insights);
+        assert_eq!(sorted[0].domain_name, "入口域");
+    }
+
+    #[test]
+    fn test_module_with_dependency_lists_it_as_related() {
+        let reports = vec![
+            report("Billing", &["src/billing/ledger.rs"]),
+            report("Storage", &["src/storage/db.rs"]),
+            report("Docs", &["docs/index.md"]),
+        ];
+        let dependency = |from: &str, to: &str| CoreDependency {
+            from: from.to_string(),
+            to: to.to_string(),
+            dependency_type: DependencyType::FunctionCall,
+            importance: 4,
+            description: None,
+        };
+        let relationships = RelationshipAnalysis {
+            core_dependencies: vec![
+                dependency("./src/billing/ledger.rs", "src/storage/db.rs"),
+                dependency("src/billing/ledger.rs", "src/billing/ledger.rs"),
+            ],
+            architecture_layers: vec![],
+            key_insights: vec![],
+        };
+
+        let related = find_related_modules(&reports, &relationships);
+        let module_links: Vec<(String, String)> = reports
+            .iter()
+            .enumerate()
+            .map(|(index, r)| {
+                (
+                    r.domain_name.clone(),
+                    module_file_name(index, &r.domain_name),
+                )
+            })
+            .collect();
+        let texts = Localizer::new(TargetLanguage::English, TargetLanguage::English);
+
+        let billing = render_related_modules(&related[0], &module_links, &texts).unwrap();
+        assert!(billing.contains("## Related Modules"));
+        assert!(billing.contains("- **Depends on**: [Storage](02-Storage.md)"));
+        assert!(!billing.contains("Used by"));
+
+        let storage = render_related_modules(&related[1], &module_links, &texts).unwrap();
+        assert!(storage.contains("- **Used by**: [Billing](01-Billing.md)"));
+
+        // 没有依赖边的模块不输出相关模块章节
+        assert_eq!(related[2], RelatedModules::default());
+        assert!(render_related_modules(&related[2], &module_links, &texts).is_none());
+    }
+
+    #[test]
+    fn test_related_modules_ignore_partial_file_name_matches() {
+        let reports = vec![
+            report("Entry", &["main.rs"]),
+            report("Domain", &["src/domain.rs"]),
+            report("Storage", &["src/storage/db.rs"]),
+        ];
+        let relationships = RelationshipAnalysis {
+            core_dependencies: vec![CoreDependency {
+                from: "src/domain.rs".to_string(),
+                to: "src/storage/db.rs".to_string(),
+                dependency_type: DependencyType::FunctionCall,
+                importance: 4,
+                description: None,
+            }],
+            architecture_layers: vec![],
+            key_insights: vec![],
+        };
+
+        // src/domain.rs以"main.rs"结尾，但不属于Entry模块，Entry不应出现相关模块
+        let related = find_related_modules(&reports, &relationships);
+        assert_eq!(related[0], RelatedModules::default());
+        assert_eq!(related[1].depends_on, BTreeSet::from([2]));
+        assert_eq!(related[2].depended_by, BTreeSet::from([1]));
+    }
+}
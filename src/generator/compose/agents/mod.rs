@@ -1,5 +1,6 @@
 pub mod architecture_editor;
 pub mod boundary_editor;
+pub mod build_system_editor;
 pub mod code_index_editor;
 pub mod key_modules_insight_editor;
 pub mod overview_editor;
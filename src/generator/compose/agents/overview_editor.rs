@@ -29,7 +29,7 @@ impl StepForwardAgent for OverviewEditor {
                 DataSource::ResearchResult(ResearchAgentType::SystemContextResearcher.to_string()),
                 DataSource::ResearchResult(ResearchAgentType::DomainModulesDetector.to_string()),
             ],
-            optional_sources: vec![DataSource::README_CONTENT],
+            optional_sources: vec![DataSource::README_CONTENT, DataSource::LANGUAGE_BREAKDOWN],
         }
     }
 
@@ -132,7 +132,7 @@ impl StepForwardAgent for OverviewEditor {
 ## 1. 项目简介
 - 项目名称和描述
 - 核心功能与价值
-- 技术特征概述
+- 技术特征概述（如提供了语言构成数据，注明主要语言及占比，例如“80% Rust，15% TypeScript”）
 
 ## 2. 目标用户
 - 用户角色定义
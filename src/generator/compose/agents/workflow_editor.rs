@@ -1,13 +1,21 @@
 use crate::generator::compose::memory::MemoryScope;
 use crate::generator::compose::types::AgentType;
+use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::memory::{MemoryScope as PreprocessMemoryScope, ScopedKeys};
 use crate::generator::research::types::AgentType as ResearchAgentType;
 use crate::generator::step_forward_agent::{
     AgentDataConfig, DataSource, FormatterConfig, LLMCallMode, PromptTemplate, StepForwardAgent,
 };
+use crate::i18n::Localizer;
+use crate::types::concurrency::ConcurrencyReport;
+use crate::types::entry_point::EntryPoint;
+use anyhow::Result;
+use async_trait::async_trait;
 
 #[derive(Default)]
 pub struct WorkflowEditor;
 
+#[async_trait]
 impl StepForwardAgent for WorkflowEditor {
     type Output = String;
 
@@ -23,6 +31,51 @@ impl StepForwardAgent for WorkflowEditor {
         true
     }
 
+    /// 提供静态分析识别出的程序入口及调用链，要求流程图以真实入口为起点；
+    /// 并提供识别出的并发原语，用于描述系统的并发模型
+    async fn provide_custom_prompt_content(
+        &self,
+        context: &GeneratorContext,
+    ) -> Result<Option<String>> {
+        let entry_points = load_entry_points(context).await;
+        let concurrency = context
+            .get_from_memory::<ConcurrencyReport>(
+                PreprocessMemoryScope::PREPROCESS,
+                ScopedKeys::CONCURRENCY,
+            )
+            .await
+            .unwrap_or_default();
+        if entry_points.is_empty() && concurrency.is_empty() {
+            return Ok(None);
+        }
+
+        let mut content = String::new();
+        if !entry_points.is_empty() {
+            content.push_str(
+                "### 程序入口与运行时调用链（静态分析）\n请以下列真实程序入口作为核心工作流程图的起点，沿调用/导入关系向外展开，并在文档中标注入口文件路径：\n\n",
+            );
+            for entry in &entry_points {
+                content.push_str(&format!(
+                    "- 入口文件: `{}`（{}）\n```mermaid\n{}```\n",
+                    entry.file_path,
+                    entry.language,
+                    entry.to_mermaid()
+                ));
+            }
+        }
+        if !concurrency.is_empty() {
+            content.push_str(&format_concurrency_summary(&concurrency));
+        }
+        Ok(Some(content))
+    }
+
+    /// 生成的文档未引用任何真实入口时，追加以入口为起点的运行时调用链章节
+    async fn refine_output(&self, result: String, context: &GeneratorContext) -> Result<String> {
+        let entry_points = load_entry_points(context).await;
+        let texts = context.config.localizer();
+        Ok(anchor_entry_points(result, &entry_points, &texts))
+    }
+
     fn data_config(&self) -> AgentDataConfig {
         AgentDataConfig {
             required_sources: vec![
@@ -254,3 +307,177 @@ match register_user_workflow(username, email).await {
         }
     }
 }
+
+async fn load_entry_points(context: &GeneratorContext) -> Vec<EntryPoint> {
+    context
+        .get_from_memory::<Vec<EntryPoint>>(
+            PreprocessMemoryScope::PREPROCESS,
+            ScopedKeys::ENTRY_POINTS,
+        )
+        .await
+        .unwrap_or_default()
+}
+
+/// 每种并发模式在提示词中列出的位置数量上限
+const MAX_CONCURRENCY_LOCATIONS: usize = 5;
+
+/// 按并发模式汇总源码中的并发原语，要求文档据此说明并发模型
+fn format_concurrency_summary(report: &ConcurrencyReport) -> String {
+    let mut content = String::from(
+        "\n### 并发模型（静态分析）\n源码中识别出以下异步运行时、线程、通道与锁等并发原语，请在「流程协调与控制」章节中据此说明系统的并发模型（任务如何调度、数据如何在并发单元间传递、共享状态如何同步），并标注代码位置：\n\n",
+    );
+    for (pattern, signals) in report.by_pattern() {
+        let locations: Vec<String> = signals
+            .iter()
+            .take(MAX_CONCURRENCY_LOCATIONS)
+            .map(|signal| format!("`{}:{}`", signal.file_path, signal.line_number))
+            .collect();
+        let more = if signals.len() > MAX_CONCURRENCY_LOCATIONS {
+            format!(" 等 {} 个文件", signals.len())
+        } else {
+            String::new()
+        };
+        content.push_str(&format!(
+            "- {}: {}{}\n",
+            pattern,
+            locations.join(", "),
+            more
+        ));
+    }
+    content
+}
+
+/// 确保工作流程文档锚定在真实的程序入口上
+fn anchor_entry_points(
+    mut document: String,
+    entry_points: &[EntryPoint],
+    texts: &Localizer,
+) -> String {
+    if entry_points.is_empty()
+        || entry_points
+            .iter()
+            .any(|entry| document.contains(&entry.file_path))
+    {
+        return document;
+    }
+
+    document.push_str(&format!(
+        "\n\n## {}\n\n",
+        texts.text("workflow.entry_points")
+    ));
+    for entry in entry_points {
+        document.push_str(&format!(
+            "📁 **{}**: `{}` ({})\n\n```mermaid\n{}```\n\n",
+            texts.text("label.entry_point"),
+            entry.file_path,
+            entry.language,
+            entry.to_mermaid()
+        ));
+    }
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::preprocess::extractors::structure_extractor::StructureExtractor;
+    use crate::generator::preprocess::extractors::{
+        concurrency_extractor, entry_point_extractor, source_reader,
+    };
+    use crate::types::code_releationship::{CoreDependency, DependencyType, RelationshipAnalysis};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_rust_main_is_identified_and_referenced_in_workflow() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("src/main.rs"),
+            "mod app;\n\nfn main() {\n    app::run();\n}\n",
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("src/app.rs"), "pub fn run() {}\n").unwrap();
+
+        let mut config = crate::config::Config {
+            project_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
+        let structure = StructureExtractor::new(context.clone())
+            .extract_structure(temp_dir.path())
+            .await
+            .unwrap();
+
+        let sources = source_reader::read_source_files(&structure, 64 * 1024, |_| true).await;
+        let mut entry_points = entry_point_extractor::extract(&context, &sources);
+        assert_eq!(entry_points.len(), 1);
+        assert_eq!(entry_points[0].file_path, "src/main.rs");
+        assert_eq!(entry_points[0].language, "Rust");
+
+        let relationships = RelationshipAnalysis {
+            core_dependencies: vec![CoreDependency {
+                from: "src/main.rs".to_string(),
+                to: "src/app.rs".to_string(),
+                dependency_type: DependencyType::Module,
+                importance: 3,
+                description: None,
+            }],
+            architecture_layers: vec![],
+            key_insights: vec![],
+        };
+        entry_point_extractor::trace_runtime_flow(&mut entry_points, &relationships);
+        context
+            .store_to_memory(
+                PreprocessMemoryScope::PREPROCESS,
+                ScopedKeys::ENTRY_POINTS,
+                &entry_points,
+            )
+            .await
+            .unwrap();
+
+        let prompt_content = WorkflowEditor
+            .provide_custom_prompt_content(&context)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(prompt_content.contains("`src/main.rs`"));
+
+        let document = WorkflowEditor
+            .refine_output("# 核心工作流程\n\n流程说明".to_string(), &context)
+            .await
+            .unwrap();
+        assert!(document.contains("`src/main.rs`"));
+        assert!(document.contains("-->|module|"));
+        assert!(document.contains("src/app.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_signals_are_summarized_in_prompt() {
+        let context = GeneratorContext::new(crate::config::Config::default()).unwrap();
+        let report = ConcurrencyReport {
+            signals: concurrency_extractor::detect_signals(
+                "async fn serve() {\n    tokio::spawn(handle());\n}\n",
+                "rs",
+                "src/server.rs",
+            ),
+        };
+        context
+            .store_to_memory(
+                PreprocessMemoryScope::PREPROCESS,
+                ScopedKeys::CONCURRENCY,
+                &report,
+            )
+            .await
+            .unwrap();
+
+        let prompt_content = WorkflowEditor
+            .provide_custom_prompt_content(&context)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(prompt_content.contains("### 并发模型"));
+        assert!(prompt_content.contains("- async/tokio: `src/server.rs:2`"));
+    }
+}
@@ -1,13 +1,25 @@
+use crate::config::DocumentProfile;
 use crate::generator::compose::agents::architecture_editor::ArchitectureEditor;
 use crate::generator::compose::agents::boundary_editor::BoundaryEditor;
-use crate::generator::compose::agents::code_index_editor::CodeIndexEditor;
+use crate::generator::compose::agents::build_system_editor::BuildSystemEditor;
+use crate::generator::compose::agents::code_index_editor::{
+    CodeIndexEditor, render_duplication_section,
+};
 use crate::generator::compose::agents::key_modules_insight_editor::KeyModulesInsightEditor;
 use crate::generator::compose::agents::overview_editor::OverviewEditor;
 use crate::generator::compose::agents::workflow_editor::WorkflowEditor;
+use crate::generator::compose::memory::MemoryScope;
+use crate::generator::compose::types::AgentType;
 use crate::generator::context::GeneratorContext;
+use crate::generator::events::GeneratorEvent;
 use crate::generator::outlet::DocTree;
-use crate::generator::step_forward_agent::StepForwardAgent;
+use crate::generator::preprocess::memory::{MemoryScope as PreprocessMemoryScope, ScopedKeys};
+use crate::generator::research::types::AgentType as ResearchAgentType;
+use crate::generator::step_forward_agent::{DataSource, StepForwardAgent};
+use crate::types::build_system::BuildSystemReport;
+use crate::types::duplication::DuplicationReport;
 use anyhow::Result;
+use log::{info, warn};
 
 mod agents;
 pub mod memory;
@@ -15,49 +27,174 @@ pub mod types;
 
 /// 执行文档生成阶段
 pub async fn execute(context: &GeneratorContext) -> Result<DocTree> {
-    if context.config.llm.disable_preset_tools {
-        println!("   ⚠️ LLM已禁用，跳过文档生成阶段");
-        return Ok(DocTree::new(&context.config.target_language));
+    if context.config.llm.offline {
+        warn!("   ⚠️ 离线模式，跳过文档生成阶段");
+        return Ok(DocTree::with_profile(
+            &context.config.target_language,
+            context.config.profile,
+        ));
     }
 
-    let mut doc_tree = DocTree::new(&context.config.target_language);
+    let mut doc_tree =
+        DocTree::with_profile(&context.config.target_language, context.config.profile);
     let composer = DocumentationComposer;
     composer.execute(context, &mut doc_tree).await?;
     Ok(doc_tree)
 }
 
+/// 文档集预设所选文档依赖的调研结果，生成全部文档时返回None
+pub fn required_research(profile: DocumentProfile) -> Option<Vec<String>> {
+    if profile == DocumentProfile::Full {
+        return None;
+    }
+
+    let editors = [
+        (AgentType::Overview, OverviewEditor.data_config()),
+        (AgentType::Architecture, ArchitectureEditor.data_config()),
+        (AgentType::Workflow, WorkflowEditor.data_config()),
+        (AgentType::Boundary, BoundaryEditor.data_config()),
+        (AgentType::CodeIndex, CodeIndexEditor.data_config()),
+        (AgentType::BuildSystem, BuildSystemEditor.data_config()),
+    ];
+    let mut required: Vec<String> = editors
+        .into_iter()
+        .filter(|(document, _)| profile.includes(document))
+        .flat_map(|(_, config)| {
+            config
+                .required_sources
+                .into_iter()
+                .chain(config.optional_sources)
+        })
+        .filter_map(|source| match source {
+            DataSource::ResearchResult(agent_type) => Some(agent_type),
+            _ => None,
+        })
+        .collect();
+    if profile.includes_key_modules() {
+        required.push(ResearchAgentType::KeyModulesInsight.to_string());
+    }
+    required.sort();
+    required.dedup();
+    Some(required)
+}
+
 /// 文档生成器
 #[derive(Default)]
 pub struct DocumentationComposer;
 
 impl DocumentationComposer {
     pub async fn execute(&self, context: &GeneratorContext, doc_tree: &mut DocTree) -> Result<()> {
-        println!("\n🤖 执行文档生成流程...");
-        println!(
+        info!("\n🤖 执行文档生成流程...");
+        info!(
             "📝 目标语言: {}",
             context.config.target_language.display_name()
         );
 
-        let overview_editor = OverviewEditor;
-        overview_editor.execute(context).await?;
+        let profile = context.config.profile;
+        info!("📚 文档集预设: {}", profile);
 
-        let architecture_editor = ArchitectureEditor;
-        architecture_editor.execute(context).await?;
+        Self::execute_selected_editor(context, AgentType::Overview, &OverviewEditor).await?;
+        Self::execute_selected_editor(context, AgentType::Architecture, &ArchitectureEditor)
+            .await?;
+        Self::execute_selected_editor(context, AgentType::Workflow, &WorkflowEditor).await?;
 
-        let workflow_editor = WorkflowEditor;
-        workflow_editor.execute(context).await?;
+        if profile.includes_key_modules() {
+            let key_modules_agent = "核心模块与组件".to_string();
+            context.emit(GeneratorEvent::AgentStarted {
+                agent: key_modules_agent.clone(),
+            });
+            let key_modules_insight_editor = KeyModulesInsightEditor::default();
+            key_modules_insight_editor
+                .execute(context, doc_tree)
+                .await?;
+            context.emit(GeneratorEvent::AgentFinished {
+                agent: key_modules_agent,
+            });
+        }
 
-        let key_modules_insight_editor = KeyModulesInsightEditor::default();
-        key_modules_insight_editor
-            .execute(context, doc_tree)
-            .await?;
+        Self::execute_selected_editor(context, AgentType::Boundary, &BoundaryEditor).await?;
+        if profile.includes(&AgentType::CodeIndex) {
+            Self::execute_editor(context, &CodeIndexEditor).await?;
+            Self::append_duplication_section(context).await?;
+        }
 
-        let boundary_editor = BoundaryEditor;
-        boundary_editor.execute(context).await?;
+        // 识别到构建与CI配置时，追加构建与持续集成文档
+        let has_build_system = profile.includes(&AgentType::BuildSystem)
+            && context
+                .get_from_memory::<BuildSystemReport>(
+                    PreprocessMemoryScope::PREPROCESS,
+                    ScopedKeys::BUILD_SYSTEM,
+                )
+                .await
+                .is_some_and(|report| !report.is_empty());
+        if has_build_system {
+            Self::execute_editor(context, &BuildSystemEditor).await?;
+            doc_tree.insert(
+                &AgentType::BuildSystem.to_string(),
+                &context
+                    .config
+                    .target_language
+                    .get_doc_filename("build_system"),
+            );
+        }
 
-        let code_index_editor = CodeIndexEditor;
-        code_index_editor.execute(context).await?;
+        Ok(())
+    }
+
+    /// 启用重复代码检测时，在代码索引文档末尾追加潜在重复代码章节
+    async fn append_duplication_section(context: &GeneratorContext) -> Result<()> {
+        let Some(report) = context
+            .get_from_memory::<DuplicationReport>(
+                PreprocessMemoryScope::PREPROCESS,
+                ScopedKeys::DUPLICATION,
+            )
+            .await
+        else {
+            return Ok(());
+        };
+        let texts = context.config.localizer();
+        let code_index_key = AgentType::CodeIndex.to_string();
+        if let Some(section) = render_duplication_section(&report, &texts)
+            && let Some(mut document) = context
+                .get_from_memory::<String>(MemoryScope::DOCUMENTATION, &code_index_key)
+                .await
+            && !document.contains(section.trim_start())
+        {
+            document.push_str(&section);
+            context
+                .store_to_memory(MemoryScope::DOCUMENTATION, &code_index_key, document)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// 文档集预设包含该文档时执行对应的编辑智能体
+    async fn execute_selected_editor<A>(
+        context: &GeneratorContext,
+        document: AgentType,
+        editor: &A,
+    ) -> Result<()>
+    where
+        A: StepForwardAgent + Send + Sync,
+    {
+        if !context.config.profile.includes(&document) {
+            info!("⏭️ 文档集预设不包含 {}，跳过", document);
+            return Ok(());
+        }
+        Self::execute_editor(context, editor).await
+    }
 
+    /// 执行单个编辑智能体，并在前后发送智能体事件
+    async fn execute_editor<A>(context: &GeneratorContext, editor: &A) -> Result<()>
+    where
+        A: StepForwardAgent + Send + Sync,
+    {
+        let agent = editor.agent_type();
+        context.emit(GeneratorEvent::AgentStarted {
+            agent: agent.clone(),
+        });
+        editor.execute(context).await?;
+        context.emit(GeneratorEvent::AgentFinished { agent });
         Ok(())
     }
 }
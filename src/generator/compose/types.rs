@@ -9,6 +9,7 @@ pub enum AgentType {
     Workflow,
     Boundary,
     CodeIndex,
+    BuildSystem,
 }
 
 impl Display for AgentType {
@@ -19,6 +20,7 @@ impl Display for AgentType {
             AgentType::Workflow => "核心流程",
             AgentType::Boundary => "边界调用",
             AgentType::CodeIndex => "代码索引",
+            AgentType::BuildSystem => "构建与持续集成",
         };
         write!(f, "{}", str)
     }
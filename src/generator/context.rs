@@ -6,7 +6,12 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
 use crate::{
-    cache::CacheManager, config::Config, generator::workflow::TimingScope, llm::client::LLMClient,
+    cache::CacheManager,
+    config::Config,
+    generator::events::{EventListener, GeneratorEvent},
+    generator::outlet::manifest::OutputManifest,
+    generator::workflow::TimingScope,
+    llm::client::LLMClient,
     memory::Memory,
 };
 
@@ -23,6 +28,10 @@ pub struct GeneratorContext {
     /// 时间跟踪器
     #[allow(dead_code)]
     pub timing_scope: Arc<RwLock<TimingScope>>,
+    /// 本次运行的输出清单，由各个输出器共同写入
+    pub output_manifest: Arc<RwLock<OutputManifest>>,
+    /// 进度事件监听器（可选）
+    pub event_listener: Option<Arc<dyn EventListener>>,
 }
 
 impl GeneratorContext {
@@ -30,8 +39,11 @@ impl GeneratorContext {
     pub fn new(config: Config) -> Result<Self> {
         let llm_client = LLMClient::new(config.clone())?;
         let cache_manager = Arc::new(RwLock::new(CacheManager::new(config.cache.clone())));
-        let memory = Arc::new(RwLock::new(Memory::new()));
+        let memory = Arc::new(RwLock::new(Memory::with_max_versions(
+            config.memory_versions,
+        )));
         let timing_scope = Arc::new(RwLock::new(TimingScope::new()));
+        let output_manifest = Arc::new(RwLock::new(OutputManifest::default()));
 
         Ok(Self {
             llm_client,
@@ -39,8 +51,25 @@ impl GeneratorContext {
             cache_manager,
             memory,
             timing_scope,
+            output_manifest,
+            event_listener: None,
         })
     }
+
+    /// 创建带进度事件监听器的生成器上下文
+    pub fn with_event_listener(config: Config, listener: Arc<dyn EventListener>) -> Result<Self> {
+        let mut context = Self::new(config)?;
+        context.event_listener = Some(listener);
+        Ok(context)
+    }
+
+    /// 向监听器发送进度事件，未设置监听器时忽略
+    pub fn emit(&self, event: GeneratorEvent) {
+        if let Some(listener) = &self.event_listener {
+            listener.on_event(&event);
+        }
+    }
+
     /// 存储数据到 Memory
     pub async fn store_to_memory<T>(&self, scope: &str, key: &str, data: T) -> Result<()>
     where
@@ -59,6 +88,34 @@ impl GeneratorContext {
         memory.get(scope, key)
     }
 
+    /// 仅在 Memory 中不存在指定数据时存储，返回是否写入
+    pub async fn store_to_memory_if_absent<T>(
+        &self,
+        scope: &str,
+        key: &str,
+        data: T,
+    ) -> Result<bool>
+    where
+        T: Serialize + Send + Sync,
+    {
+        let mut memory = self.memory.write().await;
+        memory.store_if_absent(scope, key, data)
+    }
+
+    /// 从 Memory 获取指定版本的数据，0为当前值，1为上一个版本
+    pub async fn get_versioned_from_memory<T>(
+        &self,
+        scope: &str,
+        key: &str,
+        version: usize,
+    ) -> Option<T>
+    where
+        T: for<'a> Deserialize<'a> + Send + Sync,
+    {
+        let memory = self.memory.read().await;
+        memory.get_versioned(scope, key, version)
+    }
+
     /// 检查Memory中是否存在指定数据
     pub async fn has_memory_data(&self, scope: &str, key: &str) -> bool {
         let memory = self.memory.read().await;
@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+/// 文档生成流程中的进度事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeneratorEvent {
+    /// 阶段开始，阶段名称见`TimingKeys`
+    PhaseStarted { phase: String },
+    /// 阶段结束
+    PhaseFinished { phase: String },
+    /// 智能体开始执行
+    AgentStarted { agent: String },
+    /// 智能体执行完成
+    AgentFinished { agent: String },
+    /// 单个源码文件分析完成
+    FileAnalyzed { file_path: PathBuf },
+    /// AI分析结果命中缓存
+    CacheHit { category: String },
+}
+
+/// 进度事件监听器，供嵌入生成流程的调用方观察执行进度
+///
+/// 事件可能从并发任务中发出，实现需自行保证线程安全且不应阻塞。
+pub trait EventListener: Send + Sync {
+    fn on_event(&self, event: &GeneratorEvent);
+}
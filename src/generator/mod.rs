@@ -1,8 +1,10 @@
 pub mod agent_executor;
 pub mod compose;
 pub mod context;
+pub mod events;
 pub mod outlet;
 pub mod preprocess;
+pub mod regenerate;
 pub mod research;
 pub mod step_forward_agent;
 pub mod types;
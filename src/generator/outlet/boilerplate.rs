@@ -0,0 +1,103 @@
+use crate::config::Config;
+
+/// 每个输出文档统一添加的页眉与页脚（如公司标识、"自动生成，请勿手动修改"等声明）
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentBoilerplate {
+    preamble: Option<String>,
+    postamble: Option<String>,
+}
+
+impl DocumentBoilerplate {
+    /// 根据配置创建，页眉页脚均未配置时返回None
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let date = config
+            .local_generation_time()
+            .map(|time| time.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        // 变更范围文档模式下，在页眉前注明文档覆盖的范围
+        let preamble = match &config.since_ref {
+            Some(git_ref) => {
+                let notice = config.target_language.changes_since_notice(git_ref);
+                Some(match &config.document_preamble {
+                    Some(preamble) => format!("{}\n\n{}", notice, preamble),
+                    None => notice,
+                })
+            }
+            None => config.document_preamble.clone(),
+        };
+        Self::new(
+            preamble.as_deref(),
+            config.document_postamble.as_deref(),
+            &config.get_project_name(),
+            &date,
+        )
+    }
+
+    fn new(
+        preamble: Option<&str>,
+        postamble: Option<&str>,
+        project_name: &str,
+        date: &str,
+    ) -> Option<Self> {
+        let render = |template: Option<&str>| {
+            template
+                .map(str::trim)
+                .filter(|template| !template.is_empty())
+                .map(|template| substitute_placeholders(template, project_name, date))
+        };
+        let boilerplate = Self {
+            preamble: render(preamble),
+            postamble: render(postamble),
+        };
+        (boilerplate.preamble.is_some() || boilerplate.postamble.is_some()).then_some(boilerplate)
+    }
+
+    /// 为文档内容添加页眉与页脚
+    pub fn apply(&self, content: &str) -> String {
+        let mut result = String::with_capacity(content.len());
+        if let Some(preamble) = &self.preamble {
+            result.push_str(preamble);
+            result.push_str("\n\n");
+        }
+        result.push_str(content.trim_end());
+        if let Some(postamble) = &self.postamble {
+            result.push_str("\n\n");
+            result.push_str(postamble);
+        }
+        result.push('\n');
+        result
+    }
+}
+
+/// 替换模板中的占位符，花括号内的空格可有可无（`{{ date }}`与`{{date}}`等价）
+fn substitute_placeholders(template: &str, project_name: &str, date: &str) -> String {
+    let mut result = template.to_string();
+    for (name, value) in [("project_name", project_name), ("date", date)] {
+        result = result
+            .replace(&format!("{{{{ {} }}}}", name), value)
+            .replace(&format!("{{{{{}}}}}", name), value);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders_and_placement() {
+        let boilerplate = DocumentBoilerplate::new(
+            Some("> {{ project_name }} 文档，自动生成于 {{date}}，请勿手动修改\n"),
+            Some("---\n© {{ project_name }}"),
+            "demo",
+            "2024-01-02",
+        )
+        .unwrap();
+
+        assert_eq!(
+            boilerplate.apply("# 标题\n\n正文\n"),
+            "> demo 文档，自动生成于 2024-01-02，请勿手动修改\n\n# 标题\n\n正文\n\n---\n© demo\n"
+        );
+        assert!(DocumentBoilerplate::new(Some("  "), None, "demo", "2024-01-02").is_none());
+    }
+}
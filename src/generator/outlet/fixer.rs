@@ -1,8 +1,50 @@
 use crate::generator::context::GeneratorContext;
 use anyhow::Result;
+use log::{info, warn};
+use std::future::Future;
+use std::ops::Range;
 use std::path::Path;
 use std::process::Stdio;
 use tokio::process::Command as TokioCommand;
+use walkdir::WalkDir;
+
+/// 每个图表交由LLM重新生成的最大尝试次数
+const MAX_LLM_REPAIR_ATTEMPTS: usize = 2;
+
+/// 支持的Mermaid图表类型声明
+const DIAGRAM_TYPES: &[&str] = &[
+    "graph",
+    "flowchart",
+    "sequenceDiagram",
+    "classDiagram",
+    "stateDiagram",
+    "stateDiagram-v2",
+    "erDiagram",
+    "journey",
+    "gantt",
+    "pie",
+    "gitGraph",
+    "mindmap",
+    "timeline",
+    "quadrantChart",
+    "requirementDiagram",
+    "C4Context",
+    "C4Container",
+    "C4Component",
+    "C4Dynamic",
+    "C4Deployment",
+    "sankey-beta",
+    "xychart-beta",
+    "block-beta",
+    "architecture-beta",
+    "packet-beta",
+    "kanban",
+];
+
+/// 时序图中需要以`end`结束的语句块关键字
+const SEQUENCE_BLOCK_KEYWORDS: &[&str] = &[
+    "loop", "alt", "opt", "par", "critical", "break", "rect", "box",
+];
 
 /// Mermaid图表修复器
 ///
@@ -36,12 +78,12 @@ impl MermaidFixer {
     pub async fn fix_mermaid_charts(context: &GeneratorContext, target_dir: &Path) -> Result<()> {
         // 检查mermaid-fixer是否可用
         if !Self::is_available().await {
-            println!("⚠️ 警告: mermaid-fixer 未安装或不可用，跳过mermaid图表修复");
-            println!("💡 提示: 请运行 'cargo install mermaid-fixer' 来安装mermaid修复工具");
+            warn!("⚠️ 警告: mermaid-fixer 未安装或不可用，跳过mermaid图表修复");
+            info!("💡 提示: 请运行 'cargo install mermaid-fixer' 来安装mermaid修复工具");
             return Ok(());
         }
 
-        println!("🔧 开始修复mermaid图表...");
+        info!("🔧 开始修复mermaid图表...");
 
         // 构建mermaid-fixer命令
         let mut cmd = TokioCommand::new("mermaid-fixer");
@@ -72,7 +114,7 @@ impl MermaidFixer {
         cmd.stdout(Stdio::inherit());
         cmd.stderr(Stdio::inherit());
 
-        println!(
+        info!(
             "🚀 执行命令（只显示部分信息）: mermaid-fixer --directory {} --llm-model {} --verbose",
             target_dir.display(),
             llm_config.model_powerful
@@ -82,18 +124,18 @@ impl MermaidFixer {
         match cmd.status().await {
             Ok(status) => {
                 if status.success() {
-                    println!("✅ mermaid图表修复完成");
+                    info!("✅ mermaid图表修复完成");
                 } else {
-                    println!(
+                    warn!(
                         "⚠️ mermaid-fixer执行完成，但返回非零状态码: {}",
                         status.code().unwrap_or(-1)
                     );
-                    println!("💡 这可能表示某些图表无法修复，但不会影响后续流程");
+                    info!("💡 这可能表示某些图表无法修复，但不会影响后续流程");
                 }
             }
             Err(e) => {
-                println!("⚠️ 执行mermaid-fixer时出错: {}", e);
-                println!("💡 mermaid图表修复失败，但不会阻塞后续流程");
+                warn!("⚠️ 执行mermaid-fixer时出错: {}", e);
+                info!("💡 mermaid图表修复失败，但不会阻塞后续流程");
             }
         }
 
@@ -107,10 +149,303 @@ impl MermaidFixer {
         let output_dir = &context.config.output_path;
 
         if !output_dir.exists() {
-            println!("⚠️ 输出目录不存在，跳过mermaid图表修复");
+            warn!("⚠️ 输出目录不存在，跳过mermaid图表修复");
             return Ok(());
         }
 
-        Self::fix_mermaid_charts(context, output_dir).await
+        Self::fix_mermaid_charts(context, output_dir).await?;
+
+        if context.config.llm_mermaid_repair && !context.config.llm.offline {
+            Self::repair_with_llm(context, output_dir).await?;
+        }
+        Ok(())
+    }
+
+    /// 将静态修复后仍无效的图表连同校验错误交由LLM重新生成
+    pub async fn repair_with_llm(context: &GeneratorContext, target_dir: &Path) -> Result<()> {
+        info!("🧜 使用LLM重新生成无效的mermaid图表...");
+        let mut total_prompts = 0;
+
+        for entry in WalkDir::new(target_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.file_type().is_file()
+                    && entry.path().extension().and_then(|e| e.to_str()) == Some("md")
+            })
+        {
+            let path = entry.path();
+            let content = tokio::fs::read_to_string(path).await?;
+            let (repaired, prompts) =
+                Self::repair_document(&content, MAX_LLM_REPAIR_ATTEMPTS, |diagram, errors| {
+                    Self::regenerate_diagram(context, diagram, errors)
+                })
+                .await;
+            total_prompts += prompts;
+            if repaired != content {
+                tokio::fs::write(path, repaired).await?;
+                info!("✅ 已重新生成mermaid图表: {}", path.display());
+            }
+        }
+
+        info!(
+            "🧜 mermaid图表LLM修复完成，共发起 {} 次重新生成",
+            total_prompts
+        );
+        Ok(())
+    }
+
+    /// 校验文档中的每个mermaid图表，无效时调用`regenerate`重新生成（每个图表最多`max_attempts`次）
+    ///
+    /// 返回修复后的文档及重新生成的调用次数；多次尝试仍无效的图表保持原样。
+    pub async fn repair_document<F, Fut>(
+        content: &str,
+        max_attempts: usize,
+        regenerate: F,
+    ) -> (String, usize)
+    where
+        F: Fn(String, Vec<String>) -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let mut result = String::with_capacity(content.len());
+        let mut last_end = 0;
+        let mut prompts = 0;
+
+        for block in find_mermaid_blocks(content) {
+            result.push_str(&content[last_end..block.start]);
+            last_end = block.end;
+
+            let original = &content[block];
+            let mut diagram = original.to_string();
+            let mut errors = validate_mermaid(&diagram);
+            let mut attempts = 0;
+            while !errors.is_empty() && attempts < max_attempts {
+                attempts += 1;
+                prompts += 1;
+                match regenerate(diagram.clone(), errors.clone()).await {
+                    Ok(regenerated) => {
+                        diagram = regenerated;
+                        errors = validate_mermaid(&diagram);
+                    }
+                    Err(e) => {
+                        warn!("⚠️ LLM重新生成mermaid图表失败: {}", e);
+                        break;
+                    }
+                }
+            }
+
+            if errors.is_empty() {
+                result.push_str(diagram.trim_end());
+                result.push('\n');
+            } else {
+                result.push_str(original);
+            }
+        }
+        result.push_str(&content[last_end..]);
+
+        (result, prompts)
+    }
+
+    /// 请求LLM根据校验错误重新生成图表
+    async fn regenerate_diagram(
+        context: &GeneratorContext,
+        diagram: String,
+        errors: Vec<String>,
+    ) -> Result<String> {
+        let system_prompt = "你是Mermaid图表专家，负责修正无法渲染的Mermaid图表。只输出修正后的完整Mermaid代码，不要输出任何解释。";
+        let user_prompt = format!(
+            "以下Mermaid图表无法通过校验：\n\n```mermaid\n{}```\n\n校验错误：\n- {}\n\n请在保持原有含义的前提下重新生成正确的图表。",
+            diagram,
+            errors.join("\n- ")
+        );
+        let (reply, _) = context
+            .llm_client
+            .prompt_without_react(system_prompt, &user_prompt)
+            .await?;
+        Ok(strip_code_fence(&reply))
+    }
+}
+
+/// 查找文档中的mermaid代码块，返回图表正文的字节范围
+fn find_mermaid_blocks(content: &str) -> Vec<Range<usize>> {
+    let mut blocks = Vec::new();
+    let mut offset = 0;
+    let mut start: Option<usize> = None;
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        match start {
+            None if trimmed.starts_with("```mermaid") => start = Some(offset + line.len()),
+            Some(block_start) if trimmed.starts_with("```") => {
+                blocks.push(block_start..offset);
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    blocks
+}
+
+/// 去掉LLM回复中包裹图表的代码块标记
+fn strip_code_fence(reply: &str) -> String {
+    if let Some(block) = find_mermaid_blocks(reply).into_iter().next() {
+        return reply[block].to_string();
+    }
+    let trimmed = reply.trim();
+    let trimmed = trimmed
+        .strip_prefix("```")
+        .map(|rest| rest.split_once('\n').map_or("", |(_, body)| body))
+        .unwrap_or(trimmed);
+    let trimmed = trimmed.trim_end().strip_suffix("```").unwrap_or(trimmed);
+    format!("{}\n", trimmed.trim_end())
+}
+
+/// 对mermaid图表做基本的结构校验，返回发现的错误
+pub fn validate_mermaid(diagram: &str) -> Vec<String> {
+    let lines: Vec<&str> = skip_front_matter(diagram)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("%%"))
+        .collect();
+    let Some(header) = lines.first() else {
+        return vec!["图表内容为空".to_string()];
+    };
+
+    let diagram_type = header.split_whitespace().next().unwrap_or_default();
+    if !DIAGRAM_TYPES.contains(&diagram_type) {
+        return vec![format!("未知的图表类型声明: {}", header)];
+    }
+
+    let mut errors = Vec::new();
+    let is_flowchart = matches!(diagram_type, "graph" | "flowchart");
+    let block_keywords: &[&str] = match diagram_type {
+        "graph" | "flowchart" => &["subgraph"],
+        "sequenceDiagram" => SEQUENCE_BLOCK_KEYWORDS,
+        _ => &[],
+    };
+
+    let mut open_blocks = 0usize;
+    for (index, line) in lines.iter().enumerate().skip(1) {
+        if line.matches('"').count() % 2 != 0 {
+            errors.push(format!("第 {} 行引号未闭合: {}", index + 1, line));
+        }
+        if is_flowchart && let Some(error) = check_brackets(line) {
+            errors.push(format!("第 {} 行{}: {}", index + 1, error, line));
+        }
+
+        let keyword = line.split_whitespace().next().unwrap_or_default();
+        if block_keywords.contains(&keyword) {
+            open_blocks += 1;
+        } else if !block_keywords.is_empty() && keyword == "end" {
+            if open_blocks == 0 {
+                errors.push(format!("第 {} 行存在多余的end", index + 1));
+            } else {
+                open_blocks -= 1;
+            }
+        }
+    }
+    if open_blocks > 0 {
+        errors.push(format!("有 {} 个语句块缺少end", open_blocks));
+    }
+
+    errors
+}
+
+/// 跳过图表开头以`---`包裹的front matter（如`title`、`config`）
+fn skip_front_matter(diagram: &str) -> &str {
+    let Some(rest) = diagram.trim_start().strip_prefix("---") else {
+        return diagram;
+    };
+    let mut lines = rest.split_inclusive('\n');
+    let mut offset = lines.next().map_or(0, str::len);
+    for line in lines {
+        offset += line.len();
+        if line.trim() == "---" {
+            return &rest[offset..];
+        }
+    }
+    diagram
+}
+
+/// 检查一行中引号外的括号是否配对，非对称节点`A>文本]`中的`>`视为与`]`配对的开括号
+fn check_brackets(line: &str) -> Option<String> {
+    let mut stack = Vec::new();
+    let mut in_quotes = false;
+    let mut previous = ' ';
+    for c in line.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            _ if in_quotes => {}
+            '(' | '[' | '{' => stack.push(c),
+            // 紧跟节点ID的`>`是非对称节点的开头，箭头（`-->`、`==>`）中的`>`前面是连线符号
+            '>' if stack.is_empty() && (previous.is_alphanumeric() || previous == '_') => {
+                stack.push(c)
+            }
+            ')' | ']' | '}' => {
+                let matched = match stack.pop() {
+                    Some('(') => c == ')',
+                    Some('[') | Some('>') => c == ']',
+                    Some('{') => c == '}',
+                    _ => false,
+                };
+                if !matched {
+                    return Some(format!("括号不匹配 `{}`", c));
+                }
+            }
+            _ => {}
+        }
+        previous = c;
+    }
+    (!stack.is_empty()).then(|| "括号未闭合".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_validate_mermaid() {
+        let valid = "flowchart TD\n    A[\"开始\"] --> B{判断}\n    subgraph core\n    B --> C(结束)\n    end\n";
+        assert!(validate_mermaid(valid).is_empty());
+
+        assert!(!validate_mermaid("flowchart TD\n    A[开始 --> B\n").is_empty());
+        assert!(!validate_mermaid("sequenceDiagram\n    loop 轮询\n    A->>B: ping\n").is_empty());
+        assert!(!validate_mermaid("chart TD\n    A --> B\n").is_empty());
+    }
+
+    #[test]
+    fn test_validate_mermaid_accepts_asymmetric_nodes_and_front_matter() {
+        let asymmetric = "flowchart LR\n    A>发送通知] --> B[处理]\n    B ==> C>完成]\n";
+        assert!(validate_mermaid(asymmetric).is_empty());
+        assert!(!validate_mermaid("flowchart LR\n    A>发送通知) --> B\n").is_empty());
+
+        let front_matter =
+            "---\ntitle: 请求流程\nconfig:\n  theme: forest\n---\nflowchart TD\n    A --> B\n";
+        assert!(validate_mermaid(front_matter).is_empty());
+        assert!(!validate_mermaid("---\ntitle: 请求流程\n---\nchart TD\n    A --> B\n").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unrepairable_diagram_triggers_one_reprompt() {
+        let content = "# 架构\n\n```mermaid\nflowchart TD\n    A[开始 --> B((结束\n```\n\n```mermaid\ngraph LR\n    X --> Y\n```\n";
+        let prompts = AtomicUsize::new(0);
+
+        let (repaired, attempts) = MermaidFixer::repair_document(content, 2, |diagram, errors| {
+            prompts.fetch_add(1, Ordering::SeqCst);
+            assert!(diagram.contains("A[开始"));
+            assert!(!errors.is_empty());
+            // 模拟LLM回复中带有代码块标记
+            let reply = "```mermaid\nflowchart TD\n    A[开始] --> B((结束))\n```";
+            async move { Ok(strip_code_fence(reply)) }
+        })
+        .await;
+
+        assert_eq!(attempts, 1);
+        assert_eq!(prompts.load(Ordering::SeqCst), 1);
+        assert!(repaired.contains("```mermaid\nflowchart TD\n    A[开始] --> B((结束))\n```\n"));
+        // 有效的图表保持不变
+        assert!(repaired.contains("```mermaid\ngraph LR\n    X --> Y\n```\n"));
     }
 }
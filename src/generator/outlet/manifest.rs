@@ -0,0 +1,139 @@
+use anyhow::{Context, Result};
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// 输出清单文件名，保存在 `.litho` 内部目录下
+pub const MANIFEST_FILE_NAME: &str = "output-manifest.json";
+
+/// 输出清单，记录一次运行生成的文档文件及其内容哈希
+///
+/// 下一次运行时据此跳过内容未变化的文档，并且只清理清单中记录过的孤立文件，
+/// 避免误删用户放入输出目录的其他文件。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OutputManifest {
+    /// 清单生成时间
+    pub generated_at: Option<String>,
+    /// 相对于输出目录的文件路径 -> 内容哈希
+    pub files: BTreeMap<String, String>,
+}
+
+impl OutputManifest {
+    /// 获取清单文件路径
+    pub fn path(internal_path: &Path) -> PathBuf {
+        internal_path.join(MANIFEST_FILE_NAME)
+    }
+
+    /// 加载清单，文件不存在时返回None
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read output manifest: {}", path.display()))?;
+        let manifest = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse output manifest: {}", path.display()))?;
+        Ok(Some(manifest))
+    }
+
+    /// 保存清单，先写入临时文件再重命名，避免中断时留下不完整的清单
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// 记录一个输出文件
+    pub fn record(&mut self, relative_path: &str, content: &str) {
+        self.files
+            .insert(normalize(relative_path), content_hash(content));
+    }
+
+    /// 判断文档与上次输出相比是否未变化（内容哈希一致且文件仍然存在）
+    pub fn is_unchanged(&self, relative_path: &str, content: &str, output_dir: &Path) -> bool {
+        self.files
+            .get(&normalize(relative_path))
+            .is_some_and(|hash| *hash == content_hash(content))
+            && output_dir.join(relative_path).exists()
+    }
+
+    /// 获取本清单中存在、但当前运行未再生成的文件
+    ///
+    /// 清单文件可能被手动修改，包含`..`或绝对路径的条目会指向输出目录之外，不作为待清理文件返回。
+    pub fn orphaned_files(&self, current: &OutputManifest) -> Vec<String> {
+        self.files
+            .keys()
+            .filter(|path| !current.files.contains_key(*path) && is_within_output_dir(path))
+            .cloned()
+            .collect()
+    }
+}
+
+/// 计算文档内容哈希
+pub fn content_hash(content: &str) -> String {
+    let mut hasher = Md5::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn normalize(relative_path: &str) -> String {
+    relative_path.replace('\\', "/")
+}
+
+/// 路径是否为仅由普通组件构成的相对路径，即始终位于输出目录之内
+fn is_within_output_dir(relative_path: &str) -> bool {
+    let path = Path::new(relative_path);
+    !relative_path.is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_manifest_round_trip_and_orphans() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest_path = OutputManifest::path(temp_dir.path());
+
+        let mut previous = OutputManifest::default();
+        previous.record("1.Overview.md", "overview");
+        previous.record("4.Deep-Exploration/01-Core.md", "core");
+        previous.save(&manifest_path).unwrap();
+
+        let loaded = OutputManifest::load(&manifest_path).unwrap().unwrap();
+        assert_eq!(loaded, previous);
+
+        let mut current = OutputManifest::default();
+        current.record("1.Overview.md", "overview");
+        assert_eq!(
+            loaded.orphaned_files(&current),
+            vec!["4.Deep-Exploration/01-Core.md".to_string()]
+        );
+        assert!(
+            OutputManifest::load(&temp_dir.path().join("missing.json"))
+                .unwrap()
+                .is_none()
+        );
+
+        // 指向输出目录之外的条目不会被当作孤立文件清理
+        let mut tampered = OutputManifest::default();
+        tampered.record("../outside.md", "outside");
+        tampered.record("/etc/passwd", "passwd");
+        tampered.record("docs/../../escape.md", "escape");
+        tampered.record("2.Architecture.md", "architecture");
+        assert_eq!(
+            tampered.orphaned_files(&current),
+            vec!["2.Architecture.md".to_string()]
+        );
+    }
+}
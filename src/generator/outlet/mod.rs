@@ -1,16 +1,31 @@
+use crate::config::DocumentProfile;
 use crate::generator::compose::types::AgentType;
 use crate::generator::{compose::memory::MemoryScope, context::GeneratorContext};
 use crate::i18n::TargetLanguage;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use log::{info, warn};
 use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
 
+pub mod boilerplate;
 pub mod fixer;
+pub mod manifest;
+pub mod paginator;
+pub mod redactor;
+pub mod source_links;
 pub mod summary_generator;
 pub mod summary_outlet;
+pub mod table_repair;
 
 // pub use summary_outlet::SummaryOutlet; // 暂时注释，未使用
+use boilerplate::DocumentBoilerplate;
 pub use fixer::MermaidFixer;
+use manifest::OutputManifest;
+use paginator::paginate;
+use redactor::Redactor;
+use source_links::SourceLinker;
+use table_repair::repair_tables;
 
 /// 保存文档
 pub async fn save(context: &GeneratorContext, doc_tree: DocTree) -> Result<()> {
@@ -18,6 +33,53 @@ pub async fn save(context: &GeneratorContext, doc_tree: DocTree) -> Result<()> {
     outlet.save(context).await
 }
 
+/// 检查输出目录是否可以安全清理
+///
+/// 输出目录在保存前会被整体删除，因此拒绝文件系统根目录、用户主目录，
+/// 以及等于或包含项目根目录的路径。
+pub fn check_output_path_safety(output_path: &Path, project_path: &Path) -> Result<()> {
+    let output_path = absolute_path(output_path);
+    let project_path = absolute_path(project_path);
+
+    let reason = if output_path.parent().is_none() {
+        Some("文件系统根目录")
+    } else if project_path.starts_with(&output_path) {
+        Some("项目根目录或其上级目录")
+    } else if home_dir()
+        .map(|home| absolute_path(&home))
+        .is_some_and(|home| home.starts_with(&output_path))
+    {
+        Some("用户主目录或其上级目录")
+    } else {
+        None
+    };
+
+    match reason {
+        Some(reason) => Err(anyhow!(
+            "输出目录 {} 是{}，清理该目录将导致数据丢失。请更换输出目录，或使用 --allow-unsafe-output-path 确认执行",
+            output_path.display(),
+            reason
+        )),
+        None => Ok(()),
+    }
+}
+
+/// 获取规范化的绝对路径（路径不存在时基于当前工作目录拼接）
+fn absolute_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    })
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
 pub trait Outlet {
     fn save(
         &self,
@@ -30,30 +92,32 @@ pub struct DocTree {
     structure: HashMap<String, String>,
 }
 
+/// 文档树中的核心文档及其文档类型（用于确定输出文件名），文档集预设从中选择需要生成的文档
+pub const CORE_DOCUMENTS: [(AgentType, &str); 5] = [
+    (AgentType::Overview, "overview"),
+    (AgentType::Architecture, "architecture"),
+    (AgentType::Workflow, "workflow"),
+    (AgentType::Boundary, "boundary"),
+    (AgentType::CodeIndex, "code_index"),
+];
+
 impl DocTree {
     pub fn new(target_language: &TargetLanguage) -> Self {
-        let structure = HashMap::from([
-            (
-                AgentType::Overview.to_string(),
-                target_language.get_doc_filename("overview"),
-            ),
-            (
-                AgentType::Architecture.to_string(),
-                target_language.get_doc_filename("architecture"),
-            ),
-            (
-                AgentType::Workflow.to_string(),
-                target_language.get_doc_filename("workflow"),
-            ),
-            (
-                AgentType::Boundary.to_string(),
-                target_language.get_doc_filename("boundary"),
-            ),
-            (
-                AgentType::CodeIndex.to_string(),
-                target_language.get_doc_filename("code_index"),
-            ),
-        ]);
+        Self::with_profile(target_language, DocumentProfile::Full)
+    }
+
+    /// 仅包含文档集预设所选文档的文档树
+    pub fn with_profile(target_language: &TargetLanguage, profile: DocumentProfile) -> Self {
+        let structure = CORE_DOCUMENTS
+            .into_iter()
+            .filter(|(agent_type, _)| profile.includes(agent_type))
+            .map(|(agent_type, doc_type)| {
+                (
+                    agent_type.to_string(),
+                    target_language.get_doc_filename(doc_type),
+                )
+            })
+            .collect();
         Self { structure }
     }
 
@@ -82,49 +146,443 @@ impl DiskOutlet {
 
 impl Outlet for DiskOutlet {
     async fn save(&self, context: &GeneratorContext) -> Result<()> {
-        println!("\n🖊️ 文档存储中...");
-        // 创建输出目录
+        info!("\n🖊️ 文档存储中...");
         let output_dir = &context.config.output_path;
-        if output_dir.exists() {
+        let manifest_path = OutputManifest::path(&context.config.internal_path);
+
+        // 增量输出模式下读取上次的输出清单，读取失败时退化为全量输出
+        let previous_manifest = if context.config.incremental_output {
+            OutputManifest::load(&manifest_path).unwrap_or_else(|e| {
+                warn!("⚠️ 输出清单读取失败，将全量输出文档: {}", e);
+                None
+            })
+        } else {
+            None
+        };
+
+        // 无论是否使用输出清单都会清理输出目录中的文件，先确认输出目录可以安全清理
+        if output_dir.exists() && !context.config.allow_unsafe_output_path {
+            check_output_path_safety(output_dir, &context.config.project_path)?;
+        }
+
+        // 没有可用的输出清单时，沿用整体清理输出目录的方式
+        if previous_manifest.is_none() && output_dir.exists() {
             fs::remove_dir_all(output_dir)?;
         }
         fs::create_dir_all(output_dir)?;
 
-        // 遍历文档树结构，保存每个文档
-        for (scoped_key, relative_path) in &self.doc_tree.structure {
+        // 配置了或能从git中识别出仓库地址时，将文件引用转换为源码链接
+        let source_linker = SourceLinker::from_config(&context.config);
+        // 配置了页眉页脚时，添加到每个输出文件
+        let boilerplate = DocumentBoilerplate::from_config(&context.config);
+        // 配置了脱敏模式时，替换文档中的密钥、令牌等敏感信息
+        let redactor = Redactor::from_config(&context.config)?;
+
+        // 按输出路径顺序遍历文档树结构，保存每个文档
+        let mut documents: Vec<_> = self.doc_tree.structure.iter().collect();
+        documents.sort_by(|a, b| a.1.cmp(b.1));
+        for (scoped_key, relative_path) in documents {
             // 从内存中获取文档内容
             if let Some(doc_markdown) = context
                 .get_from_memory::<String>(MemoryScope::DOCUMENTATION, scoped_key)
                 .await
             {
-                // 构建完整的输出文件路径
-                let output_file_path = output_dir.join(relative_path);
-
-                // 确保父目录存在
-                if let Some(parent_dir) = output_file_path.parent()
-                    && !parent_dir.exists()
-                {
-                    fs::create_dir_all(parent_dir)?;
+                let doc_markdown = match &source_linker {
+                    Some(linker) => linker.link_references(&doc_markdown),
+                    None => doc_markdown,
+                };
+                let doc_markdown = match &redactor {
+                    Some(redactor) => {
+                        let (redacted, count) = redactor.redact(&doc_markdown);
+                        if count > 0 {
+                            info!("🔒 已脱敏 {} 处敏感信息: {}", count, relative_path);
+                        }
+                        redacted
+                    }
+                    None => doc_markdown,
+                };
+                // 修复列数不一致、缺少表头分隔行的表格
+                let doc_markdown = if context.config.repair_markdown_tables {
+                    let (repaired, count) = repair_tables(&doc_markdown);
+                    if count > 0 {
+                        info!("🩹 已修复 {} 个格式错误的表格: {}", count, relative_path);
+                    }
+                    repaired
+                } else {
+                    doc_markdown
+                };
+
+                // 超出阈值的文档拆分为目录页与多个分页文件
+                let threshold = context.config.split_document_threshold.unwrap_or(0);
+                let pages = paginate(relative_path, &doc_markdown, threshold);
+                if pages.len() > 1 {
+                    info!(
+                        "📑 文档超出拆分阈值，已拆分为 {} 个分页: {}",
+                        pages.len() - 1,
+                        relative_path
+                    );
                 }
 
-                // 写入文档内容到文件
-                fs::write(&output_file_path, doc_markdown)?;
+                for mut page in pages {
+                    if let Some(boilerplate) = &boilerplate {
+                        page.content = boilerplate.apply(&page.content);
+                    }
+
+                    // 构建完整的输出文件路径
+                    let output_file_path = output_dir.join(&page.relative_path);
+
+                    context
+                        .output_manifest
+                        .write()
+                        .await
+                        .record(&page.relative_path, &page.content);
+
+                    if !context.config.force_regenerate
+                        && previous_manifest.as_ref().is_some_and(|manifest| {
+                            manifest.is_unchanged(&page.relative_path, &page.content, output_dir)
+                        })
+                    {
+                        info!(
+                            "⏭️ 文档内容未变化，跳过写入: {}",
+                            output_file_path.display()
+                        );
+                        continue;
+                    }
 
-                println!("💾 已保存文档: {}", output_file_path.display());
+                    // 确保父目录存在
+                    if let Some(parent_dir) = output_file_path.parent()
+                        && !parent_dir.exists()
+                    {
+                        fs::create_dir_all(parent_dir)?;
+                    }
+
+                    // 写入文档内容到文件
+                    fs::write(&output_file_path, page.content)?;
+
+                    info!("💾 已保存文档: {}", output_file_path.display());
+                }
             } else {
                 // 如果文档不存在，记录警告但不中断流程
-                eprintln!("⚠️ 警告: 未找到文档内容，键: {}", scoped_key);
+                warn!("⚠️ 警告: 未找到文档内容，键: {}", scoped_key);
+            }
+        }
+
+        let mut current_manifest = context.output_manifest.write().await;
+
+        // 只清理上次清单中记录过、但本次没有再生成的文件
+        if let Some(previous_manifest) = &previous_manifest {
+            for orphan in previous_manifest.orphaned_files(&current_manifest) {
+                let orphan_path = output_dir.join(&orphan);
+                if orphan_path.is_file() {
+                    fs::remove_file(&orphan_path)?;
+                    info!("🧹 已清理过期文档: {}", orphan_path.display());
+                }
             }
         }
 
-        println!("💾 文档保存完成，输出目录: {}", output_dir.display());
+        current_manifest.generated_at = context
+            .config
+            .generation_time()
+            .map(|time| time.to_rfc3339());
+        current_manifest.save(&manifest_path)?;
+        drop(current_manifest);
+
+        info!("💾 文档保存完成，输出目录: {}", output_dir.display());
 
         // 文档保存完成后，自动修复mermaid图表
         if let Err(e) = MermaidFixer::auto_fix_after_output(context).await {
-            eprintln!("⚠️ mermaid图表修复过程中出现错误: {}", e);
-            eprintln!("💡 这不会影响文档生成的主要流程");
+            warn!("⚠️ mermaid图表修复过程中出现错误: {}", e);
+            warn!("💡 这不会影响文档生成的主要流程");
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_output_path_equal_to_project_root_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("project");
+        std::fs::create_dir_all(project_path.join("src")).unwrap();
+
+        assert!(check_output_path_safety(&project_path, &project_path).is_err());
+        assert!(check_output_path_safety(temp_dir.path(), &project_path).is_err());
+        assert!(check_output_path_safety(Path::new("/"), &project_path).is_err());
+        assert!(check_output_path_safety(&project_path.join("litho.docs"), &project_path).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unsafe_output_path_is_rejected_with_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("project");
+        std::fs::create_dir_all(project_path.join("src")).unwrap();
+        let config = crate::config::Config {
+            project_path: project_path.clone(),
+            output_path: project_path.clone(),
+            internal_path: temp_dir.path().join(".litho"),
+            incremental_output: true,
+            ..Default::default()
+        };
+        let mut manifest = OutputManifest::default();
+        manifest.record("1.Overview.md", "# Overview");
+        manifest
+            .save(&OutputManifest::path(&config.internal_path))
+            .unwrap();
+
+        // 存在输出清单时同样校验输出目录，不清理项目根目录中的文件
+        let context = GeneratorContext::new(config).unwrap();
+        let doc_tree = DocTree {
+            structure: HashMap::new(),
+        };
+        assert!(DiskOutlet::new(doc_tree).save(&context).await.is_err());
+        assert!(project_path.join("src").exists());
+    }
+
+    #[tokio::test]
+    async fn test_manifest_reflects_saved_outputs() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = crate::config::Config {
+            project_path: temp_dir.path().join("project"),
+            output_path: temp_dir.path().join("docs"),
+            internal_path: temp_dir.path().join(".litho"),
+            incremental_output: true,
+            ..Default::default()
+        };
+        let context = GeneratorContext::new(config.clone()).unwrap();
+        context
+            .store_to_memory(
+                MemoryScope::DOCUMENTATION,
+                "overview",
+                "# Overview".to_string(),
+            )
+            .await
+            .unwrap();
+        context
+            .store_to_memory(
+                MemoryScope::DOCUMENTATION,
+                "module_a",
+                "# Module A".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let mut doc_tree = DocTree {
+            structure: HashMap::new(),
+        };
+        doc_tree.insert("overview", "1.Overview.md");
+        doc_tree.insert("module_a", "4.Deep-Exploration/01-ModuleA.md");
+        DiskOutlet::new(doc_tree).save(&context).await.unwrap();
+
+        let manifest_path = OutputManifest::path(&config.internal_path);
+        let manifest = OutputManifest::load(&manifest_path).unwrap().unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        assert_eq!(
+            manifest.files.get("1.Overview.md"),
+            Some(&manifest::content_hash("# Overview"))
+        );
+        assert_eq!(
+            manifest.files.get("4.Deep-Exploration/01-ModuleA.md"),
+            Some(&manifest::content_hash("# Module A"))
+        );
+
+        // 第二次运行不再生成module_a，清单中的旧文件被清理，清单外的文件保留
+        let user_file = config.output_path.join("notes.md");
+        fs::write(&user_file, "user notes").unwrap();
+        let context = GeneratorContext::new(config.clone()).unwrap();
+        context
+            .store_to_memory(
+                MemoryScope::DOCUMENTATION,
+                "overview",
+                "# Overview".to_string(),
+            )
+            .await
+            .unwrap();
+        let mut doc_tree = DocTree {
+            structure: HashMap::new(),
+        };
+        doc_tree.insert("overview", "1.Overview.md");
+        DiskOutlet::new(doc_tree).save(&context).await.unwrap();
+
+        let manifest = OutputManifest::load(&manifest_path).unwrap().unwrap();
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            vec!["1.Overview.md"]
+        );
+        assert!(config.output_path.join("1.Overview.md").exists());
+        assert!(
+            !config
+                .output_path
+                .join("4.Deep-Exploration/01-ModuleA.md")
+                .exists()
+        );
+        assert!(user_file.exists());
+    }
+
+    #[tokio::test]
+    async fn test_preamble_at_top_of_every_output_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = crate::config::Config {
+            project_name: Some("demo".to_string()),
+            project_path: temp_dir.path().join("project"),
+            output_path: temp_dir.path().join("docs"),
+            internal_path: temp_dir.path().join(".litho"),
+            split_document_threshold: Some(900),
+            document_preamble: Some("> {{ project_name }} 自动生成，请勿手动修改".to_string()),
+            document_postamble: Some("_{{ project_name }} 文档_".to_string()),
+            ..Default::default()
+        };
+        let context = GeneratorContext::new(config.clone()).unwrap();
+        let sections: String = ["Alpha", "Beta", "Gamma"]
+            .iter()
+            .map(|name| format!("## {}\n\n{}\n\n", name, "x".repeat(400)))
+            .collect();
+        for (key, content) in [
+            ("overview", "# Overview\n".to_string()),
+            ("code_index", format!("# Code Index\n\n{}", sections)),
+        ] {
+            context
+                .store_to_memory(MemoryScope::DOCUMENTATION, key, content)
+                .await
+                .unwrap();
+        }
+
+        let mut doc_tree = DocTree {
+            structure: HashMap::new(),
+        };
+        doc_tree.insert("overview", "1.Overview.md");
+        doc_tree.insert("code_index", "6.Code-Index.md");
+        DiskOutlet::new(doc_tree).save(&context).await.unwrap();
+
+        let mut output_files: Vec<PathBuf> = fs::read_dir(&config.output_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        output_files.sort();
+        // 概览 + 代码索引目录页及其分页
+        assert!(output_files.len() > 3);
+        for file in output_files {
+            let content = fs::read_to_string(&file).unwrap();
+            assert!(
+                content.starts_with("> demo 自动生成，请勿手动修改\n\n"),
+                "{} 缺少页眉",
+                file.display()
+            );
+            assert!(content.ends_with("_demo 文档_\n"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_runs_produce_identical_documents() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut outputs = Vec::new();
+        for run in ["first", "second"] {
+            let config = crate::config::Config {
+                project_name: Some("demo".to_string()),
+                project_path: temp_dir.path().join("project"),
+                output_path: temp_dir.path().join(run).join("docs"),
+                internal_path: temp_dir.path().join(run).join(".litho"),
+                document_preamble: Some("> {{ project_name }} 生成于 {{ date }}".to_string()),
+                deterministic: true,
+                ..Default::default()
+            };
+            let context = GeneratorContext::new(config.clone()).unwrap();
+            for (key, content) in [
+                ("overview", "# Overview\n"),
+                ("architecture", "# Architecture\n"),
+                ("module_a", "# Module A\n"),
+            ] {
+                context
+                    .store_to_memory(MemoryScope::DOCUMENTATION, key, content.to_string())
+                    .await
+                    .unwrap();
+            }
+
+            let mut doc_tree = DocTree {
+                structure: HashMap::new(),
+            };
+            doc_tree.insert("overview", "1.Overview.md");
+            doc_tree.insert("architecture", "2.Architecture.md");
+            doc_tree.insert("module_a", "4.Deep-Exploration/01-ModuleA.md");
+            DiskOutlet::new(doc_tree).save(&context).await.unwrap();
+
+            let documents: Vec<(String, String)> = [
+                "1.Overview.md",
+                "2.Architecture.md",
+                "4.Deep-Exploration/01-ModuleA.md",
+            ]
+            .iter()
+            .map(|path| {
+                let content = fs::read_to_string(config.output_path.join(path)).unwrap();
+                (path.to_string(), content)
+            })
+            .collect();
+            outputs.push(documents);
+        }
+
+        assert_eq!(outputs[0], outputs[1]);
+        let date = crate::config::Config {
+            deterministic: true,
+            ..Default::default()
+        }
+        .local_generation_time()
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+        assert!(
+            outputs[0][0]
+                .1
+                .starts_with(&format!("> demo 生成于 {}\n\n", date))
+        );
+        // 未设置SOURCE_DATE_EPOCH时省略日期，而不是写入Unix纪元
+        assert!(!outputs[0][0].1.contains("1970-01-01"));
+    }
+
+    #[tokio::test]
+    async fn test_executive_profile_emits_only_overview() {
+        let temp_dir = TempDir::new().unwrap();
+        let args = <crate::cli::Args as clap::Parser>::try_parse_from([
+            "deepwiki-rs",
+            "--profile",
+            "executive",
+            "--target-language",
+            "en",
+        ])
+        .unwrap();
+        let mut config = args.into_config();
+        config.project_path = temp_dir.path().join("project");
+        config.output_path = temp_dir.path().join("docs");
+        config.internal_path = temp_dir.path().join(".litho");
+        assert_eq!(config.profile, DocumentProfile::Executive);
+
+        let context = GeneratorContext::new(config.clone()).unwrap();
+        for agent_type in [
+            AgentType::Overview,
+            AgentType::Architecture,
+            AgentType::Workflow,
+            AgentType::Boundary,
+            AgentType::CodeIndex,
+        ] {
+            context
+                .store_to_memory(
+                    MemoryScope::DOCUMENTATION,
+                    &agent_type.to_string(),
+                    format!("# {}\n", agent_type),
+                )
+                .await
+                .unwrap();
+        }
+
+        let doc_tree = DocTree::with_profile(&config.target_language, config.profile);
+        DiskOutlet::new(doc_tree).save(&context).await.unwrap();
+
+        let output_files: Vec<String> = fs::read_dir(&config.output_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(output_files, vec!["1.Overview.md".to_string()]);
+    }
+}
@@ -0,0 +1,207 @@
+use std::path::Path;
+
+/// 拆分后的文档分页
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentPage {
+    /// 相对于输出目录的文件路径
+    pub relative_path: String,
+    /// 分页内容
+    pub content: String,
+}
+
+/// 将超出阈值的文档按章节拆分为多个分页文件
+///
+/// 原路径保留为目录页，列出各分页链接，保证其他文档中指向原路径的链接仍然有效；
+/// 分页文件命名为`<原文件名>-1.md`、`<原文件名>-2.md`，并在页尾附带上一页/下一页导航。
+/// 文档未超出阈值或无法按章节拆分时原样返回。
+pub fn paginate(relative_path: &str, content: &str, threshold: usize) -> Vec<DocumentPage> {
+    let single = || {
+        vec![DocumentPage {
+            relative_path: relative_path.to_string(),
+            content: content.to_string(),
+        }]
+    };
+    if threshold == 0 || content.len() <= threshold {
+        return single();
+    }
+
+    let (title, sections) = split_sections(content);
+    let parts = group_sections(&sections, threshold);
+    if parts.len() < 2 {
+        return single();
+    }
+
+    let path = Path::new(relative_path);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(relative_path);
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("md");
+    let part_file_names: Vec<String> = (1..=parts.len())
+        .map(|number| format!("{}-{}.{}", stem, number, extension))
+        .collect();
+    let part_path = |file_name: &str| match path.parent().and_then(|p| p.to_str()) {
+        Some(parent) if !parent.is_empty() => format!("{}/{}", parent, file_name),
+        _ => file_name.to_string(),
+    };
+
+    // 目录页
+    let mut index = title.clone().unwrap_or_else(|| format!("# {}", stem));
+    index.push_str("\n\n");
+    for (number, (part, file_name)) in parts.iter().zip(&part_file_names).enumerate() {
+        let label = part_title(part).unwrap_or_else(|| format!("{} {}", stem, number + 1));
+        index.push_str(&format!("{}. [{}]({})\n", number + 1, label, file_name));
+    }
+
+    let mut pages = vec![DocumentPage {
+        relative_path: relative_path.to_string(),
+        content: index,
+    }];
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(relative_path);
+    for (i, part) in parts.iter().enumerate() {
+        let mut page = String::new();
+        if let Some(title) = &title {
+            page.push_str(&format!("{} ({}/{})\n\n", title, i + 1, parts.len()));
+        }
+        page.push_str(part.trim_end());
+        page.push_str("\n\n---\n\n");
+        page.push_str(&navigation(&part_file_names, i, file_name));
+        pages.push(DocumentPage {
+            relative_path: part_path(&part_file_names[i]),
+            content: page,
+        });
+    }
+    pages
+}
+
+/// 拆分出文档一级标题与按二级标题划分的章节，代码块内的标题行不作为拆分点
+fn split_sections(content: &str) -> (Option<String>, Vec<String>) {
+    let mut title = None;
+    let mut sections: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut in_code_block = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+        }
+        if !in_code_block {
+            if title.is_none() && sections.is_empty() && line.starts_with("# ") {
+                title = Some(line.to_string());
+                continue;
+            }
+            if line.starts_with("## ") && !current.trim().is_empty() {
+                sections.push(std::mem::take(&mut current));
+            }
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        sections.push(current);
+    }
+    (title, sections)
+}
+
+/// 将章节依次装入分页，单个分页不超过阈值（单个章节超出阈值时独占一页）
+fn group_sections(sections: &[String], threshold: usize) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for section in sections {
+        if !current.is_empty() && current.len() + section.len() > threshold {
+            parts.push(std::mem::take(&mut current));
+        }
+        current.push_str(section);
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// 取分页中第一个二级标题作为分页名称
+fn part_title(part: &str) -> Option<String> {
+    part.lines()
+        .find_map(|line| line.strip_prefix("## "))
+        .map(|title| title.trim().to_string())
+}
+
+/// 生成分页导航
+fn navigation(part_file_names: &[String], index: usize, index_file_name: &str) -> String {
+    let mut links = Vec::new();
+    if index > 0 {
+        links.push(format!("[« {}]({})", index, part_file_names[index - 1]));
+    }
+    links.push(format!(
+        "[{} / {}]({})",
+        index + 1,
+        part_file_names.len(),
+        index_file_name
+    ));
+    if index + 1 < part_file_names.len() {
+        links.push(format!("[{} »]({})", index + 2, part_file_names[index + 1]));
+    }
+    format!("{}\n", links.join(" | "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(name: &str, body_len: usize) -> String {
+        format!("## {}\n\n{}\n\n", name, "x".repeat(body_len))
+    }
+
+    #[test]
+    fn test_oversized_document_is_split_into_expected_parts() {
+        let content = format!(
+            "# Code Index\n\n{}{}{}{}",
+            section("Alpha", 400),
+            section("Beta", 400),
+            section("Gamma", 400),
+            section("Delta", 400)
+        );
+
+        let pages = paginate("6.Code-Index.md", &content, 900);
+
+        // 目录页 + 两页，每页容纳两个章节
+        let paths: Vec<_> = pages.iter().map(|p| p.relative_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["6.Code-Index.md", "6.Code-Index-1.md", "6.Code-Index-2.md"]
+        );
+        assert!(pages[0].content.contains("[Alpha](6.Code-Index-1.md)"));
+        assert!(pages[0].content.contains("[Gamma](6.Code-Index-2.md)"));
+        assert!(pages[1].content.contains("## Beta"));
+        assert!(pages[1].content.contains("[2 »](6.Code-Index-2.md)"));
+        assert!(pages[2].content.contains("[« 1](6.Code-Index-1.md)"));
+    }
+
+    #[test]
+    fn test_small_document_is_not_split() {
+        let content = format!("# Overview\n\n{}", section("Alpha", 100));
+        let pages = paginate("4.Deep-Exploration/01-Core.md", &content, 900);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].content, content);
+
+        let pages = paginate("4.Deep-Exploration/01-Core.md", &content, 0);
+        assert_eq!(pages.len(), 1);
+    }
+
+    #[test]
+    fn test_headings_inside_code_blocks_are_not_split_points() {
+        let content = format!(
+            "# Doc\n\n## Real\n\n```markdown\n## Fake\n{}\n```\n\n{}",
+            "y".repeat(500),
+            section("Next", 500)
+        );
+        let pages = paginate("docs/guide.md", &content, 600);
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[1].relative_path, "docs/guide-1.md");
+        assert!(pages[1].content.contains("## Fake"));
+    }
+}
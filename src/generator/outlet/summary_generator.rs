@@ -10,6 +10,7 @@ use crate::generator::preprocess::memory::{MemoryScope as PreprocessMemoryScope,
 use crate::generator::research::memory::MemoryScope as ResearchMemoryScope;
 use crate::generator::research::types::AgentType as ResearchAgentType;
 use crate::generator::workflow::TimingKeys;
+use crate::i18n::Localizer;
 
 /// Summary数据收集器 - 负责从context中提取四类调研材料
 #[allow(dead_code)]
@@ -226,28 +227,29 @@ impl SummaryDataCollector {
 }
 
 impl SummaryContentGenerator {
-    /// 根据收集的数据生成Markdown格式的summary内容
+    /// 根据收集的数据生成Markdown格式的summary内容，章节标题按`texts`本地化
     #[allow(dead_code)]
-    pub fn generate_content(data: &SummaryData, mode: SummaryMode) -> String {
+    pub fn generate_content(data: &SummaryData, mode: SummaryMode, texts: &Localizer) -> String {
         match mode {
-            SummaryMode::Full => Self::generate_full_content(data),
-            SummaryMode::Brief => Self::generate_brief_content(data),
+            SummaryMode::Full => Self::generate_full_content(data, texts),
+            SummaryMode::Brief => Self::generate_brief_content(data, texts),
         }
     }
 
     /// 生成完整版本的summary内容
-    fn generate_full_content(data: &SummaryData) -> String {
+    fn generate_full_content(data: &SummaryData, texts: &Localizer) -> String {
         let mut content = String::new();
 
         // 1. 基础信息
-        content.push_str("# 项目分析总结报告（完整版）\n\n");
+        content.push_str(&format!("# {}\n\n", texts.text("summary.full_title")));
         content.push_str(&format!(
-            "生成时间: {}\n\n",
+            "{}: {}\n\n",
+            texts.text("summary.generated_at"),
             chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
         ));
 
         // 2. 执行耗时统计
-        content.push_str("## 执行耗时统计\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("summary.timing")));
         let timing = &data.timing_stats;
         content.push_str(&format!(
             "- **总执行时间**: {:.2} 秒\n",
@@ -301,10 +303,10 @@ impl SummaryContentGenerator {
         ));
 
         // 3. 缓存性能统计与节约效果
-        content.push_str("## 缓存性能统计与节约效果\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("summary.cache")));
         let stats = &data.cache_stats;
 
-        content.push_str("### 性能指标\n");
+        content.push_str(&format!("### {}\n", texts.text("summary.cache_metrics")));
         content.push_str(&format!(
             "- **缓存命中率**: {:.1}%\n",
             stats.hit_rate * 100.0
@@ -317,7 +319,7 @@ impl SummaryContentGenerator {
             content.push_str(&format!("- **缓存错误**: {} 次\n", stats.cache_errors));
         }
 
-        content.push_str("\n### 节约效果\n");
+        content.push_str(&format!("\n### {}\n", texts.text("summary.cache_savings")));
         content.push_str(&format!(
             "- **节省推理时间**: {:.1} 秒\n",
             stats.inference_time_saved
@@ -347,12 +349,12 @@ impl SummaryContentGenerator {
         content.push('\n');
 
         // 4. 核心调研数据汇总
-        content.push_str("## 核心调研数据汇总\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("summary.research_data")));
         content.push_str("根据Prompt模板数据整合规则，以下为四类调研材料的完整内容：\n\n");
 
         // 系统上下文调研报告
         if let Some(ref system_context) = data.system_context {
-            content.push_str("### 系统上下文调研报告\n");
+            content.push_str(&format!("### {}\n", texts.text("summary.system_context")));
             content.push_str("提供项目的核心目标、用户角色和系统边界信息。\n\n");
             content.push_str(&format!(
                 "```json\n{}\n```\n\n",
@@ -362,7 +364,7 @@ impl SummaryContentGenerator {
 
         // 领域模块调研报告
         if let Some(ref domain_modules) = data.domain_modules {
-            content.push_str("### 领域模块调研报告\n");
+            content.push_str(&format!("### {}\n", texts.text("summary.domain_modules")));
             content.push_str("提供高层次的领域划分、模块关系和核心业务流程信息。\n\n");
             content.push_str(&format!(
                 "```json\n{}\n```\n\n",
@@ -372,7 +374,7 @@ impl SummaryContentGenerator {
 
         // 工作流调研报告
         if let Some(ref workflow) = data.workflow {
-            content.push_str("### 工作流调研报告\n");
+            content.push_str(&format!("### {}\n", texts.text("summary.workflow")));
             content.push_str("包含对代码库的静态分析结果和业务流程分析。\n\n");
             content.push_str(&format!(
                 "```json\n{}\n```\n\n",
@@ -382,7 +384,7 @@ impl SummaryContentGenerator {
 
         // 代码洞察数据
         if let Some(ref code_insights) = data.code_insights {
-            content.push_str("### 代码洞察数据\n");
+            content.push_str(&format!("### {}\n", texts.text("summary.code_insights")));
             content.push_str("来自预处理阶段的代码分析结果，包含函数、类和模块的定义。\n\n");
             content.push_str(&format!(
                 "```json\n{}\n```\n\n",
@@ -391,7 +393,7 @@ impl SummaryContentGenerator {
         }
 
         // 5. Memory存储统计
-        content.push_str("## Memory存储统计\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("summary.memory")));
         if data.memory_stats.is_empty() {
             content.push_str("暂无Memory存储数据。\n\n");
         } else {
@@ -408,7 +410,7 @@ impl SummaryContentGenerator {
         }
 
         // 6. 生成文档统计
-        content.push_str("## 生成文档统计\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("summary.documents")));
         content.push_str(&format!(
             "生成文档数量: {} 个\n\n",
             data.generated_docs.len()
@@ -421,18 +423,22 @@ impl SummaryContentGenerator {
     }
 
     /// 生成摘要版本的summary内容
-    fn generate_brief_content(data: &SummaryData) -> String {
+    fn generate_brief_content(data: &SummaryData, texts: &Localizer) -> String {
         let mut content = String::new();
 
         // 1. 基础信息
-        content.push_str("# 项目分析摘要报告\n\n");
+        content.push_str(&format!("# {}\n\n", texts.text("summary.brief_title")));
         content.push_str(&format!(
-            "生成时间: {}\n\n",
+            "{}: {}\n\n",
+            texts.text("summary.generated_at"),
             chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
         ));
 
         // 2. 执行概览
-        content.push_str("## 执行概览\n\n");
+        content.push_str(&format!(
+            "## {}\n\n",
+            texts.text("summary.execution_overview")
+        ));
         let timing = &data.timing_stats;
         content.push_str(&format!(
             "**总执行时间**: {:.2} 秒\n",
@@ -460,7 +466,7 @@ impl SummaryContentGenerator {
         content.push('\n');
 
         // 3. 缓存效果概览
-        content.push_str("## 缓存效果概览\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("summary.cache_overview")));
         let stats = &data.cache_stats;
 
         // 核心指标
@@ -499,7 +505,10 @@ impl SummaryContentGenerator {
         content.push('\n');
 
         // 4. 调研数据概览
-        content.push_str("## 调研数据概览\n\n");
+        content.push_str(&format!(
+            "## {}\n\n",
+            texts.text("summary.research_overview")
+        ));
         content.push_str("根据Prompt模板数据整合规则，成功收集四类调研材料：\n\n");
 
         let mut collected_count = 0;
@@ -540,7 +549,7 @@ impl SummaryContentGenerator {
         ));
 
         // 5. Memory存储概览
-        content.push_str("## Memory存储概览\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("summary.memory_overview")));
         if data.memory_stats.is_empty() {
             content.push_str("暂无Memory存储数据。\n\n");
         } else {
@@ -555,7 +564,10 @@ impl SummaryContentGenerator {
             let mut sorted_stats: Vec<_> = data.memory_stats.iter().collect();
             sorted_stats.sort_by(|a, b| b.1.cmp(a.1));
 
-            content.push_str("### 主要存储分布（前3位）\n");
+            content.push_str(&format!(
+                "### {}\n",
+                texts.text("summary.memory_distribution")
+            ));
             for (scope, size) in sorted_stats.iter().take(3) {
                 let percentage = (**size as f64 / total_size as f64) * 100.0;
                 content.push_str(&format!(
@@ -567,7 +579,10 @@ impl SummaryContentGenerator {
         }
 
         // 6. 文档生成概览
-        content.push_str("## 文档生成概览\n\n");
+        content.push_str(&format!(
+            "## {}\n\n",
+            texts.text("summary.documents_overview")
+        ));
         content.push_str(&format!(
             "**文档生成数量**: {} 个\n",
             data.generated_docs.len()
@@ -581,7 +596,7 @@ impl SummaryContentGenerator {
         content.push('\n');
 
         // 7. 总体评估
-        content.push_str("## 总体评估\n\n");
+        content.push_str(&format!("## {}\n\n", texts.text("summary.assessment")));
 
         // 数据完整性评估
         let data_completeness = (collected_count as f64 / 4.0) * 100.0;
@@ -636,3 +651,68 @@ impl SummaryContentGenerator {
         content
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::TargetLanguage;
+
+    fn summary_data() -> SummaryData {
+        SummaryData {
+            system_context: Some(serde_json::json!({})),
+            domain_modules: Some(serde_json::json!({})),
+            workflow: Some(serde_json::json!({})),
+            code_insights: Some(serde_json::json!([])),
+            memory_stats: HashMap::from([("documentation".to_string(), 1024)]),
+            cache_stats: CacheStatsData {
+                hit_rate: 0.5,
+                total_operations: 4,
+                cache_hits: 2,
+                cache_misses: 2,
+                cache_writes: 2,
+                cache_errors: 0,
+                inference_time_saved: 1.0,
+                cost_saved: 0.01,
+                performance_improvement: 10.0,
+                input_tokens_saved: 100,
+                output_tokens_saved: 50,
+            },
+            generated_docs: vec!["overview".to_string()],
+            timing_stats: TimingStats {
+                total_execution_time: 10.0,
+                preprocess_time: 2.0,
+                research_time: 3.0,
+                compose_time: 4.0,
+                output_time: 1.0,
+                document_generation_time: 0.0,
+                summary_generation_time: 0.01,
+            },
+        }
+    }
+
+    #[test]
+    fn test_english_summary_has_no_chinese_headers() {
+        let texts = Localizer::new(TargetLanguage::English, TargetLanguage::Chinese);
+        let data = summary_data();
+
+        for mode in [SummaryMode::Full, SummaryMode::Brief] {
+            let content = SummaryContentGenerator::generate_content(&data, mode, &texts);
+            let headers: Vec<&str> = content
+                .lines()
+                .filter(|line| line.starts_with('#'))
+                .collect();
+            assert!(headers.len() > 5);
+            assert!(
+                headers
+                    .iter()
+                    .all(|line| !line.chars().any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c))),
+                "{:?}",
+                headers
+            );
+        }
+
+        let chinese = Localizer::new(TargetLanguage::Chinese, TargetLanguage::English);
+        let content = SummaryContentGenerator::generate_content(&data, SummaryMode::Full, &chinese);
+        assert!(content.starts_with("# 项目分析总结报告（完整版）\n\n生成时间: "));
+    }
+}
@@ -4,6 +4,7 @@ use std::fs;
 use super::Outlet;
 use super::summary_generator::{SummaryContentGenerator, SummaryDataCollector, SummaryMode};
 use crate::generator::context::GeneratorContext;
+use log::info;
 
 /// Summary输出器 - 负责生成和保存summary报告
 #[allow(dead_code)]
@@ -39,25 +40,29 @@ impl Outlet for SummaryOutlet {
                 fs::create_dir_all(output_dir)?;
             }
 
-            println!("\n🖊️ 生成项目总结报告...");
+            info!("\n🖊️ 生成项目总结报告...");
 
             // 收集数据（只需要收集一次）
             let summary_data = SummaryDataCollector::collect_data(context).await?;
+            let texts = context.config.localizer();
 
             // 生成并保存完整版
             let full_content =
-                SummaryContentGenerator::generate_content(&summary_data, SummaryMode::Full);
+                SummaryContentGenerator::generate_content(&summary_data, SummaryMode::Full, &texts);
             let full_path = output_dir.join(&self.full_file_path);
             fs::write(&full_path, full_content)?;
-            println!("💾 已保存完整版总结报告: {}", full_path.display());
+            info!("💾 已保存完整版总结报告: {}", full_path.display());
 
             // 如果需要生成摘要版
             if self.generate_both {
-                let brief_content =
-                    SummaryContentGenerator::generate_content(&summary_data, SummaryMode::Brief);
+                let brief_content = SummaryContentGenerator::generate_content(
+                    &summary_data,
+                    SummaryMode::Brief,
+                    &texts,
+                );
                 let brief_path = output_dir.join(&self.brief_file_path);
                 fs::write(&brief_path, brief_content)?;
-                println!("💾 已保存摘要版总结报告: {}", brief_path.display());
+                info!("💾 已保存摘要版总结报告: {}", brief_path.display());
             }
 
             Ok(())
@@ -0,0 +1,192 @@
+//! Markdown表格修复 - LLM生成的表格常出现列数不一致、缺少或错位的表头分隔行，
+//! 保存文档前按表头列数补齐/截断单元格并重建分隔行，保证表格能被正确渲染
+
+/// 修复文档中格式错误的表格，返回修复后的文本及被修复的表格数量；格式正确的表格与代码块内容保持原样
+pub fn repair_tables(markdown: &str) -> (String, usize) {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut output: Vec<String> = Vec::with_capacity(lines.len());
+    let mut repaired = 0;
+    let mut fence: Option<&str> = None;
+    let mut index = 0;
+
+    while index < lines.len() {
+        let trimmed = lines[index].trim_start();
+
+        // 代码块中的竖线不属于表格
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            output.push(lines[index].to_string());
+            index += 1;
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            output.push(lines[index].to_string());
+            index += 1;
+            continue;
+        }
+
+        let end = lines[index..]
+            .iter()
+            .position(|line| !is_table_line(line))
+            .map_or(lines.len(), |offset| index + offset);
+        // 至少两行才视为表格，单行竖线文本保持原样
+        if end - index < 2 {
+            output.push(lines[index].to_string());
+            index += 1;
+            continue;
+        }
+
+        let table = &lines[index..end];
+        match repair_table(table) {
+            Some(fixed) => {
+                output.extend(fixed);
+                repaired += 1;
+            }
+            None => output.extend(table.iter().map(|line| line.to_string())),
+        }
+        index = end;
+    }
+
+    let mut result = output.join("\n");
+    if markdown.ends_with('\n') {
+        result.push('\n');
+    }
+    (result, repaired)
+}
+
+fn is_table_line(line: &str) -> bool {
+    line.trim_start().starts_with('|')
+}
+
+/// 单元格对齐方式，取自表头分隔行
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Alignment {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    fn parse(cell: &str) -> Option<Self> {
+        let cell = cell.trim();
+        let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+        if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+            return None;
+        }
+        Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+            (true, true) => Alignment::Center,
+            (true, false) => Alignment::Left,
+            (false, true) => Alignment::Right,
+            (false, false) => Alignment::None,
+        })
+    }
+
+    fn separator(self) -> &'static str {
+        match self {
+            Alignment::None => "---",
+            Alignment::Left => ":---",
+            Alignment::Center => ":---:",
+            Alignment::Right => "---:",
+        }
+    }
+}
+
+/// 表格格式正确时返回None，否则返回以表头列数为准重建的表格行
+fn repair_table(table: &[&str]) -> Option<Vec<String>> {
+    let indent: String = table[0].chars().take_while(|c| c.is_whitespace()).collect();
+    let header = split_cells(table[0]);
+    let columns = header.len();
+
+    let separator = split_cells(table[1]);
+    let alignments: Option<Vec<Alignment>> = separator
+        .iter()
+        .map(|cell| Alignment::parse(cell))
+        .collect();
+    let (alignments, body) = match alignments {
+        Some(alignments) => (alignments, &table[2..]),
+        // 缺少分隔行时第二行起均为数据行
+        None => (Vec::new(), &table[1..]),
+    };
+    let body: Vec<Vec<String>> = body.iter().map(|line| split_cells(line)).collect();
+
+    let well_formed = alignments.len() == columns && body.iter().all(|row| row.len() == columns);
+    if well_formed {
+        return None;
+    }
+
+    let render = |mut cells: Vec<String>| {
+        cells.resize(columns, String::new());
+        format!("{}| {} |", indent, cells.join(" | "))
+    };
+    let separator = (0..columns)
+        .map(|column| {
+            alignments
+                .get(column)
+                .copied()
+                .unwrap_or(Alignment::None)
+                .separator()
+                .to_string()
+        })
+        .collect();
+
+    let mut rows = vec![render(header), render(separator)];
+    rows.extend(body.into_iter().map(render));
+    Some(rows)
+}
+
+/// 拆分表格行的单元格，去除首尾竖线，保留转义的`\|`
+fn split_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => line,
+    };
+
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in line.chars() {
+        if c == '|' && !escaped {
+            cells.push(current.trim().to_string());
+            current.clear();
+        } else {
+            current.push(c);
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    cells.push(current.trim().to_string());
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ragged_table_is_normalized() {
+        let markdown = "## 模块\n\n| 模块 | 职责 | 路径 |\n|---|:---:|\n| config | 配置加载 | src/config | 多余 |\n| cli | 命令行 |\n\n```\n| 代码块 | 不处理\n| x |\n```\n";
+
+        let (repaired, count) = repair_tables(markdown);
+
+        assert_eq!(count, 1);
+        assert_eq!(
+            repaired,
+            "## 模块\n\n| 模块 | 职责 | 路径 |\n| --- | :---: | --- |\n| config | 配置加载 | src/config |\n| cli | 命令行 |  |\n\n```\n| 代码块 | 不处理\n| x |\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_separator_is_inserted_and_valid_table_untouched() {
+        let (repaired, count) = repair_tables("| a | b\\|c |\n| 1 | 2 |\n");
+        assert_eq!(count, 1);
+        assert_eq!(repaired, "| a | b\\|c |\n| --- | --- |\n| 1 | 2 |\n");
+
+        let valid = "|a|b|\n|-|-:|\n|1|2|";
+        assert_eq!(repair_tables(valid), (valid.to_string(), 0));
+    }
+}
@@ -1,16 +1,20 @@
 use crate::generator::agent_executor::{AgentExecuteParams, extract};
 use crate::{
     generator::{
-        context::GeneratorContext,
+        context::GeneratorContext, events::GeneratorEvent,
         preprocess::extractors::language_processors::LanguageProcessorManager,
     },
     types::{
         code::{CodeDossier, CodeInsight},
         project_structure::ProjectStructure,
     },
-    utils::{sources::read_dependency_code_source, threads::do_parallel_with_limit},
+    utils::{
+        file_utils::read_text_with_limit, sources::read_dependency_code_source,
+        threads::do_parallel_with_limit,
+    },
 };
 use anyhow::Result;
+use log::{error, info, warn};
 
 pub struct CodeAnalyze {
     language_processor: LanguageProcessorManager,
@@ -35,7 +39,11 @@ impl CodeAnalyze {
         codes: &[CodeDossier],
         project_structure: &ProjectStructure,
     ) -> Result<Vec<CodeInsight>> {
-        let max_parallels = context.config.llm.max_parallels;
+        let max_parallels = context.config.llm.effective_max_parallels();
+        let language_processor =
+            LanguageProcessorManager::with_overrides(&context.config.language_overrides)
+                .with_interface_limit(context.config.max_interfaces_per_file)
+                .with_comment_markers(&context.config.important_comment_markers);
 
         // 创建并发任务
         let analysis_futures: Vec<_> = codes
@@ -44,18 +52,30 @@ impl CodeAnalyze {
                 let code_clone = code.clone();
                 let context_clone = context.clone();
                 let project_structure_clone = project_structure.clone();
-                let language_processor = self.language_processor.clone();
+                let language_processor = language_processor.clone();
 
                 Box::pin(async move {
                     let code_analyze = CodeAnalyze { language_processor };
                     let agent_params = code_analyze
-                        .prepare_single_code_agent_params(&project_structure_clone, &code_clone)
+                        .prepare_single_code_agent_params(
+                            &context_clone,
+                            &project_structure_clone,
+                            &code_clone,
+                        )
                         .await?;
                     let mut code_insight =
                         extract::<CodeInsight>(&context_clone, agent_params).await?;
 
                     // LLM会重写source_summary，在这里排除掉并做覆盖
                     code_insight.code_dossier.source_summary = code_clone.source_summary.to_owned();
+                    // LLM（或缓存）返回的分类可能是任意字符串，规范化为已配置的分类
+                    code_insight.code_dossier.code_purpose = code_insight
+                        .code_dossier
+                        .code_purpose
+                        .resolve_custom(&context_clone.config.custom_code_purposes);
+                    context_clone.emit(GeneratorEvent::FileAnalyzed {
+                        file_path: code_clone.file_path.clone(),
+                    });
 
                     Result::<CodeInsight>::Ok(code_insight)
                 })
@@ -73,13 +93,13 @@ impl CodeAnalyze {
                     code_insights.push(code_insight);
                 }
                 Err(e) => {
-                    eprintln!("❌ 代码分析失败: {}", e);
+                    error!("❌ 代码分析失败: {}", e);
                     return Err(e);
                 }
             }
         }
 
-        println!("✓ 并发代码分析完成，成功分析{}个文件", code_insights.len());
+        info!("✓ 并发代码分析完成，成功分析{}个文件", code_insights.len());
         Ok(code_insights)
     }
 }
@@ -87,11 +107,14 @@ impl CodeAnalyze {
 impl CodeAnalyze {
     async fn prepare_single_code_agent_params(
         &self,
+        context: &GeneratorContext,
         project_structure: &ProjectStructure,
         codes: &CodeDossier,
     ) -> Result<AgentExecuteParams> {
         // 首先进行静态分析
-        let code_analyse = self.analyze_code_by_rules(codes, project_structure).await?;
+        let code_analyse = self
+            .analyze_code_by_rules(context, codes, project_structure)
+            .await?;
 
         // 然后使用AI增强分析
         let prompt_user = self.build_code_analysis_prompt(project_structure, &code_analyse);
@@ -118,6 +141,18 @@ impl CodeAnalyze {
         let dependency_code =
             read_dependency_code_source(&self.language_processor, analysis, project_path);
 
+        // 跳过复杂度计算的文件明确标注，避免模型将其当作真实的度量结果
+        let (lines_of_code, cyclomatic_complexity) = match &analysis.complexity_metrics {
+            Some(metrics) => (
+                metrics.lines_of_code.to_string(),
+                format!("{:.1}", metrics.cyclomatic_complexity),
+            ),
+            None => (
+                "未计算（文件超出复杂度计算阈值）".to_string(),
+                "未计算".to_string(),
+            ),
+        };
+
         format!(
             include_str!("prompts/code_analyze_user.tpl"),
             analysis.code_dossier.name,
@@ -127,8 +162,8 @@ impl CodeAnalyze {
             analysis.responsibilities.join(", "),
             analysis.interfaces.len(),
             analysis.dependencies.len(),
-            analysis.complexity_metrics.lines_of_code,
-            analysis.complexity_metrics.cyclomatic_complexity,
+            lines_of_code,
+            cyclomatic_complexity,
             analysis.code_dossier.source_summary,
             dependency_code
         )
@@ -136,16 +171,23 @@ impl CodeAnalyze {
 
     async fn analyze_code_by_rules(
         &self,
+        context: &GeneratorContext,
         code: &CodeDossier,
         project_structure: &ProjectStructure,
     ) -> Result<CodeInsight> {
         let full_path = project_structure.root_path.join(&code.file_path);
+        let max_complexity_file_size = context.config.max_complexity_file_size;
 
-        // 读取文件内容
-        let content = if full_path.exists() {
-            tokio::fs::read_to_string(&full_path).await?
+        // 读取文件内容（按源码文件大小上限有界读取，避免超大文件占满内存）
+        let (content, truncated) = if full_path.exists() {
+            match read_text_with_limit(&full_path, context.config.max_file_size).await {
+                Ok(Some(result)) => result,
+                // 二进制内容不参与静态分析
+                Ok(None) => (String::new(), false),
+                Err(e) => return Err(e.into()),
+            }
         } else {
-            String::new()
+            (String::new(), false)
         };
 
         // 分析接口
@@ -158,10 +200,20 @@ impl CodeAnalyze {
             .language_processor
             .extract_dependencies(&code.file_path, &content);
 
-        // 计算复杂度指标
-        let complexity_metrics = self
-            .language_processor
-            .calculate_complexity_metrics(&content);
+        // 计算复杂度指标，超出阈值的文件跳过计算
+        let complexity_metrics = if truncated || content.len() as u64 > max_complexity_file_size {
+            warn!(
+                "   ⚠️ 文件 {} 超出复杂度计算阈值（{}字节），跳过复杂度计算",
+                code.file_path.display(),
+                max_complexity_file_size
+            );
+            None
+        } else {
+            Some(
+                self.language_processor
+                    .calculate_complexity_metrics(&content),
+            )
+        };
 
         Ok(CodeInsight {
             code_dossier: code.clone(),
@@ -173,3 +225,65 @@ impl CodeAnalyze {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::code::CodePurpose;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_complexity_skipped_above_threshold_without_truncating_code() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source: String = (0..40)
+            .map(|i| format!("pub fn handler_{}() -> u32 {{\n    {}\n}}\n\n", i, i))
+            .collect();
+        std::fs::write(temp_dir.path().join("lib.rs"), &source).unwrap();
+
+        let mut config = crate::config::Config {
+            max_document_read_bytes: 64,
+            max_complexity_file_size: 256,
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
+        let project_structure = ProjectStructure {
+            project_name: "demo".to_string(),
+            root_path: temp_dir.path().to_path_buf(),
+            directories: vec![],
+            files: vec![],
+            total_files: 1,
+            total_directories: 0,
+            file_types: HashMap::new(),
+            size_distribution: HashMap::new(),
+        };
+        let code = CodeDossier {
+            name: "lib.rs".to_string(),
+            file_path: PathBuf::from("lib.rs"),
+            source_summary: String::new(),
+            code_purpose: CodePurpose::Lib,
+            importance_score: 0.8,
+            description: None,
+            functions: vec![],
+            interfaces: vec![],
+        };
+
+        let insight = CodeAnalyze::new()
+            .analyze_code_by_rules(&context, &code, &project_structure)
+            .await
+            .unwrap();
+
+        // 源码不受文档读取上限截断，末尾的接口同样被提取
+        assert!(
+            insight
+                .interfaces
+                .iter()
+                .any(|interface| interface.name == "handler_39")
+        );
+        // 超出复杂度计算阈值时不记录复杂度，而不是记录为0
+        assert!(insight.complexity_metrics.is_none());
+        let prompt = CodeAnalyze::new().build_code_analysis_prompt(&project_structure, &insight);
+        assert!(prompt.contains("- 圈复杂度: 未计算"));
+    }
+}
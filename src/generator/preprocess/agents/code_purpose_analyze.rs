@@ -3,6 +3,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use crate::config::CustomCodePurpose;
 use crate::generator::agent_executor::{AgentExecuteParams, extract};
 use crate::generator::context::GeneratorContext;
 use crate::types::code::{CodePurpose, CodePurposeMapper};
@@ -38,19 +39,33 @@ impl CodePurposeEnhancer {
         file_name: &str,
         file_content: &str,
     ) -> Result<CodePurpose> {
+        let custom_purposes = &context.config.custom_code_purposes;
+
+        // 自定义分类的路径规则优先于内置规则
+        if let Some(custom) = custom_purposes
+            .iter()
+            .find(|custom| custom.matches_path(&file_path.to_string_lossy()))
+        {
+            return Ok(CodePurpose::Custom(custom.name.clone()));
+        }
+
         // 首先使用规则映射
         let rule_based_type =
             CodePurposeMapper::map_by_path_and_name(&file_path.to_string_lossy(), file_name);
 
-        // 如果规则映射得到明确类型且有高置信度，直接返回
-        if rule_based_type != CodePurpose::Other {
+        // 如果规则映射得到明确类型且有高置信度，直接返回；离线模式下不使用AI分析
+        if rule_based_type != CodePurpose::Other || context.config.llm.offline {
             return Ok(rule_based_type);
         }
 
         // 如果有AI分析器且有文件内容，使用AI增强分析
         let prompt_sys = "你是一个专业的代码架构分析师，专门分析代码文件的组件类型。".to_string();
-        let prompt_user =
-            self.build_code_purpose_analysis_prompt(file_path, file_content, file_name);
+        let prompt_user = self.build_code_purpose_analysis_prompt(
+            file_path,
+            file_content,
+            file_name,
+            custom_purposes,
+        );
 
         let analyze_result = extract::<AICodePurposeAnalysis>(
             context,
@@ -64,7 +79,8 @@ impl CodePurposeEnhancer {
         .await;
 
         match analyze_result {
-            Ok(ai_analysis) => {
+            Ok(mut ai_analysis) => {
+                ai_analysis.code_purpose = ai_analysis.code_purpose.resolve_custom(custom_purposes);
                 // 如果AI分析置信度高，使用AI结果
                 if ai_analysis.confidence > 0.7 {
                     return Ok(ai_analysis.code_purpose);
@@ -89,6 +105,7 @@ impl CodePurposeEnhancer {
         file_path: &Path,
         file_content: &str,
         file_name: &str,
+        custom_purposes: &[CustomCodePurpose],
     ) -> String {
         // 安全地截取文件内容的前1000个字符用于分析
         let content_preview = if file_content.chars().count() > 1000 {
@@ -98,11 +115,107 @@ impl CodePurposeEnhancer {
             file_content.to_string()
         };
 
+        let custom_options: String = custom_purposes
+            .iter()
+            .map(|custom| format!("- \"{}\" ({})\n", custom.name, custom.description))
+            .collect();
+
         format!(
             include_str!("prompts/code_purpose_analyze_user.tpl"),
             file_path.display(),
             file_name,
-            content_preview
+            content_preview,
+            custom_options
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::generator::research::agents::boundary_analyzer::is_boundary_purpose;
+
+    #[tokio::test]
+    async fn test_custom_purpose_round_trips_through_classification_and_filtering() {
+        let mut config = Config {
+            custom_code_purposes: vec![
+                CustomCodePurpose {
+                    name: "Saga".to_string(),
+                    description: "跨服务的长事务编排".to_string(),
+                    path_patterns: vec!["/sagas/".to_string()],
+                    boundary: false,
+                },
+                CustomCodePurpose {
+                    name: "EventHandler".to_string(),
+                    description: "消费外部事件的处理器".to_string(),
+                    path_patterns: vec![],
+                    boundary: true,
+                },
+            ],
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
+
+        // 路径规则直接归入自定义分类，无需调用LLM
+        let purpose = CodePurposeEnhancer::new()
+            .execute(&context, Path::new("src/sagas/order.rs"), "order.rs", "")
+            .await
+            .unwrap();
+        assert_eq!(purpose, CodePurpose::Custom("Saga".to_string()));
+        assert_eq!(purpose.display_name(), "Saga");
+
+        // 自定义分类序列化为名称本身，内置分类保持原有格式
+        let json = serde_json::to_string(&purpose).unwrap();
+        assert_eq!(json, "\"Saga\"");
+        assert_eq!(serde_json::from_str::<CodePurpose>(&json).unwrap(), purpose);
+        assert_eq!(
+            serde_json::from_str::<CodePurpose>("\"service\"").unwrap(),
+            CodePurpose::Service
+        );
+
+        // LLM返回的分类规范化为配置中的名称，未配置的分类归为Other
+        let custom_purposes = &context.config.custom_code_purposes;
+        let handler =
+            CodePurpose::Custom("eventhandler".to_string()).resolve_custom(custom_purposes);
+        assert_eq!(handler, CodePurpose::Custom("EventHandler".to_string()));
+        assert_eq!(
+            CodePurpose::Custom("Unknown".to_string()).resolve_custom(custom_purposes),
+            CodePurpose::Other
+        );
+
+        // 声明为边界的自定义分类参与边界代码筛选
+        assert!(is_boundary_purpose(&handler, custom_purposes));
+        assert!(!is_boundary_purpose(&purpose, custom_purposes));
+    }
+    #[tokio::test]
+    async fn test_unconfigured_purpose_from_llm_is_normalized() {
+        use crate::config::LLMProvider;
+        use crate::llm::client::mock_server::{MockServer, openai_reply};
+
+        // 模型返回一个未配置的分类名称
+        let server = MockServer::with_handler(|request| {
+            let mut reply = openai_reply(request);
+            reply["choices"][0]["message"] = serde_json::json!({
+                "role": "assistant",
+                "content": r#"{"code_purpose":"Bogus","confidence":0.9,"reasoning":"mock"}"#,
+            });
+            Some(reply)
+        })
+        .await;
+        let mut config = Config::default();
+        config.llm.provider = LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = server.api_base_url.clone();
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
+
+        let purpose = CodePurposeEnhancer::new()
+            .execute(&context, Path::new("misc/blob.txt"), "blob.txt", "content")
+            .await
+            .unwrap();
+        assert_eq!(purpose, CodePurpose::Other);
+        assert_eq!(server.requests().len(), 1);
+    }
+}
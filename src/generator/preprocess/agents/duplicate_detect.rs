@@ -0,0 +1,179 @@
+use anyhow::Result;
+
+use crate::llm::client::embedding::{Embedder, similar_pairs};
+use crate::types::code::CodeInsight;
+use crate::types::duplication::{DuplicateGroup, DuplicationReport};
+
+/// 基于代码摘要的向量相似度识别疑似重复的文件，相似度不低于阈值的文件对按传递关系合并为组
+pub async fn detect<E: Embedder>(
+    embedder: &E,
+    insights: &[CodeInsight],
+    threshold: f32,
+) -> Result<DuplicationReport> {
+    let candidates: Vec<(String, String)> = insights
+        .iter()
+        .filter_map(|insight| {
+            let summary = summary_text(insight)?;
+            let file_path = insight
+                .code_dossier
+                .file_path
+                .to_string_lossy()
+                .replace('\\', "/");
+            Some((file_path, summary))
+        })
+        .collect();
+    let summaries: Vec<String> = candidates
+        .iter()
+        .map(|(_, summary)| summary.clone())
+        .collect();
+    let pairs = similar_pairs(embedder, &summaries, threshold).await?;
+
+    // 并查集：相似度降序合并，组内最后一次合并使用的相似度即组内最低连接相似度
+    let mut parent: Vec<usize> = (0..candidates.len()).collect();
+    let mut group_similarity = vec![f32::MAX; candidates.len()];
+    for (a, b, similarity) in pairs {
+        let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+        if root_a != root_b {
+            parent[root_b] = root_a;
+            group_similarity[root_a] = group_similarity[root_a]
+                .min(group_similarity[root_b])
+                .min(similarity);
+        }
+    }
+
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); candidates.len()];
+    for index in 0..candidates.len() {
+        let root = find(&mut parent, index);
+        members[root].push(index);
+    }
+    let mut groups: Vec<DuplicateGroup> = members
+        .into_iter()
+        .enumerate()
+        .filter(|(_, indexes)| indexes.len() > 1)
+        .map(|(root, indexes)| {
+            let mut files: Vec<String> = indexes
+                .into_iter()
+                .map(|index| candidates[index].0.clone())
+                .collect();
+            files.sort();
+            DuplicateGroup {
+                files,
+                similarity: group_similarity[root],
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| a.files.cmp(&b.files))
+    });
+
+    Ok(DuplicationReport { threshold, groups })
+}
+
+/// 参与比较的摘要：优先使用源码摘要，缺失时使用AI生成的详细描述
+fn summary_text(insight: &CodeInsight) -> Option<String> {
+    [
+        &insight.code_dossier.source_summary,
+        &insight.detailed_description,
+    ]
+    .into_iter()
+    .map(|text| text.trim())
+    .find(|text| !text.is_empty())
+    .map(str::to_string)
+}
+
+fn find(parent: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    // 路径压缩
+    let mut current = index;
+    while parent[current] != root {
+        let next = parent[current];
+        parent[current] = root;
+        current = next;
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::code::{CodeComplexity, CodeDossier, CodePurpose};
+    use std::path::PathBuf;
+
+    /// 按摘要中的词频构造向量的测试嵌入器，措辞相近的摘要得到相近的向量
+    struct BagOfWordsEmbedder;
+
+    impl Embedder for BagOfWordsEmbedder {
+        async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+            let vocabulary = [
+                "parse", "config", "toml", "file", "render", "html", "template", "error",
+            ];
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    vocabulary
+                        .iter()
+                        .map(|word| text.matches(word).count() as f32)
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    fn insight(file_path: &str, source_summary: &str) -> CodeInsight {
+        CodeInsight {
+            code_dossier: CodeDossier {
+                name: file_path.rsplit('/').next().unwrap().to_string(),
+                file_path: PathBuf::from(file_path),
+                source_summary: source_summary.to_string(),
+                code_purpose: CodePurpose::Util,
+                importance_score: 0.5,
+                description: None,
+                functions: Vec::new(),
+                interfaces: Vec::new(),
+            },
+            detailed_description: String::new(),
+            responsibilities: Vec::new(),
+            interfaces: Vec::new(),
+            dependencies: Vec::new(),
+            complexity_metrics: Some(CodeComplexity {
+                cyclomatic_complexity: 0.0,
+                lines_of_code: 0,
+                number_of_functions: 0,
+                number_of_classes: 0,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_near_identical_summaries_are_grouped() {
+        let insights = vec![
+            insight(
+                "src/config/loader.rs",
+                "fn parse_config(file) { read toml file; parse toml into config; map error }",
+            ),
+            insight(
+                "src/view/page.rs",
+                "fn render(template) { render html template; escape html }",
+            ),
+            insight(
+                "src/legacy/config_loader.rs",
+                "fn parse_config(file) { read toml file; parse toml into config; map error; }",
+            ),
+        ];
+
+        let report = detect(&BagOfWordsEmbedder, &insights, 0.95).await.unwrap();
+
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(
+            report.groups[0].files,
+            vec!["src/config/loader.rs", "src/legacy/config_loader.rs"]
+        );
+        assert!(report.groups[0].similarity > 0.99);
+        assert_eq!(report.threshold, 0.95);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod code_analyze;
 pub mod code_purpose_analyze;
+pub mod duplicate_detect;
 pub mod relationships_analyze;
@@ -11,7 +11,7 @@
 - 接口数量: {}
 - 依赖数量: {}
 - 代码行数: {}
-- 圈复杂度: {:.1}
+- 圈复杂度: {}
 
 ## 组件源代码
 ```
@@ -44,6 +44,7 @@
 - "plugin" (插件)
 - "router" (路由组件)
 - "database" (数据库组件)
+- "migration" (数据库迁移脚本，如migrations目录下的SQL、Diesel、Prisma、Flyway迁移文件)
 - "api" (供外部调用的服务API)
 - "controller" (MVC架构中的Controller组件)
 - "service" (MVC架构中的Service组件)
@@ -52,7 +53,7 @@
 - "test" (测试组件)
 - "doc" (文档组件)
 - "other" (其他未归类)
-
+{}
 ### 置信度评估标准：
 - **0.9-1.0**: 完全确定，特征非常明显
 - **0.7-0.9**: 比较确定，特征比较明显
@@ -7,6 +7,7 @@ use crate::{
     types::{code_releationship::RelationshipAnalysis, project_structure::ProjectStructure},
     utils::prompt_compressor::{CompressionConfig, PromptCompressor},
 };
+use log::info;
 
 pub struct RelationshipsAnalyze {
     prompt_compressor: PromptCompressor,
@@ -63,7 +64,7 @@ impl RelationshipsAnalyze {
             .await?;
 
         if compression_result.was_compressed {
-            println!(
+            info!(
                 "   ✅ 压缩完成: {} -> {} tokens",
                 compression_result.original_tokens, compression_result.compressed_tokens
             );
@@ -141,12 +142,16 @@ JSON输出模板：
                     .join(", ");
 
                 format!(
-                    "- {}: {} (路径: `{}`，重要性: {:.2}, 复杂度: {:.1}, 依赖: [{}])",
+                    "- {}: {} (路径: `{}`，重要性: {:.2}, 复杂度: {}, 依赖: [{}])",
                     insight.code_dossier.name,
                     insight.code_dossier.code_purpose.display_name(),
                     insight.code_dossier.file_path.to_string_lossy(),
                     insight.code_dossier.importance_score,
-                    insight.complexity_metrics.cyclomatic_complexity,
+                    insight
+                        .complexity_metrics
+                        .as_ref()
+                        .map(|metrics| format!("{:.1}", metrics.cyclomatic_complexity))
+                        .unwrap_or_else(|| "未计算".to_string()),
                     dependencies_introduce
                 )
             })
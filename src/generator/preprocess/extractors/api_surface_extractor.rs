@@ -0,0 +1,155 @@
+use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::extractors::language_processors::{
+    LanguageProcessorManager, visibility_rank,
+};
+use crate::types::api_surface::{ApiStability, ApiSurfaceItem, ApiSurfaceReport};
+use crate::types::code::CodeDossier;
+use crate::utils::file_utils::read_text_with_limit;
+use std::path::Path;
+
+/// 定义行向上查找属性、注解与文档注释的最大行数
+const MAX_MARKER_LOOKBACK_LINES: usize = 30;
+
+/// 基于静态提取的接口可见性，以及定义前的属性/注解/文档注释，统计核心文件的公开API表面
+pub async fn extract(
+    context: &GeneratorContext,
+    codes: &[CodeDossier],
+    root_path: &Path,
+) -> ApiSurfaceReport {
+    let language_processor =
+        LanguageProcessorManager::with_overrides(&context.config.language_overrides)
+            .with_interface_limit(context.config.max_interfaces_per_file);
+    let max_bytes = context.config.max_document_read_bytes;
+
+    let mut report = ApiSurfaceReport::default();
+    for code in codes {
+        let Ok(Some((content, _))) =
+            read_text_with_limit(&root_path.join(&code.file_path), max_bytes).await
+        else {
+            continue;
+        };
+        collect_public_surface(&mut report, &language_processor, &code.file_path, &content);
+    }
+    report
+}
+
+/// 提取单个文件的公开接口，累加到报告中，并记录被标记为弃用/内部/不稳定的接口
+pub fn collect_public_surface(
+    report: &mut ApiSurfaceReport,
+    language_processor: &LanguageProcessorManager,
+    file_path: &Path,
+    content: &str,
+) {
+    let lines: Vec<&str> = content.lines().collect();
+    let file_path_str = file_path.to_string_lossy().replace('\\', "/");
+
+    for interface in language_processor.extract_interfaces(file_path, content) {
+        if visibility_rank(&interface.visibility) != 0 {
+            continue;
+        }
+        report.public_items += 1;
+
+        let Some((stability, marker)) = interface
+            .line_number
+            .and_then(|line_number| stability_marker(&lines, line_number))
+        else {
+            continue;
+        };
+        report.flagged.push(ApiSurfaceItem {
+            name: interface.name,
+            interface_type: interface.interface_type,
+            file_path: file_path_str.clone(),
+            line_number: interface.line_number,
+            stability,
+            marker,
+        });
+    }
+}
+
+/// 在定义行及其上方紧邻的属性、注解与文档注释中查找稳定性标记，`line_number`从1开始
+fn stability_marker(lines: &[&str], line_number: usize) -> Option<(ApiStability, String)> {
+    let definition = line_number.checked_sub(1)?;
+    let definition_line = lines.get(definition)?;
+    if let Some(stability) = classify_marker(definition_line) {
+        return Some((stability, definition_line.trim().to_string()));
+    }
+
+    for line in lines[..definition]
+        .iter()
+        .rev()
+        .take(MAX_MARKER_LOOKBACK_LINES)
+    {
+        let trimmed = line.trim();
+        let is_decoration = trimmed.starts_with("#[")
+            || trimmed.starts_with('@')
+            || trimmed.starts_with("//")
+            || trimmed.starts_with("/*")
+            || trimmed.starts_with('*')
+            || trimmed.starts_with('#');
+        if !is_decoration {
+            break;
+        }
+        if let Some(stability) = classify_marker(trimmed) {
+            return Some((stability, trimmed.to_string()));
+        }
+    }
+    None
+}
+
+fn classify_marker(line: &str) -> Option<ApiStability> {
+    let line = line.to_lowercase();
+    if line.contains("#[deprecated") || line.contains("@deprecated") {
+        Some(ApiStability::Deprecated)
+    } else if line.contains("@internal") || line.contains("#[doc(hidden)]") {
+        Some(ApiStability::Internal)
+    } else if ["#[unstable", "@experimental", "@beta", "@alpha"]
+        .iter()
+        .any(|marker| line.contains(marker))
+    {
+        Some(ApiStability::Unstable)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deprecated_rust_function_is_flagged() {
+        let content = r#"
+/// 旧的入口
+#[deprecated(note = "use new_api instead")]
+pub fn old_api() -> u32 {
+    1
+}
+
+/// 新的入口
+pub fn new_api() -> u32 {
+    2
+}
+
+fn private_helper() {}
+"#;
+        let mut report = ApiSurfaceReport::default();
+        collect_public_surface(
+            &mut report,
+            &LanguageProcessorManager::new(),
+            Path::new("src/lib.rs"),
+            content,
+        );
+
+        assert_eq!(report.public_items, 2);
+        assert_eq!(report.flagged.len(), 1);
+        let item = &report.flagged[0];
+        assert_eq!(item.name, "old_api");
+        assert_eq!(item.stability, ApiStability::Deprecated);
+        assert_eq!(item.file_path, "src/lib.rs");
+        assert_eq!(
+            item.marker,
+            r#"#[deprecated(note = "use new_api instead")]"#
+        );
+        assert_eq!(report.count(ApiStability::Deprecated), 1);
+    }
+}
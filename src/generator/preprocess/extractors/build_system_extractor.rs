@@ -0,0 +1,320 @@
+use crate::types::build_system::{BuildConfigFile, BuildSystemReport, PackageScript};
+use crate::utils::file_utils::read_text_with_limit;
+use regex::Regex;
+use std::path::Path;
+
+/// 每个配置文件最多记录的要点数量
+const MAX_DETAILS: usize = 20;
+
+/// 项目根目录下的构建工具配置文件及其工具名称
+const BUILD_TOOL_FILES: &[(&str, &str)] = &[
+    ("Makefile", "Make"),
+    ("makefile", "Make"),
+    ("GNUmakefile", "Make"),
+    ("justfile", "just"),
+    ("Justfile", "just"),
+    (".justfile", "just"),
+    ("Dockerfile", "Docker"),
+    ("docker-compose.yml", "Docker Compose"),
+    ("docker-compose.yaml", "Docker Compose"),
+    ("compose.yml", "Docker Compose"),
+    ("compose.yaml", "Docker Compose"),
+];
+
+/// 识别项目的构建系统与CI配置：CI流水线、构建工具、容器化配置以及包管理器脚本
+pub async fn extract(project_path: &Path, max_bytes: u64) -> BuildSystemReport {
+    let mut report = BuildSystemReport::default();
+
+    // 1. CI流水线
+    let workflows_dir = project_path.join(".github").join("workflows");
+    if let Ok(entries) = std::fs::read_dir(&workflows_dir) {
+        let mut workflow_files: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "yml" || extension == "yaml")
+            })
+            .collect();
+        workflow_files.sort();
+        for path in workflow_files {
+            if let Some(content) = read(&path, max_bytes).await {
+                report.ci.push(config_file(
+                    "GitHub Actions",
+                    project_path,
+                    &path,
+                    github_workflow_details(&content),
+                ));
+            }
+        }
+    }
+    let gitlab_ci = project_path.join(".gitlab-ci.yml");
+    if let Some(content) = read(&gitlab_ci, max_bytes).await {
+        report.ci.push(config_file(
+            "GitLab CI",
+            project_path,
+            &gitlab_ci,
+            gitlab_ci_details(&content),
+        ));
+    }
+    let jenkinsfile = project_path.join("Jenkinsfile");
+    if let Some(content) = read(&jenkinsfile, max_bytes).await {
+        report.ci.push(config_file(
+            "Jenkins",
+            project_path,
+            &jenkinsfile,
+            jenkins_stages(&content),
+        ));
+    }
+
+    // 2. 构建与容器化工具
+    for (file_name, tool) in BUILD_TOOL_FILES {
+        let path = project_path.join(file_name);
+        // 大小写不敏感的文件系统上makefile与Makefile指向同一文件
+        if report
+            .build_tools
+            .iter()
+            .any(|existing| existing.file_path.eq_ignore_ascii_case(file_name))
+        {
+            continue;
+        }
+        let Some(content) = read(&path, max_bytes).await else {
+            continue;
+        };
+        let details = match *tool {
+            "Make" => make_targets(&content),
+            "just" => just_recipes(&content),
+            "Docker" => docker_base_images(&content),
+            _ => compose_services(&content),
+        };
+        report
+            .build_tools
+            .push(config_file(tool, project_path, &path, details));
+    }
+
+    // 3. 包管理器脚本
+    if let Some(content) = read(&project_path.join("package.json"), max_bytes).await {
+        report
+            .package_scripts
+            .extend(package_json_scripts(&content, "package.json"));
+    }
+
+    report
+}
+
+async fn read(path: &Path, max_bytes: u64) -> Option<String> {
+    if !path.is_file() {
+        return None;
+    }
+    read_text_with_limit(path, max_bytes)
+        .await
+        .ok()
+        .flatten()
+        .map(|(content, _)| content)
+}
+
+fn config_file(
+    tool: &str,
+    project_path: &Path,
+    path: &Path,
+    mut details: Vec<String>,
+) -> BuildConfigFile {
+    details.truncate(MAX_DETAILS);
+    BuildConfigFile {
+        tool: tool.to_string(),
+        file_path: path
+            .strip_prefix(project_path)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/"),
+        details,
+    }
+}
+
+/// YAML映射的键名列表
+fn mapping_keys(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    match value {
+        Some(serde_yaml::Value::Mapping(mapping)) => mapping
+            .keys()
+            .filter_map(|key| key.as_str().map(str::to_string))
+            .collect(),
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        Some(serde_yaml::Value::String(item)) => vec![item.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// 工作流名称、触发条件与任务列表
+fn github_workflow_details(content: &str) -> Vec<String> {
+    let Ok(workflow) = serde_yaml::from_str::<serde_yaml::Value>(content) else {
+        return Vec::new();
+    };
+    let mut details = Vec::new();
+    if let Some(name) = workflow.get("name").and_then(|name| name.as_str()) {
+        details.push(format!("工作流: {}", name));
+    }
+    let triggers = mapping_keys(workflow.get("on"));
+    if !triggers.is_empty() {
+        details.push(format!("触发条件: {}", triggers.join(", ")));
+    }
+    for job in mapping_keys(workflow.get("jobs")) {
+        details.push(format!("任务: {}", job));
+    }
+    details
+}
+
+/// 流水线阶段与任务列表（以`.`开头的隐藏任务和全局关键字除外）
+fn gitlab_ci_details(content: &str) -> Vec<String> {
+    const KEYWORDS: &[&str] = &[
+        "stages",
+        "variables",
+        "default",
+        "include",
+        "workflow",
+        "image",
+        "services",
+        "cache",
+        "before_script",
+        "after_script",
+    ];
+    let Ok(pipeline) = serde_yaml::from_str::<serde_yaml::Value>(content) else {
+        return Vec::new();
+    };
+    let mut details = Vec::new();
+    let stages = mapping_keys(pipeline.get("stages"));
+    if !stages.is_empty() {
+        details.push(format!("阶段: {}", stages.join(" → ")));
+    }
+    for job in mapping_keys(Some(&pipeline)) {
+        if !job.starts_with('.') && !KEYWORDS.contains(&job.as_str()) {
+            details.push(format!("任务: {}", job));
+        }
+    }
+    details
+}
+
+fn jenkins_stages(content: &str) -> Vec<String> {
+    let stage = Regex::new(r#"stage\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap();
+    stage
+        .captures_iter(content)
+        .map(|captures| format!("阶段: {}", &captures[1]))
+        .collect()
+}
+
+/// Makefile中的构建目标（不含`.PHONY`等特殊目标与变量赋值）
+fn make_targets(content: &str) -> Vec<String> {
+    let target = Regex::new(r"^([A-Za-z0-9][A-Za-z0-9_.\-/ ]*):(?:[^=]|$)").unwrap();
+    let mut targets = Vec::new();
+    for line in content.lines() {
+        if let Some(captures) = target.captures(line) {
+            for name in captures[1].split_whitespace() {
+                let name = format!("目标: {}", name);
+                if !targets.contains(&name) {
+                    targets.push(name);
+                }
+            }
+        }
+    }
+    targets
+}
+
+/// justfile中的配方名称
+fn just_recipes(content: &str) -> Vec<String> {
+    let recipe = Regex::new(r"^@?([A-Za-z0-9_\-]+)[^:]*:(?:[^=]|$)").unwrap();
+    content
+        .lines()
+        .filter_map(|line| recipe.captures(line))
+        .map(|captures| format!("配方: {}", &captures[1]))
+        .collect()
+}
+
+/// Dockerfile中的基础镜像（多阶段构建时包含阶段名称）
+fn docker_base_images(content: &str) -> Vec<String> {
+    let from = Regex::new(r"(?i)^\s*FROM\s+(?:--\S+\s+)*(\S+)(?:\s+AS\s+(\S+))?").unwrap();
+    content
+        .lines()
+        .filter_map(|line| from.captures(line))
+        .map(|captures| match captures.get(2) {
+            Some(stage) => format!("基础镜像: {}（阶段 {}）", &captures[1], stage.as_str()),
+            None => format!("基础镜像: {}", &captures[1]),
+        })
+        .collect()
+}
+
+fn compose_services(content: &str) -> Vec<String> {
+    serde_yaml::from_str::<serde_yaml::Value>(content)
+        .map(|compose| {
+            mapping_keys(compose.get("services"))
+                .into_iter()
+                .map(|service| format!("服务: {}", service))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn package_json_scripts(content: &str, manifest_path: &str) -> Vec<PackageScript> {
+    let Ok(package) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let Some(scripts) = package
+        .get("scripts")
+        .and_then(|scripts| scripts.as_object())
+    else {
+        return Vec::new();
+    };
+    scripts
+        .iter()
+        .filter_map(|(name, command)| {
+            Some(PackageScript {
+                name: name.clone(),
+                command: command.as_str()?.to_string(),
+                manifest_path: manifest_path.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_tool_details() {
+        assert_eq!(
+            make_targets(
+                ".PHONY: build test\nCC := gcc\nbuild test: deps\n\tcargo build\nrelease:\n"
+            ),
+            vec!["目标: build", "目标: test", "目标: release"]
+        );
+        assert_eq!(
+            docker_base_images(
+                "FROM rust:1.85 AS builder\nRUN cargo build\nFROM --platform=linux/amd64 debian:bookworm-slim\n"
+            ),
+            vec![
+                "基础镜像: rust:1.85（阶段 builder）",
+                "基础镜像: debian:bookworm-slim"
+            ]
+        );
+        assert_eq!(
+            just_recipes(
+                "set shell := [\"bash\"]\ndefault: build\n@lint target='all':\n    cargo clippy\n"
+            ),
+            vec!["配方: default", "配方: lint"]
+        );
+        assert_eq!(
+            gitlab_ci_details(
+                "stages: [build, test]\nvariables:\n  A: b\n.template:\n  image: x\ncompile:\n  stage: build\n"
+            ),
+            vec!["阶段: build → test", "任务: compile"]
+        );
+        assert_eq!(
+            jenkins_stages(
+                "pipeline {\n  stages {\n    stage('Build') { }\n    stage(\"Deploy\") { }\n"
+            ),
+            vec!["阶段: Build", "阶段: Deploy"]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::generator::preprocess::extractors::source_reader::SourceFile;
+use crate::generator::preprocess::extractors::test_example_extractor::is_test_source;
+use crate::types::concurrency::{ConcurrencyReport, ConcurrencySignal};
+use regex::Regex;
+use std::sync::LazyLock;
+
+const RUST: &[&str] = &["rs"];
+const GO: &[&str] = &["go"];
+const JS: &[&str] = &["js", "jsx", "ts", "tsx", "mjs", "cjs", "vue", "svelte"];
+const PYTHON: &[&str] = &["py"];
+const JVM: &[&str] = &["java", "kt", "kts", "scala"];
+const CSHARP: &[&str] = &["cs"];
+
+/// 各语言的并发原语：(适用的扩展名, 并发模式, 匹配规则)
+const PATTERN_RULES: &[(&[&str], &str, &str)] = &[
+    (
+        RUST,
+        "async/tokio",
+        r"\btokio::(?:\{|(?:spawn|select|join|try_join|task|sync|runtime|time|net|fs|io|process|signal)\b)|#\[tokio::main",
+    ),
+    (RUST, "async/async-std", r"\basync_std::|#\[async_std::main"),
+    (
+        RUST,
+        "thread",
+        r"\bthread::(?:spawn|scope)\b|\brayon::|\.par_iter\(\)",
+    ),
+    (
+        RUST,
+        "channel",
+        r"\b(?:mpsc|oneshot|broadcast|watch)::(?:channel|unbounded_channel|Sender|Receiver)\b|\bcrossbeam(?:_channel)?::",
+    ),
+    (RUST, "lock", r"\b(?:Mutex|RwLock)\s*(?:<|::new)"),
+    (RUST, "atomic", r"\bAtomic(?:Bool|Usize|U64|I64|U32|I32)\b"),
+    (GO, "goroutine", r"\bgo\s+(?:func\b|[A-Za-z_][\w.]*\()"),
+    (GO, "channel", r"\bchan\s+[\w*\[\]{}]|<-\s*\w|\bselect\s*\{"),
+    (GO, "lock", r"\bsync\.(?:Mutex|RWMutex)\b"),
+    (GO, "waitgroup", r"\bsync\.WaitGroup\b|\berrgroup\."),
+    (
+        JS,
+        "async/promise",
+        r"\bnew Promise\b|\bPromise\.(?:all|allSettled|race|any)\b|\basync\s+(?:function\b|\(|\w+\s*=>)",
+    ),
+    (JS, "worker", r"\bnew Worker\(|\bworker_threads\b"),
+    (
+        PYTHON,
+        "async/asyncio",
+        r"\bimport asyncio\b|\basyncio\.|\basync def\b",
+    ),
+    (
+        PYTHON,
+        "thread",
+        r"\bthreading\.Thread\b|\bThreadPoolExecutor\b|\bfrom threading import\b",
+    ),
+    (
+        PYTHON,
+        "process",
+        r"\bmultiprocessing\b|\bProcessPoolExecutor\b",
+    ),
+    (
+        PYTHON,
+        "lock",
+        r"\b(?:threading|asyncio)\.(?:Lock|RLock|Semaphore)\(",
+    ),
+    (
+        JVM,
+        "thread",
+        r"\bnew Thread\(|\bExecutorService\b|\bExecutors\.|\bThreadPoolExecutor\b",
+    ),
+    (JVM, "async/future", r"\bCompletableFuture\b"),
+    (
+        JVM,
+        "coroutine",
+        r"\bsuspend fun\b|\b(?:launch|async)\s*\{|\bkotlinx\.coroutines\b",
+    ),
+    (
+        JVM,
+        "lock",
+        r"\bsynchronized\s*\(|@Synchronized\b|\bReentrant(?:ReadWrite)?Lock\b",
+    ),
+    (
+        CSHARP,
+        "async/task",
+        r"\basync\s+Task\b|\bTask\.(?:Run|WhenAll|WhenAny)\b",
+    ),
+    (
+        CSHARP,
+        "thread",
+        r"\bnew Thread\(|\bThreadPool\.|\bParallel\.For",
+    ),
+    (
+        CSHARP,
+        "channel",
+        r"\bChannel\.Create(?:Bounded|Unbounded)\b",
+    ),
+    (
+        CSHARP,
+        "lock",
+        r"\block\s*\(|\bSemaphoreSlim\b|\bReaderWriterLockSlim\b",
+    ),
+];
+
+static PATTERNS: LazyLock<Vec<(&'static [&'static str], &'static str, Regex)>> =
+    LazyLock::new(|| {
+        PATTERN_RULES
+            .iter()
+            .map(|(extensions, pattern, rule)| (*extensions, *pattern, Regex::new(rule).unwrap()))
+            .collect()
+    });
+
+/// 判断该扩展名的文件是否需要扫描并发原语
+pub fn is_concurrency_source(extension: &str) -> bool {
+    PATTERNS
+        .iter()
+        .any(|(extensions, _, _)| extensions.contains(&extension))
+}
+
+/// 扫描预处理阶段已读取的项目源码中的并发原语，汇总项目的并发模型；测试代码不代表运行时的并发模型，不参与扫描
+pub fn extract(sources: &[SourceFile]) -> ConcurrencyReport {
+    let mut report = ConcurrencyReport::default();
+
+    for source in sources {
+        if !is_concurrency_source(&source.extension) || is_test_source(&source.path) {
+            continue;
+        }
+        let file_path = source.path.to_string_lossy().replace('\\', "/");
+        report.signals.extend(detect_signals(
+            &source.content,
+            &source.extension,
+            &file_path,
+        ));
+    }
+
+    report
+}
+
+/// 识别单个文件中使用的并发模式，每种模式只记录首次出现的位置
+pub fn detect_signals(content: &str, extension: &str, file_path: &str) -> Vec<ConcurrencySignal> {
+    PATTERNS
+        .iter()
+        .filter(|(extensions, _, _)| extensions.contains(&extension))
+        .filter_map(|(_, pattern, regex)| {
+            let line_index = content.lines().position(|line| {
+                let trimmed = line.trim_start();
+                // 跳过注释行，避免将说明文字识别为并发原语
+                !(trimmed.starts_with("//") || (extension == "py" && trimmed.starts_with('#')))
+                    && regex.is_match(line)
+            })?;
+            Some(ConcurrencySignal {
+                pattern: pattern.to_string(),
+                file_path: file_path.to_string(),
+                line_number: line_index + 1,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokio_spawn_contributes_async_tokio_signal() {
+        let content = r#"
+use std::sync::{Arc, Mutex};
+
+// tokio::spawn 之前需要先克隆状态
+pub async fn run(state: Arc<Mutex<State>>) {
+    let handle = tokio::spawn(async move {
+        state.lock().unwrap().tick();
+    });
+    handle.await.unwrap();
+}
+"#;
+        let signals = detect_signals(content, "rs", "src/worker.rs");
+
+        let tokio = signals
+            .iter()
+            .find(|signal| signal.pattern == "async/tokio")
+            .unwrap();
+        assert_eq!(tokio.file_path, "src/worker.rs");
+        assert_eq!(tokio.line_number, 6);
+        assert!(signals.iter().any(|signal| signal.pattern == "lock"));
+        assert!(!signals.iter().any(|signal| signal.pattern == "thread"));
+
+        let go_signals = detect_signals(
+            "func main() {\n\tresults := make(chan int)\n\tgo worker(results)\n}\n",
+            "go",
+            "main.go",
+        );
+        let patterns: Vec<_> = go_signals.iter().map(|s| s.pattern.as_str()).collect();
+        assert_eq!(patterns, vec!["goroutine", "channel"]);
+        assert!(detect_signals(content, "md", "README.md").is_empty());
+    }
+
+    #[test]
+    fn test_tokio_test_attributes_are_not_runtime_signals() {
+        let inline_tests =
+            "#[cfg(test)]\nmod tests {\n    #[tokio::test]\n    async fn it_works() {}\n}\n";
+        assert!(detect_signals(inline_tests, "rs", "src/lib.rs").is_empty());
+
+        let source = |path: &str, content: &str| SourceFile {
+            path: path.into(),
+            extension: "rs".to_string(),
+            content: content.to_string(),
+        };
+        let report = extract(&[
+            source("tests/integration.rs", "use tokio::sync::Mutex;\n"),
+            source("src/main.rs", "#[tokio::main]\nasync fn main() {}\n"),
+        ]);
+        assert_eq!(report.signals.len(), 1);
+        assert_eq!(report.signals[0].file_path, "src/main.rs");
+        assert_eq!(report.signals[0].pattern, "async/tokio");
+    }
+}
@@ -0,0 +1,210 @@
+use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::extractors::language_processors::LanguageProcessorManager;
+use crate::generator::preprocess::extractors::source_reader::SourceFile;
+use crate::types::code_releationship::{CoreDependency, RelationshipAnalysis};
+use crate::types::entry_point::EntryPoint;
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+
+/// 最多保留的程序入口数量
+const MAX_ENTRY_POINTS: usize = 5;
+
+/// 单个入口最多追踪的依赖边数量
+const MAX_FLOW_EDGES: usize = 40;
+
+/// 不属于程序入口的目录：示例、基准测试与测试中的main只用于演示或验证
+const NON_ENTRY_DIRS: &[&str] = &["examples", "example", "benches", "tests", "test"];
+
+/// 约定俗成的入口文件名
+const CONVENTIONAL_ENTRY_FILES: &[&str] = &[
+    "main.rs",
+    "main.go",
+    "main.py",
+    "__main__.py",
+    "main.ts",
+    "main.js",
+    "index.ts",
+    "index.js",
+    "Main.java",
+    "Main.kt",
+];
+
+/// 借助各语言处理器，从已读取的源码中识别程序入口（`fn main`、`main.ts`、`__main__`、`public static void main`等）
+pub fn extract(context: &GeneratorContext, sources: &[SourceFile]) -> Vec<EntryPoint> {
+    let language_processor =
+        LanguageProcessorManager::with_overrides(&context.config.language_overrides);
+
+    let mut entry_points = Vec::new();
+    for source in sources {
+        if is_excluded(&source.path)
+            || !language_processor.is_entry_point(&source.path, &source.content)
+        {
+            continue;
+        }
+        let Some(processor) =
+            language_processor.get_processor_for_content(&source.path, &source.content)
+        else {
+            continue;
+        };
+        entry_points.push(EntryPoint {
+            file_path: normalize_label(&source.path.to_string_lossy()),
+            language: processor.language_name().to_string(),
+            flow: Vec::new(),
+        });
+    }
+
+    // 约定的入口路径优先，其次目录层级越浅越可能是真正的程序入口
+    entry_points.sort_by(|a, b| {
+        let depth = |entry: &EntryPoint| entry.file_path.matches('/').count();
+        entry_rank(&a.file_path)
+            .cmp(&entry_rank(&b.file_path))
+            .then_with(|| depth(a).cmp(&depth(b)))
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+    entry_points.truncate(MAX_ENTRY_POINTS);
+    entry_points
+}
+
+/// 构建脚本以及示例、基准测试、测试目录中的文件不作为程序入口
+fn is_excluded(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "build.rs")
+        || path.parent().is_some_and(|parent| {
+            parent.components().any(|component| {
+                NON_ENTRY_DIRS
+                    .iter()
+                    .any(|dir| component.as_os_str() == *dir)
+            })
+        })
+}
+
+/// 入口排序优先级：`src/main.rs`与`src/bin/*`最优先，其次为约定的入口文件名
+fn entry_rank(file_path: &str) -> u8 {
+    let file_name = file_path.rsplit('/').next().unwrap_or(file_path);
+    if file_path == "src/main.rs"
+        || file_path.ends_with("/src/main.rs")
+        || file_path.starts_with("src/bin/")
+        || file_path.contains("/src/bin/")
+    {
+        0
+    } else if CONVENTIONAL_ENTRY_FILES.contains(&file_name) {
+        1
+    } else {
+        2
+    }
+}
+
+/// 从每个入口出发，沿调用/导入依赖边广度优先向外追踪运行时调用链
+pub fn trace_runtime_flow(entry_points: &mut [EntryPoint], relationships: &RelationshipAnalysis) {
+    let edges: Vec<CoreDependency> = relationships
+        .core_dependencies
+        .iter()
+        .map(|dependency| CoreDependency {
+            from: normalize_label(&dependency.from),
+            to: normalize_label(&dependency.to),
+            ..dependency.clone()
+        })
+        .collect();
+
+    for entry in entry_points.iter_mut() {
+        let mut flow = Vec::new();
+        let mut visited: HashSet<&str> = HashSet::from([entry.file_path.as_str()]);
+        let mut queue = VecDeque::from([entry.file_path.as_str()]);
+
+        'trace: while let Some(current) = queue.pop_front() {
+            for edge in edges.iter().filter(|edge| edge.from == current) {
+                if flow.len() >= MAX_FLOW_EDGES {
+                    break 'trace;
+                }
+                flow.push(edge.clone());
+                if visited.insert(edge.to.as_str()) {
+                    queue.push_back(edge.to.as_str());
+                }
+            }
+        }
+        entry.flow = flow;
+    }
+}
+
+/// 统一路径标签格式：使用`/`分隔并移除`./`前缀
+fn normalize_label(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::code_releationship::DependencyType;
+
+    fn edge(from: &str, to: &str) -> CoreDependency {
+        CoreDependency {
+            from: from.to_string(),
+            to: to.to_string(),
+            dependency_type: DependencyType::FunctionCall,
+            importance: 3,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_trace_runtime_flow_follows_edges_from_entry() {
+        let relationships = RelationshipAnalysis {
+            core_dependencies: vec![
+                edge("./src/main.rs", "src/app.rs"),
+                edge("src/app.rs", "src/db.rs"),
+                edge("src/db.rs", "src/app.rs"),
+                edge("src/unrelated.rs", "src/db.rs"),
+            ],
+            architecture_layers: vec![],
+            key_insights: vec![],
+        };
+        let mut entry_points = vec![EntryPoint {
+            file_path: "src/main.rs".to_string(),
+            language: "Rust".to_string(),
+            flow: vec![],
+        }];
+
+        trace_runtime_flow(&mut entry_points, &relationships);
+
+        let flow: Vec<(&str, &str)> = entry_points[0]
+            .flow
+            .iter()
+            .map(|edge| (edge.from.as_str(), edge.to.as_str()))
+            .collect();
+        assert_eq!(
+            flow,
+            vec![
+                ("src/main.rs", "src/app.rs"),
+                ("src/app.rs", "src/db.rs"),
+                ("src/db.rs", "src/app.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_scripts_and_examples_are_not_entry_points() {
+        let context = GeneratorContext::new(crate::config::Config::default()).unwrap();
+        let source = |path: &str| SourceFile {
+            path: path.into(),
+            extension: "rs".to_string(),
+            content: "fn main() {\n    run();\n}\n".to_string(),
+        };
+        let sources = vec![
+            source("build.rs"),
+            source("examples/demo.rs"),
+            source("benches/bench.rs"),
+            source("tests/smoke.rs"),
+            source("tool.rs"),
+            source("src/bin/cli.rs"),
+            source("src/main.rs"),
+        ];
+
+        let entry_points = extract(&context, &sources);
+
+        let paths: Vec<&str> = entry_points
+            .iter()
+            .map(|entry| entry.file_path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["src/main.rs", "src/bin/cli.rs", "tool.rs"]);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::extractors::source_reader::SourceFile;
+use crate::types::feature_flag::{CargoFeature, FeatureFlagReport, FeatureGate};
+use crate::types::project_structure::ProjectStructure;
+use crate::utils::file_utils::read_text_with_limit;
+use anyhow::Result;
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+/// 需要扫描特性开关的源码扩展名
+const GATE_SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "js", "jsx", "ts", "tsx", "mjs", "cjs", "vue", "svelte",
+];
+
+static CFG_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:#!?\[\s*cfg(?:_attr)?\s*\(|\bcfg!\s*\()").unwrap());
+
+static FEATURE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"feature\s*=\s*"([^"]+)""#).unwrap());
+
+static ENV_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"process\.env(?:\.([A-Za-z_][A-Za-z0-9_]*)|\[\s*['"]([^'"]+)['"]\s*\])|\benv::var(?:_os)?\s*\(\s*"([^"]+)"|\b(?:option_)?env!\s*\(\s*"([^"]+)""#,
+    )
+    .unwrap()
+});
+
+/// 是否为需要扫描特性门控与环境变量开关的源码文件
+pub fn is_gate_source(extension: &str) -> bool {
+    GATE_SOURCE_EXTENSIONS.contains(&extension)
+}
+
+/// 提取项目中可配置的构建/运行时特性：Cargo features、`cfg` 特性门控与环境变量开关
+pub async fn extract(
+    context: &GeneratorContext,
+    structure: &ProjectStructure,
+    sources: &[SourceFile],
+) -> Result<FeatureFlagReport> {
+    let project_path = &context.config.project_path;
+    let max_bytes = context.config.max_document_read_bytes;
+    let mut report = FeatureFlagReport::default();
+
+    // 1. 解析根目录及子crate的Cargo.toml
+    let mut manifests: BTreeSet<PathBuf> = BTreeSet::new();
+    if project_path.join("Cargo.toml").is_file() {
+        manifests.insert(PathBuf::from("Cargo.toml"));
+    }
+    for file in &structure.files {
+        if file.name == "Cargo.toml" {
+            manifests.insert(file.path.clone());
+        }
+    }
+    for manifest in &manifests {
+        if let Ok(Some((content, _))) =
+            read_text_with_limit(&project_path.join(manifest), max_bytes).await
+        {
+            let manifest_path = manifest.to_string_lossy().replace('\\', "/");
+            report
+                .cargo_features
+                .extend(parse_cargo_features(&content, &manifest_path));
+        }
+    }
+
+    // 2. 扫描源码中的特性门控与环境变量开关
+    for source in sources {
+        if !is_gate_source(&source.extension) {
+            continue;
+        }
+        let file_path = source.path.to_string_lossy().replace('\\', "/");
+        if source.extension == "rs" {
+            report
+                .gates
+                .extend(extract_cfg_feature_gates(&source.content, &file_path));
+        }
+        report
+            .gates
+            .extend(extract_env_gates(&source.content, &file_path));
+    }
+
+    Ok(report)
+}
+
+/// 解析Cargo.toml中的 `[features]` 表
+pub fn parse_cargo_features(content: &str, manifest_path: &str) -> Vec<CargoFeature> {
+    let Ok(manifest) = toml::from_str::<toml::Table>(content) else {
+        return Vec::new();
+    };
+    let Some(features) = manifest.get("features").and_then(|f| f.as_table()) else {
+        return Vec::new();
+    };
+
+    let to_names = |value: &toml::Value| -> Vec<String> {
+        value
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let default_features = features.get("default").map(to_names).unwrap_or_default();
+
+    features
+        .iter()
+        .filter(|(name, _)| name.as_str() != "default")
+        .map(|(name, value)| CargoFeature {
+            name: name.clone(),
+            enables: to_names(value),
+            is_default: default_features.contains(name),
+            manifest_path: manifest_path.to_string(),
+        })
+        .collect()
+}
+
+/// 提取Rust源码中 `#[cfg(...)]`、`#[cfg_attr(...)]` 与 `cfg!(...)` 里的特性门控
+pub fn extract_cfg_feature_gates(content: &str, file_path: &str) -> Vec<FeatureGate> {
+    let mut gates = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let Some(cfg_match) = CFG_REGEX.find(line) else {
+            continue;
+        };
+        for cap in FEATURE_REGEX.captures_iter(&line[cfg_match.start()..]) {
+            gates.push(FeatureGate {
+                name: cap[1].to_string(),
+                gate_type: "cfg_feature".to_string(),
+                file_path: file_path.to_string(),
+                line_number: index + 1,
+            });
+        }
+    }
+    gates
+}
+
+/// 提取源码中读取的环境变量：JS/TS 的 `process.env.X`，Rust 的 `env::var("X")` 与 `env!("X")`
+pub fn extract_env_gates(content: &str, file_path: &str) -> Vec<FeatureGate> {
+    let mut gates = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        for cap in ENV_REGEX.captures_iter(line) {
+            let Some(name) = (1..=4).find_map(|i| cap.get(i)) else {
+                continue;
+            };
+            gates.push(FeatureGate {
+                name: name.as_str().to_string(),
+                gate_type: "env".to_string(),
+                file_path: file_path.to_string(),
+                line_number: index + 1,
+            });
+        }
+    }
+    gates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo_features_table() {
+        let content = r#"
+[package]
+name = "demo"
+
+[features]
+default = ["foo"]
+foo = []
+bar = ["foo", "dep:serde"]
+"#;
+        let features = parse_cargo_features(content, "Cargo.toml");
+
+        assert_eq!(features.len(), 2);
+        let foo = features.iter().find(|f| f.name == "foo").unwrap();
+        assert!(foo.is_default);
+        assert!(foo.enables.is_empty());
+        let bar = features.iter().find(|f| f.name == "bar").unwrap();
+        assert!(!bar.is_default);
+        assert_eq!(bar.enables, vec!["foo", "dep:serde"]);
+        assert_eq!(bar.manifest_path, "Cargo.toml");
+    }
+
+    #[test]
+    fn test_extract_cfg_feature_gate() {
+        let content = r#"
+#[cfg(feature = "foo")]
+mod foo_support;
+
+#[cfg(all(feature = "bar", not(test)))]
+fn bar() {}
+
+fn run() {
+    if cfg!(feature = "baz") {}
+    let feature = "not-a-gate";
+}
+"#;
+        let gates = extract_cfg_feature_gates(content, "src/lib.rs");
+
+        let names: Vec<_> = gates.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["foo", "bar", "baz"]);
+        assert_eq!(gates[0].line_number, 2);
+        assert_eq!(gates[0].gate_type, "cfg_feature");
+        assert_eq!(gates[0].file_path, "src/lib.rs");
+    }
+
+    #[test]
+    fn test_extract_env_gates() {
+        let content = r#"
+const debug = process.env.DEBUG_MODE;
+const region = process.env["AWS_REGION"];
+let key = std::env::var("API_KEY");
+"#;
+        let gates = extract_env_gates(content, "src/index.js");
+
+        let names: Vec<_> = gates.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["DEBUG_MODE", "AWS_REGION", "API_KEY"]);
+        assert!(gates.iter().all(|g| g.gate_type == "env"));
+    }
+}
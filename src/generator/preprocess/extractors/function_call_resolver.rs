@@ -0,0 +1,295 @@
+use crate::types::code::CodeInsight;
+use crate::types::code_releationship::{CoreDependency, DependencyType};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// 参与解析的函数名最短长度，过短的名称误报率太高
+const MIN_NAME_LEN: usize = 3;
+
+/// 每个文件最多解析的被调用函数数量
+const MAX_CALLEES_PER_FILE: usize = 50;
+
+/// 最多生成的调用边数量
+const MAX_CALL_EDGES: usize = 300;
+
+/// 过于通用、无法据此判断跨文件调用的函数名
+const IGNORED_NAMES: &[&str] = &[
+    "new",
+    "default",
+    "main",
+    "init",
+    "from",
+    "into",
+    "clone",
+    "fmt",
+    "drop",
+    "build",
+    "run",
+    "get",
+    "set",
+    "len",
+    "map",
+    "test",
+    "constructor",
+    "render",
+    "toString",
+    "__init__",
+];
+
+/// 模块入口文件名，此类文件以所在目录名作为模块名
+const MODULE_ENTRY_STEMS: &[&str] = &["mod", "index", "__init__", "lib", "main"];
+
+/// 函数调用位置：标识符后紧跟`(`，带有定义关键字时为函数定义
+static CALL_SITE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:(fn|def|function|func)\s+)?([A-Za-z_]\w*)\s*\(").unwrap());
+
+/// 提取源码中被调用的函数名，跳过同名函数的定义
+pub fn called_names(source: &str) -> HashSet<&str> {
+    CALL_SITE_REGEX
+        .captures_iter(source)
+        .filter(|cap| cap.get(1).is_none())
+        .filter_map(|cap| cap.get(2).map(|name| name.as_str()))
+        .collect()
+}
+
+/// 基于已提取的接口名称解析跨文件的函数调用关系
+///
+/// 在每个文件的源码中查找其他文件定义的函数调用；同名函数定义在多个文件中时，
+/// 依据调用方的导入依赖消除歧义，无法确定时放弃该调用，避免产生错误的依赖边。
+pub fn resolve_function_calls(insights: &[CodeInsight]) -> Vec<CoreDependency> {
+    // 函数名 -> 定义该函数的文件下标
+    let mut definitions: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (index, insight) in insights.iter().enumerate() {
+        for interface in &insight.interfaces {
+            let name = interface.name.as_str();
+            if name.chars().count() < MIN_NAME_LEN || IGNORED_NAMES.contains(&name) {
+                continue;
+            }
+            let files = definitions.entry(name).or_default();
+            if !files.contains(&index) {
+                files.push(index);
+            }
+        }
+    }
+
+    // (调用方, 被调用方) -> 被调用的函数名
+    let mut edges: BTreeMap<(usize, usize), BTreeSet<&str>> = BTreeMap::new();
+
+    for (caller_index, caller) in insights.iter().enumerate() {
+        let source = &caller.code_dossier.source_summary;
+        if source.is_empty() {
+            continue;
+        }
+        let local_names: BTreeSet<&str> = caller
+            .interfaces
+            .iter()
+            .map(|interface| interface.name.as_str())
+            .collect();
+        let called = called_names(source);
+
+        let mut callees = 0;
+        for (name, files) in &definitions {
+            if callees >= MAX_CALLEES_PER_FILE {
+                break;
+            }
+            // 调用方自身定义了同名函数时视为本地调用
+            if local_names.contains(name) || !called.contains(name) {
+                continue;
+            }
+
+            let Some(callee_index) = disambiguate(caller, files, insights) else {
+                continue;
+            };
+            edges
+                .entry((caller_index, callee_index))
+                .or_default()
+                .insert(name);
+            callees += 1;
+        }
+    }
+
+    edges
+        .into_iter()
+        .take(MAX_CALL_EDGES)
+        .map(|((caller_index, callee_index), names)| {
+            let names: Vec<&str> = names.into_iter().collect();
+            CoreDependency {
+                from: file_label(&insights[caller_index]),
+                to: file_label(&insights[callee_index]),
+                dependency_type: DependencyType::FunctionCall,
+                importance: (names.len() as u8).saturating_add(1).min(5),
+                description: Some(format!("调用 {}", names.join(", "))),
+            }
+        })
+        .collect()
+}
+
+/// 从多个同名函数定义中确定调用方实际调用的文件
+fn disambiguate(caller: &CodeInsight, files: &[usize], insights: &[CodeInsight]) -> Option<usize> {
+    if let [only] = files {
+        return Some(*only);
+    }
+
+    let imported: Vec<usize> = files
+        .iter()
+        .copied()
+        .filter(|index| {
+            let module = module_name(&insights[*index].code_dossier.file_path);
+            caller.dependencies.iter().any(|dependency| {
+                dependency.name.contains(&module)
+                    || dependency
+                        .path
+                        .as_deref()
+                        .is_some_and(|path| path.contains(&module))
+            })
+        })
+        .collect();
+
+    match imported.as_slice() {
+        [only] => Some(*only),
+        _ => None,
+    }
+}
+
+/// 获取文件对应的模块名，入口文件取所在目录名
+pub(crate) fn module_name(file_path: &Path) -> String {
+    let stem = file_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    if MODULE_ENTRY_STEMS.contains(&stem)
+        && let Some(parent) = file_path
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+    {
+        return parent.to_string();
+    }
+    stem.to_string()
+}
+
+fn file_label(insight: &CodeInsight) -> String {
+    insight
+        .code_dossier
+        .file_path
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::code::{CodeComplexity, CodeDossier, CodePurpose, Dependency, InterfaceInfo};
+    use std::path::PathBuf;
+
+    fn insight(path: &str, source: &str, functions: &[&str]) -> CodeInsight {
+        CodeInsight {
+            code_dossier: CodeDossier {
+                name: path.rsplit('/').next().unwrap().to_string(),
+                file_path: PathBuf::from(path),
+                source_summary: source.to_string(),
+                code_purpose: CodePurpose::Other,
+                importance_score: 0.8,
+                description: None,
+                functions: vec![],
+                interfaces: vec![],
+            },
+            detailed_description: String::new(),
+            responsibilities: vec![],
+            interfaces: functions
+                .iter()
+                .map(|name| {
+                    InterfaceInfo::new(
+                        name.to_string(),
+                        "function".to_string(),
+                        "public".to_string(),
+                        vec![],
+                        None,
+                        None,
+                    )
+                })
+                .collect(),
+            dependencies: vec![],
+            complexity_metrics: Some(CodeComplexity {
+                cyclomatic_complexity: 1.0,
+                lines_of_code: 10,
+                number_of_functions: functions.len(),
+                number_of_classes: 0,
+            }),
+        }
+    }
+
+    fn import(name: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            path: None,
+            is_external: false,
+            line_number: None,
+            dependency_type: "use".to_string(),
+            version: None,
+        }
+    }
+
+    #[test]
+    fn test_cross_file_call_edge_is_found() {
+        let insights = vec![
+            insight(
+                "src/pricing.rs",
+                "pub fn calculate_total(items: &[u32]) -> u32 { items.iter().sum() }",
+                &["calculate_total"],
+            ),
+            insight(
+                "src/checkout.rs",
+                "pub fn checkout(items: &[u32]) -> u32 {\n    calculate_total(items)\n}",
+                &["checkout"],
+            ),
+        ];
+
+        let edges = resolve_function_calls(&insights);
+
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].from, "src/checkout.rs");
+        assert_eq!(edges[0].to, "src/pricing.rs");
+        assert!(matches!(
+            edges[0].dependency_type,
+            DependencyType::FunctionCall
+        ));
+        assert!(
+            edges[0]
+                .description
+                .as_deref()
+                .unwrap()
+                .contains("calculate_total")
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_call_resolved_by_import() {
+        let mut caller = insight(
+            "src/handler.rs",
+            "use crate::storage::save_record;\nfn handle() { save_record(1); }",
+            &["handle"],
+        );
+        caller.dependencies = vec![import("crate::storage::save_record")];
+        let insights = vec![
+            insight(
+                "src/cache/mod.rs",
+                "pub fn save_record(id: u32) {}",
+                &["save_record"],
+            ),
+            insight(
+                "src/storage.rs",
+                "pub fn save_record(id: u32) {}",
+                &["save_record"],
+            ),
+            caller,
+        ];
+
+        let edges = resolve_function_calls(&insights);
+
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].to, "src/storage.rs");
+    }
+}
@@ -0,0 +1,239 @@
+use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::extractors::function_call_resolver::module_name;
+use crate::generator::preprocess::extractors::language_processors::LanguageProcessorManager;
+use crate::types::code::{CodeDossier, Dependency};
+use crate::types::code_releationship::{CoreDependency, DependencyType, RelationshipAnalysis};
+use crate::types::project_structure::ProjectStructure;
+use crate::utils::file_utils::read_text_with_limit;
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+/// 最多生成的导入依赖边数量
+const MAX_IMPORT_EDGES: usize = 300;
+
+/// 单个源码文件及其导入依赖
+pub struct FileImports {
+    pub file_path: PathBuf,
+    pub dependencies: Vec<Dependency>,
+    /// 依赖所在的源码行，按行号索引（行号从1开始）
+    pub lines: BTreeMap<usize, String>,
+}
+
+/// 不调用LLM，基于语言处理器提取的导入依赖（import/use/include等）构建文件间的依赖关系
+pub async fn extract(
+    context: &GeneratorContext,
+    codes: &[CodeDossier],
+    project_structure: &ProjectStructure,
+) -> Result<RelationshipAnalysis> {
+    let language_processor =
+        LanguageProcessorManager::with_overrides(&context.config.language_overrides);
+
+    let mut files = Vec::with_capacity(codes.len());
+    for code in codes {
+        let full_path = project_structure.root_path.join(&code.file_path);
+        let Ok(Some((content, _))) =
+            read_text_with_limit(&full_path, context.config.max_document_read_bytes).await
+        else {
+            continue;
+        };
+        let dependencies = language_processor.extract_dependencies(&code.file_path, &content);
+        let source_lines: Vec<&str> = content.lines().collect();
+        let lines = dependencies
+            .iter()
+            .filter_map(|dependency| dependency.line_number)
+            .filter_map(|line| {
+                source_lines
+                    .get(line.checked_sub(1)?)
+                    .map(|text| (line, text.to_string()))
+            })
+            .collect();
+        files.push(FileImports {
+            file_path: code.file_path.clone(),
+            dependencies,
+            lines,
+        });
+    }
+
+    let core_dependencies = build_import_graph(&files);
+    let key_insights = vec![format!(
+        "组件关系由导入语句静态分析得出（未使用LLM），共 {} 条依赖",
+        core_dependencies.len()
+    )];
+    Ok(RelationshipAnalysis {
+        core_dependencies,
+        architecture_layers: Vec::new(),
+        key_insights,
+    })
+}
+
+/// 将各文件的导入依赖解析为指向项目内其他文件的依赖边，无法确定目标文件的依赖会被忽略
+pub fn build_import_graph(files: &[FileImports]) -> Vec<CoreDependency> {
+    // 模块名 -> 文件下标
+    let mut modules: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, file) in files.iter().enumerate() {
+        modules
+            .entry(module_name(&file.file_path))
+            .or_default()
+            .push(index);
+    }
+
+    let mut seen: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let mut edges = Vec::new();
+    for (from_index, file) in files.iter().enumerate() {
+        let source_file = file.file_path.to_string_lossy();
+        for dependency in &file.dependencies {
+            let line = dependency
+                .line_number
+                .and_then(|line| file.lines.get(&line))
+                .map(String::as_str)
+                .unwrap_or_default();
+            let Some(to_index) = resolve_target(dependency, &source_file, line, &modules)
+                .filter(|to_index| *to_index != from_index)
+            else {
+                continue;
+            };
+            if !seen.insert((from_index, to_index)) {
+                continue;
+            }
+
+            let dependency_type = if dependency.dependency_type == "mod" {
+                DependencyType::Module
+            } else {
+                DependencyType::Import
+            };
+            edges.push(CoreDependency {
+                from: file_label(&file.file_path),
+                to: file_label(&files[to_index].file_path),
+                dependency_type,
+                importance: 3,
+                description: Some(line.trim().to_string()).filter(|line| !line.is_empty()),
+            });
+            if edges.len() >= MAX_IMPORT_EDGES {
+                return edges;
+            }
+        }
+    }
+    edges
+}
+
+/// 确定依赖指向的项目文件：优先使用依赖的目标名称，其次使用依赖所在源码行中的路径片段
+///
+/// 各语言处理器记录依赖的方式不同：有的把目标放在`name`，有的把源文件放在`name`、目标放在`path`。
+fn resolve_target(
+    dependency: &Dependency,
+    source_file: &str,
+    line: &str,
+    modules: &BTreeMap<String, Vec<usize>>,
+) -> Option<usize> {
+    let target = if dependency.name == source_file {
+        dependency.path.as_deref().unwrap_or_default()
+    } else {
+        dependency.name.as_str()
+    };
+
+    let segments = |text: &str| -> Vec<String> {
+        text.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    segments(target)
+        .into_iter()
+        .rev()
+        .chain(segments(line).into_iter().rev())
+        .find_map(|segment| match modules.get(&segment).map(Vec::as_slice) {
+            Some([only]) => Some(*only),
+            _ => None,
+        })
+}
+
+fn file_label(file_path: &std::path::Path) -> String {
+    file_path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::types::code::CodePurpose;
+    use tempfile::TempDir;
+
+    fn dossier(path: &str) -> CodeDossier {
+        CodeDossier {
+            name: path.rsplit('/').next().unwrap().to_string(),
+            file_path: PathBuf::from(path),
+            source_summary: String::new(),
+            code_purpose: CodePurpose::Other,
+            importance_score: 0.8,
+            description: None,
+            functions: vec![],
+            interfaces: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_edges_without_llm() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = [
+            (
+                "app/service.py",
+                "from app.models import User\nimport os\n\ndef load():\n    return User()\n",
+            ),
+            ("app/models.py", "class User:\n    pass\n"),
+            (
+                "src/main.rs",
+                "mod config;\nuse crate::config::Config;\n\nfn main() {}\n",
+            ),
+            ("src/config.rs", "pub struct Config;\n"),
+            // 二进制内容（含空字节）被跳过，不会中断整个阶段
+            ("app/blob.py", "from app.models import User\0\0\n"),
+        ];
+        for (path, content) in files {
+            let full_path = temp_dir.path().join(path);
+            std::fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+            std::fs::write(full_path, content).unwrap();
+        }
+
+        let mut config = Config {
+            project_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        config.llm.offline = true;
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
+        let project_structure = ProjectStructure {
+            project_name: "demo".to_string(),
+            root_path: temp_dir.path().to_path_buf(),
+            directories: vec![],
+            files: vec![],
+            total_files: files.len(),
+            total_directories: 2,
+            file_types: Default::default(),
+            size_distribution: Default::default(),
+        };
+        let codes: Vec<CodeDossier> = files.iter().map(|(path, _)| dossier(path)).collect();
+
+        let relationships = extract(&context, &codes, &project_structure).await.unwrap();
+        let edges: Vec<(&str, &str, &str)> = relationships
+            .core_dependencies
+            .iter()
+            .map(|dep| {
+                (
+                    dep.from.as_str(),
+                    dep.to.as_str(),
+                    dep.dependency_type.as_str(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            edges,
+            vec![
+                ("app/service.py", "app/models.py", "import"),
+                ("src/main.rs", "src/config.rs", "module"),
+            ]
+        );
+        assert_eq!(relationships.key_insights.len(), 1);
+    }
+}
@@ -104,7 +104,7 @@ impl LanguageProcessor for JavaProcessor {
         }
     }
 
-    fn is_important_line(&self, line: &str) -> bool {
+    fn is_important_code_line(&self, line: &str) -> bool {
         let trimmed = line.trim();
 
         if trimmed.starts_with("public class ")
@@ -120,14 +120,6 @@ impl LanguageProcessor for JavaProcessor {
             return true;
         }
 
-        if trimmed.contains("TODO")
-            || trimmed.contains("FIXME")
-            || trimmed.contains("NOTE")
-            || trimmed.contains("HACK")
-        {
-            return true;
-        }
-
         false
     }
 
@@ -135,6 +127,10 @@ impl LanguageProcessor for JavaProcessor {
         "Java"
     }
 
+    fn is_entry_point(&self, _file_path: &Path, content: &str) -> bool {
+        content.contains("public static void main(")
+    }
+
     fn extract_interfaces(&self, content: &str, file_path: &Path) -> Vec<InterfaceInfo> {
         let mut interfaces = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
@@ -8,6 +8,7 @@ pub struct JavaScriptProcessor {
     import_regex: Regex,
     require_regex: Regex,
     dynamic_import_regex: Regex,
+    re_export_regex: Regex,
     function_regex: Regex,
     arrow_function_regex: Regex,
     class_regex: Regex,
@@ -27,6 +28,10 @@ impl JavaScriptProcessor {
             import_regex: Regex::new(r#"^\s*import\s+(?:.*\s+from\s+)?['"]([^'"]+)['"]"#).unwrap(),
             require_regex: Regex::new(r#"require\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap(),
             dynamic_import_regex: Regex::new(r#"import\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap(),
+            re_export_regex: Regex::new(
+                r#"^\s*export\s+(?:\*(?:\s+as\s+\w+)?|\{[^}]*\})\s*from\s*['"]([^'"]+)['"]"#,
+            )
+            .unwrap(),
             function_regex: Regex::new(r"^\s*(async\s+)?function\s+(\w+)\s*\(([^)]*)\)").unwrap(),
             arrow_function_regex: Regex::new(
                 r"^\s*(const|let|var)\s+(\w+)\s*=\s*(async\s+)?\(([^)]*)\)\s*=>",
@@ -69,28 +74,45 @@ impl LanguageProcessor for JavaScriptProcessor {
                 });
             }
 
-            // 提取require语句
-            if let Some(captures) = self.require_regex.captures(line)
-                && let Some(require_path) = captures.get(1)
-            {
-                let path_str = require_path.as_str();
-                let is_external = !path_str.starts_with('.') && !path_str.starts_with('/');
+            // 提取require语句（同一行可能包含多个require）
+            for captures in self.require_regex.captures_iter(line) {
+                if let Some(require_path) = captures.get(1) {
+                    let path_str = require_path.as_str();
+                    let is_external = !path_str.starts_with('.') && !path_str.starts_with('/');
+
+                    dependencies.push(Dependency {
+                        name: source_file.clone(),
+                        path: Some(path_str.to_string()),
+                        is_external,
+                        line_number: Some(line_num + 1),
+                        dependency_type: "require".to_string(),
+                        version: None,
+                    });
+                }
+            }
 
-                dependencies.push(Dependency {
-                    name: source_file.clone(),
-                    path: Some(path_str.to_string()),
-                    is_external,
-                    line_number: Some(line_num + 1),
-                    dependency_type: "require".to_string(),
-                    version: None,
-                });
+            // 提取动态import（同一行可能包含多个import()）
+            for captures in self.dynamic_import_regex.captures_iter(line) {
+                if let Some(import_path) = captures.get(1) {
+                    let path_str = import_path.as_str();
+                    let is_external = !path_str.starts_with('.') && !path_str.starts_with('/');
+
+                    dependencies.push(Dependency {
+                        name: source_file.clone(),
+                        path: Some(path_str.to_string()),
+                        is_external,
+                        line_number: Some(line_num + 1),
+                        dependency_type: "dynamic_import".to_string(),
+                        version: None,
+                    });
+                }
             }
 
-            // 提取动态import
-            if let Some(captures) = self.dynamic_import_regex.captures(line)
-                && let Some(import_path) = captures.get(1)
+            // 提取re-export语句，如 export * from './x' 或 export { a } from './x'
+            if let Some(captures) = self.re_export_regex.captures(line)
+                && let Some(export_path) = captures.get(1)
             {
-                let path_str = import_path.as_str();
+                let path_str = export_path.as_str();
                 let is_external = !path_str.starts_with('.') && !path_str.starts_with('/');
 
                 dependencies.push(Dependency {
@@ -98,7 +120,7 @@ impl LanguageProcessor for JavaScriptProcessor {
                     path: Some(path_str.to_string()),
                     is_external,
                     line_number: Some(line_num + 1),
-                    dependency_type: "dynamic_import".to_string(),
+                    dependency_type: "re_export".to_string(),
                     version: None,
                 });
             }
@@ -138,7 +160,7 @@ impl LanguageProcessor for JavaScriptProcessor {
         }
     }
 
-    fn is_important_line(&self, line: &str) -> bool {
+    fn is_important_code_line(&self, line: &str) -> bool {
         let trimmed = line.trim();
 
         // 函数定义
@@ -164,15 +186,6 @@ impl LanguageProcessor for JavaScriptProcessor {
             return true;
         }
 
-        // 重要注释
-        if trimmed.contains("TODO")
-            || trimmed.contains("FIXME")
-            || trimmed.contains("NOTE")
-            || trimmed.contains("HACK")
-        {
-            return true;
-        }
-
         false
     }
 
@@ -180,6 +193,10 @@ impl LanguageProcessor for JavaScriptProcessor {
         "JavaScript"
     }
 
+    fn is_entry_point(&self, file_path: &Path, _content: &str) -> bool {
+        file_path.file_stem().is_some_and(|stem| stem == "main")
+    }
+
     fn extract_interfaces(&self, content: &str, _file_path: &Path) -> Vec<InterfaceInfo> {
         let mut interfaces = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
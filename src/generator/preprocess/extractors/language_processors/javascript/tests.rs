@@ -283,4 +283,45 @@ const path = require('path');
         assert!(types.contains(&"require".to_string()));
         assert!(types.contains(&"dynamic_import".to_string()));
     }
+
+    #[test]
+    fn test_extract_dependencies_multiple_requires_on_one_line() {
+        let processor = JavaScriptProcessor::new();
+        let content = r#"
+const a = require('./a'), b = require('lodash');
+const pages = [import('./pages/home'), import('./pages/about')];
+"#;
+
+        let deps = processor.extract_dependencies(content, Path::new("test.js"));
+
+        assert_eq!(deps.len(), 4);
+        assert_eq!(deps[0].path, Some("./a".to_string()));
+        assert_eq!(deps[1].path, Some("lodash".to_string()));
+        assert!(deps[1].is_external);
+        assert_eq!(deps[2].dependency_type, "dynamic_import");
+        assert_eq!(deps[3].path, Some("./pages/about".to_string()));
+    }
+
+    #[test]
+    fn test_extract_dependencies_re_export() {
+        let processor = JavaScriptProcessor::new();
+        let content = r#"
+export * from './components';
+export * as helpers from './helpers';
+export { default as Button, Input } from './ui/button';
+export { formatDate } from 'date-utils';
+export const localValue = 1;
+"#;
+
+        let deps = processor.extract_dependencies(content, Path::new("index.js"));
+
+        assert_eq!(deps.len(), 4);
+        assert!(deps.iter().all(|d| d.dependency_type == "re_export"));
+        assert_eq!(deps[0].path, Some("./components".to_string()));
+        assert_eq!(deps[1].path, Some("./helpers".to_string()));
+        assert_eq!(deps[2].path, Some("./ui/button".to_string()));
+        assert!(!deps[2].is_external);
+        assert_eq!(deps[3].path, Some("date-utils".to_string()));
+        assert!(deps[3].is_external);
+    }
 }
@@ -129,7 +129,7 @@ impl LanguageProcessor for KotlinProcessor {
         }
     }
 
-    fn is_important_line(&self, line: &str) -> bool {
+    fn is_important_code_line(&self, line: &str) -> bool {
         let trimmed = line.trim();
 
         // 类、接口、对象定义
@@ -173,15 +173,6 @@ impl LanguageProcessor for KotlinProcessor {
             return true;
         }
 
-        // 重要注释
-        if trimmed.contains("TODO")
-            || trimmed.contains("FIXME")
-            || trimmed.contains("NOTE")
-            || trimmed.contains("HACK")
-        {
-            return true;
-        }
-
         false
     }
 
@@ -189,6 +180,12 @@ impl LanguageProcessor for KotlinProcessor {
         "Kotlin"
     }
 
+    fn is_entry_point(&self, _file_path: &Path, content: &str) -> bool {
+        content
+            .lines()
+            .any(|line| line.trim_start().starts_with("fun main("))
+    }
+
     fn extract_interfaces(&self, content: &str, _file_path: &Path) -> Vec<InterfaceInfo> {
         let mut interfaces = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
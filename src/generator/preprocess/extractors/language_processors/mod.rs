@@ -1,5 +1,8 @@
+use log::debug;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
+use crate::config::DEFAULT_IMPORTANT_COMMENT_MARKERS;
 use crate::types::code::{CodeComplexity, Dependency, InterfaceInfo};
 
 /// 语言处理器特征
@@ -14,26 +17,59 @@ pub trait LanguageProcessor: Send + Sync + std::fmt::Debug {
     #[allow(dead_code)]
     fn determine_component_type(&self, file_path: &Path, content: &str) -> String;
 
-    /// 识别重要代码行
-    fn is_important_line(&self, line: &str) -> bool;
+    /// 识别重要的结构性代码行（定义、导入等），注释标记由`has_comment_marker`统一识别
+    fn is_important_code_line(&self, line: &str) -> bool;
 
-    /// 获取语言名称
+    /// 识别重要代码行：结构性代码行，或包含默认重要注释标记的行
     #[allow(dead_code)]
+    fn is_important_line(&self, line: &str) -> bool {
+        self.is_important_code_line(line)
+            || has_comment_marker(line, DEFAULT_IMPORTANT_COMMENT_MARKERS)
+    }
+
+    /// 获取语言名称
     fn language_name(&self) -> &'static str;
 
     /// 提取代码接口定义
     fn extract_interfaces(&self, content: &str, file_path: &Path) -> Vec<InterfaceInfo>;
+
+    /// 判断文件是否为程序入口（如`fn main`、`__main__`），默认不识别
+    fn is_entry_point(&self, _file_path: &Path, _content: &str) -> bool {
+        false
+    }
+}
+
+/// 判断代码行是否包含任一重要注释标记（区分大小写）
+pub fn has_comment_marker<S: AsRef<str>>(line: &str, markers: &[S]) -> bool {
+    markers.iter().any(|marker| {
+        let marker = marker.as_ref();
+        !marker.is_empty() && line.contains(marker)
+    })
 }
 
+/// 文件首行中强制指定语言的标记，如`# litho:lang=python`
+const MODELINE_MARKER: &str = "litho:lang=";
+
 /// 语言处理器管理器
 #[derive(Debug)]
 pub struct LanguageProcessorManager {
     processors: Vec<Box<dyn LanguageProcessor>>,
+    /// 配置的语言映射（模式, 语言名称），按模式排序以保证匹配结果稳定
+    overrides: Vec<(String, String)>,
+    /// 单个文件最多保留的接口数量
+    max_interfaces_per_file: Option<usize>,
+    /// 截取源码时优先保留的注释标记
+    important_comment_markers: Vec<String>,
 }
 
 impl Clone for LanguageProcessorManager {
     fn clone(&self) -> Self {
-        Self::new()
+        Self {
+            overrides: self.overrides.clone(),
+            max_interfaces_per_file: self.max_interfaces_per_file,
+            important_comment_markers: self.important_comment_markers.clone(),
+            ..Self::new()
+        }
     }
 }
 
@@ -57,11 +93,50 @@ impl LanguageProcessorManager {
                 Box::new(python::PythonProcessor::new()),
                 Box::new(java::JavaProcessor::new()),
             ],
+            overrides: Vec::new(),
+            max_interfaces_per_file: None,
+            important_comment_markers: DEFAULT_IMPORTANT_COMMENT_MARKERS
+                .iter()
+                .map(|marker| marker.to_string())
+                .collect(),
+        }
+    }
+
+    /// 创建带语言映射的处理器管理器，映射来自配置的`language_overrides`
+    pub fn with_overrides(overrides: &HashMap<String, String>) -> Self {
+        let overrides: BTreeMap<&String, &String> = overrides.iter().collect();
+        Self {
+            overrides: overrides
+                .into_iter()
+                .map(|(pattern, language)| (pattern.clone(), language.clone()))
+                .collect(),
+            ..Self::new()
         }
     }
 
-    /// 根据文件扩展名获取处理器
+    /// 限制单个文件保留的接口数量
+    pub fn with_interface_limit(mut self, max_interfaces_per_file: usize) -> Self {
+        self.max_interfaces_per_file = Some(max_interfaces_per_file);
+        self
+    }
+
+    /// 使用配置的重要注释标记（如`SAFETY`、`PERF`）替换默认标记
+    pub fn with_comment_markers(mut self, markers: &[String]) -> Self {
+        self.important_comment_markers = markers.to_vec();
+        self
+    }
+
+    /// 获取处理器：优先使用配置的语言映射，其次按文件扩展名识别
     pub fn get_processor(&self, file_path: &Path) -> Option<&dyn LanguageProcessor> {
+        if let Some(processor) = self
+            .overrides
+            .iter()
+            .find(|(pattern, _)| override_matches(pattern, file_path))
+            .and_then(|(_, language)| self.processor_for_language(language))
+        {
+            return Some(processor);
+        }
+
         let extension = file_path.extension()?.to_str()?;
 
         for processor in &self.processors {
@@ -73,9 +148,36 @@ impl LanguageProcessorManager {
         None
     }
 
+    /// 根据文件内容获取处理器：文件首行的语言标记优先于配置映射和扩展名
+    pub fn get_processor_for_content(
+        &self,
+        file_path: &Path,
+        content: &str,
+    ) -> Option<&dyn LanguageProcessor> {
+        parse_modeline(content)
+            .and_then(|language| self.processor_for_language(language))
+            .or_else(|| self.get_processor(file_path))
+    }
+
+    /// 按语言名称（如`rust`）或扩展名（如`rs`）查找处理器
+    fn processor_for_language(&self, language: &str) -> Option<&dyn LanguageProcessor> {
+        let language = language.trim().trim_start_matches('.').to_lowercase();
+        self.processors
+            .iter()
+            .find(|processor| processor.language_name().to_lowercase() == language)
+            .or_else(|| {
+                self.processors.iter().find(|processor| {
+                    processor
+                        .supported_extensions()
+                        .contains(&language.as_str())
+                })
+            })
+            .map(|processor| processor.as_ref())
+    }
+
     /// 提取文件依赖
     pub fn extract_dependencies(&self, file_path: &Path, content: &str) -> Vec<Dependency> {
-        if let Some(processor) = self.get_processor(file_path) {
+        if let Some(processor) = self.get_processor_for_content(file_path, content) {
             processor.extract_dependencies(content, file_path)
         } else {
             Vec::new()
@@ -85,7 +187,7 @@ impl LanguageProcessorManager {
     /// 判断组件类型
     #[allow(dead_code)]
     pub fn determine_component_type(&self, file_path: &Path, content: &str) -> String {
-        if let Some(processor) = self.get_processor(file_path) {
+        if let Some(processor) = self.get_processor_for_content(file_path, content) {
             processor.determine_component_type(file_path, content)
         } else {
             "unknown".to_string()
@@ -95,18 +197,40 @@ impl LanguageProcessorManager {
     /// 识别重要代码行
     pub fn is_important_line(&self, file_path: &Path, line: &str) -> bool {
         if let Some(processor) = self.get_processor(file_path) {
-            processor.is_important_line(line)
+            processor.is_important_code_line(line)
+                || has_comment_marker(line, &self.important_comment_markers)
         } else {
             false
         }
     }
 
+    /// 判断文件是否为程序入口
+    pub fn is_entry_point(&self, file_path: &Path, content: &str) -> bool {
+        self.get_processor_for_content(file_path, content)
+            .is_some_and(|processor| processor.is_entry_point(file_path, content))
+    }
+
     /// 提取代码接口定义
     pub fn extract_interfaces(&self, file_path: &Path, content: &str) -> Vec<InterfaceInfo> {
-        if let Some(processor) = self.get_processor(file_path) {
-            processor.extract_interfaces(content, file_path)
-        } else {
-            Vec::new()
+        let Some(processor) = self.get_processor_for_content(file_path, content) else {
+            return Vec::new();
+        };
+        let interfaces = processor.extract_interfaces(content, file_path);
+
+        match self.max_interfaces_per_file {
+            Some(limit) if interfaces.len() > limit => {
+                let total = interfaces.len();
+                let kept = cap_interfaces(interfaces, limit);
+                debug!(
+                    "   ✂️ {} 提取到 {} 个接口，超出上限 {}，已省略 {} 个",
+                    file_path.display(),
+                    total,
+                    limit,
+                    total - kept.len()
+                );
+                kept
+            }
+            _ => interfaces,
         }
     }
 
@@ -139,6 +263,48 @@ impl LanguageProcessorManager {
     }
 }
 
+/// 判断文件是否命中语言映射模式
+///
+/// 含路径分隔符或通配符的模式按glob匹配路径（可匹配任意上级目录），
+/// 其余模式视为扩展名或完整文件名。
+fn override_matches(pattern: &str, file_path: &Path) -> bool {
+    let pattern = pattern.trim();
+    if pattern.contains(['/', '*', '?', '[']) {
+        let pattern = pattern.trim_start_matches("./").trim_start_matches('/');
+        let path = file_path.to_string_lossy().replace('\\', "/");
+        return glob::Pattern::new(&format!("**/{}", pattern))
+            .map(|glob_pattern| glob_pattern.matches(&path))
+            .unwrap_or(false);
+    }
+
+    let pattern = pattern.trim_start_matches('.');
+    let extension_matches = file_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case(pattern));
+    let file_name_matches = file_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|file_name| file_name == pattern);
+    extension_matches || file_name_matches
+}
+
+/// 解析文件首行（首行为shebang时为第二行）中的语言标记，如`# litho:lang=python`
+pub fn parse_modeline(content: &str) -> Option<&str> {
+    let mut lines = content.lines();
+    let mut line = lines.next()?;
+    if line.starts_with("#!") {
+        line = lines.next()?;
+    }
+    let start = line.find(MODELINE_MARKER)? + MODELINE_MARKER.len();
+    let rest = &line[start..];
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '+' | '.')))
+        .unwrap_or(rest.len());
+    let language = &rest[..end];
+    (!language.is_empty()).then_some(language)
+}
+
 // 子模块
 pub mod java;
 pub mod javascript;
@@ -149,3 +315,122 @@ pub mod rust;
 pub mod svelte;
 pub mod typescript;
 pub mod vue;
+
+/// 可见性的保留优先级，数值越小越优先
+pub(crate) fn visibility_rank(visibility: &str) -> u8 {
+    match visibility.to_lowercase().as_str() {
+        "public" | "pub" | "export" | "exported" | "open" => 0,
+        "private" | "fileprivate" => 2,
+        _ => 1,
+    }
+}
+
+/// 按可见性优先保留至多`limit`个接口，保留的接口维持原有顺序
+fn cap_interfaces(interfaces: Vec<InterfaceInfo>, limit: usize) -> Vec<InterfaceInfo> {
+    let mut ranked: Vec<(u8, usize)> = interfaces
+        .iter()
+        .enumerate()
+        .map(|(index, interface)| (visibility_rank(&interface.visibility), index))
+        .collect();
+    ranked.sort();
+    let mut keep = vec![false; interfaces.len()];
+    for (_, index) in ranked.into_iter().take(limit) {
+        keep[index] = true;
+    }
+
+    interfaces
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(interface, keep)| keep.then_some(interface))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_extension_maps_to_rust_processor() {
+        let manager = LanguageProcessorManager::with_overrides(&HashMap::from([
+            ("rsx".to_string(), "rust".to_string()),
+            ("scripts/*".to_string(), "Python".to_string()),
+        ]));
+
+        let processor = manager.get_processor(Path::new("src/view.rsx")).unwrap();
+        assert_eq!(processor.language_name(), "Rust");
+        let processor = manager.get_processor(Path::new("scripts/deploy")).unwrap();
+        assert_eq!(processor.language_name(), "Python");
+        // 映射优先于扩展名
+        let processor = manager.get_processor(Path::new("scripts/tool.js")).unwrap();
+        assert_eq!(processor.language_name(), "Python");
+        assert!(
+            LanguageProcessorManager::new()
+                .get_processor(Path::new("src/view.rsx"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_modeline_forces_processor() {
+        let manager = LanguageProcessorManager::new();
+        let content = "#!/usr/bin/env run-script\n# litho:lang=python\ndef main():\n    pass\n";
+
+        assert_eq!(parse_modeline(content), Some("python"));
+        let processor = manager
+            .get_processor_for_content(Path::new("tools/build"), content)
+            .unwrap();
+        assert_eq!(processor.language_name(), "Python");
+
+        let processor = manager
+            .get_processor_for_content(Path::new("main.rs"), "// litho:lang=rs\nfn main() {}")
+            .unwrap();
+        assert_eq!(processor.language_name(), "Rust");
+        assert_eq!(parse_modeline("fn main() {}\n// litho:lang=python"), None);
+    }
+
+    #[test]
+    fn test_interfaces_capped_per_file_keeping_public_first() {
+        let content: String = (0..1000)
+            .map(|i| {
+                let visibility = if i % 10 == 0 { "pub " } else { "" };
+                format!("{}fn function_{}() {{}}\n", visibility, i)
+            })
+            .collect();
+        let file_path = Path::new("src/generated.rs");
+
+        let uncapped = LanguageProcessorManager::new().extract_interfaces(file_path, &content);
+        assert_eq!(uncapped.len(), 1000);
+
+        let manager = LanguageProcessorManager::new().with_interface_limit(150);
+        let interfaces = manager.extract_interfaces(file_path, &content);
+        assert_eq!(interfaces.len(), 150);
+        let public = interfaces
+            .iter()
+            .filter(|interface| interface.visibility == "public")
+            .count();
+        assert_eq!(public, 100);
+        // 保留的接口维持源码中的顺序
+        assert_eq!(interfaces[0].name, "function_0");
+        assert_eq!(interfaces[1].name, "function_1");
+        assert_eq!(interfaces.last().unwrap().name, "function_990");
+        // 克隆后仍保留上限配置
+        let cloned = manager.clone();
+        assert_eq!(cloned.extract_interfaces(file_path, &content).len(), 150);
+    }
+
+    #[test]
+    fn test_custom_comment_marker_is_important() {
+        let file_path = Path::new("src/ffi.rs");
+        let line = "// SAFETY: the pointer comes from Box::into_raw";
+
+        assert!(!LanguageProcessorManager::new().is_important_line(file_path, line));
+
+        let markers = vec!["SAFETY:".to_string(), "PERF".to_string()];
+        let manager = LanguageProcessorManager::new().with_comment_markers(&markers);
+        assert!(manager.is_important_line(file_path, line));
+        assert!(manager.clone().is_important_line(file_path, line));
+        // 配置的标记替换默认标记，结构性代码行不受影响
+        assert!(!manager.is_important_line(file_path, "// TODO: remove"));
+        assert!(manager.is_important_line(file_path, "pub fn release() {}"));
+    }
+}
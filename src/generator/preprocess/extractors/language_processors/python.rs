@@ -108,7 +108,7 @@ impl LanguageProcessor for PythonProcessor {
         }
     }
 
-    fn is_important_line(&self, line: &str) -> bool {
+    fn is_important_code_line(&self, line: &str) -> bool {
         let trimmed = line.trim();
 
         if trimmed.starts_with("class ")
@@ -120,14 +120,6 @@ impl LanguageProcessor for PythonProcessor {
             return true;
         }
 
-        if trimmed.contains("TODO")
-            || trimmed.contains("FIXME")
-            || trimmed.contains("NOTE")
-            || trimmed.contains("HACK")
-        {
-            return true;
-        }
-
         false
     }
 
@@ -135,6 +127,14 @@ impl LanguageProcessor for PythonProcessor {
         "Python"
     }
 
+    fn is_entry_point(&self, file_path: &Path, content: &str) -> bool {
+        file_path
+            .file_name()
+            .is_some_and(|name| name == "__main__.py")
+            || content.contains("if __name__ == \"__main__\"")
+            || content.contains("if __name__ == '__main__'")
+    }
+
     fn extract_interfaces(&self, content: &str, file_path: &Path) -> Vec<InterfaceInfo> {
         let mut interfaces = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
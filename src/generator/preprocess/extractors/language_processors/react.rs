@@ -101,7 +101,7 @@ impl LanguageProcessor for ReactProcessor {
         }
     }
 
-    fn is_important_line(&self, line: &str) -> bool {
+    fn is_important_code_line(&self, line: &str) -> bool {
         let trimmed = line.trim();
 
         // React组件定义
@@ -143,15 +143,6 @@ impl LanguageProcessor for ReactProcessor {
             return true;
         }
 
-        // 重要注释
-        if trimmed.contains("TODO")
-            || trimmed.contains("FIXME")
-            || trimmed.contains("NOTE")
-            || trimmed.contains("HACK")
-        {
-            return true;
-        }
-
         false
     }
 
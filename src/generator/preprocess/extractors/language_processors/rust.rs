@@ -117,7 +117,7 @@ impl LanguageProcessor for RustProcessor {
         }
     }
 
-    fn is_important_line(&self, line: &str) -> bool {
+    fn is_important_code_line(&self, line: &str) -> bool {
         let trimmed = line.trim();
 
         // 函数定义
@@ -155,15 +155,6 @@ impl LanguageProcessor for RustProcessor {
             return true;
         }
 
-        // 重要注释
-        if trimmed.contains("TODO")
-            || trimmed.contains("FIXME")
-            || trimmed.contains("NOTE")
-            || trimmed.contains("HACK")
-        {
-            return true;
-        }
-
         false
     }
 
@@ -171,6 +162,15 @@ impl LanguageProcessor for RustProcessor {
         "Rust"
     }
 
+    fn is_entry_point(&self, _file_path: &Path, content: &str) -> bool {
+        content.lines().any(|line| {
+            let line = line.trim_start();
+            ["fn main(", "async fn main(", "pub fn main("]
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+        })
+    }
+
     fn extract_interfaces(&self, content: &str, file_path: &Path) -> Vec<InterfaceInfo> {
         // 🆕 首先尝试使用 syn 进行深度解析
         if let Ok(syntax) = syn::parse_file(content) {
@@ -115,7 +115,7 @@ impl LanguageProcessor for SvelteProcessor {
         }
     }
 
-    fn is_important_line(&self, line: &str) -> bool {
+    fn is_important_code_line(&self, line: &str) -> bool {
         let trimmed = line.trim();
 
         // Svelte标签
@@ -164,15 +164,6 @@ impl LanguageProcessor for SvelteProcessor {
             return true;
         }
 
-        // 重要注释
-        if trimmed.contains("TODO")
-            || trimmed.contains("FIXME")
-            || trimmed.contains("NOTE")
-            || trimmed.contains("HACK")
-        {
-            return true;
-        }
-
         false
     }
 
@@ -7,6 +7,9 @@ use std::path::Path;
 pub struct TypeScriptProcessor {
     import_regex: Regex,
     type_import_regex: Regex,
+    require_regex: Regex,
+    dynamic_import_regex: Regex,
+    re_export_regex: Regex,
     function_regex: Regex,
     interface_regex: Regex,
     type_alias_regex: Regex,
@@ -26,6 +29,9 @@ impl TypeScriptProcessor {
         Self {
             import_regex: Regex::new(r#"^\s*import\s+(?:.*\s+from\s+)?['"]([^'"]+)['"]"#).unwrap(),
             type_import_regex: Regex::new(r#"^\s*import\s+type\s+.*\s+from\s+['"]([^'"]+)['"]"#).unwrap(),
+            require_regex: Regex::new(r#"require\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap(),
+            dynamic_import_regex: Regex::new(r#"import\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap(),
+            re_export_regex: Regex::new(r#"^\s*export\s+(type\s+)?(?:\*(?:\s+as\s+\w+)?|\{[^}]*\})\s*from\s*['"]([^'"]+)['"]"#).unwrap(),
             function_regex: Regex::new(r"^\s*(export\s+)?(async\s+)?function\s+(\w+)\s*\(([^)]*)\)\s*:\s*([^{]+)?").unwrap(),
             interface_regex: Regex::new(r"^\s*(export\s+)?interface\s+(\w+)").unwrap(),
             type_alias_regex: Regex::new(r"^\s*(export\s+)?type\s+(\w+)\s*=").unwrap(),
@@ -78,6 +84,62 @@ impl LanguageProcessor for TypeScriptProcessor {
                     version: None,
                 });
             }
+
+            // 提取require语句，包括 import x = require('x') 形式
+            for captures in self.require_regex.captures_iter(line) {
+                if let Some(require_path) = captures.get(1) {
+                    let path_str = require_path.as_str();
+                    let is_external = !path_str.starts_with('.') && !path_str.starts_with('/');
+
+                    dependencies.push(Dependency {
+                        name: source_file.clone(),
+                        path: Some(path_str.to_string()),
+                        is_external,
+                        line_number: Some(line_num + 1),
+                        dependency_type: "require".to_string(),
+                        version: None,
+                    });
+                }
+            }
+
+            // 提取动态import，常见于路由懒加载等代码分割场景
+            for captures in self.dynamic_import_regex.captures_iter(line) {
+                if let Some(import_path) = captures.get(1) {
+                    let path_str = import_path.as_str();
+                    let is_external = !path_str.starts_with('.') && !path_str.starts_with('/');
+
+                    dependencies.push(Dependency {
+                        name: source_file.clone(),
+                        path: Some(path_str.to_string()),
+                        is_external,
+                        line_number: Some(line_num + 1),
+                        dependency_type: "dynamic_import".to_string(),
+                        version: None,
+                    });
+                }
+            }
+
+            // 提取re-export语句，export type { X } from './x' 记为类型re-export
+            if let Some(captures) = self.re_export_regex.captures(line)
+                && let Some(export_path) = captures.get(2)
+            {
+                let path_str = export_path.as_str();
+                let is_external = !path_str.starts_with('.') && !path_str.starts_with('/');
+                let dependency_type = if captures.get(1).is_some() {
+                    "type_re_export"
+                } else {
+                    "re_export"
+                };
+
+                dependencies.push(Dependency {
+                    name: source_file.clone(),
+                    path: Some(path_str.to_string()),
+                    is_external,
+                    line_number: Some(line_num + 1),
+                    dependency_type: dependency_type.to_string(),
+                    version: None,
+                });
+            }
         }
 
         dependencies
@@ -119,7 +181,7 @@ impl LanguageProcessor for TypeScriptProcessor {
         }
     }
 
-    fn is_important_line(&self, line: &str) -> bool {
+    fn is_important_code_line(&self, line: &str) -> bool {
         let trimmed = line.trim();
 
         // 函数定义
@@ -145,15 +207,6 @@ impl LanguageProcessor for TypeScriptProcessor {
             return true;
         }
 
-        // 重要注释
-        if trimmed.contains("TODO")
-            || trimmed.contains("FIXME")
-            || trimmed.contains("NOTE")
-            || trimmed.contains("HACK")
-        {
-            return true;
-        }
-
         false
     }
 
@@ -161,6 +214,10 @@ impl LanguageProcessor for TypeScriptProcessor {
         "TypeScript"
     }
 
+    fn is_entry_point(&self, file_path: &Path, _content: &str) -> bool {
+        file_path.file_stem().is_some_and(|stem| stem == "main")
+    }
+
     fn extract_interfaces(&self, content: &str, file_path: &Path) -> Vec<InterfaceInfo> {
         let mut interfaces = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
@@ -523,3 +580,7 @@ impl TypeScriptProcessor {
         }
     }
 }
+
+// Include tests
+#[cfg(test)]
+mod tests;
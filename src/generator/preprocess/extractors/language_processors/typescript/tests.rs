@@ -0,0 +1,76 @@
+use crate::generator::preprocess::extractors::language_processors::LanguageProcessor;
+use crate::generator::preprocess::extractors::language_processors::typescript::TypeScriptProcessor;
+use std::path::Path;
+
+#[test]
+fn test_extract_dependencies_import_and_type_import() {
+    let processor = TypeScriptProcessor::new();
+    let content = r#"
+import { Injectable } from '@angular/core';
+import type { User } from './models/user';
+"#;
+
+    let deps = processor.extract_dependencies(content, Path::new("service.ts"));
+
+    assert_eq!(deps.len(), 2);
+    assert_eq!(deps[0].dependency_type, "import");
+    assert!(deps[0].is_external);
+    assert_eq!(deps[1].dependency_type, "type_import");
+    assert_eq!(deps[1].path, Some("./models/user".to_string()));
+}
+
+#[test]
+fn test_extract_dependencies_require() {
+    let processor = TypeScriptProcessor::new();
+    let content = r#"
+import fs = require('fs');
+const config = require('./config');
+"#;
+
+    let deps = processor.extract_dependencies(content, Path::new("legacy.ts"));
+
+    assert_eq!(deps.len(), 2);
+    assert!(deps.iter().all(|d| d.dependency_type == "require"));
+    assert_eq!(deps[0].path, Some("fs".to_string()));
+    assert!(deps[0].is_external);
+    assert_eq!(deps[1].path, Some("./config".to_string()));
+    assert!(!deps[1].is_external);
+}
+
+#[test]
+fn test_extract_dependencies_dynamic_import() {
+    let processor = TypeScriptProcessor::new();
+    let content = r#"
+const routes = [
+  { path: '/home', component: () => import('./pages/Home') },
+  { path: '/chart', component: () => import('chart.js') },
+];
+"#;
+
+    let deps = processor.extract_dependencies(content, Path::new("routes.tsx"));
+
+    assert_eq!(deps.len(), 2);
+    assert!(deps.iter().all(|d| d.dependency_type == "dynamic_import"));
+    assert_eq!(deps[0].path, Some("./pages/Home".to_string()));
+    assert_eq!(deps[0].line_number, Some(3));
+    assert!(deps[1].is_external);
+}
+
+#[test]
+fn test_extract_dependencies_re_export() {
+    let processor = TypeScriptProcessor::new();
+    let content = r#"
+export * from './api';
+export { UserService } from './services/user';
+export type { UserDto } from './dto';
+"#;
+
+    let deps = processor.extract_dependencies(content, Path::new("index.ts"));
+
+    assert_eq!(deps.len(), 3);
+    assert_eq!(deps[0].dependency_type, "re_export");
+    assert_eq!(deps[0].path, Some("./api".to_string()));
+    assert_eq!(deps[1].dependency_type, "re_export");
+    assert_eq!(deps[2].dependency_type, "type_re_export");
+    assert_eq!(deps[2].path, Some("./dto".to_string()));
+}
@@ -1,3 +1,13 @@
+pub mod api_surface_extractor;
+pub mod build_system_extractor;
+pub mod concurrency_extractor;
+pub mod entry_point_extractor;
+pub mod feature_flag_extractor;
+pub mod function_call_resolver;
+pub mod import_graph_extractor;
 pub mod language_processors;
+pub mod openapi_parser;
 pub mod original_document_extractor;
+pub mod source_reader;
 pub mod structure_extractor;
+pub mod test_example_extractor;
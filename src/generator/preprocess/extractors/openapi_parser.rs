@@ -0,0 +1,421 @@
+//! OpenAPI/Swagger规范解析 - 从项目中的接口规范文件直接提取权威的API端点定义
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use crate::config::Config;
+use crate::types::api_endpoint::ApiEndpoint;
+use crate::types::code::ParameterInfo;
+use log::{info, warn};
+
+/// 常见的OpenAPI/Swagger规范文件名
+const SPEC_FILE_NAMES: &[&str] = &[
+    "openapi.yaml",
+    "openapi.yml",
+    "openapi.json",
+    "swagger.yaml",
+    "swagger.yml",
+    "swagger.json",
+];
+
+/// OpenAPI中支持的HTTP方法
+const HTTP_METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "trace",
+];
+
+/// 查找项目中的OpenAPI/Swagger规范文件（最多向下搜索3层目录）
+pub fn find_spec_files(config: &Config) -> Vec<PathBuf> {
+    let mut spec_files: Vec<PathBuf> = WalkDir::new(&config.project_path)
+        .max_depth(3)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            !(entry.file_type().is_dir()
+                && entry.depth() > 0
+                && config
+                    .excluded_dirs
+                    .iter()
+                    .any(|excluded| excluded.to_lowercase() == name))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            SPEC_FILE_NAMES.contains(&name.as_str())
+        })
+        .map(|entry| entry.into_path())
+        .collect();
+    spec_files.sort();
+    spec_files
+}
+
+/// 加载项目中所有规范文件定义的API端点，解析失败的文件会被忽略
+pub fn load_spec_endpoints(config: &Config) -> Vec<ApiEndpoint> {
+    let mut endpoints = Vec::new();
+
+    for spec_file in find_spec_files(config) {
+        let source_location = spec_file
+            .strip_prefix(&config.project_path)
+            .unwrap_or(&spec_file)
+            .to_string_lossy()
+            .to_string();
+
+        match std::fs::read_to_string(&spec_file)
+            .map_err(|e| anyhow!(e))
+            .and_then(|content| parse_spec(&content, &source_location))
+        {
+            Ok(spec_endpoints) => {
+                info!(
+                    "   📘 从接口规范 {} 中解析到 {} 个API端点",
+                    source_location,
+                    spec_endpoints.len()
+                );
+                endpoints.extend(spec_endpoints);
+            }
+            Err(e) => {
+                warn!("   ⚠️ 解析接口规范 {} 失败: {}", source_location, e);
+            }
+        }
+    }
+
+    endpoints
+}
+
+/// 解析OpenAPI 3.x / Swagger 2.0文档（JSON或YAML格式）
+pub fn parse_spec(content: &str, source_location: &str) -> Result<Vec<ApiEndpoint>> {
+    let document: Value = if content.trim_start().starts_with('{') {
+        serde_json::from_str(content)?
+    } else {
+        yaml_to_json(serde_yaml::from_str(content)?)
+    };
+
+    if document.get("openapi").is_none() && document.get("swagger").is_none() {
+        return Err(anyhow!(
+            "缺少openapi/swagger版本声明，不是有效的接口规范文件"
+        ));
+    }
+
+    let Some(paths) = document.get("paths").and_then(|p| p.as_object()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut endpoints = Vec::new();
+    for (path, path_item) in paths {
+        // 路径级别的参数对该路径下所有操作生效
+        let shared_parameters = path_item
+            .get("parameters")
+            .map(parse_parameters)
+            .unwrap_or_default();
+
+        for method in HTTP_METHODS {
+            let Some(operation) = path_item.get(*method) else {
+                continue;
+            };
+
+            let mut parameters = shared_parameters.clone();
+            if let Some(operation_parameters) = operation.get("parameters") {
+                parameters.extend(parse_parameters(operation_parameters));
+            }
+            if let Some(request_body) = operation.get("requestBody") {
+                parameters.push(parse_request_body(request_body));
+            }
+
+            let handler = operation
+                .get("operationId")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+
+            let description = ["summary", "description"]
+                .iter()
+                .filter_map(|key| operation.get(*key).and_then(|v| v.as_str()))
+                .map(|s| s.trim().to_string())
+                .find(|s| !s.is_empty());
+
+            endpoints.push(ApiEndpoint {
+                method: method.to_uppercase(),
+                path: path.clone(),
+                handler,
+                file_path: source_location.to_string(),
+                line_number: find_line_number(content, path),
+                parameters,
+                return_type: operation.get("responses").and_then(format_responses),
+                description,
+                framework: Some("openapi".to_string()),
+            });
+        }
+    }
+
+    Ok(endpoints)
+}
+
+fn parse_parameters(parameters: &Value) -> Vec<ParameterInfo> {
+    let Some(parameters) = parameters.as_array() else {
+        return Vec::new();
+    };
+
+    parameters
+        .iter()
+        .map(|parameter| {
+            if let Some(reference) = parameter.get("$ref").and_then(|r| r.as_str()) {
+                return ParameterInfo {
+                    name: reference
+                        .rsplit('/')
+                        .next()
+                        .unwrap_or(reference)
+                        .to_string(),
+                    param_type: "ref".to_string(),
+                    is_optional: true,
+                    description: Some(reference.to_string()),
+                };
+            }
+
+            let location = parameter
+                .get("in")
+                .and_then(|v| v.as_str())
+                .unwrap_or("query");
+            // OpenAPI 3.x的类型定义在schema中，Swagger 2.0直接定义在参数上
+            let schema_type = parameter
+                .get("schema")
+                .map(schema_type_name)
+                .or_else(|| {
+                    parameter
+                        .get("type")
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                })
+                .unwrap_or_else(|| "any".to_string());
+
+            ParameterInfo {
+                name: parameter
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                param_type: format!("{} ({})", schema_type, location),
+                is_optional: !parameter
+                    .get("required")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(location == "path"),
+                description: parameter
+                    .get("description")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+            }
+        })
+        .collect()
+}
+
+fn parse_request_body(request_body: &Value) -> ParameterInfo {
+    let content_types = request_body
+        .get("content")
+        .and_then(|c| c.as_object())
+        .map(|content| {
+            content
+                .iter()
+                .map(|(media_type, media)| match media.get("schema") {
+                    Some(schema) => format!("{} {}", media_type, schema_type_name(schema)),
+                    None => media_type.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(" | ")
+        })
+        .unwrap_or_else(|| "any".to_string());
+
+    ParameterInfo {
+        name: "body".to_string(),
+        param_type: format!("{} (body)", content_types),
+        is_optional: !request_body
+            .get("required")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        description: request_body
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+    }
+}
+
+fn schema_type_name(schema: &Value) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+        return reference
+            .rsplit('/')
+            .next()
+            .unwrap_or(reference)
+            .to_string();
+    }
+    match schema.get("type").and_then(|t| t.as_str()) {
+        Some("array") => format!(
+            "array<{}>",
+            schema
+                .get("items")
+                .map(schema_type_name)
+                .unwrap_or_else(|| "any".to_string())
+        ),
+        Some(schema_type) => schema_type.to_string(),
+        None => "object".to_string(),
+    }
+}
+
+fn format_responses(responses: &Value) -> Option<String> {
+    let responses = responses.as_object()?;
+    let mut status_codes: Vec<_> = responses.iter().collect();
+    status_codes.sort_by(|a, b| a.0.cmp(b.0));
+
+    let formatted = status_codes
+        .into_iter()
+        .map(|(status, response)| {
+            let description = response
+                .get("description")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let schema = response
+                .get("content")
+                .and_then(|c| c.as_object())
+                .and_then(|content| content.values().find_map(|media| media.get("schema")))
+                .or_else(|| response.get("schema"))
+                .map(schema_type_name);
+            match schema {
+                Some(schema) => format!("{}: {} ({})", status, description, schema),
+                None => format!("{}: {}", status, description),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    (!formatted.is_empty()).then_some(formatted)
+}
+
+/// 定位路径定义在规范文件中的行号（从1开始，未找到时返回0）
+fn find_line_number(content: &str, path: &str) -> usize {
+    let candidate_keys = [
+        format!("{}:", path),
+        format!("'{}':", path),
+        format!("\"{}\"", path),
+    ];
+    content
+        .lines()
+        .position(|line| {
+            let trimmed = line.trim_start();
+            candidate_keys.iter().any(|key| trimmed.starts_with(key))
+        })
+        .map(|index| index + 1)
+        .unwrap_or(0)
+}
+
+/// 将YAML值转换为JSON值（YAML中的非字符串键，如状态码`200`，统一转换为字符串）
+fn yaml_to_json(value: serde_yaml::Value) -> Value {
+    match value {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(b) => Value::Bool(b),
+        serde_yaml::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::from(i)
+            } else if let Some(u) = n.as_u64() {
+                Value::from(u)
+            } else {
+                n.as_f64().map(Value::from).unwrap_or(Value::Null)
+            }
+        }
+        serde_yaml::Value::String(s) => Value::String(s),
+        serde_yaml::Value::Sequence(items) => {
+            Value::Array(items.into_iter().map(yaml_to_json).collect())
+        }
+        serde_yaml::Value::Mapping(mapping) => Value::Object(
+            mapping
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        serde_yaml::Value::String(s) => s,
+                        other => serde_yaml::to_string(&other)
+                            .map(|s| s.trim().to_string())
+                            .unwrap_or_default(),
+                    };
+                    (key, yaml_to_json(value))
+                })
+                .collect(),
+        ),
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL_OPENAPI: &str = r#"
+openapi: 3.0.0
+info:
+  title: User Service
+  version: 1.0.0
+paths:
+  /users/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+        schema:
+          type: integer
+    get:
+      operationId: getUser
+      summary: Get a user by id
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/User'
+        '404':
+          description: Not found
+  /users:
+    post:
+      summary: Create a user
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/NewUser'
+      responses:
+        '201':
+          description: Created
+"#;
+
+    #[test]
+    fn test_parse_minimal_openapi_into_endpoints() {
+        let endpoints = parse_spec(MINIMAL_OPENAPI, "openapi.yaml").unwrap();
+        assert_eq!(endpoints.len(), 2);
+
+        let get_user = endpoints.iter().find(|e| e.method == "GET").unwrap();
+        assert_eq!(get_user.path, "/users/{id}");
+        assert_eq!(get_user.handler, "getUser");
+        assert_eq!(get_user.description.as_deref(), Some("Get a user by id"));
+        assert_eq!(get_user.parameters.len(), 1);
+        assert_eq!(get_user.parameters[0].name, "id");
+        assert_eq!(get_user.parameters[0].param_type, "integer (path)");
+        assert!(!get_user.parameters[0].is_optional);
+        assert_eq!(
+            get_user.return_type.as_deref(),
+            Some("200: OK (User); 404: Not found")
+        );
+        assert_eq!(get_user.line_number, 7);
+
+        let create_user = endpoints.iter().find(|e| e.method == "POST").unwrap();
+        assert_eq!(create_user.path, "/users");
+        assert_eq!(create_user.handler, "POST /users");
+        assert_eq!(create_user.parameters[0].name, "body");
+        assert_eq!(
+            create_user.parameters[0].param_type,
+            "application/json NewUser (body)"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_non_openapi_document() {
+        assert!(parse_spec("name: not-a-spec\n", "openapi.yaml").is_err());
+    }
+}
@@ -1,16 +1,29 @@
 use crate::generator::context::GeneratorContext;
 use crate::types::original_document::OriginalDocument;
+use crate::utils::file_utils::read_text_with_limit;
 use anyhow::Result;
-use tokio::fs::read_to_string;
+use log::warn;
+
+/// 文档超出读取上限时追加的截断标记
+const TRUNCATION_MARKER: &str = "...[内容过长，已截断]";
 
 pub async fn extract(context: &GeneratorContext) -> Result<OriginalDocument> {
-    let readme = match read_to_string(context.config.project_path.join("README.md")).await {
-        Ok(content) => {
-            let trimmed_content = trim_markdown(&content);
-            Some(trimmed_content)
-        }
-        Err(_) => None,
-    };
+    let readme_path = context.config.project_path.join("README.md");
+    let readme =
+        match read_text_with_limit(&readme_path, context.config.max_document_read_bytes).await {
+            Ok(Some((content, truncated))) => {
+                let mut trimmed_content = trim_markdown(&content);
+                if truncated {
+                    warn!(
+                        "   ⚠️ README.md 超出读取上限（{}字节），已截断",
+                        context.config.max_document_read_bytes
+                    );
+                    trimmed_content.push_str(TRUNCATION_MARKER);
+                }
+                Some(trimmed_content)
+            }
+            Ok(None) | Err(_) => None,
+        };
     Ok(OriginalDocument { readme })
 }
 
@@ -0,0 +1,40 @@
+use crate::types::FileInfo;
+use crate::types::project_structure::ProjectStructure;
+use crate::utils::file_utils::read_texts_with_limit;
+use std::path::PathBuf;
+
+/// 预处理阶段一次性读取的源码文件，供入口识别、特性开关等静态提取器共享，避免每个提取器重复读取
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    /// 相对项目根目录的路径
+    pub path: PathBuf,
+    /// 文件扩展名，无扩展名时为空
+    pub extension: String,
+    pub content: String,
+}
+
+/// 并发读取项目中满足条件的源码文件，读取失败或内容为二进制的文件被跳过
+pub async fn read_source_files(
+    structure: &ProjectStructure,
+    max_bytes: u64,
+    wanted: impl Fn(&FileInfo) -> bool,
+) -> Vec<SourceFile> {
+    let files: Vec<&FileInfo> = structure.files.iter().filter(|file| wanted(file)).collect();
+    let paths: Vec<PathBuf> = files
+        .iter()
+        .map(|file| structure.root_path.join(&file.path))
+        .collect();
+    let contents = read_texts_with_limit(&paths, max_bytes).await;
+
+    files
+        .into_iter()
+        .zip(contents)
+        .filter_map(|(file, content)| {
+            Some(SourceFile {
+                path: file.path.clone(),
+                extension: file.extension.clone().unwrap_or_default(),
+                content: content?,
+            })
+        })
+        .collect()
+}
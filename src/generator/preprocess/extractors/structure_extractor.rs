@@ -1,33 +1,59 @@
 use crate::generator::context::GeneratorContext;
 use crate::generator::preprocess::agents::code_purpose_analyze::CodePurposeEnhancer;
 use crate::generator::preprocess::extractors::language_processors::LanguageProcessorManager;
+use crate::types::analysis_report::{AnalysisReport, FileAnalysisEntry, FileAnalysisStatus};
 use crate::types::code::{CodeDossier, CodePurpose, CodePurposeMapper};
+use crate::types::language_breakdown::{LanguageBreakdown, LanguageStat, OTHER_LANGUAGE};
 use crate::types::project_structure::ProjectStructure;
 use crate::types::{DirectoryInfo, FileInfo};
-use crate::utils::file_utils::{is_binary_file_path, is_test_directory, is_test_file};
+use crate::utils::file_utils::{
+    is_binary_file_content, is_binary_file_path, is_test_directory, is_test_file,
+    matches_hidden_whitelist,
+};
 use crate::utils::sources::read_code_source;
 use anyhow::Result;
 use futures::future::BoxFuture;
-use std::collections::HashMap;
+use log::warn;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fs::Metadata;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 大型数据目录中抽样参与分析的文件数量
+const LARGE_DIRECTORY_SAMPLE_SIZE: usize = 20;
 
 /// 项目结构提取器
 pub struct StructureExtractor {
     language_processor: LanguageProcessorManager,
     code_purpose_enhancer: CodePurposeEnhancer,
     context: GeneratorContext,
+    /// 仅分析这些文件（相对于项目根目录），用于`--since`变更范围文档模式
+    changed_files: Option<HashSet<PathBuf>>,
+    /// 扫描过程中被跳过的文件及原因
+    skipped_files: Mutex<Vec<FileAnalysisEntry>>,
 }
 
 impl StructureExtractor {
     pub fn new(context: GeneratorContext) -> Self {
         Self {
-            language_processor: LanguageProcessorManager::new(),
+            language_processor: LanguageProcessorManager::with_overrides(
+                &context.config.language_overrides,
+            )
+            .with_interface_limit(context.config.max_interfaces_per_file)
+            .with_comment_markers(&context.config.important_comment_markers),
             code_purpose_enhancer: CodePurposeEnhancer::new(),
             context,
+            changed_files: None,
+            skipped_files: Mutex::new(Vec::new()),
         }
     }
 
+    /// 将分析范围限制为指定的变更文件
+    pub fn with_changed_files(mut self, changed_files: Option<HashSet<PathBuf>>) -> Self {
+        self.changed_files = changed_files;
+        self
+    }
+
     /// 提取项目结构
     pub async fn extract_structure(&self, project_path: &Path) -> Result<ProjectStructure> {
         let cache_key = format!("structure_{}", project_path.display());
@@ -82,6 +108,74 @@ impl StructureExtractor {
         })
     }
 
+    /// 统计项目的语言构成，按代码行数计算占比
+    pub fn compute_language_breakdown(&self, structure: &ProjectStructure) -> LanguageBreakdown {
+        let max_bytes = self.context.config.max_document_read_bytes;
+        let files: Vec<(PathBuf, usize)> = structure
+            .files
+            .iter()
+            .map(|file| {
+                // 超出读取上限的文件只计入文件数
+                let lines = if file.size <= max_bytes {
+                    std::fs::read_to_string(structure.root_path.join(&file.path))
+                        .map(|content| content.lines().count())
+                        .unwrap_or(0)
+                } else {
+                    0
+                };
+                (file.path.clone(), lines)
+            })
+            .collect();
+        Self::summarize_languages(&self.language_processor, &files)
+    }
+
+    /// 依据语言处理器注册的扩展名汇总各语言的文件数与行数，无对应处理器的文件归为other
+    pub fn summarize_languages(
+        language_processor: &LanguageProcessorManager,
+        files: &[(PathBuf, usize)],
+    ) -> LanguageBreakdown {
+        let mut stats: HashMap<&str, (usize, usize)> = HashMap::new();
+        for (path, lines) in files {
+            let language = language_processor
+                .get_processor(path)
+                .map(|processor| processor.language_name())
+                .unwrap_or(OTHER_LANGUAGE);
+            let entry = stats.entry(language).or_default();
+            entry.0 += 1;
+            entry.1 += lines;
+        }
+
+        let total_files = files.len();
+        let total_lines: usize = files.iter().map(|(_, lines)| lines).sum();
+        let mut languages: Vec<LanguageStat> = stats
+            .into_iter()
+            .map(|(language, (files, lines))| {
+                let percentage = if total_lines > 0 {
+                    lines as f64 * 100.0 / total_lines as f64
+                } else {
+                    files as f64 * 100.0 / total_files as f64
+                };
+                LanguageStat {
+                    language: language.to_string(),
+                    files,
+                    lines,
+                    percentage,
+                }
+            })
+            .collect();
+        languages.sort_by(|a, b| {
+            b.percentage
+                .total_cmp(&a.percentage)
+                .then_with(|| a.language.cmp(&b.language))
+        });
+
+        LanguageBreakdown {
+            languages,
+            total_files,
+            total_lines,
+        }
+    }
+
     fn scan_directory<'a>(
         &'a self,
         current_path: &'a Path,
@@ -98,59 +192,124 @@ impl StructureExtractor {
                 return Ok(());
             }
 
-            let mut entries = tokio::fs::read_dir(current_path).await?;
+            // 目录项类型直接取自目录读取结果，无需为每个条目单独发起异步调用
+            let mut file_paths = Vec::new();
+            let mut dir_paths = Vec::new();
+            for entry in std::fs::read_dir(current_path)? {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                if file_type.is_file() {
+                    file_paths.push(entry.path());
+                } else if file_type.is_dir() {
+                    dir_paths.push(entry.path());
+                }
+            }
+            // 可复现模式下按路径顺序扫描，不依赖文件系统返回的目录项顺序
+            if self.context.config.deterministic {
+                file_paths.sort();
+                dir_paths.sort();
+            }
+
+            // 先按路径规则过滤，被排除的文件不计入目录文件数，也不会被抽样
+            let mut file_paths: Vec<PathBuf> = file_paths
+                .into_iter()
+                .filter(|path| match self.path_skip_reason(path, root_path) {
+                    Some((status, reason)) => {
+                        let relative_path = path.strip_prefix(root_path).unwrap_or(path);
+                        self.record_skipped(FileAnalysisEntry::new(relative_path, status, reason));
+                        false
+                    }
+                    None => true,
+                })
+                .collect();
+
+            // 单个目录的文件数量超出上限时视为大型数据目录（如数据导出），仅抽样代表性文件，避免逐一分析海量文件
+            let max_files_per_directory = self.context.config.max_files_per_directory;
+            let mut large_data_file_count = None;
+            if file_paths.len() > max_files_per_directory {
+                let total_files = file_paths.len();
+                file_paths = sample_representative_files(file_paths, LARGE_DIRECTORY_SAMPLE_SIZE);
+                let relative_path = current_path.strip_prefix(root_path).unwrap_or(current_path);
+                warn!(
+                    "⚠️ 大型数据目录 {} 包含 {} 个文件，仅抽样分析 {} 个代表性文件",
+                    relative_path.display(),
+                    total_files,
+                    file_paths.len()
+                );
+                let mut entry = FileAnalysisEntry::new(
+                    relative_path,
+                    FileAnalysisStatus::SkippedLargeDirectory,
+                    format!(
+                        "大型数据目录（{} 个文件），超过 max_files_per_directory（{}），仅抽样分析 {} 个代表性文件",
+                        total_files,
+                        max_files_per_directory,
+                        file_paths.len()
+                    ),
+                );
+                entry.path.push('/');
+                self.record_skipped(entry);
+                large_data_file_count = Some(total_files);
+            }
+
             let mut dir_file_count = 0;
             let mut dir_subdirectory_count = 0;
             let mut dir_total_size = 0;
 
-            while let Some(entry) = entries.next_entry().await? {
-                let path = entry.path();
-                let file_type = entry.file_type().await?;
-
-                if file_type.is_file() {
-                    // 检查是否应该忽略此文件
-                    if !self.should_ignore_file(&path)
-                        && let Ok(metadata) = std::fs::metadata(&path)
-                    {
-                        let file_info = self.create_file_info(&path, root_path, &metadata)?;
+            for path in file_paths {
+                // 检查文件大小与内容，只对抽样后的文件读取元数据与内容
+                if let Some((status, reason)) = self.content_skip_reason(&path) {
+                    let relative_path = path.strip_prefix(root_path).unwrap_or(&path);
+                    self.record_skipped(FileAnalysisEntry::new(relative_path, status, reason));
+                } else if let Ok(metadata) = std::fs::metadata(&path) {
+                    let file_info = self.create_file_info(&path, root_path, &metadata)?;
+
+                    // 更新统计信息
+                    if let Some(ext) = &file_info.extension {
+                        *file_types.entry(ext.clone()).or_insert(0) += 1;
+                    }
 
-                        // 更新统计信息
-                        if let Some(ext) = &file_info.extension {
-                            *file_types.entry(ext.clone()).or_insert(0) += 1;
-                        }
+                    let size_category = self.categorize_file_size(file_info.size);
+                    *size_distribution.entry(size_category).or_insert(0) += 1;
 
-                        let size_category = self.categorize_file_size(file_info.size);
-                        *size_distribution.entry(size_category).or_insert(0) += 1;
+                    dir_file_count += 1;
+                    dir_total_size += file_info.size;
 
-                        dir_file_count += 1;
-                        dir_total_size += file_info.size;
+                    files.push(file_info);
+                }
+            }
 
-                        files.push(file_info);
-                    }
-                } else if file_type.is_dir() {
-                    let dir_name = path
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string();
-
-                    // 跳过隐藏目录和常见的忽略目录
-                    if !self.should_ignore_directory(&dir_name) {
-                        dir_subdirectory_count += 1;
-
-                        // 递归扫描子目录
-                        self.scan_directory(
-                            &path,
-                            root_path,
-                            directories,
-                            files,
-                            file_types,
-                            size_distribution,
-                            current_depth + 1,
-                            max_depth,
-                        )
-                        .await?;
-                    }
+            for path in dir_paths {
+                let dir_name = path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+
+                // 跳过隐藏目录和常见的忽略目录
+                if let Some(reason) = self.directory_skip_reason(&dir_name, &path, root_path) {
+                    let relative_path = path.strip_prefix(root_path).unwrap_or(&path);
+                    let mut entry = FileAnalysisEntry::new(
+                        relative_path,
+                        FileAnalysisStatus::SkippedExcluded,
+                        reason,
+                    );
+                    entry.path.push('/');
+                    self.record_skipped(entry);
+                } else {
+                    dir_subdirectory_count += 1;
+
+                    // 递归扫描子目录
+                    self.scan_directory(
+                        &path,
+                        root_path,
+                        directories,
+                        files,
+                        file_types,
+                        size_distribution,
+                        current_depth + 1,
+                        max_depth,
+                    )
+                    .await?;
                 }
             }
 
@@ -167,6 +326,7 @@ impl StructureExtractor {
                     subdirectory_count: dir_subdirectory_count,
                     total_size: dir_total_size,
                     importance_score: 0.0, // 稍后计算
+                    large_data_file_count,
                 };
                 directories.push(dir_info);
             }
@@ -222,50 +382,74 @@ impl StructureExtractor {
         }
     }
 
-    fn should_ignore_directory(&self, dir_name: &str) -> bool {
+    /// 目录被整体跳过的原因，不跳过时返回None
+    fn directory_skip_reason(
+        &self,
+        dir_name: &str,
+        path: &Path,
+        root_path: &Path,
+    ) -> Option<String> {
         let config = &self.context.config;
         let dir_name_lower = dir_name.to_lowercase();
 
         // 检查Config中配置的排除目录
         for excluded_dir in &config.excluded_dirs {
             if dir_name_lower == excluded_dir.to_lowercase() {
-                return true;
+                return Some(format!("目录匹配 excluded_dirs 规则 `{}`", excluded_dir));
             }
         }
 
         // 检查是否为测试目录（如果不包含测试文件）
         if !config.include_tests && is_test_directory(dir_name) {
-            return true;
+            return Some("测试目录（include_tests 未开启）".to_string());
         }
 
-        // 检查隐藏目录
+        // 检查隐藏目录（白名单模式中的隐藏路径除外）
         if !config.include_hidden && dir_name.starts_with('.') {
-            return true;
+            let relative_path = path.strip_prefix(root_path).unwrap_or(path);
+            if !matches_hidden_whitelist(relative_path, &config.included_hidden_patterns) {
+                return Some("隐藏目录（include_hidden 未开启）".to_string());
+            }
         }
 
-        false
+        None
     }
 
-    fn should_ignore_file(&self, path: &Path) -> bool {
+    /// 仅根据路径判断文件被跳过的状态与原因（不访问文件内容），不跳过时返回None
+    fn path_skip_reason(
+        &self,
+        path: &Path,
+        root_path: &Path,
+    ) -> Option<(FileAnalysisStatus, String)> {
         let config = &self.context.config;
+        let excluded = |reason: String| Some((FileAnalysisStatus::SkippedExcluded, reason));
         let file_name = path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("")
             .to_lowercase();
 
-        let _path_str = path.to_string_lossy().to_lowercase();
+        // 变更范围模式下跳过未变更的文件
+        if let Some(changed_files) = &self.changed_files
+            && !changed_files.contains(path.strip_prefix(root_path).unwrap_or(path))
+        {
+            return excluded(format!(
+                "自 {} 以来未变更",
+                config.since_ref.as_deref().unwrap_or("指定的git引用")
+            ));
+        }
 
         // 检查排除的文件
         for excluded_file in &config.excluded_files {
-            if excluded_file.contains('*') {
+            let matched = if excluded_file.contains('*') {
                 // 简单的通配符匹配
                 let pattern = excluded_file.replace('*', "");
-                if file_name.contains(&pattern.to_lowercase()) {
-                    return true;
-                }
-            } else if file_name == excluded_file.to_lowercase() {
-                return true;
+                file_name.contains(&pattern.to_lowercase())
+            } else {
+                file_name == excluded_file.to_lowercase()
+            };
+            if matched {
+                return excluded(format!("匹配 excluded_files 规则 `{}`", excluded_file));
             }
         }
 
@@ -275,7 +459,7 @@ impl StructureExtractor {
                 .excluded_extensions
                 .contains(&extension.to_lowercase())
         {
-            return true;
+            return excluded(format!("扩展名 `{}` 在 excluded_extensions 中", extension));
         }
 
         // 检查包含的扩展名（如果指定了）
@@ -285,36 +469,122 @@ impl StructureExtractor {
                     .included_extensions
                     .contains(&extension.to_lowercase())
                 {
-                    return true;
+                    return excluded(format!(
+                        "扩展名 `{}` 不在 included_extensions 中",
+                        extension
+                    ));
                 }
             } else {
-                return true; // 没有扩展名且指定了包含列表
+                // 没有扩展名且指定了包含列表
+                return excluded("没有扩展名，且配置了 included_extensions".to_string());
             }
         }
 
         // 检查测试文件（如果不包含测试文件）
         if !config.include_tests && is_test_file(path) {
-            return true;
+            return excluded("测试文件（include_tests 未开启）".to_string());
         }
 
-        // 检查隐藏文件
+        // 检查隐藏文件（白名单模式中的隐藏路径除外）
         if !config.include_hidden && file_name.starts_with('.') {
-            return true;
+            let relative_path = path.strip_prefix(root_path).unwrap_or(path);
+            if !matches_hidden_whitelist(relative_path, &config.included_hidden_patterns) {
+                return excluded("隐藏文件（include_hidden 未开启）".to_string());
+            }
         }
 
+        // 检查二进制文件
+        if is_binary_file_path(path) {
+            return Some((
+                FileAnalysisStatus::SkippedBinary,
+                "二进制文件扩展名".to_string(),
+            ));
+        }
+
+        None
+    }
+
+    /// 根据文件大小与内容判断文件被跳过的状态与原因，不跳过时返回None
+    fn content_skip_reason(&self, path: &Path) -> Option<(FileAnalysisStatus, String)> {
+        let config = &self.context.config;
+
         // 检查文件大小
         if let Ok(metadata) = std::fs::metadata(path)
             && metadata.len() > config.max_file_size
         {
-            return true;
+            return Some((
+                FileAnalysisStatus::SkippedTooLarge,
+                format!(
+                    "文件大小 {} 字节超过 max_file_size（{} 字节）",
+                    metadata.len(),
+                    config.max_file_size
+                ),
+            ));
         }
 
-        // 检查二进制文件
-        if is_binary_file_path(path) {
-            return true;
+        // 无扩展名或扩展名未知的二进制文件，根据文件内容判断
+        if config.skip_binary_content && is_binary_file_content(path) {
+            return Some((
+                FileAnalysisStatus::SkippedBinary,
+                "文件内容为二进制数据".to_string(),
+            ));
+        }
+
+        None
+    }
+
+    /// 记录被跳过的文件或目录，供分析报告使用
+    fn record_skipped(&self, entry: FileAnalysisEntry) {
+        if let Ok(mut skipped_files) = self.skipped_files.lock() {
+            skipped_files.push(entry);
+        }
+    }
+
+    /// 生成已分析与被跳过文件的清单：扫描阶段跳过的文件附带具体原因，
+    /// 已发现但未入选核心文件的标记为skipped-not-core
+    pub fn build_analysis_report(
+        &self,
+        structure: &ProjectStructure,
+        core_codes: &[CodeDossier],
+    ) -> AnalysisReport {
+        let config = &self.context.config;
+        let mut files = self
+            .skipped_files
+            .lock()
+            .map(|skipped_files| skipped_files.clone())
+            .unwrap_or_default();
+
+        for file in &structure.files {
+            let entry = if core_codes.iter().any(|code| code.file_path == file.path) {
+                FileAnalysisEntry::new(
+                    &file.path,
+                    FileAnalysisStatus::Analyzed,
+                    format!("核心文件，重要性分数 {:.2}", file.importance_score),
+                )
+            } else {
+                let reason = if !file.is_core {
+                    format!(
+                        "重要性分数 {:.2}，未被识别为核心文件",
+                        file.importance_score
+                    )
+                } else if file.importance_score < config.min_importance_score {
+                    format!(
+                        "重要性分数 {:.2} 低于 min_importance_score（{:.2}）",
+                        file.importance_score, config.min_importance_score
+                    )
+                } else {
+                    format!(
+                        "重要性分数 {:.2}，超出 core_component_percentage（{}%）限定的核心文件数量",
+                        file.importance_score, config.core_component_percentage
+                    )
+                };
+                FileAnalysisEntry::new(&file.path, FileAnalysisStatus::SkippedNotCore, reason)
+            };
+            files.push(entry);
         }
 
-        false
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        AnalysisReport { files }
     }
 
     fn calculate_importance_scores(
@@ -337,6 +607,10 @@ impl StructureExtractor {
             if path_str.contains("config") || path_str.contains("setup") {
                 score += 0.1;
             }
+            // 数据库迁移脚本记录了数据结构的演进
+            if CodePurposeMapper::is_migration_path(&path_str, &file.name.to_lowercase()) {
+                score += 0.3;
+            }
 
             // 基于文件大小的权重
             if file.size > 1024 && file.size < 50 * 1024 {
@@ -361,6 +635,8 @@ impl StructureExtractor {
                     "gradle" | "pom" => score += 0.15,
                     "package" => score += 0.15,
                     "lock" => score += 0.05,
+                    // 数据库脚本与Schema定义
+                    "sql" | "prisma" => score += 0.1,
                     // 样式文件
                     "css" | "scss" | "sass" | "less" | "styl" => score += 0.1,
                     // 模板文件
@@ -400,6 +676,34 @@ impl StructureExtractor {
         }
     }
 
+    /// 筛选参与分析的核心文件
+    ///
+    /// 仅保留重要性分数不低于`min_importance_score`的核心文件，并按`core_component_percentage`
+    /// 限制数量（以项目文件总数为基数），两者取更严格的结果；结果按重要性分数降序排列。
+    pub fn select_core_files(
+        files: &[FileInfo],
+        min_importance_score: f64,
+        core_component_percentage: f64,
+    ) -> Vec<&FileInfo> {
+        let mut core_files: Vec<_> = files
+            .iter()
+            .filter(|f| f.is_core && f.importance_score >= min_importance_score)
+            .collect();
+
+        // 按重要性分数降序排列，确保最重要的组件优先处理
+        core_files.sort_by(|a, b| {
+            b.importance_score
+                .partial_cmp(&a.importance_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let percentage = core_component_percentage.clamp(0.0, 100.0);
+        let max_count = ((files.len() as f64 * percentage / 100.0).ceil() as usize).max(1);
+        core_files.truncate(max_count);
+
+        core_files
+    }
+
     /// 识别核心文件
     pub async fn identify_core_codes(
         &self,
@@ -408,14 +712,12 @@ impl StructureExtractor {
         let mut core_codes = Vec::new();
 
         // 基于重要性分数筛选核心文件
-        let mut core_files: Vec<_> = structure.files.iter().filter(|f| f.is_core).collect();
-
-        // 按重要性分数降序排列，确保最重要的组件优先处理
-        core_files.sort_by(|a, b| {
-            b.importance_score
-                .partial_cmp(&a.importance_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        let config = &self.context.config;
+        let core_files = Self::select_core_files(
+            &structure.files,
+            config.min_importance_score,
+            config.core_component_percentage,
+        );
 
         for file in core_files {
             let code_purpose = self.determine_code_purpose(file).await;
@@ -491,3 +793,319 @@ impl StructureExtractor {
         Ok(Vec::new())
     }
 }
+
+/// 从大型数据目录中抽样代表性文件：按扩展名分组后轮流选取，使每种文件类型都有样本
+fn sample_representative_files(mut paths: Vec<PathBuf>, sample_size: usize) -> Vec<PathBuf> {
+    paths.sort();
+    let mut groups: BTreeMap<String, VecDeque<PathBuf>> = BTreeMap::new();
+    for path in paths {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        groups.entry(extension).or_default().push_back(path);
+    }
+
+    let mut samples = Vec::with_capacity(sample_size);
+    while samples.len() < sample_size && !groups.is_empty() {
+        groups.retain(|_, group| {
+            if samples.len() < sample_size
+                && let Some(path) = group.pop_front()
+            {
+                samples.push(path);
+            }
+            !group.is_empty()
+        });
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::project_structure_formatter::ProjectStructureFormatter;
+    use std::path::PathBuf;
+
+    fn file_with_score(name: &str, importance_score: f64) -> FileInfo {
+        FileInfo {
+            path: PathBuf::from(format!("src/{}", name)),
+            name: name.to_string(),
+            size: 2048,
+            extension: Some("rs".to_string()),
+            is_core: importance_score > 0.5,
+            importance_score,
+            complexity_score: 0.0,
+            last_modified: None,
+        }
+    }
+
+    #[test]
+    fn test_min_importance_score_reduces_analyzed_set() {
+        let files = vec![
+            file_with_score("main.rs", 1.0),
+            file_with_score("lib.rs", 0.9),
+            file_with_score("service.rs", 0.8),
+            file_with_score("helper.rs", 0.6),
+            file_with_score("readme.rs", 0.2),
+        ];
+
+        let baseline = StructureExtractor::select_core_files(&files, 0.0, 100.0);
+        assert_eq!(baseline.len(), 4);
+
+        let filtered = StructureExtractor::select_core_files(&files, 0.85, 100.0);
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered[0].name, "main.rs");
+        assert_eq!(filtered[1].name, "lib.rs");
+
+        // 百分比上限更严格时以百分比为准
+        let capped = StructureExtractor::select_core_files(&files, 0.0, 20.0);
+        assert_eq!(capped.len(), 1);
+        assert_eq!(capped[0].name, "main.rs");
+    }
+
+    #[test]
+    fn test_project_under_cap_keeps_every_file_above_floor() {
+        let mut files = vec![
+            file_with_score("main.rs", 1.0),
+            file_with_score("lib.rs", 0.9),
+            file_with_score("service.rs", 0.7),
+        ];
+        files.extend((0..7).map(|i| file_with_score(&format!("fixture_{}.rs", i), 0.1)));
+
+        // 10个文件按20%计算上限为2个，按50%计算上限为5个，不低于分数下限的3个文件全部保留
+        let capped = StructureExtractor::select_core_files(&files, 0.6, 20.0);
+        assert_eq!(capped.len(), 2);
+        let under_cap = StructureExtractor::select_core_files(&files, 0.6, 50.0);
+        let names: Vec<&str> = under_cap.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["main.rs", "lib.rs", "service.rs"]);
+    }
+
+    #[test]
+    fn test_language_breakdown_of_mixed_project() {
+        let files = vec![
+            (PathBuf::from("src/main.rs"), 400),
+            (PathBuf::from("src/lib.rs"), 400),
+            (PathBuf::from("web/app.ts"), 150),
+            (PathBuf::from("README.md"), 50),
+            (PathBuf::from("Makefile"), 0),
+        ];
+
+        let breakdown =
+            StructureExtractor::summarize_languages(&LanguageProcessorManager::new(), &files);
+
+        let summary: Vec<_> = breakdown
+            .languages
+            .iter()
+            .map(|stat| (stat.language.as_str(), stat.files, stat.lines))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Rust", 2, 800),
+                ("TypeScript", 1, 150),
+                (OTHER_LANGUAGE, 2, 50)
+            ]
+        );
+        assert_eq!(breakdown.languages[0].percentage, 80.0);
+        let total: f64 = breakdown.languages.iter().map(|s| s.percentage).sum();
+        assert!((total - 100.0).abs() < 0.01);
+        assert_eq!(breakdown.primary_language().unwrap().language, "Rust");
+    }
+
+    #[tokio::test]
+    async fn test_extensionless_binary_file_is_skipped() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("main.py"), "print('hi')\n").unwrap();
+        std::fs::write(temp_dir.path().join("Makefile"), "all:\n\tpython main.py\n").unwrap();
+        // 无扩展名的编译产物
+        let mut artifact = b"\x7fELF\x02\x01\x01".to_vec();
+        artifact.extend_from_slice(&[0u8; 64]);
+        std::fs::write(temp_dir.path().join("app"), artifact).unwrap();
+
+        let mut config = crate::config::Config {
+            project_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config.clone()).unwrap();
+        let structure = StructureExtractor::new(context)
+            .extract_structure(temp_dir.path())
+            .await
+            .unwrap();
+        let mut names: Vec<&str> = structure.files.iter().map(|f| f.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Makefile", "main.py"]);
+
+        // 关闭内容检测后不再跳过
+        config.skip_binary_content = false;
+        let context = GeneratorContext::new(config).unwrap();
+        let structure = StructureExtractor::new(context)
+            .extract_structure(temp_dir.path())
+            .await
+            .unwrap();
+        assert!(structure.files.iter().any(|f| f.name == "app"));
+    }
+
+    #[tokio::test]
+    async fn test_analysis_report_lists_excluded_file_with_reason() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("src/generated.rs"), "pub fn g() {}\n").unwrap();
+        std::fs::write(root.join("src/huge.rs"), "x".repeat(2048)).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        std::fs::write(root.join("node_modules/pkg/index.js"), "1").unwrap();
+
+        let mut config = crate::config::Config {
+            project_path: root.to_path_buf(),
+            internal_path: root.join(".litho"),
+            max_file_size: 1024,
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        config.excluded_files.push("generated.rs".to_string());
+        let context = GeneratorContext::new(config.clone()).unwrap();
+        let extractor = StructureExtractor::new(context);
+        let structure = extractor.extract_structure(root).await.unwrap();
+        let report = extractor.build_analysis_report(&structure, &[]);
+
+        let entry = |path: &str| {
+            report
+                .files
+                .iter()
+                .find(|entry| entry.path == path)
+                .unwrap_or_else(|| panic!("清单中缺少 {}", path))
+        };
+        let generated = entry("src/generated.rs");
+        assert_eq!(generated.status, FileAnalysisStatus::SkippedExcluded);
+        assert_eq!(generated.reason, "匹配 excluded_files 规则 `generated.rs`");
+        assert_eq!(
+            entry("src/huge.rs").status,
+            FileAnalysisStatus::SkippedTooLarge
+        );
+        assert_eq!(
+            entry("node_modules/").status,
+            FileAnalysisStatus::SkippedExcluded
+        );
+        assert_eq!(
+            entry("src/main.rs").status,
+            FileAnalysisStatus::SkippedNotCore
+        );
+
+        let report_path = AnalysisReport::path(&config.internal_path);
+        report.save(&report_path).unwrap();
+        let saved = std::fs::read_to_string(report_path).unwrap();
+        assert!(saved.contains("\"status\": \"skipped-excluded\""));
+    }
+
+    #[tokio::test]
+    async fn test_huge_flat_directory_is_summarized() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::create_dir_all(root.join("dump")).unwrap();
+        for i in 0..300 {
+            std::fs::write(root.join(format!("dump/row_{:04}.csv", i)), "a,b\n").unwrap();
+        }
+        std::fs::write(root.join("dump/schema.json"), "{}").unwrap();
+        // 被排除的文件不计入目录文件数，也不会被抽样
+        for i in 0..50 {
+            std::fs::write(root.join(format!("dump/row_{:04}.tmp", i)), "").unwrap();
+        }
+
+        let mut config = crate::config::Config {
+            project_path: root.to_path_buf(),
+            max_files_per_directory: 100,
+            ..Default::default()
+        };
+        config.excluded_extensions.push("tmp".to_string());
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
+        let extractor = StructureExtractor::new(context);
+        let structure = extractor.extract_structure(root).await.unwrap();
+
+        let dump_files: Vec<&FileInfo> = structure
+            .files
+            .iter()
+            .filter(|file| file.path.starts_with("dump"))
+            .collect();
+        assert_eq!(dump_files.len(), LARGE_DIRECTORY_SAMPLE_SIZE);
+        assert!(dump_files.iter().any(|file| file.name == "schema.json"));
+        assert!(!dump_files.iter().any(|file| file.name.ends_with(".tmp")));
+        assert!(structure.files.iter().any(|file| file.name == "main.rs"));
+
+        let dump_dir = structure
+            .directories
+            .iter()
+            .find(|dir| dir.name == "dump")
+            .unwrap();
+        assert_eq!(dump_dir.large_data_file_count, Some(301));
+
+        let report = extractor.build_analysis_report(&structure, &[]);
+        let entry = report
+            .files
+            .iter()
+            .find(|entry| entry.path == "dump/")
+            .unwrap();
+        assert_eq!(entry.status, FileAnalysisStatus::SkippedLargeDirectory);
+        assert!(entry.reason.starts_with("大型数据目录（301 个文件）"));
+
+        let tree = ProjectStructureFormatter::format_as_tree(&structure);
+        assert!(tree.contains("dump  [大型数据目录：共 301 个文件，仅列出抽样的 20 个]"));
+        let directory_tree = ProjectStructureFormatter::format_as_directory_tree(&structure);
+        assert!(
+            directory_tree.contains("dump/  [大型数据目录：共 301 个文件，仅列出抽样的 20 个]")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_since_ref_limits_analysis_to_changed_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .arg("-C")
+                .arg(root)
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {:?} 执行失败", args);
+        };
+
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub fn a() {}\n").unwrap();
+        std::fs::write(root.join("src/util.rs"), "pub fn b() {}\n").unwrap();
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "dev@example.com"]);
+        git(&["config", "user.name", "dev"]);
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "initial"]);
+        git(&["tag", "v1.0"]);
+
+        // 已提交的修改、未提交的修改与新文件都属于变更
+        std::fs::write(root.join("src/lib.rs"), "pub fn a() -> u8 { 1 }\n").unwrap();
+        git(&["commit", "-q", "-am", "change lib"]);
+        std::fs::write(root.join("src/util.rs"), "pub fn b() -> u8 { 2 }\n").unwrap();
+        std::fs::write(root.join("src/new.rs"), "pub fn c() {}\n").unwrap();
+
+        let changed = crate::utils::git_changes::changed_files_since(root, "v1.0").unwrap();
+        let mut config = crate::config::Config {
+            project_path: root.to_path_buf(),
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
+        let structure = StructureExtractor::new(context)
+            .with_changed_files(Some(changed))
+            .extract_structure(root)
+            .await
+            .unwrap();
+
+        let mut names: Vec<&str> = structure.files.iter().map(|f| f.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["lib.rs", "new.rs", "util.rs"]);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::config::Config;
+use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::extractors::function_call_resolver::called_names;
+use crate::types::code::{CodeInsight, UsageExample};
+use crate::utils::file_utils::{is_test_directory, is_test_file, read_text_with_limit};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 每个接口最多附加的使用示例数量
+const MAX_EXAMPLES_PER_INTERFACE: usize = 3;
+
+/// 单个示例片段的最大字符数
+const MAX_SNIPPET_CHARS: usize = 200;
+
+/// 从测试文件中挖掘公共接口的调用示例并附加到对应的接口上，返回附加的示例数量
+///
+/// 测试文件只作为示例来源，不会加入代码洞察成为独立的文档组件。
+pub async fn attach_usage_examples(
+    context: &GeneratorContext,
+    insights: &mut [CodeInsight],
+) -> usize {
+    let config = &context.config;
+    let mut attached = 0;
+
+    for test_file in find_test_files(config) {
+        let Ok(Some((content, _))) =
+            read_text_with_limit(&test_file, config.max_document_read_bytes).await
+        else {
+            continue;
+        };
+        let relative_path = test_file
+            .strip_prefix(&config.project_path)
+            .unwrap_or(&test_file)
+            .to_string_lossy()
+            .replace('\\', "/");
+        attached += attach_examples_from_source(insights, &relative_path, &content);
+    }
+
+    attached
+}
+
+/// 查找项目中的测试源码文件
+fn find_test_files(config: &Config) -> Vec<PathBuf> {
+    let mut test_files: Vec<PathBuf> = WalkDir::new(&config.project_path)
+        .max_depth(config.max_depth as usize)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            !(entry.file_type().is_dir()
+                && entry.depth() > 0
+                && (name.starts_with('.')
+                    || config
+                        .excluded_dirs
+                        .iter()
+                        .any(|excluded| excluded.to_lowercase() == name)))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            let relative = path.strip_prefix(&config.project_path).unwrap_or(path);
+            is_test_source(relative)
+        })
+        .collect();
+    test_files.sort();
+    test_files
+}
+
+/// 判断路径是否为测试源码（测试目录或常见的测试文件命名约定）
+pub fn is_test_source(path: &Path) -> bool {
+    if is_test_file(path) {
+        return true;
+    }
+    if path
+        .parent()
+        .into_iter()
+        .flat_map(|parent| parent.components())
+        .any(|component| is_test_directory(&component.as_os_str().to_string_lossy()))
+    {
+        return true;
+    }
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    file_name == "tests.rs"
+        || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+        || file_name.ends_with("_test.go")
+        || file_name.ends_with("_test.py")
+        || file_name.ends_with("_spec.rb")
+        || (file_name.starts_with("test_") && file_name.ends_with(".py"))
+}
+
+/// 在测试源码中查找公共接口的调用，并作为使用示例附加到接口上，返回附加的示例数量
+pub fn attach_examples_from_source(
+    insights: &mut [CodeInsight],
+    test_file: &str,
+    content: &str,
+) -> usize {
+    let lines: Vec<&str> = content.lines().collect();
+    let line_calls: Vec<HashSet<&str>> = lines.iter().map(|line| called_names(line)).collect();
+    let mut attached = 0;
+
+    for insight in insights.iter_mut() {
+        for interface in insight.interfaces.iter_mut() {
+            if interface.visibility != "public"
+                || interface.usage_examples.len() >= MAX_EXAMPLES_PER_INTERFACE
+            {
+                continue;
+            }
+
+            for (index, line) in lines.iter().enumerate() {
+                if interface.usage_examples.len() >= MAX_EXAMPLES_PER_INTERFACE {
+                    break;
+                }
+                // 跳过同名函数的定义，只保留调用
+                if !line_calls[index].contains(interface.name.as_str()) {
+                    continue;
+                }
+
+                let snippet: String = line.trim().chars().take(MAX_SNIPPET_CHARS).collect();
+                if interface
+                    .usage_examples
+                    .iter()
+                    .any(|example| example.snippet == snippet)
+                {
+                    continue;
+                }
+                interface.usage_examples.push(UsageExample {
+                    snippet,
+                    file_path: test_file.to_string(),
+                    line_number: index + 1,
+                });
+                attached += 1;
+            }
+        }
+    }
+
+    attached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::code::{
+        CodeComplexity, CodeDossier, CodePurpose, InterfaceInfo, ParameterInfo,
+    };
+
+    fn insight_with_interfaces(interfaces: Vec<InterfaceInfo>) -> CodeInsight {
+        CodeInsight {
+            code_dossier: CodeDossier {
+                name: "math.rs".to_string(),
+                file_path: PathBuf::from("src/math.rs"),
+                source_summary: String::new(),
+                code_purpose: CodePurpose::Util,
+                importance_score: 0.5,
+                description: None,
+                functions: vec![],
+                interfaces: vec![],
+            },
+            detailed_description: String::new(),
+            responsibilities: vec![],
+            interfaces,
+            dependencies: vec![],
+            complexity_metrics: Some(CodeComplexity {
+                cyclomatic_complexity: 1.0,
+                lines_of_code: 10,
+                number_of_functions: 1,
+                number_of_classes: 0,
+            }),
+        }
+    }
+
+    fn interface(name: &str, visibility: &str) -> InterfaceInfo {
+        InterfaceInfo::new(
+            name.to_string(),
+            "function".to_string(),
+            visibility.to_string(),
+            vec![ParameterInfo {
+                name: "a".to_string(),
+                param_type: "i32".to_string(),
+                is_optional: false,
+                description: None,
+            }],
+            Some("i32".to_string()),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_public_function_called_in_test_gets_example() {
+        let mut insights = vec![insight_with_interfaces(vec![
+            interface("add_numbers", "public"),
+            interface("internal_helper", "private"),
+        ])];
+        let test_source = r#"
+use demo::math::add_numbers;
+
+fn add_numbers_helper() {}
+
+#[test]
+fn test_add() {
+    let sum = add_numbers(1, 2);
+    assert_eq!(internal_helper(sum), 3);
+}
+"#;
+
+        let attached =
+            attach_examples_from_source(&mut insights, "tests/math_test.rs", test_source);
+
+        assert_eq!(attached, 1);
+        let examples = &insights[0].interfaces[0].usage_examples;
+        assert_eq!(
+            examples,
+            &vec![UsageExample {
+                snippet: "let sum = add_numbers(1, 2);".to_string(),
+                file_path: "tests/math_test.rs".to_string(),
+                line_number: 8,
+            }]
+        );
+        assert!(insights[0].interfaces[1].usage_examples.is_empty());
+    }
+
+    #[test]
+    fn test_is_test_source() {
+        assert!(is_test_source(Path::new("tests/integration.rs")));
+        assert!(is_test_source(Path::new("src/utils/format.test.ts")));
+        assert!(is_test_source(Path::new("pkg/server_test.go")));
+        assert!(is_test_source(Path::new("app/test_models.py")));
+        assert!(!is_test_source(Path::new("src/main.rs")));
+    }
+}
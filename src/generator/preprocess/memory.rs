@@ -11,4 +11,12 @@ impl ScopedKeys {
     pub const PROJECT_STRUCTURE: &'static str = "project_structure";
     pub const CODE_INSIGHTS: &'static str = "code_insights";
     pub const RELATIONSHIPS: &'static str = "relationships";
+    pub const FEATURE_FLAGS: &'static str = "feature_flags";
+    pub const LANGUAGE_BREAKDOWN: &'static str = "language_breakdown";
+    pub const BUILD_SYSTEM: &'static str = "build_system";
+    pub const ENTRY_POINTS: &'static str = "entry_points";
+    pub const API_SURFACE: &'static str = "api_surface";
+    pub const DUPLICATION: &'static str = "duplication";
+    pub const CONCURRENCY: &'static str = "concurrency";
+    pub const API_SPEC_ENDPOINTS: &'static str = "api_spec_endpoints";
 }
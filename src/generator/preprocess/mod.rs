@@ -2,14 +2,24 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use tokio::time::Instant;
 
-use crate::generator::preprocess::extractors::original_document_extractor;
+use crate::generator::preprocess::extractors::language_processors::LanguageProcessorManager;
+use crate::generator::preprocess::extractors::{
+    api_surface_extractor, build_system_extractor, concurrency_extractor, entry_point_extractor,
+    feature_flag_extractor, function_call_resolver, import_graph_extractor, openapi_parser,
+    original_document_extractor, source_reader, test_example_extractor,
+};
 use crate::generator::preprocess::memory::{MemoryScope, ScopedKeys};
+use crate::types::analysis_report::{AnalysisReport, FileAnalysisStatus};
 use crate::types::original_document::OriginalDocument;
+use crate::utils::git_changes::changed_files_since;
 use crate::{
     generator::{
         context::GeneratorContext,
         preprocess::{
-            agents::{code_analyze::CodeAnalyze, relationships_analyze::RelationshipsAnalyze},
+            agents::{
+                code_analyze::CodeAnalyze, duplicate_detect,
+                relationships_analyze::RelationshipsAnalyze,
+            },
             extractors::structure_extractor::StructureExtractor,
         },
         types::Generator,
@@ -19,6 +29,7 @@ use crate::{
         project_structure::ProjectStructure,
     },
 };
+use log::{info, warn};
 
 pub mod agents;
 pub mod extractors;
@@ -63,17 +74,32 @@ impl Generator<PreprocessingResult> for PreProcessAgent {
     async fn execute(&self, context: GeneratorContext) -> Result<PreprocessingResult> {
         let start_time = Instant::now();
 
-        let structure_extractor = StructureExtractor::new(context.clone());
         let config = &context.config;
 
-        println!("🔍 开始项目预处理阶段...");
+        info!("🔍 开始项目预处理阶段...");
+
+        // 变更范围文档模式下，只分析自指定git引用以来变更的文件
+        let changed_files = match &config.since_ref {
+            Some(git_ref) => {
+                let changed_files = changed_files_since(&config.project_path, git_ref)?;
+                info!(
+                    "🔀 变更范围模式: 自 {} 以来共有 {} 个文件变更",
+                    git_ref,
+                    changed_files.len()
+                );
+                Some(changed_files)
+            }
+            None => None,
+        };
+        let structure_extractor =
+            StructureExtractor::new(context.clone()).with_changed_files(changed_files);
 
         // 1. 提取项目原始文档素材
-        println!("📁 提取项目原始文档素材...");
+        info!("📁 提取项目原始文档素材...");
         let original_document = original_document_extractor::extract(&context).await?;
 
         // 2. 提取项目结构
-        println!("📁 提取项目结构...");
+        info!("📁 提取项目结构...");
         let project_structure = structure_extractor
             .extract_structure(&config.project_path)
             .await?;
@@ -81,46 +107,195 @@ impl Generator<PreprocessingResult> for PreProcessAgent {
         // 🆕 显示项目规格统计
         display_project_stats(&project_structure, config);
 
-        println!(
+        info!(
             "   🔭 发现 {} 个文件，{} 个目录",
             project_structure.total_files, project_structure.total_directories
         );
 
+        // 统计项目语言构成
+        let language_breakdown = structure_extractor.compute_language_breakdown(&project_structure);
+        if let Some(primary) = language_breakdown.primary_language() {
+            info!(
+                "   🈯 主要语言: {} ({:.1}%)",
+                primary.language, primary.percentage
+            );
+        }
+
+        // 一次性并发读取各语言源码，供下列静态提取器共享
+        let language_processor =
+            LanguageProcessorManager::with_overrides(&config.language_overrides);
+        let source_files = source_reader::read_source_files(
+            &project_structure,
+            config.max_document_read_bytes,
+            |file| {
+                language_processor.get_processor(&file.path).is_some()
+                    || feature_flag_extractor::is_gate_source(
+                        file.extension.as_deref().unwrap_or(""),
+                    )
+                    || concurrency_extractor::is_concurrency_source(
+                        file.extension.as_deref().unwrap_or(""),
+                    )
+            },
+        )
+        .await;
+
+        // 识别程序入口，调用链在组件关系分析完成后追踪
+        info!("🚪 识别程序入口...");
+        let mut entry_points = entry_point_extractor::extract(&context, &source_files);
+
+        // 提取可配置的构建/运行时特性
+        info!("🚩 提取特性开关与环境变量...");
+        let feature_flags =
+            feature_flag_extractor::extract(&context, &project_structure, &source_files).await?;
+        info!(
+            "   发现 {} 个Cargo特性，{} 处特性门控",
+            feature_flags.cargo_features.len(),
+            feature_flags.gates.len()
+        );
+
+        // 识别构建系统与CI配置
+        info!("🏗️ 识别构建系统与CI配置...");
+        let build_system =
+            build_system_extractor::extract(&config.project_path, config.max_document_read_bytes)
+                .await;
+        info!(
+            "   发现 {} 个CI配置，{} 个构建工具配置，{} 个包管理器脚本",
+            build_system.ci.len(),
+            build_system.build_tools.len(),
+            build_system.package_scripts.len()
+        );
+
+        // 识别异步运行时、线程、通道与锁等并发原语
+        info!("🧵 识别并发模型...");
+        let concurrency = concurrency_extractor::extract(&source_files);
+        info!(
+            "   发现 {} 种并发模式，共 {} 处",
+            concurrency.by_pattern().len(),
+            concurrency.signals.len()
+        );
+
+        // 解析项目中的OpenAPI/Swagger接口规范，供边界分析使用
+        let spec_endpoints = openapi_parser::load_spec_endpoints(config);
+
         // 3. 识别核心组件
-        println!("🎯 识别主要的源码文件...");
+        info!("🎯 识别主要的源码文件...");
         let important_codes = structure_extractor
             .identify_core_codes(&project_structure)
             .await?;
 
-        println!("   识别出 {} 个主要的源码文件", important_codes.len());
+        info!("   识别出 {} 个主要的源码文件", important_codes.len());
+
+        // 输出已分析与被跳过文件的清单，便于排查过滤规则
+        let analysis_report =
+            structure_extractor.build_analysis_report(&project_structure, &important_codes);
+        let analysis_report_path = AnalysisReport::path(&config.internal_path);
+        match analysis_report.save(&analysis_report_path) {
+            Ok(()) => info!(
+                "   📋 分析清单已写入 {}（分析 {} 个，跳过 {} 个）",
+                analysis_report_path.display(),
+                analysis_report.count(FileAnalysisStatus::Analyzed),
+                analysis_report.files.len() - analysis_report.count(FileAnalysisStatus::Analyzed)
+            ),
+            Err(e) => warn!("   ⚠️ 分析清单写入失败: {}", e),
+        }
+
+        // 统计公开API表面，标记弃用、内部与不稳定的接口
+        let api_surface = api_surface_extractor::extract(
+            &context,
+            &important_codes,
+            &project_structure.root_path,
+        )
+        .await;
+        info!(
+            "   📐 公开接口 {} 个，其中 {} 个被标记为弃用或不稳定",
+            api_surface.public_items,
+            api_surface.flagged.len()
+        );
 
         // 4. 使用AI分析核心组件（如果未禁用）
-        let core_code_insights = if config.llm.disable_preset_tools {
-            println!("   ⚠️ LLM已禁用，跳过AI分析步骤");
+        let mut core_code_insights = if config.llm.offline {
+            warn!("   ⚠️ 离线模式，跳过AI分析步骤");
             Vec::new()
         } else {
-            println!("🤖 使用AI分析核心文件...");
+            info!("🤖 使用AI分析核心文件...");
             let code_analyze = CodeAnalyze::new();
             code_analyze
                 .execute(&context, &important_codes, &project_structure)
                 .await?
         };
 
+        // 从测试文件中挖掘公共接口的调用示例
+        if config.mine_test_examples {
+            info!("🧪 从测试文件中挖掘接口使用示例...");
+            let attached =
+                test_example_extractor::attach_usage_examples(&context, &mut core_code_insights)
+                    .await;
+            info!("   附加了 {} 个接口使用示例", attached);
+        }
+
+        // 基于向量嵌入识别疑似重复的核心文件（需要provider支持向量嵌入）
+        let duplication = if config.detect_duplicates && !core_code_insights.is_empty() {
+            info!("🧬 检测疑似重复的代码...");
+            match duplicate_detect::detect(
+                &context.llm_client,
+                &core_code_insights,
+                config.duplicate_similarity_threshold,
+            )
+            .await
+            {
+                Ok(report) => {
+                    info!("   发现 {} 组疑似重复的文件", report.groups.len());
+                    Some(report)
+                }
+                Err(e) => {
+                    warn!("   ⚠️ 重复代码检测失败，跳过: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         // 5. 分析组件关系（如果未禁用）
-        let relationships = if config.llm.disable_preset_tools {
-            println!("   ⚠️ LLM已禁用，跳过关系分析步骤");
-            RelationshipAnalysis::default()
+        let mut relationships = if config.llm.offline {
+            warn!("   ⚠️ 离线模式，基于导入语句静态分析组件关系");
+            import_graph_extractor::extract(&context, &important_codes, &project_structure).await?
         } else {
-            println!("🔗 分析组件关系...");
+            info!("🔗 分析组件关系...");
             let relationships_analyze = RelationshipsAnalyze::new();
             relationships_analyze
                 .execute(&context, &core_code_insights, &project_structure)
                 .await?
         };
 
+        // 基于已提取的接口名称在本地解析跨文件调用关系，补充LLM未识别的调用边
+        let call_edges = function_call_resolver::resolve_function_calls(&core_code_insights);
+        let resolved_count = call_edges.len();
+        for edge in call_edges {
+            let exists = relationships
+                .core_dependencies
+                .iter()
+                .any(|dep| dep.from == edge.from && dep.to == edge.to);
+            if !exists {
+                relationships.core_dependencies.push(edge);
+            }
+        }
+        info!("   🔗 本地解析出 {} 条函数调用关系", resolved_count);
+
+        // 沿依赖关系追踪各程序入口的运行时调用链
+        entry_point_extractor::trace_runtime_flow(&mut entry_points, &relationships);
+        for entry in &entry_points {
+            info!(
+                "   入口: {} ({})，追踪到 {} 条调用链路",
+                entry.file_path,
+                entry.language,
+                entry.flow.len()
+            );
+        }
+
         let processing_time = start_time.elapsed().as_secs_f64();
 
-        println!("✅ 项目预处理完成，耗时 {:.2}秒", processing_time);
+        info!("✅ 项目预处理完成，耗时 {:.2}秒", processing_time);
 
         // 6. 存储预处理结果到 Memory
         context
@@ -151,6 +326,64 @@ impl Generator<PreprocessingResult> for PreProcessAgent {
                 &original_document,
             )
             .await?;
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::FEATURE_FLAGS,
+                &feature_flags,
+            )
+            .await?;
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::LANGUAGE_BREAKDOWN,
+                &language_breakdown,
+            )
+            .await?;
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::BUILD_SYSTEM,
+                &build_system,
+            )
+            .await?;
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::ENTRY_POINTS,
+                &entry_points,
+            )
+            .await?;
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::API_SURFACE,
+                &api_surface,
+            )
+            .await?;
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::CONCURRENCY,
+                &concurrency,
+            )
+            .await?;
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::API_SPEC_ENDPOINTS,
+                &spec_endpoints,
+            )
+            .await?;
+        if let Some(duplication) = &duplication {
+            context
+                .store_to_memory(
+                    MemoryScope::PREPROCESS,
+                    ScopedKeys::DUPLICATION,
+                    duplication,
+                )
+                .await?;
+        }
 
         Ok(PreprocessingResult {
             original_document,
@@ -173,15 +406,18 @@ enum ProjectScale {
 
 /// 显示项目规格统计
 fn display_project_stats(structure: &ProjectStructure, config: &crate::config::Config) {
-    println!("\n📊 项目规格统计");
-    println!("├─ 文件数量: {}", structure.total_files);
-    println!("├─ 目录数量: {}", structure.total_directories);
+    info!("\n📊 项目规格统计");
+    info!("├─ 文件数量: {}", structure.total_files);
+    info!("├─ 目录数量: {}", structure.total_directories);
 
     let (total_size, total_lines) = calculate_stats(structure);
-    println!("├─ 总文件大小: {}", format_size(total_size));
-    println!("├─ 代码行数: {}", format_number(total_lines));
+    info!("├─ 总文件大小: {}", format_size(total_size));
+    info!(
+        "├─ 代码行数: {}",
+        config.target_language.format_number(total_lines)
+    );
     if structure.total_files > 0 {
-        println!(
+        info!(
             "└─ 平均文件大小: {}",
             format_size(total_size / structure.total_files as u64)
         );
@@ -227,18 +463,6 @@ fn format_size(bytes: u64) -> String {
     }
 }
 
-/// 格式化数字（添加千位分隔符）
-fn format_number(n: usize) -> String {
-    n.to_string()
-        .as_bytes()
-        .rchunks(3)
-        .rev()
-        .map(std::str::from_utf8)
-        .collect::<Result<Vec<&str>, _>>()
-        .unwrap()
-        .join(",")
-}
-
 /// 判定项目规模
 fn determine_scale(file_count: usize) -> ProjectScale {
     match file_count {
@@ -255,48 +479,48 @@ fn provide_recommendations(
     structure: &ProjectStructure,
     config: &crate::config::Config,
 ) {
-    println!();
+    info!("");
 
     match scale {
         ProjectScale::Small => {
-            println!("✅ 项目规模：小型");
-            println!("💡 预计处理时间：3-5 分钟");
+            info!("✅ 项目规模：小型");
+            info!("💡 预计处理时间：3-5 分钟");
         }
         ProjectScale::Medium => {
-            println!("⚠️  项目规模：中型");
-            println!("💡 预计处理时间：5-15 分钟");
-            println!("💡 建议：使用 --max-parallels 5 提高并发");
+            warn!("⚠️  项目规模：中型");
+            info!("💡 预计处理时间：5-15 分钟");
+            info!("💡 建议：使用 --max-parallels 5 提高并发");
         }
         ProjectScale::Large => {
-            println!("🔴 项目规模：大型");
-            println!("💡 预计处理时间：15-45 分钟");
-            println!("💡 建议：");
-            println!("   - 使用 --max-parallels 10 提高并发");
-            println!("   - 考虑排除非核心目录（examples, tests）");
-            println!("   - 可以分模块生成：deepwiki-rs -p ./submodule");
+            info!("🔴 项目规模：大型");
+            info!("💡 预计处理时间：15-45 分钟");
+            info!("💡 建议：");
+            info!("   - 使用 --max-parallels 10 提高并发");
+            info!("   - 考虑排除非核心目录（examples, tests）");
+            info!("   - 可以分模块生成：deepwiki-rs -p ./submodule");
         }
         ProjectScale::ExtraLarge => {
-            println!("🚨 项目规模：超大型");
-            println!("💡 预计处理时间：> 1 小时");
-            println!("⚠️  警告：可能遇到以下问题：");
-            println!("   - LLM 上下文窗口限制");
-            println!("   - API 调用次数过多");
-            println!("   - 处理时间过长");
-            println!("💡 强烈建议：");
-            println!("   - 按子系统分别生成文档");
-            println!("   - 配置更严格的过滤规则");
-            println!("   - 使用 included_extensions 只分析核心语言");
-            println!("   - 示例: deepwiki-rs -p ./core --max-parallels 15");
+            warn!("🚨 项目规模：超大型");
+            info!("💡 预计处理时间：> 1 小时");
+            warn!("⚠️  警告：可能遇到以下问题：");
+            info!("   - LLM 上下文窗口限制");
+            info!("   - API 调用次数过多");
+            info!("   - 处理时间过长");
+            info!("💡 强烈建议：");
+            info!("   - 按子系统分别生成文档");
+            info!("   - 配置更严格的过滤规则");
+            info!("   - 使用 included_extensions 只分析核心语言");
+            info!("   - 示例: deepwiki-rs -p ./core --max-parallels 15");
         }
     }
 
     // 检查当前配置并给出提示
     if structure.total_files > 500 && config.llm.max_parallels < 5 {
-        println!(
+        warn!(
             "\n⚠️  提示：当前 max_parallels = {}，建议增加到至少 5",
             config.llm.max_parallels
         );
     }
 
-    println!();
+    info!("");
 }
@@ -0,0 +1,238 @@
+use crate::cache::CacheManager;
+use crate::config::Config;
+use crate::generator::compose::memory::MemoryScope as ComposeScope;
+use crate::generator::compose::types::AgentType as ComposeAgent;
+use crate::generator::context::GeneratorContext;
+use crate::generator::research::agents::importance_recalibrator::ImportanceRecalibrator;
+use crate::generator::research::memory::MemoryScope as ResearchScope;
+use crate::generator::research::types::AgentType as ResearchAgent;
+use anyhow::{Result, bail};
+use log::info;
+
+/// 预处理阶段写入的缓存类别
+const PREPROCESS_CATEGORIES: &[&str] = &[
+    "structure",
+    "ai_code_insight",
+    "ai_code_purpose",
+    "ai_relationships_insights",
+];
+
+/// 强制重新生成时需要失效的缓存范围
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegenerateScope {
+    /// 清除全部缓存
+    All,
+    /// 只清除指定的缓存类别（缓存目录下的相对路径）
+    Categories(Vec<String>),
+}
+
+impl RegenerateScope {
+    /// 解析范围：`all`、`preprocess`、`research`、`compose`，或逗号分隔的智能体类型（如`Boundary,WorkflowResearcher`）
+    pub fn parse(value: &str) -> Result<Self> {
+        let mut categories: Vec<String> = Vec::new();
+        for item in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let normalized = item.to_lowercase().replace(['_', '-'], "");
+            let resolved = match normalized.as_str() {
+                "all" => return Ok(Self::All),
+                "preprocess" => PREPROCESS_CATEGORIES
+                    .iter()
+                    .map(|category| category.to_string())
+                    .collect(),
+                "research" => vec![ResearchScope::STUDIES_RESEARCH.to_string()],
+                "compose" => vec![ComposeScope::DOCUMENTATION.to_string()],
+                _ => match agent_category(&normalized) {
+                    Some(category) => vec![category],
+                    None => bail!("未知的重新生成范围: {}", item),
+                },
+            };
+            for category in resolved {
+                if !categories.contains(&category) {
+                    categories.push(category);
+                }
+            }
+        }
+
+        if categories.is_empty() {
+            Ok(Self::All)
+        } else {
+            Ok(Self::Categories(categories))
+        }
+    }
+}
+
+impl RegenerateScope {
+    /// 范围是否覆盖指定的类别（类别自身或其上级类别被选中均视为覆盖）
+    pub fn covers(&self, category: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Categories(categories) => categories.iter().any(|selected| {
+                category == selected
+                    || category
+                        .strip_prefix(selected.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            }),
+        }
+    }
+}
+
+/// 配置是否要求强制重新生成指定类别（如`studies_research/领域模块调研报告`）的结果
+pub fn forces_regeneration(config: &Config, category: &str) -> bool {
+    if !config.force_regenerate {
+        return false;
+    }
+    // 无法解析的范围在清除缓存时会报错，这里按全部重新生成处理
+    RegenerateScope::parse(&config.force_regenerate_scope)
+        .map(|scope| scope.covers(category))
+        .unwrap_or(true)
+}
+
+/// 将智能体类型名称映射为其缓存类别
+fn agent_category(normalized: &str) -> Option<String> {
+    let research = |agent: ResearchAgent| format!("{}/{}", ResearchScope::STUDIES_RESEARCH, agent);
+    let compose = |agent: ComposeAgent| format!("{}/{}", ComposeScope::DOCUMENTATION, agent);
+
+    let category = match normalized {
+        "systemcontextresearcher" => research(ResearchAgent::SystemContextResearcher),
+        "modulesummaryresearcher" => research(ResearchAgent::ModuleSummaryResearcher),
+        // 重要性再校准不调用LLM、每次运行都会重新执行，没有需要清除的缓存，仅接受该名称
+        "importancerecalibrator" => format!(
+            "{}/{}",
+            ResearchScope::STUDIES_RESEARCH,
+            ImportanceRecalibrator.step_name()
+        ),
+        "domainmodulesdetector" => research(ResearchAgent::DomainModulesDetector),
+        "architectureresearcher" => research(ResearchAgent::ArchitectureResearcher),
+        "workflowresearcher" => research(ResearchAgent::WorkflowResearcher),
+        "keymodulesinsight" => research(ResearchAgent::KeyModulesInsight),
+        "boundaryanalyzer" => research(ResearchAgent::BoundaryAnalyzer),
+        "overview" => compose(ComposeAgent::Overview),
+        "architecture" => compose(ComposeAgent::Architecture),
+        "workflow" => compose(ComposeAgent::Workflow),
+        "boundary" => compose(ComposeAgent::Boundary),
+        "codeindex" => compose(ComposeAgent::CodeIndex),
+        "buildsystem" => compose(ComposeAgent::BuildSystem),
+        _ => return None,
+    };
+    Some(category)
+}
+
+/// 按范围清除缓存
+pub async fn invalidate(cache_manager: &CacheManager, scope: &RegenerateScope) -> Result<()> {
+    match scope {
+        RegenerateScope::All => {
+            cache_manager.clear_all().await?;
+            info!("🗑️ 已清除全部缓存");
+        }
+        RegenerateScope::Categories(categories) => {
+            for category in categories {
+                if cache_manager.clear_category(category).await? {
+                    info!("🗑️ 已清除缓存: {}", category);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 强制重新生成时，按配置的范围清除缓存
+pub async fn invalidate_cache(context: &GeneratorContext) -> Result<()> {
+    let scope = RegenerateScope::parse(&context.config.force_regenerate_scope)?;
+    let cache_manager = context.cache_manager.read().await;
+    invalidate(&cache_manager, &scope).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CacheConfig;
+    use crate::llm::client::types::TokenUsage;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_scope() {
+        assert_eq!(RegenerateScope::parse("all").unwrap(), RegenerateScope::All);
+        assert_eq!(
+            RegenerateScope::parse("compose").unwrap(),
+            RegenerateScope::Categories(vec!["documentation".to_string()])
+        );
+        assert_eq!(
+            RegenerateScope::parse("Boundary, workflow_researcher").unwrap(),
+            RegenerateScope::Categories(vec![
+                format!("documentation/{}", ComposeAgent::Boundary),
+                format!("studies_research/{}", ResearchAgent::WorkflowResearcher),
+            ])
+        );
+        assert_eq!(
+            RegenerateScope::parse("ModuleSummaryResearcher").unwrap(),
+            RegenerateScope::Categories(vec![format!(
+                "studies_research/{}",
+                ResearchAgent::ModuleSummaryResearcher
+            )])
+        );
+        assert_eq!(
+            RegenerateScope::parse("build-system").unwrap(),
+            RegenerateScope::Categories(vec![format!(
+                "documentation/{}",
+                ComposeAgent::BuildSystem
+            )])
+        );
+        assert!(RegenerateScope::parse("ImportanceRecalibrator").is_ok());
+        assert!(RegenerateScope::parse("unknown").is_err());
+    }
+
+    #[test]
+    fn test_forces_regeneration_by_scope() {
+        let boundary = format!("studies_research/{}", ResearchAgent::BoundaryAnalyzer);
+        let mut config = Config::default();
+        assert!(!forces_regeneration(&config, &boundary));
+
+        config.force_regenerate = true;
+        assert!(forces_regeneration(&config, &boundary));
+
+        config.force_regenerate_scope = "compose".to_string();
+        assert!(!forces_regeneration(&config, &boundary));
+
+        config.force_regenerate_scope = "research".to_string();
+        assert!(forces_regeneration(&config, &boundary));
+
+        config.force_regenerate_scope = "WorkflowResearcher".to_string();
+        assert!(!forces_regeneration(&config, &boundary));
+        assert!(forces_regeneration(
+            &config,
+            &format!("studies_research/{}", ResearchAgent::WorkflowResearcher)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_compose_scope_preserves_preprocess_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_manager = CacheManager::new(CacheConfig {
+            enabled: true,
+            cache_dir: temp_dir.path().to_path_buf(),
+            expire_hours: 1,
+            category_expire_hours: HashMap::new(),
+        });
+        let compose_category = format!("documentation/{}/v1", ComposeAgent::Overview);
+        for category in ["ai_code_insight", compose_category.as_str()] {
+            cache_manager
+                .set_with_tokens(category, "prompt", category, TokenUsage::new(10, 5))
+                .await
+                .unwrap();
+        }
+
+        let scope = RegenerateScope::parse("compose").unwrap();
+        invalidate(&cache_manager, &scope).await.unwrap();
+
+        let preprocess: Option<String> = cache_manager
+            .get("ai_code_insight", "prompt")
+            .await
+            .unwrap();
+        let compose: Option<String> = cache_manager
+            .get(&compose_category, "prompt")
+            .await
+            .unwrap();
+        assert_eq!(preprocess.as_deref(), Some("ai_code_insight"));
+        assert!(compose.is_none());
+    }
+}
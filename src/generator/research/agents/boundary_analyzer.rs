@@ -1,14 +1,18 @@
+use crate::config::CustomCodePurpose;
 use crate::generator::preprocess::memory::{MemoryScope, ScopedKeys};
-use crate::generator::research::types::{AgentType, BoundaryAnalysisReport};
+use crate::generator::research::types::{APIBoundary, AgentType, BoundaryAnalysisReport};
 use crate::generator::{
     context::GeneratorContext,
     step_forward_agent::{
         AgentDataConfig, DataSource, FormatterConfig, LLMCallMode, PromptTemplate, StepForwardAgent,
     },
 };
-use crate::types::code::{CodeInsight, CodePurpose, ParameterInfo};
+use crate::types::api_endpoint::ApiEndpoint;
+use crate::types::code::{CodeInsight, CodePurpose};
+use crate::utils::token_estimator::TokenEstimator;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
+use log::info;
 
 /// 边界接口分析师 - 负责分析系统的外部调用边界，包括CLI、API、配置等接口
 #[derive(Default, Clone)]
@@ -33,7 +37,7 @@ impl StepForwardAgent for BoundaryAnalyzer {
                 DataSource::DEPENDENCY_ANALYSIS,
                 DataSource::ResearchResult(AgentType::SystemContextResearcher.to_string()),
             ],
-            optional_sources: vec![],
+            optional_sources: vec![DataSource::FEATURE_FLAGS],
         }
     }
 
@@ -87,70 +91,173 @@ impl StepForwardAgent for BoundaryAnalyzer {
         // 1. 筛选边界相关的代码洞察
         let boundary_insights = self.filter_boundary_code_insights(context).await?;
 
+        // 接口规范文件（OpenAPI/Swagger）中的端点定义最为权威，优先提供给模型
+        let spec_endpoints = self.spec_endpoints(context).await;
+        let spec_content = self.format_spec_endpoints(&spec_endpoints);
+
         if boundary_insights.is_empty() {
-            return Ok(Some(
-                "### 边界相关代码洞察\n未发现明显的边界接口相关代码。\n\n".to_string(),
-            ));
+            return Ok(Some(format!(
+                "{}### 边界相关代码洞察\n未发现明显的边界接口相关代码。\n\n",
+                spec_content
+            )));
         }
 
         // 2. 提取详细的 API 端点信息
         let api_endpoints = self.extract_api_endpoints(&boundary_insights).await?;
 
-        // 3. 格式化边界代码洞察
-        let mut formatted_content = self.format_boundary_insights(&boundary_insights);
-
-        // 4. 添加详细的 API 端点分析
-        if !api_endpoints.is_empty() {
-            formatted_content.push_str("#### API 端点详细分析\n\n");
-            for endpoint in &api_endpoints {
-                formatted_content.push_str(&format!(
-                    "**{} {}**\n- 定义位置: `{}:{}`\n- 处理函数: `{}`\n- 参数: {}\n- 返回类型: {}\n\n",
-                    endpoint.method,
-                    endpoint.path,
-                    endpoint.file_path,
-                    endpoint.line_number,
-                    endpoint.handler,
-                    endpoint.parameters.iter()
-                        .map(|p| format!("{}: {}", p.name, p.param_type))
-                        .collect::<Vec<_>>()
-                        .join(", "),
-                    endpoint.return_type.as_deref().unwrap_or("未知")
-                ));
-            }
-        }
+        // 3. 按token预算分配内容：接口规范最权威，始终保留；其次是按重要性排列的API端点，剩余预算用于边界代码洞察
+        let mut budget = PromptBudget::new(context.config.boundary_prompt_token_budget);
+        budget.consume(&spec_content);
+        let endpoint_content = self.format_api_endpoints(&api_endpoints, &mut budget);
+        let insight_content = self.format_boundary_insights(
+            &boundary_insights,
+            context.config.boundary_full_source_files,
+            &mut budget,
+        );
+
+        let mut formatted_content = spec_content;
+        formatted_content.push_str(&insight_content);
+        formatted_content.push_str(&endpoint_content);
 
         Ok(Some(formatted_content))
     }
 
+    /// 结果修正 - 使用接口规范中的端点定义覆盖模型推断的同名端点
+    async fn refine_output(
+        &self,
+        mut result: BoundaryAnalysisReport,
+        context: &GeneratorContext,
+    ) -> Result<BoundaryAnalysisReport> {
+        let spec_endpoints = self.spec_endpoints(context).await;
+        if !spec_endpoints.is_empty() {
+            merge_spec_endpoints(&mut result.api_boundaries, &spec_endpoints);
+        }
+        Ok(result)
+    }
+
     /// 后处理 - 输出分析摘要
     fn post_process(
         &self,
         result: &BoundaryAnalysisReport,
         _context: &GeneratorContext,
     ) -> Result<()> {
-        println!("✅ 边界接口分析完成:");
-        println!("   - CLI命令: {} 个", result.cli_boundaries.len());
-        println!("   - API接口: {} 个", result.api_boundaries.len());
-        println!("   - Router路由: {} 个", result.router_boundaries.len());
-        println!("   - 集成建议: {} 项", result.integration_suggestions.len());
-        println!("   - 置信度: {:.1}/10", result.confidence_score);
+        info!("✅ 边界接口分析完成:");
+        info!("   - CLI命令: {} 个", result.cli_boundaries.len());
+        info!("   - API接口: {} 个", result.api_boundaries.len());
+        info!("   - Router路由: {} 个", result.router_boundaries.len());
+        info!("   - 集成建议: {} 项", result.integration_suggestions.len());
+        info!("   - 置信度: {:.1}/10", result.confidence_score);
 
         Ok(())
     }
 }
 
-/// API 端点信息
-#[derive(Debug, Clone)]
-struct ApiEndpoint {
-    method: String,                 // GET, POST, etc.
-    path: String,                   // /api/users/:id
-    handler: String,                // 处理函数名
-    file_path: String,              // 定义位置
-    line_number: usize,             // 行号
-    parameters: Vec<ParameterInfo>, // 参数列表
-    return_type: Option<String>,    // 返回类型
-    #[allow(dead_code)]
-    framework: Option<String>, // 框架类型 (Actix, Axum, Rocket等)
+/// 边界分析提示词的token预算，按内容的优先级依次扣除
+struct PromptBudget {
+    estimator: TokenEstimator,
+    /// 剩余可用的token数量，None表示不限制
+    remaining: Option<usize>,
+}
+
+impl PromptBudget {
+    fn new(limit: usize) -> Self {
+        let estimator = TokenEstimator::new();
+        // 估算器对每段文本都计入固定开销，整体只需预留一次
+        let overhead = estimator.estimate_tokens("").estimated_tokens;
+        Self {
+            estimator,
+            remaining: (limit > 0).then(|| limit.saturating_sub(overhead)),
+        }
+    }
+
+    fn cost(&self, text: &str) -> usize {
+        self.estimator.estimate_tokens(text).estimated_tokens
+            - self.estimator.estimate_tokens("").estimated_tokens
+    }
+
+    /// 无论预算是否充足都需要保留的内容
+    fn consume(&mut self, text: &str) {
+        let cost = self.cost(text);
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_sub(cost);
+        }
+    }
+
+    /// 预算充足时扣除并返回true，否则不扣除并返回false
+    fn try_consume(&mut self, text: &str) -> bool {
+        let cost = self.cost(text);
+        match &mut self.remaining {
+            Some(remaining) if *remaining < cost => false,
+            Some(remaining) => {
+                *remaining -= cost;
+                true
+            }
+            None => true,
+        }
+    }
+}
+
+/// 边界代码洞察的呈现粒度
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InsightDetail {
+    /// 描述、职责、接口、依赖与源码摘要
+    Full,
+    /// 仅描述与接口名称
+    Summary,
+}
+
+/// 边界代码的分组标题与说明，顺序即提示词中的呈现顺序
+const BOUNDARY_GROUPS: [(&str, &str); 5] = [
+    (
+        "#### 入口点代码 (Entry)\n",
+        "这些代码通常包含CLI命令定义、主函数入口等：\n\n",
+    ),
+    (
+        "#### API/控制器代码 (API/Controller)\n",
+        "这些代码通常包含HTTP端点、API路由、控制器逻辑等：\n\n",
+    ),
+    (
+        "#### 配置相关代码 (Config)\n",
+        "这些代码通常包含配置结构体、参数定义、环境变量等：\n\n",
+    ),
+    (
+        "#### 路由相关代码 (Router)\n",
+        "这些代码通常包含路由定义、中间件、请求处理等：\n\n",
+    ),
+    (
+        "#### 自定义边界分类代码\n",
+        "这些代码属于配置中声明为系统边界的自定义分类：\n\n",
+    ),
+];
+
+/// 代码用途所属的边界分组
+fn boundary_group(purpose: &CodePurpose) -> Option<usize> {
+    match purpose {
+        CodePurpose::Entry => Some(0),
+        CodePurpose::Api | CodePurpose::Controller => Some(1),
+        CodePurpose::Config => Some(2),
+        CodePurpose::Router => Some(3),
+        CodePurpose::Custom(_) => Some(4),
+        _ => None,
+    }
+}
+
+/// 是否为边界相关的代码用途，配置为边界的自定义分类同样参与分析
+pub(crate) fn is_boundary_purpose(
+    purpose: &CodePurpose,
+    custom_purposes: &[CustomCodePurpose],
+) -> bool {
+    match purpose {
+        CodePurpose::Entry
+        | CodePurpose::Api
+        | CodePurpose::Config
+        | CodePurpose::Router
+        | CodePurpose::Controller => true,
+        CodePurpose::Custom(name) => custom_purposes
+            .iter()
+            .any(|custom| custom.boundary && &custom.name == name),
+        _ => false,
+    }
 }
 
 impl BoundaryAnalyzer {
@@ -273,6 +380,7 @@ impl BoundaryAnalyzer {
                         .unwrap_or(0),
                     parameters: Vec::new(),
                     return_type: None,
+                    description: None,
                     framework: Some("actix".to_string()),
                 });
             }
@@ -308,6 +416,7 @@ impl BoundaryAnalyzer {
                     .unwrap_or(0),
                 parameters: Vec::new(),
                 return_type: None,
+                description: None,
                 framework: Some("axum".to_string()),
             });
         }
@@ -358,6 +467,7 @@ impl BoundaryAnalyzer {
                         .unwrap_or(0),
                     parameters: Vec::new(),
                     return_type: None,
+                    description: None,
                     framework: Some("rocket".to_string()),
                 });
             }
@@ -397,6 +507,7 @@ impl BoundaryAnalyzer {
                     .unwrap_or(0),
                 parameters: Vec::new(),
                 return_type: None,
+                description: None,
                 framework: Some("express".to_string()),
             });
         }
@@ -447,6 +558,7 @@ impl BoundaryAnalyzer {
                         .unwrap_or(0),
                     parameters: Vec::new(),
                     return_type: None,
+                    description: None,
                     framework: Some("fastapi".to_string()),
                 });
             }
@@ -506,6 +618,7 @@ impl BoundaryAnalyzer {
                         .unwrap_or(0),
                     parameters: Vec::new(),
                     return_type: None,
+                    description: None,
                     framework: Some("spring".to_string()),
                 });
             }
@@ -542,6 +655,7 @@ impl BoundaryAnalyzer {
                                 .unwrap_or(0),
                             parameters: Vec::new(),
                             return_type: None,
+                            description: None,
                             framework: None,
                         });
                     }
@@ -552,6 +666,46 @@ impl BoundaryAnalyzer {
         endpoints
     }
 
+    /// 预处理阶段从接口规范文件（OpenAPI/Swagger）中解析出的端点
+    async fn spec_endpoints(&self, context: &GeneratorContext) -> Vec<ApiEndpoint> {
+        context
+            .get_from_memory::<Vec<ApiEndpoint>>(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::API_SPEC_ENDPOINTS,
+            )
+            .await
+            .unwrap_or_default()
+    }
+
+    /// 格式化接口规范中定义的端点
+    fn format_spec_endpoints(&self, endpoints: &[ApiEndpoint]) -> String {
+        if endpoints.is_empty() {
+            return String::new();
+        }
+
+        let mut content = String::from(
+            "### OpenAPI/Swagger 接口规范\n以下端点来自项目中的接口规范文件，请以此为准：\n\n",
+        );
+        for endpoint in endpoints {
+            content.push_str(&format!(
+                "**{} {}**\n- 定义位置: `{}:{}`\n- 说明: {}\n- 参数: {}\n- 响应: {}\n\n",
+                endpoint.method,
+                endpoint.path,
+                endpoint.file_path,
+                endpoint.line_number,
+                endpoint.description.as_deref().unwrap_or(&endpoint.handler),
+                endpoint
+                    .parameters
+                    .iter()
+                    .map(|p| format!("{}: {}", p.name, p.param_type))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                endpoint.return_type.as_deref().unwrap_or("未知")
+            ));
+        }
+        content
+    }
+
     /// 从接口信息中提取端点
     fn extract_endpoint_from_interface(
         &self,
@@ -574,6 +728,7 @@ impl BoundaryAnalyzer {
                     line_number: interface.line_number.unwrap_or(0),
                     parameters: interface.parameters.clone(),
                     return_type: interface.return_type.clone(),
+                    description: None,
                     framework: None,
                 });
             }
@@ -593,17 +748,11 @@ impl BoundaryAnalyzer {
             .ok_or_else(|| anyhow!("CODE_INSIGHTS not found in PREPROCESS memory"))?;
 
         // 筛选边界相关的代码
+        let custom_purposes = &context.config.custom_code_purposes;
         let boundary_insights: Vec<CodeInsight> = all_insights
             .into_iter()
             .filter(|insight| {
-                matches!(
-                    insight.code_dossier.code_purpose,
-                    CodePurpose::Entry
-                        | CodePurpose::Api
-                        | CodePurpose::Config
-                        | CodePurpose::Router
-                        | CodePurpose::Controller
-                )
+                is_boundary_purpose(&insight.code_dossier.code_purpose, custom_purposes)
             })
             .collect();
 
@@ -634,7 +783,7 @@ impl BoundaryAnalyzer {
             }
         }
 
-        println!(
+        info!(
             "📊 边界代码分布：Entry({}) API/Controller({}) Config({}) Router({})",
             entry_count, api_count, config_count, router_count
         );
@@ -642,76 +791,147 @@ impl BoundaryAnalyzer {
         Ok(sorted_insights)
     }
 
+    /// 格式化 API 端点详细分析，端点按所在文件的重要性排列，预算不足时省略靠后的端点
+    fn format_api_endpoints(&self, endpoints: &[ApiEndpoint], budget: &mut PromptBudget) -> String {
+        if endpoints.is_empty() {
+            return String::new();
+        }
+
+        let mut content = String::from("#### API 端点详细分析\n\n");
+        budget.consume(&content);
+        budget.consume(&omission_note(endpoints.len(), "个API端点"));
+        let mut omitted = 0;
+        for endpoint in endpoints {
+            let item = format!(
+                "**{} {}**\n- 定义位置: `{}:{}`\n- 处理函数: `{}`\n- 参数: {}\n- 返回类型: {}\n\n",
+                endpoint.method,
+                endpoint.path,
+                endpoint.file_path,
+                endpoint.line_number,
+                endpoint.handler,
+                endpoint
+                    .parameters
+                    .iter()
+                    .map(|p| format!("{}: {}", p.name, p.param_type))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                endpoint.return_type.as_deref().unwrap_or("未知")
+            );
+            if budget.try_consume(&item) {
+                content.push_str(&item);
+            } else {
+                omitted += 1;
+            }
+        }
+
+        if omitted > 0 {
+            info!("✂️ 边界分析提示词超出预算，省略了 {} 个API端点", omitted);
+            content.push_str(&omission_note(omitted, "个API端点"));
+        }
+        content
+    }
+
     /// 格式化边界代码洞察 - 专门的格式化逻辑
-    fn format_boundary_insights(&self, insights: &[CodeInsight]) -> String {
+    ///
+    /// 洞察已按重要性排序，前`full_source_files`个文件附带源码摘要，其余只提供摘要；
+    /// 预算不足时先将源码降级为摘要，仍不足时省略该文件
+    fn format_boundary_insights(
+        &self,
+        insights: &[CodeInsight],
+        full_source_files: usize,
+        budget: &mut PromptBudget,
+    ) -> String {
         let mut content = String::from("### 边界相关代码洞察\n");
+        budget.consume(&content);
+        budget.consume(&omission_note(insights.len(), "个重要性较低的边界文件"));
 
         // 按CodePurpose分组显示
-        let mut entry_codes = Vec::new();
-        let mut api_codes = Vec::new();
-        let mut config_codes = Vec::new();
-        let mut router_codes = Vec::new();
-
-        for insight in insights {
-            match insight.code_dossier.code_purpose {
-                CodePurpose::Entry => entry_codes.push(insight),
-                CodePurpose::Api => api_codes.push(insight),
-                CodePurpose::Controller => api_codes.push(insight),
-                CodePurpose::Config => config_codes.push(insight),
-                CodePurpose::Router => router_codes.push(insight),
-                _ => {}
+        let mut groups: [Vec<String>; BOUNDARY_GROUPS.len()] = Default::default();
+        for (index, (title, intro)) in BOUNDARY_GROUPS.iter().enumerate() {
+            if insights
+                .iter()
+                .any(|insight| boundary_group(&insight.code_dossier.code_purpose) == Some(index))
+            {
+                budget.consume(&format!("{}{}", title, intro));
             }
         }
 
-        if !entry_codes.is_empty() {
-            content.push_str("#### 入口点代码 (Entry)\n");
-            content.push_str("这些代码通常包含CLI命令定义、主函数入口等：\n\n");
-            for insight in entry_codes {
-                self.add_boundary_insight_item(&mut content, insight);
+        let mut downgraded = 0;
+        let mut omitted = 0;
+        for (rank, insight) in insights.iter().enumerate() {
+            let Some(group) = boundary_group(&insight.code_dossier.code_purpose) else {
+                continue;
+            };
+            let detail = if rank < full_source_files {
+                InsightDetail::Full
+            } else {
+                InsightDetail::Summary
+            };
+            let item = self.format_boundary_insight_item(insight, detail);
+            if budget.try_consume(&item) {
+                groups[group].push(item);
+                continue;
             }
-        }
-
-        if !api_codes.is_empty() {
-            content.push_str("#### API/控制器代码 (API/Controller)\n");
-            content.push_str("这些代码通常包含HTTP端点、API路由、控制器逻辑等：\n\n");
-            for insight in api_codes {
-                self.add_boundary_insight_item(&mut content, insight);
+            let summary = self.format_boundary_insight_item(insight, InsightDetail::Summary);
+            if detail == InsightDetail::Full && budget.try_consume(&summary) {
+                groups[group].push(summary);
+                downgraded += 1;
+            } else {
+                omitted += 1;
             }
         }
 
-        if !config_codes.is_empty() {
-            content.push_str("#### 配置相关代码 (Config)\n");
-            content.push_str("这些代码通常包含配置结构体、参数定义、环境变量等：\n\n");
-            for insight in config_codes {
-                self.add_boundary_insight_item(&mut content, insight);
+        for ((title, intro), items) in BOUNDARY_GROUPS.iter().zip(groups) {
+            if items.is_empty() {
+                continue;
+            }
+            content.push_str(title);
+            content.push_str(intro);
+            for item in items {
+                content.push_str(&item);
             }
         }
 
-        if !router_codes.is_empty() {
-            content.push_str("#### 路由相关代码 (Router)\n");
-            content.push_str("这些代码通常包含路由定义、中间件、请求处理等：\n\n");
-            for insight in router_codes {
-                self.add_boundary_insight_item(&mut content, insight);
-            }
+        if downgraded > 0 || omitted > 0 {
+            info!(
+                "✂️ 边界分析提示词超出预算，{} 个文件的源码降级为摘要，省略了 {} 个文件",
+                downgraded, omitted
+            );
+        }
+        if omitted > 0 {
+            content.push_str(&omission_note(omitted, "个重要性较低的边界文件"));
         }
 
         content.push('\n');
         content
     }
 
-    /// 添加单个边界代码洞察项
-    fn add_boundary_insight_item(&self, content: &mut String, insight: &CodeInsight) {
-        content.push_str(&format!(
+    /// 格式化单个边界代码洞察项
+    fn format_boundary_insight_item(&self, insight: &CodeInsight, detail: InsightDetail) -> String {
+        let mut content = format!(
             "**文件**: `{}` (重要性: {:.2}, 用途: {:?})\n",
             insight.code_dossier.file_path.to_string_lossy(),
             insight.code_dossier.importance_score,
             insight.code_dossier.code_purpose
-        ));
+        );
 
         if !insight.detailed_description.is_empty() {
             content.push_str(&format!("- **描述**: {}\n", insight.detailed_description));
         }
 
+        if detail == InsightDetail::Summary {
+            if !insight.interfaces.is_empty() {
+                let names: Vec<&str> = insight
+                    .interfaces
+                    .iter()
+                    .map(|interface| interface.name.as_str())
+                    .collect();
+                content.push_str(&format!("- **接口**: {}\n", names.join(", ")));
+            }
+            content.push('\n');
+            return content;
+        }
+
         if !insight.responsibilities.is_empty() {
             content.push_str(&format!("- **职责**: {:?}\n", insight.responsibilities));
         }
@@ -732,5 +952,225 @@ impl BoundaryAnalyzer {
         }
 
         content.push('\n');
+        content
+    }
+}
+
+/// 将规范中的端点合并到边界分析结果中，同一端点以规范定义为准
+pub fn merge_spec_endpoints(api_boundaries: &mut Vec<APIBoundary>, spec_endpoints: &[ApiEndpoint]) {
+    for endpoint in spec_endpoints {
+        let key = endpoint_key(&endpoint.method, &endpoint.path);
+        let existing = api_boundaries
+            .iter()
+            .position(|b| endpoint_key(&b.method, &b.endpoint) == key)
+            .map(|index| api_boundaries.remove(index));
+
+        let request_format = if endpoint.parameters.is_empty() {
+            existing.as_ref().and_then(|b| b.request_format.clone())
+        } else {
+            Some(
+                endpoint
+                    .parameters
+                    .iter()
+                    .map(|p| format!("{}: {}", p.name, p.param_type))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        };
+
+        api_boundaries.push(APIBoundary {
+            endpoint: endpoint.path.clone(),
+            method: endpoint.method.clone(),
+            description: endpoint
+                .description
+                .clone()
+                .or_else(|| existing.as_ref().map(|b| b.description.clone()))
+                .unwrap_or_else(|| endpoint.handler.clone()),
+            request_format,
+            response_format: endpoint
+                .return_type
+                .clone()
+                .or_else(|| existing.as_ref().and_then(|b| b.response_format.clone())),
+            authentication: existing.as_ref().and_then(|b| b.authentication.clone()),
+            source_location: format!("{}:{}", endpoint.file_path, endpoint.line_number),
+        });
+    }
+}
+
+/// 端点去重键：忽略方法大小写以及路径参数写法的差异（`{id}`、`:id`、`<id>`）
+fn endpoint_key(method: &str, path: &str) -> String {
+    let normalized_path = path
+        .trim_end_matches('/')
+        .split('/')
+        .map(|segment| {
+            if (segment.starts_with('{') && segment.ends_with('}'))
+                || (segment.starts_with('<') && segment.ends_with('>'))
+                || segment.starts_with(':')
+            {
+                "{}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{} {}", method.to_uppercase(), normalized_path)
+}
+
+/// 因预算省略内容时的提示
+fn omission_note(count: usize, unit: &str) -> String {
+    format!("> 受提示词token预算限制，省略了 {} {}\n\n", count, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::preprocess::extractors::openapi_parser::parse_spec;
+    use crate::types::code::{CodeComplexity, CodeDossier};
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn boundary_insight(
+        file_path: &str,
+        code_purpose: CodePurpose,
+        importance_score: f64,
+        source_summary: String,
+    ) -> CodeInsight {
+        CodeInsight {
+            code_dossier: CodeDossier {
+                name: file_path.rsplit('/').next().unwrap().to_string(),
+                file_path: PathBuf::from(file_path),
+                source_summary,
+                code_purpose,
+                importance_score,
+                description: None,
+                functions: Vec::new(),
+                interfaces: Vec::new(),
+            },
+            detailed_description: format!("{} 的边界代码", file_path),
+            responsibilities: Vec::new(),
+            interfaces: Vec::new(),
+            dependencies: Vec::new(),
+            complexity_metrics: Some(CodeComplexity {
+                cyclomatic_complexity: 0.0,
+                lines_of_code: 0,
+                number_of_functions: 0,
+                number_of_classes: 0,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_boundary_prompt_stays_within_budget_and_keeps_top_endpoints() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = crate::config::Config {
+            project_path: temp_dir.path().to_path_buf(),
+            boundary_full_source_files: 2,
+            boundary_prompt_token_budget: 4000,
+            ..Default::default()
+        };
+        let context = GeneratorContext::new(config).unwrap();
+
+        let filler = "let value = compute_something_expensive(input);\n".repeat(120);
+        let mut insights = vec![boundary_insight(
+            "src/api/users.rs",
+            CodePurpose::Api,
+            0.99,
+            format!(
+                "use axum::Router;\nRouter::new()\n    .route(\"/api/users\", get(list_users))\n    .route(\"/api/users\", post(create_user));\n{}",
+                filler
+            ),
+        )];
+        for index in 0..40 {
+            insights.push(boundary_insight(
+                &format!("src/handlers/handler_{:02}.rs", index),
+                CodePurpose::Controller,
+                0.9 - index as f64 * 0.01,
+                filler.clone(),
+            ));
+        }
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::CODE_INSIGHTS,
+                &insights,
+            )
+            .await
+            .unwrap();
+
+        let content = BoundaryAnalyzer
+            .provide_custom_prompt_content(&context)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let tokens = TokenEstimator::new()
+            .estimate_tokens(&content)
+            .estimated_tokens;
+        assert!(tokens <= 4000, "边界提示词估算 {} tokens，超出预算", tokens);
+        assert!(content.contains("**GET /api/users**"));
+        assert!(content.contains("**POST /api/users**"));
+        assert!(content.contains("`src/api/users.rs`"));
+        assert!(content.contains("省略了"));
+        assert!(!content.contains("`src/handlers/handler_39.rs`"));
+
+        // 不限制预算时包含全部边界文件
+        let mut config = context.config.clone();
+        config.boundary_prompt_token_budget = 0;
+        let context = GeneratorContext::new(config).unwrap();
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::CODE_INSIGHTS,
+                &insights,
+            )
+            .await
+            .unwrap();
+        let content = BoundaryAnalyzer
+            .provide_custom_prompt_content(&context)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(content.contains("`src/handlers/handler_39.rs`"));
+        assert!(!content.contains("省略了"));
+    }
+
+    #[test]
+    fn test_merge_prefers_spec_definition() {
+        let endpoints = parse_spec(
+            r#"
+openapi: 3.0.0
+paths:
+  /users/{id}:
+    get:
+      summary: Get a user by id
+      responses:
+        '200':
+          description: OK
+  /users:
+    post:
+      summary: Create a user
+"#,
+            "openapi.yaml",
+        )
+        .unwrap();
+        let mut api_boundaries = vec![APIBoundary {
+            endpoint: "/users/:id".to_string(),
+            method: "get".to_string(),
+            description: "从代码中推断的描述".to_string(),
+            request_format: None,
+            response_format: None,
+            authentication: Some("Bearer".to_string()),
+            source_location: "src/api/users.rs:10".to_string(),
+        }];
+
+        merge_spec_endpoints(&mut api_boundaries, &endpoints);
+
+        assert_eq!(api_boundaries.len(), 2);
+        let get_user = api_boundaries.iter().find(|b| b.method == "GET").unwrap();
+        assert_eq!(get_user.endpoint, "/users/{id}");
+        assert_eq!(get_user.description, "Get a user by id");
+        assert_eq!(get_user.authentication.as_deref(), Some("Bearer"));
+        assert_eq!(get_user.source_location, "openapi.yaml:4");
     }
 }
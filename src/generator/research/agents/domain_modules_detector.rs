@@ -8,6 +8,7 @@ use crate::generator::{
         AgentDataConfig, DataSource, FormatterConfig, LLMCallMode, PromptTemplate, StepForwardAgent,
     },
 };
+use log::info;
 
 /// 领域划分与顶层抽象模块研究员 - 识别High-Level-System领域架构与抽象模块，以及其内部关联关系。
 #[derive(Default)]
@@ -28,6 +29,8 @@ impl StepForwardAgent for DomainModulesDetector {
         AgentDataConfig {
             required_sources: vec![
                 DataSource::ResearchResult(AgentType::SystemContextResearcher.to_string()),
+                // 按目录汇总的模块摘要补充逐文件的代码洞察，便于识别模块边界
+                DataSource::ResearchResult(AgentType::ModuleSummaryResearcher.to_string()),
                 DataSource::DEPENDENCY_ANALYSIS,
                 DataSource::CODE_INSIGHTS,
             ],
@@ -65,18 +68,18 @@ impl StepForwardAgent for DomainModulesDetector {
         _context: &GeneratorContext,
     ) -> Result<()> {
         // 简化版存储逻辑
-        println!("✅ 领域架构分析完成:");
-        println!("   - 识别领域模块: {} 个", result.domain_modules.len());
+        info!("✅ 领域架构分析完成:");
+        info!("   - 识别领域模块: {} 个", result.domain_modules.len());
 
         let total_sub_modules: usize = result
             .domain_modules
             .iter()
             .map(|d| d.sub_modules.len())
             .sum();
-        println!("   - 子模块总数: {} 个", total_sub_modules);
-        println!("   - 领域关系: {} 个", result.domain_relations.len());
-        println!("   - 执行流程: {} 个", result.business_flows.len());
-        println!("   - 置信度: {:.1}/10", result.confidence_score);
+        info!("   - 子模块总数: {} 个", total_sub_modules);
+        info!("   - 领域关系: {} 个", result.domain_relations.len());
+        info!("   - 执行流程: {} 个", result.business_flows.len());
+        info!("   - 置信度: {:.1}/10", result.confidence_score);
 
         Ok(())
     }
@@ -0,0 +1,189 @@
+//! 重要性再校准 - 预处理阶段的重要性评分在系统上下文未知时给出，
+//! 系统上下文调研完成后，根据调研结果对各文件的引用次数提升被反复提及文件的评分
+
+use anyhow::Result;
+use log::info;
+
+use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::memory::{MemoryScope, ScopedKeys};
+use crate::generator::research::memory::MemoryRetriever;
+use crate::generator::research::types::AgentType;
+use crate::types::code::CodeInsight;
+
+/// 每次被引用提升的重要性分数
+const BOOST_PER_REFERENCE: f64 = 0.05;
+/// 计入提升的最大引用次数
+const MAX_COUNTED_REFERENCES: usize = 4;
+/// 通用的模块入口文件名，仅按完整路径统计引用，避免同名文件互相干扰
+const GENERIC_FILE_STEMS: &[&str] = &["mod", "index", "__init__", "lib", "main"];
+
+/// 重要性再校准步骤 - 不调用LLM，仅在配置启用时执行
+#[derive(Default)]
+pub struct ImportanceRecalibrator;
+
+impl ImportanceRecalibrator {
+    pub fn step_name(&self) -> String {
+        "重要性再校准".to_string()
+    }
+
+    /// 依据系统上下文调研结果更新内存中的代码洞察重要性评分，返回被提升的洞察数量
+    pub async fn execute(&self, context: &GeneratorContext) -> Result<usize> {
+        if !context.config.recalibrate_importance {
+            return Ok(0);
+        }
+        let Some(research) = context
+            .get_research(&AgentType::SystemContextResearcher.to_string())
+            .await
+        else {
+            return Ok(0);
+        };
+        let Some(mut insights) = context
+            .get_from_memory::<Vec<CodeInsight>>(MemoryScope::PREPROCESS, ScopedKeys::CODE_INSIGHTS)
+            .await
+        else {
+            return Ok(0);
+        };
+
+        let boosted = recalibrate_importance(&mut insights, &research.to_string());
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::CODE_INSIGHTS,
+                &insights,
+            )
+            .await?;
+        info!("✅ 重要性再校准完成，提升了 {} 个文件的重要性评分", boosted);
+        Ok(boosted)
+    }
+}
+
+/// 按调研文本中对各文件的引用次数提升重要性评分（上限1.0），返回被提升的洞察数量
+pub fn recalibrate_importance(insights: &mut [CodeInsight], research_text: &str) -> usize {
+    let mut boosted = 0;
+    for insight in insights.iter_mut() {
+        let references = reference_count(insight, research_text).min(MAX_COUNTED_REFERENCES);
+        if references == 0 {
+            continue;
+        }
+        let dossier = &mut insight.code_dossier;
+        let score = (dossier.importance_score + BOOST_PER_REFERENCE * references as f64).min(1.0);
+        if score > dossier.importance_score {
+            dossier.importance_score = score;
+            boosted += 1;
+        }
+    }
+    boosted
+}
+
+/// 调研文本中引用该文件的次数：通用入口文件按完整路径统计，其余按文件名统计
+fn reference_count(insight: &CodeInsight, research_text: &str) -> usize {
+    let file_path = &insight.code_dossier.file_path;
+    let is_generic = file_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_none_or(|stem| GENERIC_FILE_STEMS.contains(&stem));
+
+    let needle = if is_generic {
+        let path = file_path.to_string_lossy().replace('\\', "/");
+        path.strip_prefix("./").unwrap_or(&path).to_string()
+    } else {
+        match file_path.file_name().and_then(|name| name.to_str()) {
+            Some(name) => name.to_string(),
+            None => return 0,
+        }
+    };
+    if needle.is_empty() {
+        return 0;
+    }
+    research_text.matches(needle.as_str()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::research::types::{ProjectType, SystemBoundary, SystemContextReport};
+    use crate::types::code::{CodeComplexity, CodeDossier, CodePurpose};
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn insight(file_path: &str, importance_score: f64) -> CodeInsight {
+        CodeInsight {
+            code_dossier: CodeDossier {
+                name: file_path.to_string(),
+                file_path: PathBuf::from(file_path),
+                source_summary: String::new(),
+                code_purpose: CodePurpose::Util,
+                importance_score,
+                description: None,
+                functions: vec![],
+                interfaces: vec![],
+            },
+            detailed_description: String::new(),
+            responsibilities: vec![],
+            interfaces: vec![],
+            dependencies: vec![],
+            complexity_metrics: Some(CodeComplexity {
+                cyclomatic_complexity: 0.0,
+                lines_of_code: 0,
+                number_of_functions: 0,
+                number_of_classes: 0,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_referenced_by_system_context_is_boosted() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = crate::config::Config {
+            project_path: temp_dir.path().to_path_buf(),
+            recalibrate_importance: true,
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
+
+        let insights = vec![
+            insight("src/billing/ledger.rs", 0.3),
+            insight("src/utils/strings.rs", 0.3),
+        ];
+        context
+            .store_to_memory(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::CODE_INSIGHTS,
+                &insights,
+            )
+            .await
+            .unwrap();
+        let report = SystemContextReport {
+            project_name: "demo".to_string(),
+            project_description: "账务核心由 ledger.rs 实现，所有扣费都经过 ledger.rs".to_string(),
+            project_type: ProjectType::BackendService,
+            business_value: String::new(),
+            target_users: vec![],
+            external_systems: vec![],
+            system_boundary: SystemBoundary {
+                scope: String::new(),
+                included_components: vec!["src/billing/ledger.rs".to_string()],
+                excluded_components: vec![],
+            },
+            confidence_score: 8.0,
+        };
+        context
+            .store_research(
+                &AgentType::SystemContextResearcher.to_string(),
+                serde_json::to_value(&report).unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let boosted = ImportanceRecalibrator.execute(&context).await.unwrap();
+        assert_eq!(boosted, 1);
+
+        let insights = context
+            .get_from_memory::<Vec<CodeInsight>>(MemoryScope::PREPROCESS, ScopedKeys::CODE_INSIGHTS)
+            .await
+            .unwrap();
+        assert!(insights[0].code_dossier.importance_score > 0.3);
+        assert_eq!(insights[1].code_dossier.importance_score, 0.3);
+    }
+}
@@ -7,13 +7,15 @@ use crate::generator::{
     agent_executor::{AgentExecuteParams, extract},
     context::GeneratorContext,
     step_forward_agent::{
-        AgentDataConfig, DataSource, FormatterConfig, LLMCallMode, PromptTemplate, StepForwardAgent,
+        AgentDataConfig, DataSource, FormatterConfig, LLMCallMode, PromptTemplate,
+        StepForwardAgent, prompt_fingerprint,
     },
 };
 use crate::types::code::CodeInsight;
 use crate::utils::threads::do_parallel_with_limit;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
+use log::{info, warn};
 use std::collections::HashSet;
 
 // 按照领域模块的调研材料
@@ -72,9 +74,9 @@ impl KeyModulesInsight {
         &self,
         context: &GeneratorContext,
     ) -> Result<Vec<KeyModuleReport>> {
-        println!("🔍 开始多领域模块分析...");
+        info!("🔍 开始多领域模块分析...");
         let mut reports = vec![];
-        let max_parallels = context.config.llm.max_parallels;
+        let max_parallels = context.config.llm.effective_max_parallels();
 
         // 1. 获取领域模块数据
         let domain_modules = self.get_domain_modules(context).await?;
@@ -84,14 +86,14 @@ impl KeyModulesInsight {
         }
 
         let domain_names: Vec<String> = domain_modules.iter().map(|d| d.name.clone()).collect();
-        println!(
+        info!(
             "📋 发现{}个领域模块：{}",
             domain_modules.len(),
             domain_names.join("、")
         );
 
         // 2. 为每个领域模块进行并发分析
-        println!("🚀 启动并发分析，最大并发数：{}", max_parallels);
+        info!("🚀 启动并发分析，最大并发数：{}", max_parallels);
 
         // 创建并发任务
         let analysis_futures: Vec<_> = domain_modules
@@ -124,10 +126,10 @@ impl KeyModulesInsight {
                         .await?;
                     successful_analyses += 1;
                     reports.push(report);
-                    println!("✅ 领域模块分析：{} 分析完成并已存储", domain_name);
+                    info!("✅ 领域模块分析：{} 分析完成并已存储", domain_name);
                 }
                 Err(e) => {
-                    println!("⚠️ 领域模块分析：{} 分析失败: {}", domain_name, e);
+                    warn!("⚠️ 领域模块分析：{} 分析失败: {}", domain_name, e);
                     // 继续处理其他领域，不中断整个流程
                 }
             }
@@ -180,7 +182,7 @@ impl KeyModulesInsight {
         }
 
         if domain_paths.is_empty() {
-            println!("⚠️ 领域'{}'没有关联的代码路径", domain.name);
+            warn!("⚠️ 领域'{}'没有关联的代码路径", domain.name);
             return Ok(Vec::new());
         }
 
@@ -197,7 +199,7 @@ impl KeyModulesInsight {
             .take(50)
             .collect();
 
-        println!(
+        info!(
             "📁 为领域'{}'筛选到{}个相关代码文件",
             domain.name,
             filtered.len()
@@ -220,19 +222,26 @@ impl KeyModulesInsight {
         let (system_prompt, user_prompt) = self.build_domain_prompt(domain, &filtered_insights);
 
         // 3. 使用 agent_executor::extract 进行分析
+        let scope = format!(
+            "{}/{}/{}",
+            crate::generator::research::memory::MemoryScope::STUDIES_RESEARCH,
+            self.agent_type(),
+            domain.name
+        );
+        let fingerprint = prompt_fingerprint(
+            &scope,
+            &system_prompt,
+            self.template_version(),
+            &context.config,
+        );
         let params = AgentExecuteParams {
             prompt_sys: system_prompt,
             prompt_user: user_prompt,
-            cache_scope: format!(
-                "{}/{}/{}",
-                crate::generator::research::memory::MemoryScope::STUDIES_RESEARCH,
-                self.agent_type(),
-                domain.name
-            ),
+            cache_scope: format!("{}/{}", scope, fingerprint),
             log_tag: format!("{}领域分析", domain.name),
         };
 
-        println!("🤖 正在分析'{}'领域...", domain.name);
+        info!("🤖 正在分析'{}'领域...", domain.name);
         let mut report: KeyModuleReport = extract(context, params).await?;
 
         // 4. 设置领域上下文信息
@@ -241,7 +250,7 @@ impl KeyModulesInsight {
             report.module_name = format!("{}核心模块", domain.name);
         }
 
-        println!("✅ '{}'领域分析完成", domain.name);
+        info!("✅ '{}'领域分析完成", domain.name);
         Ok(report)
     }
 
@@ -1,6 +1,8 @@
 pub mod architecture_researcher;
 pub mod boundary_analyzer;
 pub mod domain_modules_detector;
+pub mod importance_recalibrator;
 pub mod key_modules_insight;
+pub mod module_summary_researcher;
 pub mod system_context_researcher;
 pub mod workflow_researcher;
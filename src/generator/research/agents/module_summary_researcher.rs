@@ -0,0 +1,256 @@
+//! 目录模块摘要 - 按目录汇总源码洞察，为领域模块识别等调研提供比逐文件洞察更精简的材料
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use log::info;
+
+use crate::generator::context::GeneratorContext;
+use crate::generator::preprocess::extractors::language_processors::visibility_rank;
+use crate::generator::preprocess::memory::{MemoryScope, ScopedKeys};
+use crate::generator::research::memory::MemoryRetriever;
+use crate::generator::research::types::{AgentType, ModuleSummary, ModuleSummaryReport};
+use crate::types::code::{CodeInsight, CodePurpose};
+
+/// 每个目录保留的关键文件数
+const MAX_KEY_FILES: usize = 5;
+/// 每个目录保留的职责描述数
+const MAX_RESPONSIBILITIES: usize = 3;
+/// 每个目录保留的公开接口数
+const MAX_PUBLIC_SURFACE: usize = 10;
+
+/// 目录模块摘要研究员 - 不调用LLM，直接基于代码洞察按目录生成摘要
+#[derive(Default)]
+pub struct ModuleSummaryResearcher;
+
+impl ModuleSummaryResearcher {
+    pub fn agent_type(&self) -> String {
+        AgentType::ModuleSummaryResearcher.to_string()
+    }
+
+    /// 生成目录模块摘要并存入研究结果
+    pub async fn execute(&self, context: &GeneratorContext) -> Result<ModuleSummaryReport> {
+        let insights = context
+            .get_from_memory::<Vec<CodeInsight>>(MemoryScope::PREPROCESS, ScopedKeys::CODE_INSIGHTS)
+            .await
+            .ok_or_else(|| {
+                anyhow!(
+                    "必需的数据源 {}:{} 不可用",
+                    MemoryScope::PREPROCESS,
+                    ScopedKeys::CODE_INSIGHTS
+                )
+            })?;
+
+        let report = summarize_directories(&insights);
+        context
+            .store_research(&self.agent_type(), serde_json::to_value(&report)?)
+            .await?;
+        info!("✅ 目录模块摘要完成，共 {} 个目录", report.modules.len());
+        Ok(report)
+    }
+}
+
+/// 将代码洞察按所在目录分组，为每个包含洞察的目录生成摘要
+pub fn summarize_directories(insights: &[CodeInsight]) -> ModuleSummaryReport {
+    let mut directories: BTreeMap<String, Vec<&CodeInsight>> = BTreeMap::new();
+    for insight in insights {
+        directories
+            .entry(directory_of(&insight.code_dossier.file_path))
+            .or_default()
+            .push(insight);
+    }
+
+    let modules = directories
+        .into_iter()
+        .map(|(directory, mut files)| {
+            files.sort_by(|a, b| {
+                b.code_dossier
+                    .importance_score
+                    .partial_cmp(&a.code_dossier.importance_score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            summarize_directory(directory, &files)
+        })
+        .collect();
+    ModuleSummaryReport { modules }
+}
+
+/// `files`已按重要性从高到低排序
+fn summarize_directory(directory: String, files: &[&CodeInsight]) -> ModuleSummary {
+    let mut responsibilities: Vec<String> = Vec::new();
+    for responsibility in files
+        .iter()
+        .take(MAX_KEY_FILES)
+        .flat_map(|insight| &insight.responsibilities)
+    {
+        if responsibilities.len() >= MAX_RESPONSIBILITIES {
+            break;
+        }
+        if !responsibilities.contains(responsibility) {
+            responsibilities.push(responsibility.clone());
+        }
+    }
+
+    let mut public_surface: Vec<String> = Vec::new();
+    for interface in files
+        .iter()
+        .flat_map(|insight| &insight.interfaces)
+        .filter(|interface| visibility_rank(&interface.visibility) == 0)
+    {
+        if public_surface.len() >= MAX_PUBLIC_SURFACE {
+            break;
+        }
+        if !public_surface.contains(&interface.name) {
+            public_surface.push(interface.name.clone());
+        }
+    }
+
+    ModuleSummary {
+        directory,
+        purpose: dominant_purpose(files).display_name().to_string(),
+        responsibilities,
+        key_files: files
+            .iter()
+            .take(MAX_KEY_FILES)
+            .map(|insight| file_label(&insight.code_dossier.file_path))
+            .collect(),
+        public_surface,
+        file_count: files.len(),
+    }
+}
+
+/// 目录内出现次数最多的代码用途，次数相同时取更重要文件的用途
+fn dominant_purpose(files: &[&CodeInsight]) -> CodePurpose {
+    let mut counts: Vec<(&CodePurpose, usize)> = Vec::new();
+    for insight in files {
+        let purpose = &insight.code_dossier.code_purpose;
+        match counts.iter_mut().find(|(known, _)| *known == purpose) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((purpose, 1)),
+        }
+    }
+
+    let mut dominant: Option<(&CodePurpose, usize)> = None;
+    for (purpose, count) in counts {
+        if dominant.is_none_or(|(_, best)| count > best) {
+            dominant = Some((purpose, count));
+        }
+    }
+    dominant
+        .map(|(purpose, _)| purpose.clone())
+        .unwrap_or_default()
+}
+
+fn directory_of(file_path: &Path) -> String {
+    match file_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => file_label(parent),
+        _ => ".".to_string(),
+    }
+}
+
+fn file_label(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::code::{CodeComplexity, CodeDossier, InterfaceInfo};
+    use std::path::PathBuf;
+
+    fn interface(name: &str, visibility: &str) -> InterfaceInfo {
+        InterfaceInfo {
+            name: name.to_string(),
+            interface_type: "function".to_string(),
+            visibility: visibility.to_string(),
+            parameters: vec![],
+            return_type: None,
+            description: None,
+            file_path: None,
+            line_number: None,
+            fields: vec![],
+            variants: vec![],
+            source_code: None,
+            usage_examples: vec![],
+        }
+    }
+
+    fn insight(
+        file_path: &str,
+        code_purpose: CodePurpose,
+        importance_score: f64,
+        responsibilities: &[&str],
+        interfaces: Vec<InterfaceInfo>,
+    ) -> CodeInsight {
+        CodeInsight {
+            code_dossier: CodeDossier {
+                name: file_path.to_string(),
+                file_path: PathBuf::from(file_path),
+                source_summary: String::new(),
+                code_purpose,
+                importance_score,
+                description: None,
+                functions: vec![],
+                interfaces: vec![],
+            },
+            detailed_description: String::new(),
+            responsibilities: responsibilities.iter().map(|r| r.to_string()).collect(),
+            interfaces,
+            dependencies: vec![],
+            complexity_metrics: Some(CodeComplexity {
+                cyclomatic_complexity: 0.0,
+                lines_of_code: 0,
+                number_of_functions: 0,
+                number_of_classes: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_each_directory_with_insights_gets_a_summary() {
+        let insights = vec![
+            insight(
+                "src/service/order.rs",
+                CodePurpose::Service,
+                0.6,
+                &["订单处理"],
+                vec![interface("create_order", "public")],
+            ),
+            insight(
+                "src/service/user.rs",
+                CodePurpose::Service,
+                0.9,
+                &["用户管理"],
+                vec![
+                    interface("load_user", "pub"),
+                    interface("hash_password", "private"),
+                ],
+            ),
+            insight("src/utils/strings.rs", CodePurpose::Util, 0.3, &[], vec![]),
+            insight("main.rs", CodePurpose::Entry, 1.0, &["启动程序"], vec![]),
+        ];
+
+        let report = summarize_directories(&insights);
+        let directories: Vec<&str> = report
+            .modules
+            .iter()
+            .map(|module| module.directory.as_str())
+            .collect();
+        assert_eq!(directories, vec![".", "src/service", "src/utils"]);
+
+        let service = &report.modules[1];
+        assert_eq!(service.file_count, 2);
+        assert_eq!(service.purpose, CodePurpose::Service.display_name());
+        assert_eq!(
+            service.key_files,
+            vec!["src/service/user.rs", "src/service/order.rs"]
+        );
+        assert_eq!(service.responsibilities, vec!["用户管理", "订单处理"]);
+        assert_eq!(service.public_surface, vec!["load_user", "create_order"]);
+
+        let utils = &report.modules[2];
+        assert_eq!(utils.key_files, vec!["src/utils/strings.rs"]);
+        assert!(utils.public_surface.is_empty());
+    }
+}
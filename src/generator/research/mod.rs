@@ -1,7 +1,8 @@
 // Multi-Agent 项目深度调研系统
 // A（宏观，C1） = SystemContextResearcher 这个项目是做什么的、功能概览、上下游 = README.md + structure + code_insights-top50
 
-// B（中观、C2）：DomainModulesDetector 高层设计视角下的领域模块都有哪些，这些都是做什么的 = A + structure + code_insights-top50 + relationship-top50
+// M（中观，C2）：ModuleSummaryResearcher 每个目录的用途、关键文件与公开接口 = code_insights（不调用LLM）
+// B（中观、C2）：DomainModulesDetector 高层设计视角下的领域模块都有哪些，这些都是做什么的 = A + M + structure + relationship-top50
 // C（中观，C2）: ArchitectureResearcher 架构设计是怎样的 = A + B
 // D（中观，C2）WorkflowResearcher 工作流程是怎样的 = A + B
 
@@ -11,6 +12,7 @@
 use crate::generator::context::GeneratorContext;
 use crate::generator::research::orchestrator::ResearchOrchestrator;
 use anyhow::Result;
+use log::warn;
 
 pub mod agents;
 pub mod memory;
@@ -19,8 +21,8 @@ pub mod types;
 
 /// 执行研究阶段
 pub async fn execute(context: &GeneratorContext) -> Result<()> {
-    if context.config.llm.disable_preset_tools {
-        println!("   ⚠️ LLM已禁用，跳过研究阶段");
+    if context.config.llm.offline {
+        warn!("   ⚠️ 离线模式，跳过研究阶段");
         return Ok(());
     }
 
@@ -1,13 +1,89 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
+use async_trait::async_trait;
 
+use crate::generator::compose::required_research;
 use crate::generator::context::GeneratorContext;
+use crate::generator::events::GeneratorEvent;
 use crate::generator::research::agents::architecture_researcher::ArchitectureResearcher;
 use crate::generator::research::agents::boundary_analyzer::BoundaryAnalyzer;
 use crate::generator::research::agents::domain_modules_detector::DomainModulesDetector;
+use crate::generator::research::agents::importance_recalibrator::ImportanceRecalibrator;
 use crate::generator::research::agents::key_modules_insight::KeyModulesInsight;
+use crate::generator::research::agents::module_summary_researcher::ModuleSummaryResearcher;
 use crate::generator::research::agents::system_context_researcher::SystemContextResearcher;
 use crate::generator::research::agents::workflow_researcher::WorkflowResearcher;
-use crate::generator::step_forward_agent::StepForwardAgent;
+use crate::generator::research::types::AgentType;
+use crate::generator::step_forward_agent::{DataSource, StepForwardAgent};
+use log::info;
+
+/// 可由编排器调度的调研步骤
+#[async_trait]
+trait ResearchStep: Send + Sync {
+    /// 步骤名称，与其产出的研究结果键一致
+    fn step_name(&self) -> String;
+
+    /// 依赖的其他研究结果
+    fn research_dependencies(&self) -> Vec<String>;
+
+    async fn run(&self, context: &GeneratorContext) -> Result<()>;
+}
+
+#[async_trait]
+impl<T> ResearchStep for T
+where
+    T: StepForwardAgent + Send + Sync,
+{
+    fn step_name(&self) -> String {
+        self.agent_type()
+    }
+
+    fn research_dependencies(&self) -> Vec<String> {
+        let config = self.data_config();
+        config
+            .required_sources
+            .into_iter()
+            .chain(config.optional_sources)
+            .filter_map(|source| match source {
+                DataSource::ResearchResult(agent_type) => Some(agent_type),
+                _ => None,
+            })
+            .collect()
+    }
+
+    async fn run(&self, context: &GeneratorContext) -> Result<()> {
+        self.execute(context).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl ResearchStep for ModuleSummaryResearcher {
+    fn step_name(&self) -> String {
+        self.agent_type()
+    }
+
+    fn research_dependencies(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    async fn run(&self, context: &GeneratorContext) -> Result<()> {
+        self.execute(context).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl ResearchStep for ImportanceRecalibrator {
+    fn step_name(&self) -> String {
+        ImportanceRecalibrator::step_name(self)
+    }
+
+    fn research_dependencies(&self) -> Vec<String> {
+        vec![AgentType::SystemContextResearcher.to_string()]
+    }
+
+    async fn run(&self, context: &GeneratorContext) -> Result<()> {
+        self.execute(context).await.map(|_| ())
+    }
+}
 
 /// 多智能体研究编排器
 #[derive(Default)]
@@ -16,47 +92,215 @@ pub struct ResearchOrchestrator;
 impl ResearchOrchestrator {
     /// 执行所有智能体的分析流程
     pub async fn execute_research_pipeline(&self, context: &GeneratorContext) -> Result<()> {
-        println!("🚀 开始执行Litho Studies Research调研流程...");
+        info!("🚀 开始执行Litho Studies Research调研流程...");
 
-        // 第一层：宏观分析（C1）
-        self.execute_agent("SystemContextResearcher", &SystemContextResearcher, context)
-            .await?;
+        let steps = Self::research_steps();
+        let selected = match required_research(context.config.profile) {
+            Some(required) => Self::select_steps(&steps, required),
+            None => vec![true; steps.len()],
+        };
+        for index in Self::execution_order(&steps)? {
+            if !selected[index] {
+                info!(
+                    "⏭️ 文档集预设 {} 不需要 {}，跳过",
+                    context.config.profile,
+                    steps[index].step_name()
+                );
+                continue;
+            }
+            self.execute_agent(steps[index].as_ref(), context).await?;
+        }
 
-        // 第二层：中观分析（C2）
-        self.execute_agent("DomainModulesDetector", &DomainModulesDetector, context)
-            .await?;
-        self.execute_agent("ArchitectureResearcher", &ArchitectureResearcher, context)
-            .await?;
-        self.execute_agent("WorkflowResearcher", &WorkflowResearcher, context)
-            .await?;
+        info!("✓ Litho Studies Research流程执行完毕");
 
-        // 第三层：微观分析（C3-C4）
-        self.execute_agent("KeyModulesInsight", &KeyModulesInsight, context)
-            .await?;
+        Ok(())
+    }
 
-        // 边界接口分析
-        self.execute_agent("BoundaryAnalyzer", &BoundaryAnalyzer, context)
-            .await?;
+    /// 参与调研的智能体，执行顺序由各自声明的研究结果依赖自动确定
+    fn research_steps() -> Vec<Box<dyn ResearchStep>> {
+        vec![
+            // 宏观分析（C1）
+            Box::new(SystemContextResearcher),
+            // 基于系统上下文校准代码洞察的重要性（需配置启用）
+            Box::new(ImportanceRecalibrator),
+            // 中观分析（C2）
+            Box::new(ModuleSummaryResearcher),
+            Box::new(DomainModulesDetector),
+            Box::new(ArchitectureResearcher),
+            Box::new(WorkflowResearcher),
+            // 微观分析（C3-C4）
+            Box::new(KeyModulesInsight),
+            // 边界接口分析
+            Box::new(BoundaryAnalyzer),
+        ]
+    }
 
-        println!("✓ Litho Studies Research流程执行完毕");
+    /// 选出产出所需调研结果的步骤，以及这些步骤传递依赖的步骤；重要性再校准会影响所有文档，始终保留
+    fn select_steps(steps: &[Box<dyn ResearchStep>], mut required: Vec<String>) -> Vec<bool> {
+        required.push(ImportanceRecalibrator.step_name());
+        let mut selected = vec![false; steps.len()];
+        while let Some(name) = required.pop() {
+            if let Some(index) = steps.iter().position(|step| step.step_name() == name)
+                && !selected[index]
+            {
+                selected[index] = true;
+                required.extend(steps[index].research_dependencies());
+            }
+        }
+        selected
+    }
 
-        Ok(())
+    fn execution_order(steps: &[Box<dyn ResearchStep>]) -> Result<Vec<usize>> {
+        let graph: Vec<(String, Vec<String>)> = steps
+            .iter()
+            .map(|step| (step.step_name(), step.research_dependencies()))
+            .collect();
+        resolve_execution_order(&graph)
     }
 
     /// 执行单个智能体
-    async fn execute_agent<T>(
+    async fn execute_agent(
         &self,
-        name: &str,
-        agent: &T,
+        agent: &dyn ResearchStep,
         context: &GeneratorContext,
-    ) -> Result<()>
-    where
-        T: StepForwardAgent + Send + Sync,
-    {
-        println!("🤖 执行 {} 智能体分析...", name);
-
-        agent.execute(context).await?;
-        println!("✓ {} 分析完成", name);
+    ) -> Result<()> {
+        let name = agent.step_name();
+        info!("🤖 执行 {} 智能体分析...", name);
+        context.emit(GeneratorEvent::AgentStarted {
+            agent: name.clone(),
+        });
+
+        agent.run(context).await?;
+        info!("✓ {} 分析完成", name);
+        context.emit(GeneratorEvent::AgentFinished { agent: name });
         Ok(())
     }
 }
+
+/// 按依赖关系对调研步骤做拓扑排序，返回执行顺序（步骤下标）
+///
+/// 依赖均已满足的步骤按注册顺序执行；依赖不在步骤列表中的研究结果视为外部提供，
+/// 由智能体执行时自行校验；存在循环依赖时返回错误。
+pub fn resolve_execution_order(steps: &[(String, Vec<String>)]) -> Result<Vec<usize>> {
+    let mut order: Vec<usize> = Vec::with_capacity(steps.len());
+    let mut done = vec![false; steps.len()];
+
+    while order.len() < steps.len() {
+        let ready = (0..steps.len()).find(|&index| {
+            !done[index]
+                && steps[index].1.iter().all(|dependency| {
+                    steps
+                        .iter()
+                        .enumerate()
+                        .all(|(other, (name, _))| name != dependency || done[other])
+                })
+        });
+        match ready {
+            Some(index) => {
+                done[index] = true;
+                order.push(index);
+            }
+            None => {
+                let pending: Vec<&str> = steps
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !done[*index])
+                    .map(|(_, (name, _))| name.as_str())
+                    .collect();
+                bail!("调研智能体之间存在循环依赖: {}", pending.join(", "));
+            }
+        }
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(name: &str, dependencies: &[&str]) -> (String, Vec<String>) {
+        (
+            name.to_string(),
+            dependencies.iter().map(|d| d.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_dependency_chain_executes_in_order() {
+        // 按依赖链的逆序注册
+        let steps = vec![
+            step("c", &["b"]),
+            step("b", &["a"]),
+            step("independent", &["external"]),
+            step("a", &[]),
+        ];
+
+        let order = resolve_execution_order(&steps).unwrap();
+
+        let names: Vec<&str> = order.iter().map(|&i| steps[i].0.as_str()).collect();
+        assert_eq!(names, vec!["independent", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_dependency_cycle_is_an_error() {
+        let steps = vec![step("a", &["c"]), step("b", &["a"]), step("c", &["b"])];
+
+        let err = resolve_execution_order(&steps).unwrap_err();
+        assert!(err.to_string().contains("循环依赖"));
+    }
+
+    #[test]
+    fn test_registered_research_agents_are_ordered_by_dependencies() {
+        let steps = ResearchOrchestrator::research_steps();
+        let order = ResearchOrchestrator::execution_order(&steps).unwrap();
+
+        let position = |agent: AgentType| {
+            order
+                .iter()
+                .position(|&i| steps[i].step_name() == agent.to_string())
+                .unwrap()
+        };
+        assert_eq!(position(AgentType::SystemContextResearcher), 0);
+        assert!(
+            position(AgentType::ModuleSummaryResearcher)
+                < position(AgentType::DomainModulesDetector)
+        );
+        assert!(
+            position(AgentType::DomainModulesDetector) < position(AgentType::KeyModulesInsight)
+        );
+        assert!(
+            position(AgentType::DomainModulesDetector) < position(AgentType::WorkflowResearcher)
+        );
+    }
+
+    #[test]
+    fn test_executive_profile_selects_only_overview_research() {
+        let steps = ResearchOrchestrator::research_steps();
+        let required = required_research(crate::config::DocumentProfile::Executive).unwrap();
+
+        let selected = ResearchOrchestrator::select_steps(&steps, required);
+
+        let selected_names: Vec<String> = steps
+            .iter()
+            .zip(&selected)
+            .filter(|(_, selected)| **selected)
+            .map(|(step, _)| step.step_name())
+            .collect();
+        for agent in [
+            AgentType::SystemContextResearcher,
+            AgentType::ModuleSummaryResearcher,
+            AgentType::DomainModulesDetector,
+        ] {
+            assert!(selected_names.contains(&agent.to_string()));
+        }
+        for agent in [
+            AgentType::ArchitectureResearcher,
+            AgentType::WorkflowResearcher,
+            AgentType::KeyModulesInsight,
+            AgentType::BoundaryAnalyzer,
+        ] {
+            assert!(!selected_names.contains(&agent.to_string()));
+        }
+    }
+}
@@ -6,6 +6,7 @@ use std::fmt::Display;
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AgentType {
     SystemContextResearcher,
+    ModuleSummaryResearcher,
     DomainModulesDetector,
     ArchitectureResearcher,
     WorkflowResearcher,
@@ -17,6 +18,7 @@ impl Display for AgentType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = match self {
             AgentType::SystemContextResearcher => "项目概览调研报告".to_string(),
+            AgentType::ModuleSummaryResearcher => "目录模块摘要".to_string(),
             AgentType::DomainModulesDetector => "领域模块调研报告".to_string(),
             AgentType::ArchitectureResearcher => "系统架构调研报告".to_string(),
             AgentType::WorkflowResearcher => "工作流调研报告".to_string(),
@@ -179,6 +181,29 @@ pub struct KeyModuleReport {
     pub sequence_diagram_mermaid: String,
 }
 
+/// 单个目录的模块摘要
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModuleSummary {
+    /// 目录路径（相对项目根目录，根目录为`.`）
+    pub directory: String,
+    /// 目录的主要用途，取目录内文件最多的代码用途类型
+    pub purpose: String,
+    /// 来自关键文件的主要职责
+    pub responsibilities: Vec<String>,
+    /// 按重要性排序的关键文件
+    pub key_files: Vec<String>,
+    /// 对外公开的接口
+    pub public_surface: Vec<String>,
+    /// 目录内已分析的文件数
+    pub file_count: usize,
+}
+
+/// 按目录汇总的模块摘要，介于文件级代码洞察与系统级调研之间
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ModuleSummaryReport {
+    pub modules: Vec<ModuleSummary>,
+}
+
 /// 高层次架构视角下的领域模块分析结果
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DomainModulesReport {
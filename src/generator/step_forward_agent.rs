@@ -1,32 +1,57 @@
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt, stream};
+use md5::{Digest, Md5};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::generator::agent_executor::{AgentExecuteParams, extract, prompt, prompt_with_tools};
 use crate::generator::preprocess::memory::{MemoryScope, ScopedKeys};
+use crate::generator::regenerate::forces_regeneration;
 use crate::generator::research::memory::MemoryRetriever;
 use crate::{
+    config::Config,
     generator::context::GeneratorContext,
     types::{
         code::CodeInsight, code_releationship::RelationshipAnalysis,
+        feature_flag::FeatureFlagReport, language_breakdown::LanguageBreakdown,
         project_structure::ProjectStructure,
     },
     utils::project_structure_formatter::ProjectStructureFormatter,
     utils::prompt_compressor::{CompressionConfig, PromptCompressor},
 };
+use log::{info, warn};
 
 /// 替换时间占位符为实际时间信息
-/// 这个函数将LLM响应中的时间占位符替换为当前的实际时间
-pub fn replace_time_placeholders(content: &str) -> String {
-    let now = chrono::Utc::now();
+/// 这个函数将LLM响应中的时间占位符替换为配置时区下的生成时间（可复现模式下为固定时间）
+pub fn replace_time_placeholders(
+    content: &str,
+    now: Option<chrono::DateTime<chrono_tz::Tz>>,
+) -> String {
+    let (time, timestamp) = match now {
+        Some(now) => (
+            format!("{} ({})", now.format("%Y-%m-%d %H:%M:%S"), now.timezone()),
+            now.timestamp().to_string(),
+        ),
+        // 没有可用的生成时间时（可复现模式且未设置SOURCE_DATE_EPOCH）省略时间
+        None => (String::new(), String::new()),
+    };
     content
-        .replace(
-            "__CURRENT_UTC_TIME__",
-            &format!("{} (UTC)", now.format("%Y-%m-%d %H:%M:%S")),
-        )
-        .replace("__CURRENT_TIMESTAMP__", &now.timestamp().to_string())
+        .replace("__CURRENT_UTC_TIME__", &time)
+        .replace("__CURRENT_TIMESTAMP__", &timestamp)
+}
+
+/// 判断智能体输出是否为空（空文本、空列表、空对象或null）
+fn is_empty_output<T: Serialize>(output: &T) -> bool {
+    match serde_json::to_value(output) {
+        Ok(serde_json::Value::Null) => true,
+        Ok(serde_json::Value::String(text)) => text.trim().is_empty(),
+        Ok(serde_json::Value::Array(items)) => items.is_empty(),
+        Ok(serde_json::Value::Object(fields)) => fields.is_empty(),
+        _ => false,
+    }
 }
 
 /// 数据源配置 - 基于Memory Key的直接数据访问机制
@@ -59,6 +84,18 @@ impl DataSource {
         scope: MemoryScope::PREPROCESS,
         key: ScopedKeys::ORIGINAL_DOCUMENT,
     };
+    pub const FEATURE_FLAGS: DataSource = DataSource::MemoryData {
+        scope: MemoryScope::PREPROCESS,
+        key: ScopedKeys::FEATURE_FLAGS,
+    };
+    pub const LANGUAGE_BREAKDOWN: DataSource = DataSource::MemoryData {
+        scope: MemoryScope::PREPROCESS,
+        key: ScopedKeys::LANGUAGE_BREAKDOWN,
+    };
+    pub const BUILD_SYSTEM: DataSource = DataSource::MemoryData {
+        scope: MemoryScope::PREPROCESS,
+        key: ScopedKeys::BUILD_SYSTEM,
+    };
 }
 
 /// Agent数据配置 - 声明所需的数据源
@@ -99,6 +136,8 @@ pub struct FormatterConfig {
     pub enable_compression: bool,
     /// 压缩配置
     pub compression_config: CompressionConfig,
+    /// 是否在项目结构树的文件节点上标注用途类型与重要性（来自代码洞察）
+    pub annotate_files_with_insights: bool,
 }
 
 impl Default for FormatterConfig {
@@ -111,6 +150,7 @@ impl Default for FormatterConfig {
             enable_compression: true,
             compression_config: CompressionConfig::default(),
             only_directories_when_files_more_than: None,
+            annotate_files_with_insights: false,
         }
     }
 }
@@ -130,10 +170,62 @@ pub struct PromptTemplate {
     pub formatter_config: FormatterConfig,
 }
 
+impl PromptTemplate {
+    /// 计算模板指纹（基于缓存作用域、模板版本、各段提示词及影响模型输出的配置），用于缓存作用域的版本化
+    pub fn fingerprint(&self, scope: &str, template_version: u32, config: &Config) -> String {
+        prompt_fingerprint(
+            scope,
+            &format!(
+                "{}|{}|{}",
+                self.system_prompt, self.opening_instruction, self.closing_instruction
+            ),
+            template_version,
+            config,
+        )
+    }
+}
+
+/// 计算提示词指纹，作用域、提示词、版本或影响模型输出的配置（provider、模型、温度、最大token数、目标语言）
+/// 变化时指纹随之变化，从而使旧缓存自然失效
+pub fn prompt_fingerprint(
+    scope: &str,
+    prompt: &str,
+    template_version: u32,
+    config: &Config,
+) -> String {
+    let llm = &config.llm;
+    // 可复现模式下实际使用的温度为0
+    let temperature = if config.deterministic {
+        0.0
+    } else {
+        llm.temperature
+    };
+    let mut hasher = Md5::new();
+    hasher.update(
+        format!(
+            "v{}|{}|{:?}|{}|{}|{}|{}|{:?}|{}",
+            template_version,
+            scope,
+            llm.provider,
+            llm.model_efficient,
+            llm.model_powerful,
+            temperature,
+            llm.max_tokens,
+            config.target_language,
+            prompt
+        )
+        .as_bytes(),
+    );
+    let hash = format!("{:x}", hasher.finalize());
+    format!("v{}-{}", template_version, &hash[..8])
+}
+
 /// 通用数据格式化器
 pub struct DataFormatter {
     config: FormatterConfig,
     prompt_compressor: Option<PromptCompressor>,
+    /// 实际交给压缩器处理（超出阈值）的内容段数
+    compression_calls: AtomicUsize,
 }
 
 impl DataFormatter {
@@ -147,11 +239,26 @@ impl DataFormatter {
         Self {
             config,
             prompt_compressor,
+            compression_calls: AtomicUsize::new(0),
         }
     }
 
-    /// 格式化项目结构信息
-    pub fn format_project_structure(&self, structure: &ProjectStructure) -> String {
+    /// 已发起的压缩调用次数
+    pub fn compression_calls(&self) -> usize {
+        self.compression_calls.load(Ordering::Relaxed)
+    }
+
+    /// 是否需要在项目结构树上标注代码洞察
+    pub fn annotates_files_with_insights(&self) -> bool {
+        self.config.annotate_files_with_insights
+    }
+
+    /// 格式化项目结构信息，提供代码洞察时在文件节点上标注用途类型与重要性
+    pub fn format_project_structure(
+        &self,
+        structure: &ProjectStructure,
+        insights: Option<&[CodeInsight]>,
+    ) -> String {
         let config = &self.config;
         if let Some(files_limit) = config.only_directories_when_files_more_than {
             // 如果超限，则使用精简版项目结构信息（只显示目录）
@@ -161,7 +268,12 @@ impl DataFormatter {
         }
 
         // 否则使用完整版项目结构信息
-        ProjectStructureFormatter::format_as_tree(structure)
+        match insights {
+            Some(insights) => {
+                ProjectStructureFormatter::format_as_annotated_tree(structure, insights)
+            }
+            None => ProjectStructureFormatter::format_as_tree(structure),
+        }
     }
 
     /// 格式化代码洞察信息
@@ -199,6 +311,14 @@ impl DataFormatter {
                     &insight.code_dossier.source_summary
                 ));
             }
+            for interface in &insight.interfaces {
+                for example in &interface.usage_examples {
+                    content.push_str(&format!(
+                        "   `{}` 使用示例（{}:{}）: `{}`\n",
+                        interface.name, example.file_path, example.line_number, example.snippet
+                    ));
+                }
+            }
         }
         content.push('\n');
         content
@@ -246,6 +366,43 @@ impl DataFormatter {
         content
     }
 
+    /// 格式化可配置特性
+    pub fn format_feature_flags(&self, report: &FeatureFlagReport) -> String {
+        let mut content = String::from("### 可配置特性\n");
+
+        for feature in &report.cargo_features {
+            let default_mark = if feature.is_default { " [default]" } else { "" };
+            content.push_str(&format!(
+                "feature `{}`{} -> [{}] ({})\n",
+                feature.name,
+                default_mark,
+                feature.enables.join(", "),
+                feature.manifest_path
+            ));
+        }
+        for gate in &report.gates {
+            content.push_str(&format!(
+                "{} `{}` @ {}:{}\n",
+                gate.gate_type, gate.name, gate.file_path, gate.line_number
+            ));
+        }
+        content.push('\n');
+        content
+    }
+
+    /// 格式化项目语言构成
+    pub fn format_language_breakdown(&self, breakdown: &LanguageBreakdown) -> String {
+        let mut content = String::from("### 语言构成\n");
+        for stat in &breakdown.languages {
+            content.push_str(&format!(
+                "- {}: {:.1}% ({} 个文件, {} 行)\n",
+                stat.language, stat.percentage, stat.files, stat.lines
+            ));
+        }
+        content.push('\n');
+        content
+    }
+
     /// 获取依赖类型的优先级
     fn get_dependency_priority(
         &self,
@@ -265,6 +422,9 @@ impl DataFormatter {
     /// 格式化研究结果
     pub fn format_research_results(&self, results: &HashMap<String, serde_json::Value>) -> String {
         let mut content = String::from("### 已有调研结果\n");
+        // 按智能体名称排序，保证prompt内容（及其缓存键）在多次运行间保持一致
+        let mut results: Vec<_> = results.iter().collect();
+        results.sort_by(|a, b| a.0.cmp(b.0));
         for (key, value) in results {
             content.push_str(&format!(
                 "#### {}：\n{}\n\n",
@@ -282,13 +442,17 @@ impl DataFormatter {
         content: &str,
         content_type: &str,
     ) -> Result<String> {
-        if let Some(compressor) = &self.prompt_compressor {
+        // 未超出阈值时直接返回，不发起任何压缩调用
+        if let Some(compressor) = &self.prompt_compressor
+            && compressor.exceeds_threshold(content)
+        {
+            self.compression_calls.fetch_add(1, Ordering::Relaxed);
             let compression_result = compressor
                 .compress_if_needed(context, content, content_type)
                 .await?;
 
             if compression_result.was_compressed {
-                println!("   📊 {}", compression_result.compression_summary);
+                info!("   📊 {}", compression_result.compression_summary);
             }
 
             Ok(compression_result.compressed_content)
@@ -296,6 +460,41 @@ impl DataFormatter {
             Ok(content.to_string())
         }
     }
+
+    /// 并发压缩多个内容段（并发数不超过`max_concurrent`），结果保持原有顺序；
+    /// 内容类型为None的段落不参与压缩
+    pub async fn compress_sections(
+        &self,
+        context: &GeneratorContext,
+        sections: Vec<(String, Option<&'static str>)>,
+        max_concurrent: usize,
+    ) -> Result<Vec<String>> {
+        // 先创建全部压缩任务再交给stream调度，避免闭包类型出现在跨await的future中
+        let tasks: Vec<_> = sections
+            .into_iter()
+            .map(|(content, content_type)| self.compress_section(context, content, content_type))
+            .collect();
+        stream::iter(tasks)
+            .buffered(max_concurrent.max(1))
+            .try_collect()
+            .await
+    }
+
+    /// 压缩单个内容段，内容类型为None时原样返回
+    async fn compress_section(
+        &self,
+        context: &GeneratorContext,
+        content: String,
+        content_type: Option<&'static str>,
+    ) -> Result<String> {
+        match content_type {
+            Some(content_type) => {
+                self.compress_content_if_needed(context, &content, content_type)
+                    .await
+            }
+            None => Ok(content),
+        }
+    }
 }
 
 /// 标准的研究Agent Prompt构建器
@@ -362,7 +561,8 @@ impl GeneratorPromptBuilder {
             prompt.push('\n');
         }
 
-        // 收集并格式化各种数据源
+        // 收集并格式化各种数据源，各段的压缩在收集完成后并发执行
+        let mut sections: Vec<(String, Option<&'static str>)> = Vec::new();
         let mut research_results = HashMap::new();
 
         for source in data_sources {
@@ -373,12 +573,21 @@ impl GeneratorPromptBuilder {
                             .get_from_memory::<ProjectStructure>(scope, key)
                             .await
                         {
-                            let formatted = self.formatter.format_project_structure(&structure);
-                            let compressed = self
-                                .formatter
-                                .compress_content_if_needed(context, &formatted, "项目结构")
-                                .await?;
-                            prompt.push_str(&compressed);
+                            let insights = if self.formatter.annotates_files_with_insights() {
+                                context
+                                    .get_from_memory::<Vec<CodeInsight>>(
+                                        MemoryScope::PREPROCESS,
+                                        ScopedKeys::CODE_INSIGHTS,
+                                    )
+                                    .await
+                            } else {
+                                None
+                            };
+                            sections.push((
+                                self.formatter
+                                    .format_project_structure(&structure, insights.as_deref()),
+                                Some("项目结构"),
+                            ));
                         }
                     }
                     ScopedKeys::CODE_INSIGHTS => {
@@ -386,22 +595,18 @@ impl GeneratorPromptBuilder {
                             .get_from_memory::<Vec<CodeInsight>>(scope, key)
                             .await
                         {
-                            let formatted = self.formatter.format_code_insights(&insights);
-                            let compressed = self
-                                .formatter
-                                .compress_content_if_needed(context, &formatted, "代码洞察")
-                                .await?;
-                            prompt.push_str(&compressed);
+                            sections.push((
+                                self.formatter.format_code_insights(&insights),
+                                Some("代码洞察"),
+                            ));
                         }
                     }
                     ScopedKeys::ORIGINAL_DOCUMENT => {
                         if let Some(readme) = context.get_from_memory::<String>(scope, key).await {
-                            let formatted = self.formatter.format_readme_content(&readme);
-                            let compressed = self
-                                .formatter
-                                .compress_content_if_needed(context, &formatted, "README文档")
-                                .await?;
-                            prompt.push_str(&compressed);
+                            sections.push((
+                                self.formatter.format_readme_content(&readme),
+                                Some("README文档"),
+                            ));
                         }
                     }
                     ScopedKeys::RELATIONSHIPS => {
@@ -409,12 +614,32 @@ impl GeneratorPromptBuilder {
                             .get_from_memory::<RelationshipAnalysis>(scope, key)
                             .await
                         {
-                            let formatted = self.formatter.format_dependency_analysis(&deps);
-                            let compressed = self
-                                .formatter
-                                .compress_content_if_needed(context, &formatted, "依赖关系")
-                                .await?;
-                            prompt.push_str(&compressed);
+                            sections.push((
+                                self.formatter.format_dependency_analysis(&deps),
+                                Some("依赖关系"),
+                            ));
+                        }
+                    }
+                    ScopedKeys::FEATURE_FLAGS => {
+                        if let Some(report) = context
+                            .get_from_memory::<FeatureFlagReport>(scope, key)
+                            .await
+                            .filter(|report| !report.is_empty())
+                        {
+                            sections.push((
+                                self.formatter.format_feature_flags(&report),
+                                Some("可配置特性"),
+                            ));
+                        }
+                    }
+                    ScopedKeys::LANGUAGE_BREAKDOWN => {
+                        if let Some(breakdown) = context
+                            .get_from_memory::<LanguageBreakdown>(scope, key)
+                            .await
+                            .filter(|breakdown| !breakdown.is_empty())
+                        {
+                            sections
+                                .push((self.formatter.format_language_breakdown(&breakdown), None));
                         }
                     }
                     _ => {}
@@ -429,21 +654,38 @@ impl GeneratorPromptBuilder {
 
         // 添加研究结果
         if !research_results.is_empty() {
-            let formatted = self.formatter.format_research_results(&research_results);
-            let compressed = self
-                .formatter
-                .compress_content_if_needed(context, &formatted, "研究结果")
-                .await?;
-            prompt.push_str(&compressed);
+            sections.push((
+                self.formatter.format_research_results(&research_results),
+                Some("研究结果"),
+            ));
+        }
+
+        let compressed_sections = self
+            .formatter
+            .compress_sections(
+                context,
+                sections,
+                context.config.llm.effective_max_parallels(),
+            )
+            .await?;
+        for section in compressed_sections {
+            prompt.push_str(&section);
         }
 
         // 结尾强调性指令
         prompt.push_str(&self.template.closing_instruction);
 
         // 最终再次检测和压缩
-        self.formatter
+        let prompt = self
+            .formatter
             .compress_content_if_needed(context, &prompt, "StepForwardAgent_prompt_full")
-            .await
+            .await?;
+
+        let compression_calls = self.formatter.compression_calls();
+        if compression_calls > 0 {
+            info!("   🗜️ 提示词构建共压缩 {} 段超长内容", compression_calls);
+        }
+        Ok(prompt)
     }
 }
 
@@ -464,6 +706,15 @@ pub trait StepForwardAgent: Send + Sync {
     /// Prompt模板配置
     fn prompt_template(&self) -> PromptTemplate;
 
+    /// 可选的结果修正钩子，在结果存储到内存之前调用，可用于合并权威数据源
+    async fn refine_output(
+        &self,
+        result: Self::Output,
+        _context: &GeneratorContext,
+    ) -> Result<Self::Output> {
+        Ok(result)
+    }
+
     /// 可选的后处理钩子
     fn post_process(&self, _result: &Self::Output, _context: &GeneratorContext) -> Result<()> {
         Ok(())
@@ -478,6 +729,11 @@ pub trait StepForwardAgent: Send + Sync {
         Ok(None)
     }
 
+    /// Prompt模板版本号，修改模板语义（如输出结构）时递增，使旧缓存失效
+    fn template_version(&self) -> u32 {
+        1
+    }
+
     /// 是否在prompt中包含时间戳信息
     /// 默认为false，只有特定的agent（如compose目录下的editor agents）需要重写为true
     fn should_include_timestamp(&self) -> bool {
@@ -486,6 +742,18 @@ pub trait StepForwardAgent: Send + Sync {
 
     /// 默认实现的execute方法 - 完全标准化，自动数据验证
     async fn execute(&self, context: &GeneratorContext) -> Result<Self::Output> {
+        // 0. 内存中已有该智能体的结果（如通过`--resume`从中断的运行中恢复）时直接复用，除非配置要求强制重新生成
+        let memory_category = format!("{}/{}", self.memory_scope_key(), self.agent_type());
+        let forced = forces_regeneration(&context.config, &memory_category);
+        if !forced
+            && let Some(stored) = context
+                .get_from_memory::<Self::Output>(&self.memory_scope_key(), &self.agent_type())
+                .await
+        {
+            info!("♻️ 复用已存储的 [{}] 结果，跳过执行", self.agent_type());
+            return Ok(stored);
+        }
+
         // 1. 获取数据配置
         let config = self.data_config();
 
@@ -520,8 +788,8 @@ pub trait StepForwardAgent: Send + Sync {
         // 获取自定义prompt内容
         let custom_content = self.provide_custom_prompt_content(context).await?;
 
-        // 检查是否需要包含时间戳
-        let include_timestamp = self.should_include_timestamp();
+        // 检查是否需要包含时间戳，可复现模式下不包含，避免prompt随时间变化而无法命中缓存
+        let include_timestamp = self.should_include_timestamp() && !context.config.deterministic;
 
         let (system_prompt, user_prompt) = prompt_builder
             .build_prompts(context, &all_sources, custom_content, include_timestamp)
@@ -531,45 +799,298 @@ pub trait StepForwardAgent: Send + Sync {
         let params = AgentExecuteParams {
             prompt_sys: system_prompt,
             prompt_user: user_prompt,
-            cache_scope: format!("{}/{}", self.memory_scope_key(), self.agent_type()),
+            cache_scope: format!(
+                "{}/{}",
+                memory_category,
+                template.fingerprint(&memory_category, self.template_version(), &context.config)
+            ),
             log_tag: self.agent_type().to_string(),
         };
 
-        let result_value = match template.llm_call_mode {
-            LLMCallMode::Extract => {
-                let result: Self::Output = extract(context, params).await?;
-                serde_json::to_value(&result)?
-            }
+        let result: Self::Output = match template.llm_call_mode {
+            LLMCallMode::Extract => extract(context, params).await?,
             LLMCallMode::Prompt => {
                 let result_text: String = prompt(context, params).await?;
                 // 替换时间占位符
-                let processed_text = replace_time_placeholders(&result_text);
-                serde_json::to_value(&processed_text)?
+                let processed_text =
+                    replace_time_placeholders(&result_text, context.config.local_generation_time());
+                serde_json::from_value(serde_json::Value::String(processed_text))?
             }
             LLMCallMode::PromptWithTools => {
                 let result_text: String = prompt_with_tools(context, params).await?;
                 // 替换时间占位符
-                let processed_text = replace_time_placeholders(&result_text);
-                serde_json::to_value(&processed_text)?
+                let processed_text =
+                    replace_time_placeholders(&result_text, context.config.local_generation_time());
+                serde_json::from_value(serde_json::Value::String(processed_text))?
+            }
+        };
+
+        // 6. 修正结果（默认不做任何修改）
+        let mut result = self.refine_output(result, context).await?;
+
+        // 7. 存储结果：强制重新生成时覆盖（旧结果保留为历史版本），否则只在不存在时写入，不覆盖已有的有效结果
+        if forced {
+            context
+                .store_to_memory(&self.memory_scope_key(), &self.agent_type(), &result)
+                .await?;
+            // 重新生成得到空结果时恢复上一版本，避免覆盖先前的有效结果
+            if is_empty_output(&result)
+                && let Some(previous) = context
+                    .get_versioned_from_memory::<Self::Output>(
+                        &self.memory_scope_key(),
+                        &self.agent_type(),
+                        1,
+                    )
+                    .await
+            {
+                warn!(
+                    "⚠️ [{}] 重新生成的结果为空，恢复上一版本",
+                    self.agent_type()
+                );
+                context
+                    .store_to_memory(&self.memory_scope_key(), &self.agent_type(), &previous)
+                    .await?;
+                result = previous;
+            }
+        } else {
+            context
+                .store_to_memory_if_absent(&self.memory_scope_key(), &self.agent_type(), &result)
+                .await?;
+        }
+
+        // 8. 执行后处理
+        self.post_process(&result, context)?;
+        info!("✅ Sub-Agent [{}]执行完成", self.agent_type());
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheManager;
+    use crate::config::CacheConfig;
+    use crate::llm::client::types::TokenUsage;
+    use tempfile::TempDir;
+
+    fn template_with_system_prompt(system_prompt: &str) -> PromptTemplate {
+        PromptTemplate {
+            system_prompt: system_prompt.to_string(),
+            opening_instruction: "opening".to_string(),
+            closing_instruction: "closing".to_string(),
+            llm_call_mode: LLMCallMode::Extract,
+            formatter_config: FormatterConfig::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_changed_system_prompt_misses_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_manager = CacheManager::new(CacheConfig {
+            enabled: true,
+            cache_dir: temp_dir.path().to_path_buf(),
+            expire_hours: 1,
+            category_expire_hours: Default::default(),
+        });
+
+        let config = Config::default();
+        let scope = "studies_research/架构调研";
+        let versioned = |template: &PromptTemplate, version: u32, config: &Config| {
+            format!("{}/{}", scope, template.fingerprint(scope, version, config))
+        };
+        let original = template_with_system_prompt("你是一个架构分析师");
+        let edited = template_with_system_prompt("你是一个资深架构分析师");
+        let original_scope = versioned(&original, 1, &config);
+        let edited_scope = versioned(&edited, 1, &config);
+        let bumped_scope = versioned(&original, 2, &config);
+        assert_ne!(original_scope, edited_scope);
+        assert_ne!(original_scope, bumped_scope);
+
+        // 作用域或影响模型输出的配置变化时同样使缓存失效
+        assert_ne!(
+            original.fingerprint(scope, 1, &config),
+            original.fingerprint("studies_research/工作流调研", 1, &config)
+        );
+        let mut other_model = config.clone();
+        other_model.llm.model_powerful = "another-model".to_string();
+        assert_ne!(original_scope, versioned(&original, 1, &other_model));
+        let mut other_language = config.clone();
+        other_language.target_language = crate::i18n::TargetLanguage::English;
+        assert_ne!(original_scope, versioned(&original, 1, &other_language));
+
+        cache_manager
+            .set_with_tokens(
+                &original_scope,
+                "user prompt",
+                "cached",
+                TokenUsage::new(1, 1),
+            )
+            .await
+            .unwrap();
+
+        let hit: Option<String> = cache_manager
+            .get(&original_scope, "user prompt")
+            .await
+            .unwrap();
+        assert_eq!(hit.as_deref(), Some("cached"));
+        let miss: Option<String> = cache_manager
+            .get(&edited_scope, "user prompt")
+            .await
+            .unwrap();
+        assert!(miss.is_none());
+        let miss: Option<String> = cache_manager
+            .get(&bumped_scope, "user prompt")
+            .await
+            .unwrap();
+        assert!(miss.is_none());
+    }
+
+    struct StoredResultAgent;
+
+    impl StepForwardAgent for StoredResultAgent {
+        type Output = Vec<String>;
+
+        fn agent_type(&self) -> String {
+            "恢复测试".to_string()
+        }
+
+        fn memory_scope_key(&self) -> String {
+            "studies_research".to_string()
+        }
+
+        fn data_config(&self) -> AgentDataConfig {
+            AgentDataConfig {
+                required_sources: vec![DataSource::CODE_INSIGHTS],
+                optional_sources: vec![],
             }
+        }
+
+        fn prompt_template(&self) -> PromptTemplate {
+            template_with_system_prompt("system")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stored_result_short_circuits_execution() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = crate::config::Config {
+            project_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
         };
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
+        let agent = StoredResultAgent;
 
-        // 6. 存储结果
+        // 必需的数据源缺失，真正执行时会直接失败
+        assert!(agent.execute(&context).await.is_err());
+
+        let stored = vec!["上次运行的结果".to_string()];
+        context
+            .store_to_memory(&agent.memory_scope_key(), &agent.agent_type(), &stored)
+            .await
+            .unwrap();
+        assert_eq!(agent.execute(&context).await.unwrap(), stored);
+    }
+
+    struct RegeneratedAgent;
+
+    impl StepForwardAgent for RegeneratedAgent {
+        type Output = Vec<String>;
+
+        fn agent_type(&self) -> String {
+            "文档重新生成测试".to_string()
+        }
+
+        fn memory_scope_key(&self) -> String {
+            "documentation".to_string()
+        }
+
+        fn data_config(&self) -> AgentDataConfig {
+            AgentDataConfig {
+                required_sources: vec![],
+                optional_sources: vec![],
+            }
+        }
+
+        fn prompt_template(&self) -> PromptTemplate {
+            template_with_system_prompt("system")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_regenerated_result_keeps_previous_version() {
+        use crate::llm::client::mock_server::MockServer;
+
+        // 模拟服务对列表结果返回空列表，模拟重新运行得到不完整的结果
+        let server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = crate::config::Config {
+            project_path: temp_dir.path().to_path_buf(),
+            force_regenerate: true,
+            memory_versions: 1,
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        config.llm.provider = crate::config::LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = server.api_base_url.clone();
+        let context = GeneratorContext::new(config).unwrap();
+        let agent = RegeneratedAgent;
+
+        let previous = vec!["上次生成的结果".to_string()];
+        context
+            .store_to_memory(&agent.memory_scope_key(), &agent.agent_type(), &previous)
+            .await
+            .unwrap();
+
+        assert_eq!(agent.execute(&context).await.unwrap(), previous);
+        assert!(!server.requests().is_empty());
+        assert_eq!(
+            context
+                .get_from_memory::<Vec<String>>(&agent.memory_scope_key(), &agent.agent_type())
+                .await,
+            Some(previous)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sub_threshold_content_performs_no_compression_calls() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = crate::config::Config {
+            project_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        let context = GeneratorContext::new(config).unwrap();
         context
             .store_to_memory(
-                &self.memory_scope_key(),
-                &self.agent_type(),
-                result_value.clone(),
+                MemoryScope::PREPROCESS,
+                ScopedKeys::ORIGINAL_DOCUMENT,
+                "# Demo\n一个很小的项目",
             )
-            .await?;
+            .await
+            .unwrap();
+        context
+            .store_research("架构调研", serde_json::json!({ "layers": ["cli", "core"] }))
+            .await
+            .unwrap();
 
-        // 7. 执行后处理
-        if let Ok(typed_result) = serde_json::from_value::<Self::Output>(result_value) {
-            self.post_process(&typed_result, context)?;
-            println!("✅ Sub-Agent [{}]执行完成", self.agent_type());
-            Ok(typed_result)
-        } else {
-            Err(anyhow::format_err!(""))
-        }
+        let builder = GeneratorPromptBuilder::new(template_with_system_prompt("system"));
+        let (_, user_prompt) = builder
+            .build_prompts(
+                &context,
+                &[
+                    DataSource::README_CONTENT,
+                    DataSource::ResearchResult("架构调研".to_string()),
+                ],
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+
+        assert!(user_prompt.contains("一个很小的项目"));
+        assert!(user_prompt.contains("core"));
+        assert_eq!(builder.formatter.compression_calls(), 0);
     }
 }
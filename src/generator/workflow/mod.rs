@@ -1,8 +1,14 @@
 use crate::config::Config;
 use crate::generator::context::GeneratorContext;
+use crate::generator::events::{EventListener, GeneratorEvent};
+use crate::memory::MemoryDump;
+use crate::utils::file_read_limiter::FileReadLimiter;
 
 use anyhow::Result;
+use log::{debug, info, warn};
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// 时间跟踪作用域
@@ -106,34 +112,185 @@ impl TimingKeys {
     }
 }
 
+/// 内部目录中每个阶段结束后保存的内存快照，用于`--resume`恢复中断的运行
+pub const MEMORY_SNAPSHOT_FILE: &str = "memory-snapshot.json";
+
 /// 启动文档生成工作流
+#[allow(dead_code)]
 pub async fn launch(config: &Config) -> Result<()> {
-    let context = GeneratorContext::new(config.clone())?;
+    run(config, None).await
+}
+
+/// 启动文档生成工作流，并将进度事件发送给监听器
+pub async fn launch_with_listener(config: &Config, listener: Arc<dyn EventListener>) -> Result<()> {
+    run(config, Some(listener)).await
+}
 
-    // 启动时检查模型连接
-    context.llm_client.check_connection().await?;
+async fn run(config: &Config, listener: Option<Arc<dyn EventListener>>) -> Result<()> {
+    // 项目路径为归档文件时解压到临时目录后分析，运行结束时自动清理
+    let (config, archive) = crate::utils::archive::prepare_project(config)?;
+    if let Some(archive) = &archive {
+        info!("📦 已解压项目归档到: {}", archive.root().display());
+        if archive.skipped_files > 0 {
+            warn!(
+                "   ⚠️ {} 个文件超过大小限制，未从归档中解压",
+                archive.skipped_files
+            );
+        }
+    }
+    let config = &config;
+
+    let file_read_limiter = FileReadLimiter::init_global(config.max_concurrent_file_reads);
+    debug!("文件读取并发上限: {}", file_read_limiter.limit());
+
+    let context = match listener {
+        Some(listener) => GeneratorContext::with_event_listener(config.clone(), listener)?,
+        None => GeneratorContext::new(config.clone())?,
+    };
+
+    // 启动时检查模型连接，离线模式不调用模型服务，无需检查
+    if config.llm.offline {
+        info!("📴 离线模式，跳过模型连接检查");
+    } else {
+        context.llm_client.check_connection().await?;
+    }
+
+    // 强制重新生成时按范围清除缓存
+    if config.force_regenerate {
+        crate::generator::regenerate::invalidate_cache(&context).await?;
+    }
+
+    if config.resume {
+        restore_memory_snapshot(&context).await;
+    }
 
     // 执行工作流
+    let outcome = execute_phases(&context, config).await;
+
+    // 无论运行成功与否都导出内存，便于排查失败时各阶段已写入的数据
+    if let Some(path) = &config.dump_memory_path {
+        dump_memory(&context, path).await;
+    }
+
+    // 累计成本超出上限时保存已生成的部分结果，并以明确的错误结束运行
+    if let Some(exceeded) = context.llm_client.budget_exceeded() {
+        warn!("💸 {}", exceeded);
+        if outcome.is_err() {
+            warn!("💾 正在保存已生成的部分结果...");
+            let doc_tree = crate::generator::outlet::DocTree::with_profile(
+                &config.target_language,
+                config.profile,
+            );
+            crate::generator::outlet::save(&context, doc_tree).await?;
+        }
+        return Err(exceeded.into());
+    }
+
+    outcome
+}
+
+/// 依次执行预处理、调研与文档生成阶段
+async fn execute_phases(context: &GeneratorContext, config: &Config) -> Result<()> {
     if !config.skip_preprocessing {
-        crate::generator::preprocess::execute(&context).await?;
+        run_phase(
+            context,
+            TimingKeys::PREPROCESS,
+            crate::generator::preprocess::execute(context),
+        )
+        .await?;
     }
 
     if !config.skip_research {
-        crate::generator::research::execute(&context).await?;
+        run_phase(
+            context,
+            TimingKeys::RESEARCH,
+            crate::generator::research::execute(context),
+        )
+        .await?;
     }
 
-    if !config.skip_documentation {
-        let doc_tree = crate::generator::compose::execute(&context).await?;
-        crate::generator::outlet::save(&context, doc_tree).await?;
-    } else {
-        // 如果跳过文档生成，创建空的 doc_tree 并保存（如果需要）
-        let doc_tree = crate::generator::outlet::DocTree::new(&config.target_language);
-        crate::generator::outlet::save(&context, doc_tree).await?;
+    // 跳过文档生成时没有新的文档可保存，保留输出目录中已有的文档，避免部分重跑时将其清空
+    if config.skip_documentation {
+        info!(
+            "⏭️ 已跳过文档生成，保留现有输出目录: {}",
+            config.output_path.display()
+        );
+        return Ok(());
     }
 
+    let doc_tree = run_phase(
+        context,
+        TimingKeys::COMPOSE,
+        crate::generator::compose::execute(context),
+    )
+    .await?;
+    run_phase(
+        context,
+        TimingKeys::OUTPUT,
+        crate::generator::outlet::save(context, doc_tree),
+    )
+    .await?;
+
     Ok(())
 }
 
+/// 将内存中的全部数据导出为JSON，导出失败不影响运行结果
+async fn dump_memory(context: &GeneratorContext, path: &Path) {
+    let dump = context.memory.read().await.dump();
+    match dump.save(path) {
+        Ok(()) => info!(
+            "🧠 已导出内存数据 ({} 个作用域, {} 字节) 到: {}",
+            dump.scopes.len(),
+            dump.total_size,
+            path.display()
+        ),
+        Err(e) => warn!("⚠️ 导出内存数据失败: {}", e),
+    }
+}
+
+/// 从内部目录的内存快照恢复上次运行写入的数据，快照不存在或无法读取时从头运行
+async fn restore_memory_snapshot(context: &GeneratorContext) {
+    let path = context.config.internal_path.join(MEMORY_SNAPSHOT_FILE);
+    if !path.exists() {
+        info!("♻️ 未找到内存快照，将从头运行: {}", path.display());
+        return;
+    }
+    let restored = match MemoryDump::load(&path) {
+        Ok(dump) => context.memory.write().await.restore(dump),
+        Err(e) => Err(e),
+    };
+    match restored {
+        Ok(count) => info!("♻️ 已从内存快照恢复 {} 条数据: {}", count, path.display()),
+        Err(e) => warn!("⚠️ 读取内存快照失败，将从头运行: {}", e),
+    }
+}
+
+/// 将内存保存为内部目录中的快照，保存失败不影响运行结果
+async fn save_memory_snapshot(context: &GeneratorContext) {
+    let path = context.config.internal_path.join(MEMORY_SNAPSHOT_FILE);
+    if let Err(e) = context.memory.read().await.dump().save(&path) {
+        warn!("⚠️ 保存内存快照失败: {}", e);
+    }
+}
+
+/// 执行单个阶段，并在开始和成功结束时发送阶段事件
+async fn run_phase<T>(
+    context: &GeneratorContext,
+    phase: &str,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    context.emit(GeneratorEvent::PhaseStarted {
+        phase: phase.to_string(),
+    });
+    let output = future.await?;
+    // 每个阶段完成后保存快照，运行中断后可通过`--resume`复用已完成的结果
+    save_memory_snapshot(context).await;
+    context.emit(GeneratorEvent::PhaseFinished {
+        phase: phase.to_string(),
+    });
+    Ok(output)
+}
+
 // Include tests
 #[cfg(test)]
 mod tests;
@@ -2,7 +2,10 @@
 mod tests {
     use crate::config::Config;
     use crate::generator::context::GeneratorContext;
+    use crate::generator::events::{EventListener, GeneratorEvent};
+    use crate::generator::workflow::{TimingKeys, execute_phases, launch};
     use std::path::PathBuf;
+    use std::sync::Arc;
     use tempfile::TempDir;
 
     fn create_test_context() -> (GeneratorContext, TempDir) {
@@ -255,4 +258,228 @@ mod tests {
         let ctx = context.unwrap();
         assert_eq!(ctx.config.project_name, Some("Test Project".to_string()));
     }
+
+    #[derive(Default)]
+    struct RecordingListener {
+        events: std::sync::Mutex<Vec<GeneratorEvent>>,
+    }
+
+    impl EventListener for RecordingListener {
+        fn on_event(&self, event: &GeneratorEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_listener_receives_phase_events() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let mut config = Config {
+            project_path: temp_dir.path().to_path_buf(),
+            output_path: temp_dir.path().join("output"),
+            internal_path: temp_dir.path().join(".litho"),
+            skip_research: true,
+            ..Default::default()
+        };
+        config.llm.offline = true;
+        config.cache.enabled = false;
+
+        let listener = Arc::new(RecordingListener::default());
+        let context =
+            GeneratorContext::with_event_listener(config.clone(), listener.clone()).unwrap();
+        execute_phases(&context, &config).await.unwrap();
+
+        let phase_events: Vec<GeneratorEvent> = listener
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    GeneratorEvent::PhaseStarted { .. } | GeneratorEvent::PhaseFinished { .. }
+                )
+            })
+            .cloned()
+            .collect();
+        let expected: Vec<GeneratorEvent> = [
+            TimingKeys::PREPROCESS,
+            TimingKeys::COMPOSE,
+            TimingKeys::OUTPUT,
+        ]
+        .iter()
+        .flat_map(|phase| {
+            [
+                GeneratorEvent::PhaseStarted {
+                    phase: phase.to_string(),
+                },
+                GeneratorEvent::PhaseFinished {
+                    phase: phase.to_string(),
+                },
+            ]
+        })
+        .collect();
+        assert_eq!(phase_events, expected);
+    }
+
+    #[tokio::test]
+    async fn test_skip_documentation_preserves_existing_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_path = temp_dir.path().join("output");
+        std::fs::create_dir_all(&output_path).unwrap();
+        std::fs::write(output_path.join("1.Overview.md"), "# 上次生成的文档\n").unwrap();
+
+        let mut config = Config {
+            project_path: temp_dir.path().to_path_buf(),
+            output_path: output_path.clone(),
+            internal_path: temp_dir.path().join(".litho"),
+            skip_preprocessing: true,
+            skip_research: true,
+            skip_documentation: true,
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+
+        let listener = Arc::new(RecordingListener::default());
+        let context =
+            GeneratorContext::with_event_listener(config.clone(), listener.clone()).unwrap();
+        execute_phases(&context, &config).await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(output_path.join("1.Overview.md")).unwrap(),
+            "# 上次生成的文档\n"
+        );
+        assert!(listener.events.lock().unwrap().iter().all(|event| {
+            !matches!(event, GeneratorEvent::PhaseStarted { phase } if phase == TimingKeys::OUTPUT)
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_offline_launch_skips_unreachable_llm_endpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("project");
+        std::fs::create_dir_all(project_path.join("src")).unwrap();
+        std::fs::write(project_path.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let mut config = Config {
+            project_path,
+            output_path: temp_dir.path().join("output"),
+            internal_path: temp_dir.path().join(".litho"),
+            ..Default::default()
+        };
+        config.llm.offline = true;
+        config.llm.api_key = String::new();
+        // 不可达的模型服务地址：离线模式下不应发起任何请求
+        config.llm.api_base_url = "http://127.0.0.1:9/v1".to_string();
+        config.llm.retry_attempts = 1;
+        config.cache.enabled = false;
+
+        launch(&config).await.unwrap();
+        assert!(config.output_path.is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_resume_reuses_results_from_memory_snapshot() {
+        use crate::config::{DocumentProfile, LLMProvider};
+        use crate::generator::compose::memory::MemoryScope;
+        use crate::generator::compose::types::AgentType;
+        use crate::generator::workflow::MEMORY_SNAPSHOT_FILE;
+        use crate::llm::client::mock_server::MockServer;
+        use crate::memory::{Memory, MemoryDump};
+
+        let server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("project");
+        std::fs::create_dir_all(project_path.join("src")).unwrap();
+        std::fs::write(project_path.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let mut config = Config {
+            project_path,
+            output_path: temp_dir.path().join("output"),
+            internal_path: temp_dir.path().join(".litho"),
+            profile: DocumentProfile::Executive,
+            ..Default::default()
+        };
+        config.llm.provider = LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = server.api_base_url.clone();
+        config.cache.enabled = false;
+
+        // 每个阶段结束后都会在内部目录保存内存快照
+        launch(&config).await.unwrap();
+        let first_run_requests = server.requests().len();
+        let snapshot_path = config.internal_path.join(MEMORY_SNAPSHOT_FILE);
+        let mut memory = Memory::new();
+        memory
+            .restore(MemoryDump::load(&snapshot_path).unwrap())
+            .unwrap();
+        let overview_key = AgentType::Overview.to_string();
+        assert!(memory.has_data(MemoryScope::DOCUMENTATION, &overview_key));
+
+        // 修改快照中的概述文档，恢复运行时应直接复用而不是重新生成
+        memory
+            .store(
+                MemoryScope::DOCUMENTATION,
+                &overview_key,
+                "# 上次运行的概述\n",
+            )
+            .unwrap();
+        memory.dump().save(&snapshot_path).unwrap();
+
+        config.resume = true;
+        launch(&config).await.unwrap();
+        let overview = config
+            .output_path
+            .join(config.target_language.get_doc_filename("overview"));
+        assert!(
+            std::fs::read_to_string(overview)
+                .unwrap()
+                .contains("# 上次运行的概述")
+        );
+        // 调研与文档生成的结果均已复用，只有预处理会重新调用模型
+        assert!(server.requests().len() - first_run_requests < first_run_requests);
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_cached_runs_produce_identical_documents() {
+        use crate::config::{DocumentProfile, LLMProvider};
+        use crate::llm::client::mock_server::MockServer;
+
+        let server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("project");
+        std::fs::create_dir_all(project_path.join("src")).unwrap();
+        std::fs::write(project_path.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let mut outputs = Vec::new();
+        let mut request_counts = Vec::new();
+        for run in ["first", "second"] {
+            let mut config = Config {
+                project_path: project_path.clone(),
+                output_path: temp_dir.path().join(run).join("docs"),
+                internal_path: temp_dir.path().join(run).join(".litho"),
+                profile: DocumentProfile::Executive,
+                deterministic: true,
+                ..Default::default()
+            };
+            config.llm.provider = LLMProvider::OpenAI;
+            config.llm.api_key = "test-key".to_string();
+            config.llm.api_base_url = server.api_base_url.clone();
+            config.cache.cache_dir = temp_dir.path().join("cache");
+
+            launch(&config).await.unwrap();
+            request_counts.push(server.requests().len());
+            let overview = config
+                .output_path
+                .join(config.target_language.get_doc_filename("overview"));
+            outputs.push(std::fs::read_to_string(overview).unwrap());
+        }
+
+        // 第二次运行除启动时的连接检查外，模型调用全部命中第一次运行写入的缓存，生成的文档逐字节相同
+        assert_eq!(request_counts[1] - request_counts[0], 1);
+        assert_eq!(outputs[0], outputs[1]);
+        assert!(!outputs[0].contains("1970-01-01"));
+    }
 }
@@ -90,6 +90,80 @@ impl TargetLanguage {
         }
     }
 
+    /// 变更范围文档的说明，添加在每个文档的开头
+    pub fn changes_since_notice(&self, git_ref: &str) -> String {
+        match self {
+            TargetLanguage::Chinese => {
+                format!("> 本文档集仅涵盖自 `{}` 以来变更的文件。", git_ref)
+            }
+            TargetLanguage::English => format!(
+                "> This documentation set only covers files changed since `{}`.",
+                git_ref
+            ),
+            TargetLanguage::Japanese => format!(
+                "> このドキュメントセットは `{}` 以降に変更されたファイルのみを対象としています。",
+                git_ref
+            ),
+            TargetLanguage::Korean => format!(
+                "> 이 문서 모음은 `{}` 이후 변경된 파일만 다룹니다.",
+                git_ref
+            ),
+            TargetLanguage::German => format!(
+                "> Diese Dokumentation umfasst nur Dateien, die seit `{}` geändert wurden.",
+                git_ref
+            ),
+            TargetLanguage::French => format!(
+                "> Cette documentation ne couvre que les fichiers modifiés depuis `{}`.",
+                git_ref
+            ),
+            TargetLanguage::Russian => format!(
+                "> Эта документация охватывает только файлы, изменённые с `{}`.",
+                git_ref
+            ),
+        }
+    }
+
+    /// 数字的千位分隔符
+    pub fn thousands_separator(&self) -> &'static str {
+        match self {
+            TargetLanguage::Chinese
+            | TargetLanguage::English
+            | TargetLanguage::Japanese
+            | TargetLanguage::Korean => ",",
+            TargetLanguage::German => ".",
+            // 法语使用窄不换行空格，俄语使用不换行空格
+            TargetLanguage::French => "\u{202F}",
+            TargetLanguage::Russian => "\u{00A0}",
+        }
+    }
+
+    /// 按语言习惯格式化整数（添加千位分隔符）
+    pub fn format_number(&self, n: usize) -> String {
+        let digits = n.to_string();
+        let separator = self.thousands_separator();
+        let mut result = String::with_capacity(digits.len() + digits.len() / 3 * separator.len());
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                result.push_str(separator);
+            }
+            result.push(digit);
+        }
+        result
+    }
+
+    /// 文档固定文案的翻译，未收录的键返回None
+    fn translation(&self, key: &str) -> Option<&'static str> {
+        match self {
+            TargetLanguage::Chinese => chinese_text(key),
+            TargetLanguage::English => english_text(key),
+            TargetLanguage::Japanese => japanese_text(key),
+            TargetLanguage::Korean => korean_text(key),
+            TargetLanguage::German => german_text(key),
+            TargetLanguage::French => french_text(key),
+            TargetLanguage::Russian => russian_text(key),
+        }
+    }
+
     /// 获取目录名
     pub fn get_directory_name(&self, dir_type: &str) -> String {
         match self {
@@ -133,6 +207,7 @@ impl TargetLanguage {
                 "workflow" => "3、工作流程.md".to_string(),
                 "boundary" => "5、边界调用.md".to_string(),
                 "code_index" => "6、代码索引.md".to_string(),
+                "build_system" => "7、构建与持续集成.md".to_string(),
                 _ => format!("{}.md", doc_type),
             },
             TargetLanguage::English => match doc_type {
@@ -141,6 +216,7 @@ impl TargetLanguage {
                 "workflow" => "3.Workflow.md".to_string(),
                 "boundary" => "5.Boundary-Interfaces.md".to_string(),
                 "code_index" => "6.Code-Index.md".to_string(),
+                "build_system" => "7.Build-and-CI.md".to_string(),
                 _ => format!("{}.md", doc_type),
             },
             TargetLanguage::Japanese => match doc_type {
@@ -149,6 +225,7 @@ impl TargetLanguage {
                 "workflow" => "3-ワークフロー.md".to_string(),
                 "boundary" => "5-境界インターフェース.md".to_string(),
                 "code_index" => "6-コードインデックス.md".to_string(),
+                "build_system" => "7-ビルドとCI.md".to_string(),
                 _ => format!("{}.md", doc_type),
             },
             TargetLanguage::Korean => match doc_type {
@@ -157,6 +234,7 @@ impl TargetLanguage {
                 "workflow" => "3-워크플로우.md".to_string(),
                 "boundary" => "5-경계-인터페이스.md".to_string(),
                 "code_index" => "6-코드-인덱스.md".to_string(),
+                "build_system" => "7-빌드와-CI.md".to_string(),
                 _ => format!("{}.md", doc_type),
             },
             TargetLanguage::German => match doc_type {
@@ -165,6 +243,7 @@ impl TargetLanguage {
                 "workflow" => "3-Arbeitsablauf.md".to_string(),
                 "boundary" => "5-Grenzschnittstellen.md".to_string(),
                 "code_index" => "6-Code-Index.md".to_string(),
+                "build_system" => "7-Build-und-CI.md".to_string(),
                 _ => format!("{}.md", doc_type),
             },
             TargetLanguage::French => match doc_type {
@@ -173,6 +252,7 @@ impl TargetLanguage {
                 "workflow" => "3-Flux-de-Travail.md".to_string(),
                 "boundary" => "5-Interfaces-de-Frontière.md".to_string(),
                 "code_index" => "6-Index-de-Code.md".to_string(),
+                "build_system" => "7-Build-et-CI.md".to_string(),
                 _ => format!("{}.md", doc_type),
             },
             TargetLanguage::Russian => match doc_type {
@@ -181,8 +261,387 @@ impl TargetLanguage {
                 "workflow" => "3-Рабочий-Процесс.md".to_string(),
                 "boundary" => "5-Граничные-Интерфейсы.md".to_string(),
                 "code_index" => "6-Индекс-Кода.md".to_string(),
+                "build_system" => "7-Сборка-и-CI.md".to_string(),
                 _ => format!("{}.md", doc_type),
             },
         }
     }
 }
+
+/// 文档固定文案（章节标题、字段标签等）的本地化查询：
+/// 依次查找目标语言、回退语言和英文，避免缺失译文时输出中文
+#[derive(Debug, Clone)]
+pub struct Localizer {
+    language: TargetLanguage,
+    fallback: TargetLanguage,
+}
+
+impl Localizer {
+    pub fn new(language: TargetLanguage, fallback: TargetLanguage) -> Self {
+        Self { language, fallback }
+    }
+
+    /// 查询文案，所有语言均未收录时返回键本身
+    pub fn text(&self, key: &'static str) -> &'static str {
+        [&self.language, &self.fallback, &TargetLanguage::English]
+            .into_iter()
+            .find_map(|language| language.translation(key))
+            .unwrap_or(key)
+    }
+}
+
+/// 中文文案（完整）
+fn chinese_text(key: &str) -> Option<&'static str> {
+    let text = match key {
+        "build.title" => "构建与持续集成",
+        "build.intro" => "本文档汇总了从配置文件中识别出的持续集成流水线、构建工具与包管理器脚本。",
+        "build.ci" => "持续集成",
+        "build.tools" => "构建工具",
+        "build.scripts" => "包管理器脚本",
+        "build.scripts_table" => "| 脚本 | 命令 | 来源 |",
+        "boundary.title" => "系统边界接口文档",
+        "boundary.intro" => {
+            "本文档描述了系统的外部调用接口，包括CLI命令、API端点、配置参数等边界机制。"
+        }
+        "boundary.cli" => "命令行接口 (CLI)",
+        "boundary.api" => "API接口",
+        "boundary.router" => "Router路由",
+        "boundary.integration" => "集成建议",
+        "boundary.confidence" => "分析置信度",
+        "boundary.features" => "可配置特性",
+        "boundary.features_intro" => "以下构建特性与运行时开关会影响系统的编译产物或运行行为。",
+        "boundary.cargo_features" => "Cargo特性",
+        "boundary.features_table" => "| 特性 | 默认启用 | 连带启用 | 声明位置 |",
+        "boundary.cfg_gates" => "条件编译门控",
+        "boundary.env_vars" => "环境变量",
+        "boundary.api_surface" => "公开API稳定性",
+        "boundary.api_surface_intro" => {
+            "以下统计核心文件中的公开接口，并列出被标记为弃用、仅供内部使用或不稳定的接口。"
+        }
+        "boundary.api_surface_table" => "| 接口 | 类型 | 稳定性 | 位置 | 标记 |",
+        "workflow.entry_points" => "程序入口与运行时调用链",
+        "module.related" => "相关模块",
+        "module.depends_on" => "依赖的模块",
+        "module.depended_by" => "被以下模块依赖",
+        "code_index.duplication" => "潜在重复代码",
+        "code_index.duplication_intro" => {
+            "以下文件的代码摘要语义高度相似，可能存在重复实现，可考虑合并或抽取公共逻辑。"
+        }
+        "code_index.duplication_table" => "| 文件 | 相似度 |",
+        "label.config_file" => "配置文件",
+        "label.description" => "描述",
+        "label.source_file" => "源文件",
+        "label.arguments" => "参数",
+        "label.options" => "选项",
+        "label.required" => "必需",
+        "label.optional" => "可选",
+        "label.default" => "默认",
+        "label.usage_examples" => "使用示例",
+        "label.request_format" => "请求格式",
+        "label.response_format" => "响应格式",
+        "label.authentication" => "认证方式",
+        "label.curl_example" => "cURL 调用示例",
+        "label.rust_client_example" => "Rust 客户端示例",
+        "label.success_response" => "成功响应示例",
+        "label.error_response" => "错误响应示例",
+        "label.error_message" => "错误描述",
+        "label.example_code" => "示例代码",
+        "label.best_practices" => "最佳实践",
+        "label.yes" => "是",
+        "label.no" => "否",
+        "label.entry_point" => "入口点",
+        "label.public_items" => "公开接口数",
+        "stability.deprecated" => "已弃用",
+        "stability.internal" => "内部",
+        "stability.unstable" => "不稳定",
+        "summary.full_title" => "项目分析总结报告（完整版）",
+        "summary.brief_title" => "项目分析摘要报告",
+        "summary.generated_at" => "生成时间",
+        "summary.timing" => "执行耗时统计",
+        "summary.cache" => "缓存性能统计与节约效果",
+        "summary.cache_metrics" => "性能指标",
+        "summary.cache_savings" => "节约效果",
+        "summary.research_data" => "核心调研数据汇总",
+        "summary.system_context" => "系统上下文调研报告",
+        "summary.domain_modules" => "领域模块调研报告",
+        "summary.workflow" => "工作流调研报告",
+        "summary.code_insights" => "代码洞察数据",
+        "summary.memory" => "Memory存储统计",
+        "summary.documents" => "生成文档统计",
+        "summary.execution_overview" => "执行概览",
+        "summary.cache_overview" => "缓存效果概览",
+        "summary.research_overview" => "调研数据概览",
+        "summary.memory_overview" => "Memory存储概览",
+        "summary.memory_distribution" => "主要存储分布（前3位）",
+        "summary.documents_overview" => "文档生成概览",
+        "summary.assessment" => "总体评估",
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// 英文文案（完整，作为最终回退）
+fn english_text(key: &str) -> Option<&'static str> {
+    let text = match key {
+        "build.title" => "Build and Continuous Integration",
+        "build.intro" => {
+            "This document summarizes the continuous integration pipelines, build tools and package manager scripts detected in configuration files."
+        }
+        "build.ci" => "Continuous Integration",
+        "build.tools" => "Build Tools",
+        "build.scripts" => "Package Manager Scripts",
+        "build.scripts_table" => "| Script | Command | Source |",
+        "boundary.title" => "System Boundary Interfaces",
+        "boundary.intro" => {
+            "This document describes the external interfaces of the system, including CLI commands, API endpoints, configuration parameters and other boundary mechanisms."
+        }
+        "boundary.cli" => "Command-Line Interface (CLI)",
+        "boundary.api" => "API Endpoints",
+        "boundary.router" => "Router Routes",
+        "boundary.integration" => "Integration Suggestions",
+        "boundary.confidence" => "Analysis confidence",
+        "boundary.features" => "Configurable Features",
+        "boundary.features_intro" => {
+            "The following build features and runtime switches affect the compiled artifacts or runtime behavior of the system."
+        }
+        "boundary.cargo_features" => "Cargo Features",
+        "boundary.features_table" => "| Feature | Default | Enables | Declared in |",
+        "boundary.cfg_gates" => "Conditional Compilation Gates",
+        "boundary.env_vars" => "Environment Variables",
+        "boundary.api_surface" => "Public API Stability",
+        "boundary.api_surface_intro" => {
+            "The following summarizes the public items in core files and lists those marked as deprecated, internal or unstable."
+        }
+        "boundary.api_surface_table" => "| Item | Kind | Stability | Location | Marker |",
+        "workflow.entry_points" => "Program Entry Points and Runtime Flow",
+        "module.related" => "Related Modules",
+        "module.depends_on" => "Depends on",
+        "module.depended_by" => "Used by",
+        "code_index.duplication" => "Potential Duplication",
+        "code_index.duplication_intro" => {
+            "The code summaries of the following files are semantically near-identical and may duplicate each other; consider merging them or extracting shared logic."
+        }
+        "code_index.duplication_table" => "| Files | Similarity |",
+        "label.config_file" => "Configuration file",
+        "label.description" => "Description",
+        "label.source_file" => "Source file",
+        "label.arguments" => "Arguments",
+        "label.options" => "Options",
+        "label.required" => "required",
+        "label.optional" => "optional",
+        "label.default" => "default",
+        "label.usage_examples" => "Usage examples",
+        "label.request_format" => "Request format",
+        "label.response_format" => "Response format",
+        "label.authentication" => "Authentication",
+        "label.curl_example" => "cURL example",
+        "label.rust_client_example" => "Rust client example",
+        "label.success_response" => "Success response example",
+        "label.error_response" => "Error response example",
+        "label.error_message" => "Error description",
+        "label.example_code" => "Example code",
+        "label.best_practices" => "Best practices",
+        "label.yes" => "Yes",
+        "label.no" => "No",
+        "label.entry_point" => "Entry point",
+        "label.public_items" => "Public items",
+        "stability.deprecated" => "Deprecated",
+        "stability.internal" => "Internal",
+        "stability.unstable" => "Unstable",
+        "summary.full_title" => "Project Analysis Summary Report (Full)",
+        "summary.brief_title" => "Project Analysis Brief Report",
+        "summary.generated_at" => "Generated at",
+        "summary.timing" => "Execution Time Statistics",
+        "summary.cache" => "Cache Performance and Savings",
+        "summary.cache_metrics" => "Performance Metrics",
+        "summary.cache_savings" => "Savings",
+        "summary.research_data" => "Core Research Data",
+        "summary.system_context" => "System Context Research Report",
+        "summary.domain_modules" => "Domain Modules Research Report",
+        "summary.workflow" => "Workflow Research Report",
+        "summary.code_insights" => "Code Insights",
+        "summary.memory" => "Memory Storage Statistics",
+        "summary.documents" => "Generated Documents",
+        "summary.execution_overview" => "Execution Overview",
+        "summary.cache_overview" => "Cache Overview",
+        "summary.research_overview" => "Research Data Overview",
+        "summary.memory_overview" => "Memory Storage Overview",
+        "summary.memory_distribution" => "Top 3 Storage Scopes",
+        "summary.documents_overview" => "Documents Overview",
+        "summary.assessment" => "Overall Assessment",
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// 日文文案（仅章节标题，其余回退）
+fn japanese_text(key: &str) -> Option<&'static str> {
+    let text = match key {
+        "build.title" => "ビルドと継続的インテグレーション",
+        "build.ci" => "継続的インテグレーション",
+        "build.tools" => "ビルドツール",
+        "build.scripts" => "パッケージマネージャースクリプト",
+        "boundary.title" => "システム境界インターフェース",
+        "boundary.cli" => "コマンドラインインターフェース (CLI)",
+        "boundary.api" => "APIインターフェース",
+        "boundary.router" => "ルーター",
+        "boundary.integration" => "統合の提案",
+        "boundary.features" => "設定可能な機能",
+        "boundary.cargo_features" => "Cargo機能",
+        "boundary.cfg_gates" => "条件付きコンパイルゲート",
+        "boundary.env_vars" => "環境変数",
+        "boundary.api_surface" => "公開APIの安定性",
+        "workflow.entry_points" => "プログラムのエントリーポイントと実行時フロー",
+        "module.related" => "関連モジュール",
+        "code_index.duplication" => "潜在的な重複コード",
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// 韩文文案（仅章节标题，其余回退）
+fn korean_text(key: &str) -> Option<&'static str> {
+    let text = match key {
+        "build.title" => "빌드 및 지속적 통합",
+        "build.ci" => "지속적 통합",
+        "build.tools" => "빌드 도구",
+        "build.scripts" => "패키지 매니저 스크립트",
+        "boundary.title" => "시스템 경계 인터페이스",
+        "boundary.cli" => "명령줄 인터페이스 (CLI)",
+        "boundary.api" => "API 인터페이스",
+        "boundary.router" => "라우터",
+        "boundary.integration" => "통합 제안",
+        "boundary.features" => "구성 가능한 기능",
+        "boundary.cargo_features" => "Cargo 기능",
+        "boundary.cfg_gates" => "조건부 컴파일 게이트",
+        "boundary.env_vars" => "환경 변수",
+        "boundary.api_surface" => "공개 API 안정성",
+        "workflow.entry_points" => "프로그램 진입점과 런타임 흐름",
+        "module.related" => "관련 모듈",
+        "code_index.duplication" => "잠재적 중복 코드",
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// 德文文案（仅章节标题，其余回退）
+fn german_text(key: &str) -> Option<&'static str> {
+    let text = match key {
+        "build.title" => "Build und Continuous Integration",
+        "build.ci" => "Continuous Integration",
+        "build.tools" => "Build-Werkzeuge",
+        "build.scripts" => "Paketmanager-Skripte",
+        "boundary.title" => "Systemgrenzen-Schnittstellen",
+        "boundary.cli" => "Kommandozeilenschnittstelle (CLI)",
+        "boundary.api" => "API-Schnittstellen",
+        "boundary.router" => "Router-Routen",
+        "boundary.integration" => "Integrationsempfehlungen",
+        "boundary.features" => "Konfigurierbare Features",
+        "boundary.cargo_features" => "Cargo-Features",
+        "boundary.cfg_gates" => "Bedingte Kompilierung",
+        "boundary.env_vars" => "Umgebungsvariablen",
+        "boundary.api_surface" => "Stabilität der öffentlichen API",
+        "workflow.entry_points" => "Programmeinstiegspunkte und Laufzeitablauf",
+        "module.related" => "Verwandte Module",
+        "code_index.duplication" => "Mögliche Duplikate",
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// 法文文案（仅章节标题，其余回退）
+fn french_text(key: &str) -> Option<&'static str> {
+    let text = match key {
+        "build.title" => "Build et intégration continue",
+        "build.ci" => "Intégration continue",
+        "build.tools" => "Outils de build",
+        "build.scripts" => "Scripts du gestionnaire de paquets",
+        "boundary.title" => "Interfaces de frontière du système",
+        "boundary.cli" => "Interface en ligne de commande (CLI)",
+        "boundary.api" => "Interfaces API",
+        "boundary.router" => "Routes du routeur",
+        "boundary.integration" => "Recommandations d'intégration",
+        "boundary.features" => "Fonctionnalités configurables",
+        "boundary.cargo_features" => "Fonctionnalités Cargo",
+        "boundary.cfg_gates" => "Compilation conditionnelle",
+        "boundary.env_vars" => "Variables d'environnement",
+        "boundary.api_surface" => "Stabilité de l'API publique",
+        "workflow.entry_points" => "Points d'entrée du programme et flux d'exécution",
+        "module.related" => "Modules associés",
+        "code_index.duplication" => "Duplication potentielle",
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// 俄文文案（仅章节标题，其余回退）
+fn russian_text(key: &str) -> Option<&'static str> {
+    let text = match key {
+        "build.title" => "Сборка и непрерывная интеграция",
+        "build.ci" => "Непрерывная интеграция",
+        "build.tools" => "Инструменты сборки",
+        "build.scripts" => "Скрипты менеджера пакетов",
+        "boundary.title" => "Граничные интерфейсы системы",
+        "boundary.cli" => "Интерфейс командной строки (CLI)",
+        "boundary.api" => "API-интерфейсы",
+        "boundary.router" => "Маршруты роутера",
+        "boundary.integration" => "Рекомендации по интеграции",
+        "boundary.features" => "Настраиваемые возможности",
+        "boundary.cargo_features" => "Возможности Cargo",
+        "boundary.cfg_gates" => "Условная компиляция",
+        "boundary.env_vars" => "Переменные окружения",
+        "boundary.api_surface" => "Стабильность публичного API",
+        "workflow.entry_points" => "Точки входа программы и поток выполнения",
+        "module.related" => "Связанные модули",
+        "code_index.duplication" => "Возможное дублирование кода",
+        _ => return None,
+    };
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number_groups_by_thousands() {
+        let lang = TargetLanguage::English;
+        assert_eq!(lang.format_number(0), "0");
+        assert_eq!(lang.format_number(999), "999");
+        assert_eq!(lang.format_number(1000), "1,000");
+        assert_eq!(lang.format_number(123456), "123,456");
+        assert_eq!(lang.format_number(1234567), "1,234,567");
+        assert_eq!(
+            TargetLanguage::Chinese.format_number(usize::MAX),
+            "18,446,744,073,709,551,615"
+        );
+    }
+
+    #[test]
+    fn test_format_number_uses_locale_separator() {
+        assert_eq!(TargetLanguage::German.format_number(1234567), "1.234.567");
+        assert_eq!(
+            TargetLanguage::French.format_number(1234567),
+            "1\u{202F}234\u{202F}567"
+        );
+        assert_eq!(
+            TargetLanguage::Russian.format_number(12345),
+            "12\u{00A0}345"
+        );
+    }
+
+    #[test]
+    fn test_localizer_falls_back_to_english_before_chinese() {
+        let german = Localizer::new(TargetLanguage::German, TargetLanguage::English);
+        assert_eq!(german.text("build.ci"), "Continuous Integration");
+        // 德文未收录字段标签，回退到英文而不是中文
+        assert_eq!(german.text("label.description"), "Description");
+
+        let french = Localizer::new(TargetLanguage::French, TargetLanguage::Chinese);
+        assert_eq!(french.text("label.description"), "描述");
+
+        let chinese = Localizer::new(TargetLanguage::Chinese, TargetLanguage::English);
+        assert_eq!(chinese.text("boundary.title"), "系统边界接口文档");
+        assert_eq!(chinese.text("unknown.key"), "unknown.key");
+    }
+}
@@ -9,5 +9,6 @@ pub mod types;
 pub mod utils;
 
 // Re-export commonly used types
-pub use config::Config;
-pub use generator::workflow::launch;
+pub use config::{Config, builder::ConfigBuilder};
+pub use generator::events::{EventListener, GeneratorEvent};
+pub use generator::workflow::{launch, launch_with_listener};
@@ -3,7 +3,9 @@
 use crate::{
     config::Config,
     llm::client::providers::{ProviderAgent, ProviderClient},
-    llm::tools::{file_explorer::AgentToolFileExplorer, file_reader::AgentToolFileReader},
+    llm::tools::{
+        file_explorer::AgentToolFileExplorer, file_reader::AgentToolFileReader, time::AgentToolTime,
+    },
 };
 
 /// Agent构建器
@@ -25,6 +27,7 @@ impl<'a> AgentBuilder<'a> {
         if !llm_config.disable_preset_tools {
             let file_explorer = AgentToolFileExplorer::new(self.config.clone());
             let file_reader = AgentToolFileReader::new(self.config.clone());
+            let tool_time = AgentToolTime::new(self.config.time_zone());
 
             let system_prompt_with_tools = format!(
                 "{}\n不要虚构不存在的代码，如果你需要了解更多项目的工程结构和源码内容，积极的调用工具来获得更多上下文补充",
@@ -37,6 +40,7 @@ impl<'a> AgentBuilder<'a> {
                 llm_config,
                 &file_explorer,
                 &file_reader,
+                &tool_time,
             )
         } else {
             self.client
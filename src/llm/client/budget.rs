@@ -0,0 +1,73 @@
+use anyhow::Result;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::llm::client::types::TokenUsage;
+
+/// 累计成本以微美元为单位存储
+const MICRO_USD_PER_USD: f64 = 1_000_000.0;
+
+/// 累计估算成本超出上限时返回的错误
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetExceeded {
+    pub spent_usd: f64,
+    pub limit_usd: f64,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LLM调用累计估算成本 ${:.4} 已超出上限 ${:.4}，停止发起新的调用",
+            self.spent_usd, self.limit_usd
+        )
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+/// 本次运行的LLM调用成本跟踪器，由所有克隆的LLM客户端共享
+#[derive(Debug)]
+pub struct CostTracker {
+    spent_micro_usd: AtomicU64,
+    limit_usd: Option<f64>,
+}
+
+impl CostTracker {
+    pub fn new(limit_usd: Option<f64>) -> Self {
+        Self {
+            spent_micro_usd: AtomicU64::new(0),
+            limit_usd,
+        }
+    }
+
+    /// 记录一次调用的估算成本
+    pub fn record(&self, token_usage: &TokenUsage, model_name: &str) {
+        let cost = token_usage.estimate_cost(model_name);
+        self.spent_micro_usd
+            .fetch_add((cost * MICRO_USD_PER_USD).round() as u64, Ordering::Relaxed);
+    }
+
+    /// 累计估算成本（美元）
+    pub fn spent_usd(&self) -> f64 {
+        self.spent_micro_usd.load(Ordering::Relaxed) as f64 / MICRO_USD_PER_USD
+    }
+
+    /// 累计成本已超出上限时返回超限信息
+    pub fn exceeded(&self) -> Option<BudgetExceeded> {
+        let limit_usd = self.limit_usd?;
+        let spent_usd = self.spent_usd();
+        (spent_usd > limit_usd).then_some(BudgetExceeded {
+            spent_usd,
+            limit_usd,
+        })
+    }
+
+    /// 发起新的调用前检查成本上限
+    pub fn ensure_within_budget(&self) -> Result<()> {
+        match self.exceeded() {
+            Some(exceeded) => Err(exceeded.into()),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! 向量嵌入 - 为核心文件排序、重复代码识别等基于语义相似度的功能提供统一入口
+
+use anyhow::Result;
+use std::future::Future;
+
+use crate::config::LLMProvider;
+
+/// 文本向量嵌入器
+pub trait Embedder {
+    /// 批量计算文本的向量嵌入，返回顺序与输入一致
+    fn embed(&self, texts: Vec<String>) -> impl Future<Output = Result<Vec<Vec<f32>>>> + Send;
+}
+
+/// provider默认使用的嵌入模型，不支持向量嵌入的provider返回None
+pub fn default_embedding_model(provider: &LLMProvider) -> Option<&'static str> {
+    match provider {
+        LLMProvider::OpenAI => Some("text-embedding-3-small"),
+        LLMProvider::Gemini => Some("text-embedding-004"),
+        LLMProvider::Ollama => Some("nomic-embed-text"),
+        _ => None,
+    }
+}
+
+/// 余弦相似度，维度不一致或存在零向量时返回0
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+/// 计算文本两两之间的相似度，返回相似度不低于阈值的下标对（按相似度降序）
+pub async fn similar_pairs<E: Embedder>(
+    embedder: &E,
+    texts: &[String],
+    threshold: f32,
+) -> Result<Vec<(usize, usize, f32)>> {
+    if texts.len() < 2 {
+        return Ok(Vec::new());
+    }
+    let vectors = embedder.embed(texts.to_vec()).await?;
+
+    let mut pairs = Vec::new();
+    for i in 0..vectors.len() {
+        for j in (i + 1)..vectors.len() {
+            let similarity = cosine_similarity(&vectors[i], &vectors[j]);
+            if similarity >= threshold {
+                pairs.push((i, j, similarity));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按文本首个关键字返回固定向量的测试嵌入器
+    struct FixedEmbedder;
+
+    impl Embedder for FixedEmbedder {
+        async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| match text.split_whitespace().next() {
+                    Some("billing") => vec![1.0, 0.0, 0.1],
+                    Some("invoice") => vec![0.9, 0.0, 0.12],
+                    _ => vec![0.0, 1.0, 0.0],
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_similar_pairs_with_fixed_vectors() {
+        let texts = vec![
+            "billing ledger service".to_string(),
+            "logging utilities".to_string(),
+            "invoice ledger service".to_string(),
+        ];
+
+        let pairs = similar_pairs(&FixedEmbedder, &texts, 0.95).await.unwrap();
+
+        assert_eq!(pairs.len(), 1);
+        assert_eq!((pairs[0].0, pairs[0].1), (0, 2));
+        assert!(pairs[0].2 > 0.99);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+}
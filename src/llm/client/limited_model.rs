@@ -0,0 +1,48 @@
+//! 并发受限的补全模型 - 包装provider的补全模型，每次HTTP补全请求占用一个provider并发许可
+
+use rig::completion::{CompletionError, CompletionModel, CompletionRequest, CompletionResponse};
+use rig::streaming::StreamingCompletionResponse;
+use std::sync::Arc;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// 并发受限的补全模型：许可只在单次请求期间占用，ReAct多轮对话的工具执行与重试等待不占用许可
+#[derive(Clone)]
+pub struct LimitedModel<M> {
+    inner: M,
+    limiter: Arc<Semaphore>,
+}
+
+impl<M> LimitedModel<M> {
+    pub fn new(inner: M, limiter: Arc<Semaphore>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+/// 占用一个provider并发许可，超出provider并发上限的请求排队等待
+pub async fn acquire(limiter: &Semaphore) -> Result<SemaphorePermit<'_>, CompletionError> {
+    limiter
+        .acquire()
+        .await
+        .map_err(|e| CompletionError::ProviderError(e.to_string()))
+}
+
+impl<M: CompletionModel> CompletionModel for LimitedModel<M> {
+    type Response = M::Response;
+    type StreamingResponse = M::StreamingResponse;
+
+    async fn completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse<Self::Response>, CompletionError> {
+        let _permit = acquire(&self.limiter).await?;
+        self.inner.completion(request).await
+    }
+
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        let _permit = acquire(&self.limiter).await?;
+        self.inner.stream(request).await
+    }
+}
@@ -0,0 +1,263 @@
+//! 测试用的OpenAI兼容模拟服务：记录收到的请求，按请求内容返回合法的补全/嵌入响应
+
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// 模拟服务返回的token用量
+pub const MOCK_PROMPT_TOKENS: usize = 120;
+pub const MOCK_COMPLETION_TOKENS: usize = 30;
+
+/// 模拟服务收到的一次HTTP请求
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub path: String,
+    /// 小写化的请求头原文
+    pub headers: String,
+    pub body: Value,
+}
+
+type Handler = Arc<dyn Fn(&MockRequest) -> Option<Value> + Send + Sync>;
+
+/// 本地OpenAI兼容模拟服务
+pub struct MockServer {
+    pub api_base_url: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+impl MockServer {
+    /// 启动使用默认应答的模拟服务
+    pub async fn start() -> Self {
+        Self::with_handler(|request| Some(openai_reply(request))).await
+    }
+
+    /// 启动使用自定义应答的模拟服务，应答为None时返回500错误
+    pub async fn with_handler(
+        handler: impl Fn(&MockRequest) -> Option<Value> + Send + Sync + 'static,
+    ) -> Self {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let handler: Handler = Arc::new(handler);
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let recorded = recorded.clone();
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    let _ = serve(socket, recorded, handler).await;
+                });
+            }
+        });
+        Self {
+            api_base_url: format!("http://{}/v1", address),
+            requests,
+        }
+    }
+
+    /// 已收到的请求
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// 处理单个连接上的请求（支持keep-alive下的多次请求）
+async fn serve(
+    mut socket: TcpStream,
+    recorded: Arc<Mutex<Vec<MockRequest>>>,
+    handler: Handler,
+) -> std::io::Result<()> {
+    let mut buffer = Vec::new();
+    loop {
+        let header_end = loop {
+            if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+            let mut chunk = [0u8; 8192];
+            let read = socket.read(&mut chunk).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        };
+        let head = String::from_utf8_lossy(&buffer[..header_end]).to_lowercase();
+        let content_length = head
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        while buffer.len() < header_end + content_length {
+            let mut chunk = [0u8; 8192];
+            let read = socket.read(&mut chunk).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        let body: Vec<u8> = buffer.drain(..header_end + content_length).collect();
+        let request = MockRequest {
+            path: head
+                .lines()
+                .next()
+                .and_then(|line| line.split_whitespace().nth(1))
+                .unwrap_or_default()
+                .to_string(),
+            headers: head,
+            body: serde_json::from_slice(&body[header_end..]).unwrap_or(Value::Null),
+        };
+        let (status, reply) = match handler(&request) {
+            Some(reply) => ("200 OK", reply.to_string()),
+            None => ("500 Internal Server Error", "{}".to_string()),
+        };
+        recorded.lock().unwrap().push(request);
+        let response = format!(
+            "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+            status,
+            reply.len(),
+            reply
+        );
+        socket.write_all(response.as_bytes()).await?;
+    }
+}
+
+/// 默认应答：嵌入请求返回固定向量；携带submit工具的补全请求返回符合其JSON Schema的工具调用，
+/// JSON模式的补全请求返回符合系统提示词中JSON Schema的文本，其余补全请求返回一段Markdown文本
+pub fn openai_reply(request: &MockRequest) -> Value {
+    if request.path.ends_with("/embeddings") {
+        let count = request.body["input"].as_array().map_or(1, Vec::len);
+        let data: Vec<Value> = (0..count)
+            .map(|index| json!({ "object": "embedding", "index": index, "embedding": [1.0, 0.0, 0.0] }))
+            .collect();
+        return json!({
+            "object": "list",
+            "data": data,
+            "model": request.body["model"],
+            "usage": { "prompt_tokens": MOCK_PROMPT_TOKENS, "total_tokens": MOCK_PROMPT_TOKENS },
+        });
+    }
+
+    let submit_schema = request.body["tools"].as_array().and_then(|tools| {
+        tools
+            .iter()
+            .find(|tool| tool["function"]["name"] == "submit")
+            .map(|tool| &tool["function"]["parameters"])
+    });
+    if request.body["response_format"]["type"] == "json_object" {
+        let schema = request.body["messages"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .flat_map(|message| match &message["content"] {
+                Value::Array(parts) => parts.iter().map(|part| &part["text"]).collect(),
+                content => vec![content],
+            })
+            .filter_map(Value::as_str)
+            .find_map(|content| {
+                content
+                    .split_once("JSON Schema：")
+                    .map(|(_, schema)| schema)
+            })
+            .and_then(|schema| serde_json::from_str::<Value>(schema.trim()).ok())
+            .unwrap_or_else(|| json!({}));
+        return chat_completion(
+            request,
+            json!({
+                "role": "assistant",
+                "content": sample_from_schema(&schema, &schema).to_string(),
+            }),
+        );
+    }
+
+    let message = match submit_schema {
+        Some(schema) => json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{
+                "id": "call_submit",
+                "type": "function",
+                "function": {
+                    "name": "submit",
+                    "arguments": sample_from_schema(schema, schema).to_string(),
+                },
+            }],
+        }),
+        None => json!({
+            "role": "assistant",
+            "content": "# Mock\n\nGenerated by the mock model service.\n",
+        }),
+    };
+    chat_completion(request, message)
+}
+
+/// 包装为OpenAI格式的补全响应
+fn chat_completion(request: &MockRequest, message: Value) -> Value {
+    json!({
+        "id": "chatcmpl-mock",
+        "object": "chat.completion",
+        "created": 0,
+        "model": request.body["model"],
+        "choices": [{ "index": 0, "message": message, "finish_reason": "stop" }],
+        "usage": {
+            "prompt_tokens": MOCK_PROMPT_TOKENS,
+            "completion_tokens": MOCK_COMPLETION_TOKENS,
+            "total_tokens": MOCK_PROMPT_TOKENS + MOCK_COMPLETION_TOKENS,
+        },
+    })
+}
+
+/// 按JSON Schema生成一个最小的合法实例：只填充必填字段，枚举取第一个值
+pub fn sample_from_schema(schema: &Value, root: &Value) -> Value {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let pointer = reference.trim_start_matches('#');
+        return root
+            .pointer(pointer)
+            .map(|target| sample_from_schema(target, root))
+            .unwrap_or(Value::Null);
+    }
+    if let Some(value) = schema.get("const") {
+        return value.clone();
+    }
+    if let Some(values) = schema["enum"].as_array() {
+        return values.first().cloned().unwrap_or(Value::Null);
+    }
+    for key in ["oneOf", "anyOf", "allOf"] {
+        if let Some(variants) = schema[key].as_array() {
+            let variant = variants
+                .iter()
+                .find(|variant| variant["type"] != "null")
+                .or(variants.first());
+            if let Some(variant) = variant {
+                return sample_from_schema(variant, root);
+            }
+        }
+    }
+    let schema_type = match &schema["type"] {
+        Value::Array(types) => types
+            .iter()
+            .find(|t| *t != "null")
+            .and_then(Value::as_str)
+            .unwrap_or("null"),
+        other => other.as_str().unwrap_or("object"),
+    };
+    match schema_type {
+        "object" => {
+            let mut object = serde_json::Map::new();
+            for name in schema["required"].as_array().into_iter().flatten() {
+                if let Some(name) = name.as_str() {
+                    object.insert(
+                        name.to_string(),
+                        sample_from_schema(&schema["properties"][name], root),
+                    );
+                }
+            }
+            Value::Object(object)
+        }
+        "array" => json!([]),
+        "string" => json!("mock"),
+        "integer" => json!(1),
+        "number" => json!(1.0),
+        "boolean" => json!(false),
+        _ => Value::Null,
+    }
+}
@@ -1,13 +1,30 @@
 //! LLM客户端 - 提供统一的LLM服务接口
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 
-use crate::{config::Config, llm::client::utils::evaluate_befitting_model};
+use crate::{
+    config::Config,
+    llm::client::{
+        types::TokenUsage,
+        utils::{
+            estimate_token_usage, evaluate_befitting_model, is_likely_truncated, mark_truncated,
+            resolve_token_usage,
+        },
+    },
+};
+use log::{error, info, warn};
 
 mod agent_builder;
+pub mod budget;
+pub mod embedding;
+mod limited_model;
+#[cfg(test)]
+pub(crate) mod mock_server;
 mod providers;
 mod react;
 mod react_executor;
@@ -18,35 +35,88 @@ pub mod utils;
 pub use react::{ReActConfig, ReActResponse};
 
 use agent_builder::AgentBuilder;
-use providers::ProviderClient;
+use budget::{BudgetExceeded, CostTracker};
+use embedding::{Embedder, default_embedding_model};
+use providers::{ProviderAgent, ProviderClient};
 use react_executor::ReActExecutor;
 use summary_reasoner::SummaryReasoner;
 
+/// 回复被截断时自动续写的最大次数
+const MAX_TRUNCATION_CONTINUATIONS: usize = 2;
+
+/// 单次嵌入请求的最大文本数量（Gemini批量嵌入接口最多接受100条输入）
+const MAX_EMBEDDING_BATCH: usize = 100;
+
 /// LLM客户端 - 提供统一的LLM服务接口
 #[derive(Clone)]
 pub struct LLMClient {
     config: Config,
     client: ProviderClient,
+    cost_tracker: Arc<CostTracker>,
+    /// 兜底调用使用的provider客户端，未配置`fallover_provider`时使用`client`
+    fallover_client: Option<ProviderClient>,
 }
 
 impl LLMClient {
     /// 创建新的LLM客户端
-    pub fn new(config: Config) -> Result<Self> {
-        let client = ProviderClient::new(&config.llm)?;
-        Ok(Self { client, config })
+    pub fn new(mut config: Config) -> Result<Self> {
+        // 可复现模式下使用贪心解码，减少同一prompt多次调用的输出差异
+        if config.deterministic {
+            config.llm.temperature = 0.0;
+        }
+        // 并发请求信号量按provider划分，所有克隆共享；兜底provider与主provider相同时共用同一信号量
+        let limiter = Arc::new(Semaphore::new(config.llm.effective_max_parallels()));
+        let client = ProviderClient::new(&config.llm, limiter.clone())?;
+        let fallover_client = match config.llm.fallover_config() {
+            Some(fallover) => {
+                let fallover_limiter = if fallover.provider == config.llm.provider {
+                    limiter
+                } else {
+                    Arc::new(Semaphore::new(
+                        config.llm.max_parallels_for(&fallover.provider),
+                    ))
+                };
+                Some(ProviderClient::new(&fallover, fallover_limiter)?)
+            }
+            None => None,
+        };
+        let cost_tracker = Arc::new(CostTracker::new(config.llm.max_cost_usd));
+        Ok(Self {
+            client,
+            config,
+            cost_tracker,
+            fallover_client,
+        })
+    }
+
+    /// 累计估算成本已超出`max_cost_usd`时返回超限信息
+    pub fn budget_exceeded(&self) -> Option<BudgetExceeded> {
+        self.cost_tracker.exceeded()
+    }
+
+    /// 成本上限守卫：超限后不再发起调用，调用完成后按实际使用的模型累计其估算成本
+    async fn with_budget<T, F, Fut>(&self, operation: F) -> Result<(T, TokenUsage)>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<(T, TokenUsage, String)>>,
+    {
+        self.cost_tracker.ensure_within_budget()?;
+        let (result, token_usage, model) = operation().await?;
+        self.cost_tracker.record(&token_usage, &model);
+        Ok((result, token_usage))
     }
 
     /// 检查模型连接和功能是否正常
     pub async fn check_connection(&self) -> Result<()> {
-        println!("🔄 正在检查模型连接...");
+        info!("🔄 正在检查模型连接...");
         // 使用一个简单的prompt来测试连接
         match self.prompt_without_react("System: You are a helpful assistant.", "Hello").await {
             Ok(_) => {
-                println!("✅ 模型连接正常");
+                info!("✅ 模型连接正常");
                 Ok(())
             }
             Err(e) => {
-                eprintln!("❌ 模型连接失败: {}", e);
+                error!("❌ 模型连接失败: {}", e);
                 Err(e)
             }
         }
@@ -73,7 +143,7 @@ impl LLMClient {
                 Ok(result) => return Ok(result),
                 Err(err) => {
                     retries += 1;
-                    eprintln!(
+                    error!(
                         "❌ 调用模型服务出错，重试中 (第 {} / {}次尝试): {}",
                         retries, max_retries, err
                     );
@@ -86,45 +156,104 @@ impl LLMClient {
         }
     }
 
-    /// 数据提取方法
-    pub async fn extract<T>(&self, system_prompt: &str, user_prompt: &str) -> Result<T>
+    /// 校验模型回复非空白，启用`retry_on_empty_response`时空白回复视为错误以触发重试
+    fn ensure_non_blank(&self, content: &str) -> Result<()> {
+        if self.config.llm.retry_on_empty_response && content.trim().is_empty() {
+            return Err(anyhow!("模型返回了空白内容"));
+        }
+        Ok(())
+    }
+
+    /// 带空白回复检测的重试逻辑
+    async fn retry_non_blank<F, Fut>(&self, operation: F) -> Result<(String, TokenUsage)>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<(String, TokenUsage), anyhow::Error>>,
+    {
+        self.retry_with_backoff(|| async {
+            let (reply, token_usage) = operation().await?;
+            self.ensure_non_blank(&reply)?;
+            Ok((reply, token_usage))
+        })
+        .await
+    }
+
+    /// 数据提取方法，返回提取结果及token使用情况
+    pub async fn extract<T>(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+    ) -> Result<(T, TokenUsage)>
     where
         T: JsonSchema + for<'a> Deserialize<'a> + Serialize + Send + Sync + 'static,
     {
         let (befitting_model, fallover_model) =
             evaluate_befitting_model(&self.config.llm, system_prompt, user_prompt);
 
-        self.extract_inner(system_prompt, user_prompt, befitting_model, fallover_model)
-            .await
+        self.with_budget(|| {
+            self.extract_inner(
+                &self.client,
+                system_prompt,
+                user_prompt,
+                befitting_model,
+                fallover_model,
+            )
+        })
+        .await
     }
 
+    /// 执行提取，返回提取结果、token使用情况及实际完成提取的模型（失败后可能切换为备选模型，
+    /// 备选模型使用`fallover_provider`对应的客户端）
     async fn extract_inner<T>(
         &self,
+        client: &ProviderClient,
         system_prompt: &str,
         user_prompt: &str,
         befitting_model: String,
         fallover_model: Option<String>,
-    ) -> Result<T>
+    ) -> Result<(T, TokenUsage, String)>
     where
         T: JsonSchema + for<'a> Deserialize<'a> + Serialize + Send + Sync + 'static,
     {
         let llm_config = &self.config.llm;
 
-        let extractor =
-            self.client
-                .create_extractor::<T>(&befitting_model, system_prompt, llm_config);
+        let fallover_client = self.fallover_client.as_ref().unwrap_or(&self.client);
+        // 备选模型与当前模型相同且在同一provider上时（如未单独指定model_powerful），备选尝试只会重复同样的请求，直接跳过
+        let fallover_model = fallover_model
+            .filter(|model| *model != befitting_model || self.fallover_client.is_some());
+
+        let extractor = client.create_extractor::<T>(&befitting_model, system_prompt, llm_config);
 
         self.retry_with_backoff(|| async {
-            match extractor.extract(user_prompt).await {
-                Ok(r) => Ok(r),
+            let extracted = extractor.extract(user_prompt).await.and_then(|(r, token_usage)| {
+                // 提取结果为null或空字符串时同样视为空白回复
+                let output_text = match serde_json::to_value(&r).unwrap_or_default() {
+                    serde_json::Value::Null => String::new(),
+                    serde_json::Value::String(text) => text,
+                    value => value.to_string(),
+                };
+                self.ensure_non_blank(&output_text)?;
+                Ok((r, token_usage))
+            });
+            match extracted {
+                Ok((r, token_usage)) => {
+                    let output_text = serde_json::to_string(&r).unwrap_or_default();
+                    let token_usage = resolve_token_usage(
+                        token_usage,
+                        &format!("{} {}", system_prompt, user_prompt),
+                        &output_text,
+                    );
+                    Ok((r, token_usage, befitting_model.clone()))
+                }
                 Err(e) => match fallover_model {
                     Some(ref model) => {
-                        eprintln!(
+                        error!(
                             "❌ 调用模型服务出错，尝试 {} 次均失败，尝试使用备选模型{}...{}",
                             llm_config.retry_attempts, model, e
                         );
                         let user_prompt_with_fixer = format!("{}\n\n**注意事项**此前我调用大模型过程时存在错误，错误信息为“{}”，你注意你这一次要规避这个错误", user_prompt, e);
                         Box::pin(self.extract_inner(
+                            fallover_client,
                             system_prompt,
                             &user_prompt_with_fixer,
                             model.clone(),
@@ -133,7 +262,7 @@ impl LLMClient {
                         .await
                     }
                     None => {
-                        eprintln!(
+                        error!(
                             "❌ 调用模型服务出错，尝试 {} 次均失败...{}",
                             llm_config.retry_attempts, e
                         );
@@ -145,13 +274,78 @@ impl LLMClient {
         .await
     }
 
-    /// 智能对话方法（使用默认ReAct配置）
-    pub async fn prompt(&self, system_prompt: &str, user_prompt: &str) -> Result<String> {
-        let react_config = ReActConfig::default();
-        let response = self
-            .prompt_with_react(system_prompt, user_prompt, react_config)
-            .await?;
-        Ok(response.content)
+    /// 计算文本的向量嵌入，使用配置的`embedding_model`，未配置时使用provider的默认嵌入模型；
+    /// 文本按批次分多次请求，每批的估算成本计入成本上限
+    pub async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let llm_config = &self.config.llm;
+        let model = llm_config
+            .embedding_model
+            .as_deref()
+            .or_else(|| default_embedding_model(&llm_config.provider))
+            .ok_or_else(|| anyhow!("provider {} 不支持向量嵌入", llm_config.provider))?;
+        let embedder = self.client.create_embedder(model)?;
+
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(MAX_EMBEDDING_BATCH) {
+            self.cost_tracker.ensure_within_budget()?;
+            let batch_vectors = self
+                .retry_with_backoff(|| async {
+                    let _permit = self.client.acquire_permit().await?;
+                    embedder.embed(batch.to_vec()).await
+                })
+                .await?;
+            // 嵌入接口不返回token用量，按输入文本估算
+            self.cost_tracker
+                .record(&estimate_token_usage(&batch.concat(), ""), model);
+            vectors.extend(batch_vectors);
+        }
+        Ok(vectors)
+    }
+
+    /// 按LLM配置构建的ReAct配置
+    pub fn react_config(&self) -> ReActConfig {
+        ReActConfig::from(&self.config.llm)
+    }
+
+    /// 智能对话方法（使用配置中的ReAct参数），返回回复内容及token使用情况
+    pub async fn prompt(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+    ) -> Result<(String, TokenUsage)> {
+        self.with_budget(|| async {
+            let react_config = self.react_config();
+            let response = self
+                .prompt_with_react(system_prompt, user_prompt, react_config)
+                .await?;
+
+            let agent = self
+                .get_agent_builder()
+                .build_agent_without_tools(system_prompt);
+            // ReAct上报的是多轮对话的累计用量，截断判断只能针对最后一轮回复，因此按最终回复内容估算其输出token
+            let final_output_tokens = estimate_token_usage("", &response.content).output_tokens;
+            let (content, token_usage) = self
+                .complete_truncated_reply(
+                    &agent,
+                    user_prompt,
+                    response.content,
+                    response.token_usage.unwrap_or(TokenUsage::new(0, 0)),
+                    final_output_tokens,
+                )
+                .await;
+            // ReAct被中断时provider不会返回usage，此时基于文本估算
+            let token_usage = resolve_token_usage(
+                token_usage,
+                &format!("{} {}", system_prompt, user_prompt),
+                &content,
+            );
+            Ok((
+                content,
+                token_usage,
+                self.config.llm.model_efficient.clone(),
+            ))
+        })
+        .await
     }
 
     /// 使用ReAct模式进行多轮对话
@@ -166,7 +360,12 @@ impl LLMClient {
 
         let response = self
             .retry_with_backoff(|| async {
-                ReActExecutor::execute(&agent, user_prompt, &react_config).await
+                let response = ReActExecutor::execute(&agent, user_prompt, &react_config).await?;
+                // 达到最大迭代次数时由后续的总结推理补全内容
+                if !response.stopped_by_max_depth {
+                    self.ensure_non_blank(&response.content)?;
+                }
+                Ok(response)
             })
             .await?;
 
@@ -176,7 +375,7 @@ impl LLMClient {
             && response.chat_history.is_some()
         {
             if react_config.verbose {
-                println!("🔄 启动ReAct Agent总结转直接推理模式...");
+                info!("🔄 启动ReAct Agent总结转直接推理模式...");
             }
 
             match self
@@ -185,13 +384,13 @@ impl LLMClient {
             {
                 Ok(summary_response) => {
                     if react_config.verbose {
-                        println!("✅ 总结推理完成");
+                        info!("✅ 总结推理完成");
                     }
                     return Ok(summary_response);
                 }
                 Err(e) => {
                     if react_config.verbose {
-                        println!("⚠️  总结推理失败，返回原始部分结果...{}", e);
+                        warn!("⚠️  总结推理失败，返回原始部分结果...{}", e);
                     }
                     // 总结推理失败时，返回原始的部分结果
                 }
@@ -216,7 +415,7 @@ impl LLMClient {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("缺少对话历史"))?;
 
-        let summary_result = self
+        let (summary_result, token_usage) = self
             .retry_with_backoff(|| async {
                 SummaryReasoner::summarize_and_reason(
                     &agent_without_tools,
@@ -234,19 +433,440 @@ impl LLMClient {
             original_response.iterations_used,
             original_response.tool_calls_history.clone(),
             chat_history.clone(),
-        ))
+        )
+        .with_token_usage(token_usage))
     }
 
-    /// 简化的单轮对话方法（不使用工具）
+    /// 简化的单轮对话方法（不使用工具），返回回复内容及token使用情况
     pub async fn prompt_without_react(
         &self,
         system_prompt: &str,
         user_prompt: &str,
-    ) -> Result<String> {
-        let agent_builder = self.get_agent_builder();
-        let agent = agent_builder.build_agent_without_tools(system_prompt);
+    ) -> Result<(String, TokenUsage)> {
+        self.with_budget(|| async {
+            let agent_builder = self.get_agent_builder();
+            let agent = agent_builder.build_agent_without_tools(system_prompt);
 
-        self.retry_with_backoff(|| async { agent.prompt(user_prompt).await })
+            let (reply, token_usage) = self
+                .retry_non_blank(|| async { agent.prompt(user_prompt).await })
+                .await?;
+            let final_output_tokens = token_usage.output_tokens;
+            let (reply, token_usage) = self
+                .complete_truncated_reply(
+                    &agent,
+                    user_prompt,
+                    reply,
+                    token_usage,
+                    final_output_tokens,
+                )
+                .await;
+            let token_usage = resolve_token_usage(
+                token_usage,
+                &format!("{} {}", system_prompt, user_prompt),
+                &reply,
+            );
+            Ok((reply, token_usage, self.config.llm.model_efficient.clone()))
+        })
+        .await
+    }
+
+    /// 检测回复是否因达到max_tokens被截断，是则自动续写；续写后仍不完整时追加截断标记。
+    /// `final_output_tokens`为产生该回复的最后一次模型调用的输出token数，`token_usage`为累计用量
+    async fn complete_truncated_reply(
+        &self,
+        agent: &ProviderAgent,
+        user_prompt: &str,
+        reply: String,
+        token_usage: TokenUsage,
+        final_output_tokens: usize,
+    ) -> (String, TokenUsage) {
+        let max_tokens = self.config.llm.max_tokens;
+        let mut reply = reply;
+        let mut total_usage = token_usage;
+        let mut last_output_tokens = final_output_tokens;
+
+        for attempt in 1..=MAX_TRUNCATION_CONTINUATIONS {
+            if !is_likely_truncated(&reply, last_output_tokens, max_tokens) {
+                return (reply, total_usage);
+            }
+            warn!(
+                "⚠️ 模型输出疑似被截断，正在续写 ({}/{})",
+                attempt, MAX_TRUNCATION_CONTINUATIONS
+            );
+
+            let continuation_prompt = format!(
+                "{}\n\n## 你已输出的内容（因长度限制被截断）\n{}\n\n请从上文截断处直接继续输出剩余内容，不要重复已输出的部分，也不要添加任何额外说明。",
+                user_prompt, reply
+            );
+            match agent.prompt(&continuation_prompt).await {
+                Ok((continuation, usage)) => {
+                    reply.push_str(&continuation);
+                    last_output_tokens = usage.output_tokens;
+                    total_usage = TokenUsage::new(
+                        total_usage.input_tokens + usage.input_tokens,
+                        total_usage.output_tokens + usage.output_tokens,
+                    );
+                }
+                Err(e) => {
+                    warn!("⚠️ 续写失败: {}", e);
+                    break;
+                }
+            }
+        }
+
+        if is_likely_truncated(&reply, last_output_tokens, max_tokens) {
+            warn!("⚠️ 模型输出仍不完整，已追加截断标记");
+            reply = mark_truncated(&reply);
+        }
+        (reply, total_usage)
+    }
+}
+
+impl Embedder for LLMClient {
+    fn embed(&self, texts: Vec<String>) -> impl Future<Output = Result<Vec<Vec<f32>>>> + Send {
+        LLMClient::embed(self, texts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LLMProvider;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn test_client(retry_on_empty_response: bool) -> LLMClient {
+        let mut config = Config::default();
+        config.llm.api_key = "test-key".to_string();
+        config.llm.retry_attempts = 3;
+        config.llm.retry_delay_ms = 0;
+        config.llm.retry_on_empty_response = retry_on_empty_response;
+        LLMClient::new(config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_blank_response_forces_retry() {
+        let client = test_client(true);
+        let attempts = AtomicUsize::new(0);
+
+        let (reply, _) = client
+            .retry_non_blank(|| async {
+                let reply = match attempts.fetch_add(1, Ordering::SeqCst) {
+                    0 => "  \n\t",
+                    _ => "# 文档",
+                };
+                Ok((reply.to_string(), TokenUsage::new(10, 5)))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(reply, "# 文档");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_blank_response_accepted_when_retry_disabled() {
+        let client = test_client(false);
+        let attempts = AtomicUsize::new(0);
+
+        let (reply, _) = client
+            .retry_non_blank(|| async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Ok((String::new(), TokenUsage::new(10, 0)))
+            })
+            .await
+            .unwrap();
+
+        assert!(reply.is_empty());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[derive(Debug, Serialize, Deserialize, JsonSchema)]
+    struct ExtractProbe {
+        answer: String,
+    }
+
+    /// 启动始终返回500的模拟服务器，返回服务地址及收到的请求数
+    async fn failing_server() -> (String, Arc<AtomicUsize>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut buffer = vec![0u8; 65536];
+                let _ = socket.read(&mut buffer).await;
+                let _ = socket
+                    .write_all(
+                        b"HTTP/1.1 500 Internal Server Error\r\ncontent-type: application/json\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}",
+                    )
+                    .await;
+            }
+        });
+        (format!("http://{}/v1", address), requests)
+    }
+
+    #[tokio::test]
+    async fn test_equal_models_skip_fallover_pass() {
+        let (api_base_url, requests) = failing_server().await;
+        let mut config = Config::default();
+        config.llm.provider = LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = api_base_url;
+        config.llm.retry_attempts = 1;
+        config.llm.retry_delay_ms = 0;
+        let client = LLMClient::new(config).unwrap();
+
+        let result = client
+            .extract_inner::<ExtractProbe>(
+                &client.client,
+                "system",
+                "user",
+                "same-model".to_string(),
+                Some("same-model".to_string()),
+            )
+            .await;
+        assert!(result.is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // 不同的备选模型仍会再尝试一次
+        let result = client
+            .extract_inner::<ExtractProbe>(
+                &client.client,
+                "system",
+                "user",
+                "efficient-model".to_string(),
+                Some("powerful-model".to_string()),
+            )
+            .await;
+        assert!(result.is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fallover_extract_reports_model_used() {
+        use crate::llm::client::mock_server::{MockServer, openai_reply};
+
+        // 高能效模型始终失败，备选的高质量模型正常返回
+        let server = MockServer::with_handler(|request| {
+            (request.body["model"] != "efficient-model").then(|| openai_reply(request))
+        })
+        .await;
+        let mut config = Config::default();
+        config.llm.provider = LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = server.api_base_url.clone();
+        config.llm.retry_attempts = 1;
+        config.llm.retry_delay_ms = 0;
+        let client = LLMClient::new(config).unwrap();
+
+        let (_, _, model) = client
+            .extract_inner::<ExtractProbe>(
+                &client.client,
+                "system",
+                "user",
+                "efficient-model".to_string(),
+                Some("powerful-model".to_string()),
+            )
             .await
+            .unwrap();
+        assert_eq!(model, "powerful-model");
+    }
+
+    #[tokio::test]
+    async fn test_tiny_budget_aborts_after_first_call() {
+        let mut config = Config::default();
+        config.llm.api_key = "test-key".to_string();
+        config.llm.max_cost_usd = Some(0.000001);
+        let client = LLMClient::new(config).unwrap();
+        let calls = AtomicUsize::new(0);
+        let call = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok((
+                "# 文档".to_string(),
+                TokenUsage::new(1000, 500),
+                "test-model".to_string(),
+            ))
+        };
+
+        assert!(client.with_budget(call).await.is_ok());
+        let err = client.with_budget(call).await.unwrap_err();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let exceeded = err.downcast_ref::<BudgetExceeded>().unwrap();
+        assert_eq!(exceeded.limit_usd, 0.000001);
+        assert!(exceeded.spent_usd > exceeded.limit_usd);
+        assert!(client.budget_exceeded().is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_provider_concurrency_limit_overrides_global() {
+        use crate::llm::client::mock_server::{MockServer, openai_reply};
+
+        // 模拟服务记录同时处理中的HTTP请求数
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (handler_in_flight, handler_peak) = (in_flight.clone(), peak.clone());
+        let server = MockServer::with_handler(move |request| {
+            let current = handler_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            handler_peak.fetch_max(current, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            handler_in_flight.fetch_sub(1, Ordering::SeqCst);
+            Some(openai_reply(request))
+        })
+        .await;
+        let mut config = Config::default();
+        config.llm.provider = LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = server.api_base_url.clone();
+        config.llm.max_parallels = 4;
+        config
+            .llm
+            .provider_max_parallels
+            .insert(LLMProvider::OpenAI, 1);
+        let client = LLMClient::new(config).unwrap();
+
+        let calls = (0..4).map(|_| client.prompt_without_react("system", "user"));
+        let results = futures::future::join_all(calls).await;
+
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert_eq!(server.requests().len(), 4);
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_are_batched_and_counted_toward_budget() {
+        use crate::llm::client::mock_server::MockServer;
+
+        let server = MockServer::start().await;
+        let mut config = Config::default();
+        config.llm.provider = LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = server.api_base_url.clone();
+        let client = LLMClient::new(config.clone()).unwrap();
+        let texts: Vec<String> = (0..250)
+            .map(|index| format!("summary of file {}", index))
+            .collect();
+
+        let vectors = client.embed(texts.clone()).await.unwrap();
+
+        assert_eq!(vectors.len(), 250);
+        let batch_sizes: Vec<usize> = server
+            .requests()
+            .iter()
+            .map(|request| request.body["input"].as_array().unwrap().len())
+            .collect();
+        assert_eq!(batch_sizes, vec![100, 100, 50]);
+
+        // 嵌入成本计入成本上限，首批超限后不再发起后续批次的请求
+        config.llm.max_cost_usd = Some(0.000001);
+        let client = LLMClient::new(config).unwrap();
+        let err = client.embed(texts).await.unwrap_err();
+        assert!(err.downcast_ref::<BudgetExceeded>().is_some());
+        assert!(client.budget_exceeded().is_some());
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_fallover_model_runs_on_fallover_provider() {
+        use crate::config::FalloverProviderConfig;
+        use crate::llm::client::mock_server::MockServer;
+
+        // 主provider始终失败，兜底provider正常返回
+        let primary = MockServer::with_handler(|_| None).await;
+        let fallover = MockServer::start().await;
+        let mut config = Config::default();
+        config.llm.provider = LLMProvider::DeepSeek;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = primary.api_base_url.clone();
+        config.llm.model_efficient = "local-model".to_string();
+        config.llm.model_powerful = "cloud-model".to_string();
+        config.llm.retry_attempts = 1;
+        config.llm.retry_delay_ms = 0;
+        config.llm.fallover_provider = Some(FalloverProviderConfig {
+            provider: LLMProvider::OpenAI,
+            api_key: "cloud-key".to_string(),
+            api_base_url: fallover.api_base_url.clone(),
+        });
+        config
+            .llm
+            .provider_max_parallels
+            .insert(LLMProvider::OpenAI, 1);
+        let client = LLMClient::new(config).unwrap();
+
+        let (probe, _) = client
+            .extract::<ExtractProbe>("system", "user")
+            .await
+            .unwrap();
+        assert_eq!(probe.answer, "mock");
+
+        assert_eq!(primary.requests()[0].body["model"], "local-model");
+        let fallover_requests = fallover.requests();
+        assert_eq!(fallover_requests.len(), 1);
+        assert_eq!(fallover_requests[0].body["model"], "cloud-model");
+        assert!(
+            fallover_requests[0]
+                .headers
+                .contains("authorization: bearer cloud-key")
+        );
+    }
+
+    #[test]
+    fn test_configured_react_max_iterations_reaches_executor() {
+        let mut config = Config::default();
+        config.llm.api_key = "test-key".to_string();
+        config.llm.react_max_iterations = 3;
+        config.llm.enable_summary_reasoning = false;
+        let client = LLMClient::new(config).unwrap();
+
+        let react_config = client.react_config();
+        assert_eq!(react_config.max_iterations, 3);
+        assert!(!react_config.enable_summary_reasoning);
+        assert!(react_config.return_partial_on_max_depth);
+    }
+
+    #[tokio::test]
+    async fn test_cumulative_react_usage_does_not_trigger_continuation() {
+        use crate::llm::client::mock_server::MockServer;
+
+        let server = MockServer::start().await;
+        let mut config = Config::default();
+        config.llm.provider = LLMProvider::OpenAI;
+        config.llm.api_key = "test-key".to_string();
+        config.llm.api_base_url = server.api_base_url.clone();
+        config.llm.max_tokens = 100;
+        let client = LLMClient::new(config).unwrap();
+        let agent = client
+            .get_agent_builder()
+            .build_agent_without_tools("system");
+
+        // 多轮ReAct累计输出了500个token，但最后一轮的完整回复远未达到max_tokens
+        let (reply, token_usage) = client
+            .complete_truncated_reply(
+                &agent,
+                "user",
+                "# 文档\n\n内容完整。".to_string(),
+                TokenUsage::new(2000, 500),
+                20,
+            )
+            .await;
+        assert_eq!(reply, "# 文档\n\n内容完整。");
+        assert_eq!(token_usage.output_tokens, 500);
+        assert!(server.requests().is_empty());
+
+        // 最后一轮达到max_tokens时仍会续写
+        let (reply, _) = client
+            .complete_truncated_reply(
+                &agent,
+                "user",
+                "# 文档\n\n".to_string(),
+                TokenUsage::new(2000, 500),
+                100,
+            )
+            .await;
+        assert!(reply.starts_with("# 文档\n\n# Mock"));
+        assert_eq!(server.requests().len(), 1);
     }
 }
@@ -1,24 +1,43 @@
 //! LLM Provider支持模块
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use rig::{
-    agent::Agent,
-    client::CompletionClient,
-    completion::{Prompt, PromptError},
-    extractor::Extractor,
+    agent::{Agent, AgentBuilder},
+    client::{CompletionClient, EmbeddingsClient},
+    completion::{AssistantContent, Completion, CompletionModel, Prompt, PromptError},
+    embeddings::EmbeddingModel,
+    extractor::{Extractor, ExtractorBuilder},
     providers::gemini::completion::gemini_api_types::{AdditionalParameters, GenerationConfig},
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::{Semaphore, SemaphorePermit};
 
 use crate::{
     config::{LLMConfig, LLMProvider},
-    llm::tools::time::AgentToolTime,
+    llm::{
+        client::{
+            embedding::Embedder,
+            limited_model::{LimitedModel, acquire},
+            types::TokenUsage,
+        },
+        tools::time::AgentToolTime,
+    },
 };
 
-/// 统一的Provider客户端枚举
+/// 统一的Provider客户端：各provider的rig客户端及其共享的并发请求信号量
 #[derive(Clone)]
-pub enum ProviderClient {
+pub struct ProviderClient {
+    backend: ProviderBackend,
+    /// provider的并发请求信号量，每次HTTP请求占用一个许可
+    limiter: Arc<Semaphore>,
+}
+
+/// 各provider的rig客户端
+#[derive(Clone)]
+enum ProviderBackend {
     OpenAI(rig::providers::openai::Client),
     Moonshot(rig::providers::moonshot::Client),
     DeepSeek(rig::providers::deepseek::Client),
@@ -30,50 +49,73 @@ pub enum ProviderClient {
 }
 
 impl ProviderClient {
-    /// 根据配置创建相应的provider客户端
-    pub fn new(config: &LLMConfig) -> Result<Self> {
-        match config.provider {
+    /// 根据配置创建相应的provider客户端，请求并发受`limiter`限制
+    pub fn new(config: &LLMConfig, limiter: Arc<Semaphore>) -> Result<Self> {
+        let backend = match config.provider {
             LLMProvider::OpenAI => {
                 let client = rig::providers::openai::Client::builder(&config.api_key)
                     .base_url(&config.api_base_url)
+                    .with_client(build_http_client(config)?)
                     .build();
-                Ok(ProviderClient::OpenAI(client))
+                ProviderBackend::OpenAI(client)
             }
             LLMProvider::Moonshot => {
                 let client = rig::providers::moonshot::Client::builder(&config.api_key)
                     .base_url(&config.api_base_url)
+                    .with_client(build_http_client(config)?)
                     .build();
-                Ok(ProviderClient::Moonshot(client))
+                ProviderBackend::Moonshot(client)
             }
             LLMProvider::DeepSeek => {
                 let client = rig::providers::deepseek::Client::builder(&config.api_key)
                     .base_url(&config.api_base_url)
+                    .with_client(build_http_client(config)?)
                     .build();
-                Ok(ProviderClient::DeepSeek(client))
+                ProviderBackend::DeepSeek(client)
             }
             LLMProvider::Mistral => {
-                let client = rig::providers::mistral::Client::builder(&config.api_key).build();
-                Ok(ProviderClient::Mistral(client))
+                let client = rig::providers::mistral::Client::builder(&config.api_key)
+                    .with_client(build_http_client(config)?)
+                    .build();
+                ProviderBackend::Mistral(client)
             }
             LLMProvider::OpenRouter => {
                 // reference： https://docs.rig.rs/docs/integrations/model_providers/anthropic#basic-usage
-                let client = rig::providers::openrouter::Client::builder(&config.api_key).build();
-                Ok(ProviderClient::OpenRouter(client))
+                let client = rig::providers::openrouter::Client::builder(&config.api_key)
+                    .with_client(build_http_client(config)?)
+                    .build();
+                ProviderBackend::OpenRouter(client)
             }
             LLMProvider::Anthropic => {
-                let client =
-                    rig::providers::anthropic::ClientBuilder::new(&config.api_key).build()?;
-                Ok(ProviderClient::Anthropic(client))
+                let client = rig::providers::anthropic::Client::builder(&config.api_key)
+                    .with_client(build_http_client(config)?)
+                    .build()?;
+                ProviderBackend::Anthropic(client)
             }
             LLMProvider::Gemini => {
-                let client = rig::providers::gemini::Client::builder(&config.api_key).build()?;
-                Ok(ProviderClient::Gemini(client))
+                let client = rig::providers::gemini::Client::builder(&config.api_key)
+                    .with_client(build_http_client(config)?)
+                    .build()?;
+                ProviderBackend::Gemini(client)
             }
             LLMProvider::Ollama => {
-                let client = rig::providers::ollama::Client::builder().build();
-                Ok(ProviderClient::Ollama(client))
+                let client = rig::providers::ollama::Client::builder()
+                    .with_client(build_http_client(config)?)
+                    .build();
+                ProviderBackend::Ollama(client)
             }
-        }
+        };
+        Ok(Self { backend, limiter })
+    }
+
+    /// 占用一个provider并发许可，用于不经过补全模型的请求（如向量嵌入）
+    pub async fn acquire_permit(&self) -> Result<SemaphorePermit<'_>> {
+        Ok(acquire(&self.limiter).await?)
+    }
+
+    /// 包装补全模型，使其每次HTTP请求占用一个provider并发许可
+    fn limited<M>(&self, model: M) -> LimitedModel<M> {
+        LimitedModel::new(model, self.limiter.clone())
     }
 
     /// 创建Agent
@@ -83,65 +125,58 @@ impl ProviderClient {
         system_prompt: &str,
         config: &LLMConfig,
     ) -> ProviderAgent {
-        match self {
-            ProviderClient::OpenAI(client) => {
-                let agent = client
-                    .completion_model(model)
-                    .completions_api()
-                    .into_agent_builder()
-                    .preamble(system_prompt)
-                    .max_tokens(config.max_tokens.into())
-                    .temperature(config.temperature)
-                    .build();
+        match &self.backend {
+            ProviderBackend::OpenAI(client) => {
+                let agent = AgentBuilder::new(
+                    self.limited(client.completion_model(model).completions_api()),
+                )
+                .preamble(system_prompt)
+                .max_tokens(config.max_tokens.into())
+                .temperature(config.temperature)
+                .build();
                 ProviderAgent::OpenAI(agent)
             }
-            ProviderClient::Moonshot(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::Moonshot(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .temperature(config.temperature)
                     .build();
                 ProviderAgent::Moonshot(agent)
             }
-            ProviderClient::DeepSeek(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::DeepSeek(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .temperature(config.temperature)
                     .build();
                 ProviderAgent::DeepSeek(agent)
             }
-            ProviderClient::Mistral(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::Mistral(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .temperature(config.temperature)
                     .build();
                 ProviderAgent::Mistral(agent)
             }
-            ProviderClient::OpenRouter(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::OpenRouter(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .temperature(config.temperature)
                     .build();
                 ProviderAgent::OpenRouter(agent)
             }
-            ProviderClient::Anthropic(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::Anthropic(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
                     .temperature(config.temperature)
                     .build();
                 ProviderAgent::Anthropic(agent)
             }
-            ProviderClient::Gemini(client) => {
+            ProviderBackend::Gemini(client) => {
                 let gen_cfg = GenerationConfig::default();
                 let cfg = AdditionalParameters::default().with_config(gen_cfg);
 
-                let agent = client
-                    .agent(model)
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
                     .temperature(config.temperature)
@@ -149,9 +184,8 @@ impl ProviderClient {
                     .build();
                 ProviderAgent::Gemini(agent)
             }
-            ProviderClient::Ollama(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::Ollama(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
                     .temperature(config.temperature)
@@ -169,107 +203,98 @@ impl ProviderClient {
         config: &LLMConfig,
         file_explorer: &crate::llm::tools::file_explorer::AgentToolFileExplorer,
         file_reader: &crate::llm::tools::file_reader::AgentToolFileReader,
+        tool_time: &AgentToolTime,
     ) -> ProviderAgent {
-        let tool_time = AgentToolTime::new();
-
-        match self {
-            ProviderClient::OpenAI(client) => {
-                let agent = client
-                    .completion_model(model)
-                    .completions_api()
-                    .into_agent_builder()
-                    .preamble(system_prompt)
-                    .max_tokens(config.max_tokens.into())
-                    .temperature(config.temperature)
-                    .tool(file_explorer.clone())
-                    .tool(file_reader.clone())
-                    .tool(tool_time)
-                    .build();
+        match &self.backend {
+            ProviderBackend::OpenAI(client) => {
+                let agent = AgentBuilder::new(
+                    self.limited(client.completion_model(model).completions_api()),
+                )
+                .preamble(system_prompt)
+                .max_tokens(config.max_tokens.into())
+                .temperature(config.temperature)
+                .tool(file_explorer.clone())
+                .tool(file_reader.clone())
+                .tool(tool_time.clone())
+                .build();
                 ProviderAgent::OpenAI(agent)
             }
-            ProviderClient::Moonshot(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::Moonshot(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
                     .temperature(config.temperature)
                     .tool(file_explorer.clone())
                     .tool(file_reader.clone())
-                    .tool(tool_time)
+                    .tool(tool_time.clone())
                     .build();
                 ProviderAgent::Moonshot(agent)
             }
-            ProviderClient::DeepSeek(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::DeepSeek(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
                     .temperature(config.temperature)
                     .tool(file_explorer.clone())
                     .tool(file_reader.clone())
-                    .tool(tool_time)
+                    .tool(tool_time.clone())
                     .build();
                 ProviderAgent::DeepSeek(agent)
             }
-            ProviderClient::Mistral(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::Mistral(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .temperature(config.temperature)
                     .tool(file_explorer.clone())
                     .tool(file_reader.clone())
-                    .tool(tool_time)
+                    .tool(tool_time.clone())
                     .build();
                 ProviderAgent::Mistral(agent)
             }
-            ProviderClient::OpenRouter(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::OpenRouter(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .temperature(config.temperature)
                     .tool(file_explorer.clone())
                     .tool(file_reader.clone())
-                    .tool(tool_time)
+                    .tool(tool_time.clone())
                     .build();
                 ProviderAgent::OpenRouter(agent)
             }
-            ProviderClient::Anthropic(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::Anthropic(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
                     .temperature(config.temperature)
                     .tool(file_explorer.clone())
                     .tool(file_reader.clone())
-                    .tool(tool_time)
+                    .tool(tool_time.clone())
                     .build();
                 ProviderAgent::Anthropic(agent)
             }
-            ProviderClient::Gemini(client) => {
+            ProviderBackend::Gemini(client) => {
                 let gen_cfg = GenerationConfig::default();
                 let cfg = AdditionalParameters::default().with_config(gen_cfg);
 
-                let agent = client
-                    .agent(model)
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
                     .temperature(config.temperature)
                     .tool(file_explorer.clone())
                     .tool(file_reader.clone())
-                    .tool(tool_time)
+                    .tool(tool_time.clone())
                     .additional_params(serde_json::to_value(cfg).unwrap())
                     .build();
                 ProviderAgent::Gemini(agent)
             }
-            ProviderClient::Ollama(client) => {
-                let agent = client
-                    .agent(model)
+            ProviderBackend::Ollama(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
                     .temperature(config.temperature)
                     .tool(file_explorer.clone())
                     .tool(file_reader.clone())
-                    .tool(tool_time)
+                    .tool(tool_time.clone())
                     .build();
                 ProviderAgent::Ollama(agent)
             }
@@ -286,126 +311,308 @@ impl ProviderClient {
     where
         T: JsonSchema + for<'a> Deserialize<'a> + Serialize + Send + Sync + 'static,
     {
-        match self {
-            ProviderClient::OpenAI(client) => {
-                let extractor = client
-                    .extractor_completions_api::<T>(model)
-                    .preamble(system_prompt)
-                    .max_tokens(config.max_tokens.into())
-                    .build();
+        // provider原生JSON模式不能与rig Extractor强制的工具调用（ToolChoice::Required）同时使用，
+        // Gemini会直接拒绝，部分OpenAI兼容网关同样如此；开启JSON模式时改为普通prompt调用，
+        // 在提示词中给出结果的JSON Schema，由provider以JSON格式返回（OpenAI要求消息中包含"JSON"字样）
+        if let Some(params) = json_mode_params(&self.provider(), config) {
+            let schema =
+                serde_json::to_string_pretty(&schemars::schema_for!(T)).unwrap_or_default();
+            let preamble = format!(
+                "{}\n\n请以JSON格式返回结果，JSON需符合以下JSON Schema：\n{}",
+                system_prompt, schema
+            );
+            return ProviderExtractor::JsonMode(
+                self.create_json_agent(model, &preamble, config, params),
+            );
+        }
+
+        match &self.backend {
+            ProviderBackend::OpenAI(client) => {
+                let extractor = ExtractorBuilder::<_, T>::new(
+                    self.limited(client.completion_model(model).completions_api()),
+                )
+                .preamble(system_prompt)
+                .max_tokens(config.max_tokens.into())
+                .build();
                 ProviderExtractor::OpenAI(extractor)
             }
-            ProviderClient::Moonshot(client) => {
-                let extractor = client
-                    .extractor::<T>(model)
-                    .preamble(system_prompt)
-                    .max_tokens(config.max_tokens.into())
-                    .build();
+            ProviderBackend::Moonshot(client) => {
+                let extractor =
+                    ExtractorBuilder::<_, T>::new(self.limited(client.completion_model(model)))
+                        .preamble(system_prompt)
+                        .max_tokens(config.max_tokens.into())
+                        .build();
                 ProviderExtractor::Moonshot(extractor)
             }
-            ProviderClient::DeepSeek(client) => {
-                let extractor = client
-                    .extractor::<T>(model)
-                    .preamble(system_prompt)
-                    .max_tokens(config.max_tokens.into())
-                    .build();
+            ProviderBackend::DeepSeek(client) => {
+                let extractor =
+                    ExtractorBuilder::<_, T>::new(self.limited(client.completion_model(model)))
+                        .preamble(system_prompt)
+                        .max_tokens(config.max_tokens.into())
+                        .build();
                 ProviderExtractor::DeepSeek(extractor)
             }
-            ProviderClient::Mistral(client) => {
-                let extractor = client
-                    .extractor::<T>(model)
-                    .preamble(system_prompt)
-                    .max_tokens(config.max_tokens.into())
-                    .build();
+            ProviderBackend::Mistral(client) => {
+                let extractor =
+                    ExtractorBuilder::<_, T>::new(self.limited(client.completion_model(model)))
+                        .preamble(system_prompt)
+                        .max_tokens(config.max_tokens.into())
+                        .build();
                 ProviderExtractor::Mistral(extractor)
             }
-            ProviderClient::OpenRouter(client) => {
-                let extractor = client
-                    .extractor::<T>(model)
-                    .preamble(system_prompt)
-                    .max_tokens(config.max_tokens.into())
-                    .build();
+            ProviderBackend::OpenRouter(client) => {
+                let extractor =
+                    ExtractorBuilder::<_, T>::new(self.limited(client.completion_model(model)))
+                        .preamble(system_prompt)
+                        .max_tokens(config.max_tokens.into())
+                        .build();
                 ProviderExtractor::OpenRouter(extractor)
             }
-            ProviderClient::Anthropic(client) => {
-                let extractor = client
-                    .extractor::<T>(model)
-                    .preamble(system_prompt)
-                    .max_tokens(config.max_tokens.into())
-                    .build();
+            ProviderBackend::Anthropic(client) => {
+                let extractor =
+                    ExtractorBuilder::<_, T>::new(self.limited(client.completion_model(model)))
+                        .preamble(system_prompt)
+                        .max_tokens(config.max_tokens.into())
+                        .build();
                 ProviderExtractor::Anthropic(extractor)
             }
-            ProviderClient::Gemini(client) => {
+            ProviderBackend::Gemini(client) => {
                 let gen_cfg = GenerationConfig::default();
                 let cfg = AdditionalParameters::default().with_config(gen_cfg);
 
-                let extractor = client
-                    .extractor::<T>(model)
+                let extractor =
+                    ExtractorBuilder::<_, T>::new(self.limited(client.completion_model(model)))
+                        .preamble(system_prompt)
+                        .max_tokens(config.max_tokens.into())
+                        .additional_params(serde_json::to_value(cfg).unwrap())
+                        .build();
+                ProviderExtractor::Gemini(extractor)
+            }
+            ProviderBackend::Ollama(client) => {
+                let extractor =
+                    ExtractorBuilder::<_, T>::new(self.limited(client.completion_model(model)))
+                        .preamble(system_prompt)
+                        .max_tokens(config.max_tokens.into())
+                        .build();
+                ProviderExtractor::Ollama(extractor)
+            }
+        }
+    }
+
+    /// 创建开启provider原生JSON输出模式的无工具Agent，仅用于要求返回JSON的普通prompt调用
+    fn create_json_agent(
+        &self,
+        model: &str,
+        system_prompt: &str,
+        config: &LLMConfig,
+        json_mode: serde_json::Value,
+    ) -> ProviderAgent {
+        match &self.backend {
+            ProviderBackend::OpenAI(client) => {
+                let agent = AgentBuilder::new(
+                    self.limited(client.completion_model(model).completions_api()),
+                )
+                .preamble(system_prompt)
+                .max_tokens(config.max_tokens.into())
+                .temperature(config.temperature)
+                .additional_params(json_mode)
+                .build();
+                ProviderAgent::OpenAI(agent)
+            }
+            ProviderBackend::DeepSeek(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
-                    .additional_params(serde_json::to_value(cfg).unwrap())
+                    .temperature(config.temperature)
+                    .additional_params(json_mode)
                     .build();
-                ProviderExtractor::Gemini(extractor)
+                ProviderAgent::DeepSeek(agent)
             }
-            ProviderClient::Ollama(client) => {
-                let extractor = client
-                    .extractor::<T>(model)
+            ProviderBackend::Mistral(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
                     .preamble(system_prompt)
                     .max_tokens(config.max_tokens.into())
+                    .temperature(config.temperature)
+                    .additional_params(json_mode)
                     .build();
-                ProviderExtractor::Ollama(extractor)
+                ProviderAgent::Mistral(agent)
+            }
+            ProviderBackend::Gemini(client) => {
+                let agent = AgentBuilder::new(self.limited(client.completion_model(model)))
+                    .preamble(system_prompt)
+                    .max_tokens(config.max_tokens.into())
+                    .temperature(config.temperature)
+                    .additional_params(json_mode)
+                    .build();
+                ProviderAgent::Gemini(agent)
             }
+            // 其余provider不支持原生JSON模式（见json_mode_params），仅依靠提示词约束输出格式
+            _ => self.create_agent(model, system_prompt, config),
+        }
+    }
+
+    /// 创建Embedder，仅OpenAI、Gemini与Ollama支持向量嵌入，其余provider返回不支持错误
+    pub fn create_embedder(&self, model: &str) -> Result<ProviderEmbedder> {
+        match &self.backend {
+            ProviderBackend::OpenAI(client) => {
+                Ok(ProviderEmbedder::OpenAI(client.embedding_model(model)))
+            }
+            ProviderBackend::Gemini(client) => {
+                Ok(ProviderEmbedder::Gemini(client.embedding_model(model)))
+            }
+            ProviderBackend::Ollama(client) => {
+                Ok(ProviderEmbedder::Ollama(client.embedding_model(model)))
+            }
+            _ => Err(anyhow!(
+                "provider {} 不支持向量嵌入，仅openai、gemini、ollama可用",
+                self.provider()
+            )),
+        }
+    }
+
+    /// 客户端对应的provider类型
+    fn provider(&self) -> LLMProvider {
+        match &self.backend {
+            ProviderBackend::OpenAI(_) => LLMProvider::OpenAI,
+            ProviderBackend::Moonshot(_) => LLMProvider::Moonshot,
+            ProviderBackend::DeepSeek(_) => LLMProvider::DeepSeek,
+            ProviderBackend::Mistral(_) => LLMProvider::Mistral,
+            ProviderBackend::OpenRouter(_) => LLMProvider::OpenRouter,
+            ProviderBackend::Anthropic(_) => LLMProvider::Anthropic,
+            ProviderBackend::Gemini(_) => LLMProvider::Gemini,
+            ProviderBackend::Ollama(_) => LLMProvider::Ollama,
+        }
+    }
+}
+
+/// 构建携带自定义请求头（`extra_headers`）的HTTP客户端，供所有provider的rig客户端使用
+pub fn build_http_client(config: &LLMConfig) -> Result<reqwest::Client> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.extra_headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| anyhow!("无效的请求头名称 {}: {}", name, e))?;
+        let header_value =
+            HeaderValue::from_str(value).map_err(|e| anyhow!("请求头 {} 的值无效: {}", name, e))?;
+        headers.insert(header_name, header_value);
+    }
+    Ok(reqwest::Client::builder()
+        .default_headers(headers)
+        .build()?)
+}
+
+/// 结构化抽取使用的provider原生JSON输出参数，provider不支持或配置关闭时返回None
+pub fn json_mode_params(provider: &LLMProvider, config: &LLMConfig) -> Option<serde_json::Value> {
+    if !config.native_json_mode {
+        return None;
+    }
+    match provider {
+        LLMProvider::OpenAI | LLMProvider::DeepSeek | LLMProvider::Mistral => {
+            Some(serde_json::json!({ "response_format": { "type": "json_object" } }))
+        }
+        LLMProvider::Gemini => {
+            let gen_cfg = GenerationConfig {
+                response_mime_type: Some("application/json".to_string()),
+                ..Default::default()
+            };
+            serde_json::to_value(AdditionalParameters::default().with_config(gen_cfg)).ok()
         }
+        _ => None,
     }
 }
 
 /// 统一的Agent枚举
 pub enum ProviderAgent {
-    OpenAI(Agent<rig::providers::openai::CompletionModel>),
-    Mistral(Agent<rig::providers::mistral::CompletionModel>),
-    OpenRouter(Agent<rig::providers::openrouter::CompletionModel>),
-    Anthropic(Agent<rig::providers::anthropic::completion::CompletionModel>),
-    Gemini(Agent<rig::providers::gemini::completion::CompletionModel>),
-    Moonshot(Agent<rig::providers::moonshot::CompletionModel>),
-    DeepSeek(Agent<rig::providers::deepseek::CompletionModel>),
-    Ollama(Agent<rig::providers::ollama::CompletionModel<reqwest::Client>>),
+    OpenAI(Agent<LimitedModel<rig::providers::openai::CompletionModel>>),
+    Mistral(Agent<LimitedModel<rig::providers::mistral::CompletionModel>>),
+    OpenRouter(Agent<LimitedModel<rig::providers::openrouter::CompletionModel>>),
+    Anthropic(Agent<LimitedModel<rig::providers::anthropic::completion::CompletionModel>>),
+    Gemini(Agent<LimitedModel<rig::providers::gemini::completion::CompletionModel>>),
+    Moonshot(Agent<LimitedModel<rig::providers::moonshot::CompletionModel>>),
+    DeepSeek(Agent<LimitedModel<rig::providers::deepseek::CompletionModel>>),
+    Ollama(Agent<LimitedModel<rig::providers::ollama::CompletionModel<reqwest::Client>>>),
 }
 
 impl ProviderAgent {
-    /// 执行prompt
-    pub async fn prompt(&self, prompt: &str) -> Result<String> {
-        match self {
-            ProviderAgent::OpenAI(agent) => agent.prompt(prompt).await.map_err(|e| e.into()),
-            ProviderAgent::Moonshot(agent) => agent.prompt(prompt).await.map_err(|e| e.into()),
-            ProviderAgent::DeepSeek(agent) => agent.prompt(prompt).await.map_err(|e| e.into()),
-            ProviderAgent::Mistral(agent) => agent.prompt(prompt).await.map_err(|e| e.into()),
-            ProviderAgent::OpenRouter(agent) => agent.prompt(prompt).await.map_err(|e| e.into()),
-            ProviderAgent::Anthropic(agent) => agent.prompt(prompt).await.map_err(|e| e.into()),
-            ProviderAgent::Gemini(agent) => agent.prompt(prompt).await.map_err(|e| e.into()),
-            ProviderAgent::Ollama(agent) => agent.prompt(prompt).await.map_err(|e| e.into()),
-        }
+    /// 执行prompt，返回回复内容及provider上报的token使用情况
+    pub async fn prompt(&self, prompt: &str) -> Result<(String, TokenUsage)> {
+        let response = match self {
+            ProviderAgent::OpenAI(agent) => agent.prompt(prompt).extended_details().await?,
+            ProviderAgent::Moonshot(agent) => agent.prompt(prompt).extended_details().await?,
+            ProviderAgent::DeepSeek(agent) => agent.prompt(prompt).extended_details().await?,
+            ProviderAgent::Mistral(agent) => agent.prompt(prompt).extended_details().await?,
+            ProviderAgent::OpenRouter(agent) => agent.prompt(prompt).extended_details().await?,
+            ProviderAgent::Anthropic(agent) => agent.prompt(prompt).extended_details().await?,
+            ProviderAgent::Gemini(agent) => agent.prompt(prompt).extended_details().await?,
+            ProviderAgent::Ollama(agent) => agent.prompt(prompt).extended_details().await?,
+        };
+        Ok((response.output, TokenUsage::from(response.total_usage)))
     }
 
-    /// 执行多轮对话
+    /// 执行多轮对话，返回回复内容及整个对话累计的token使用情况
     pub async fn multi_turn(
         &self,
         prompt: &str,
         max_iterations: usize,
-    ) -> Result<String, PromptError> {
-        match self {
-            ProviderAgent::OpenAI(agent) => agent.prompt(prompt).multi_turn(max_iterations).await,
-            ProviderAgent::Moonshot(agent) => agent.prompt(prompt).multi_turn(max_iterations).await,
-            ProviderAgent::DeepSeek(agent) => agent.prompt(prompt).multi_turn(max_iterations).await,
-            ProviderAgent::Mistral(agent) => agent.prompt(prompt).multi_turn(max_iterations).await,
+    ) -> Result<(String, TokenUsage), PromptError> {
+        let response = match self {
+            ProviderAgent::OpenAI(agent) => {
+                agent
+                    .prompt(prompt)
+                    .multi_turn(max_iterations)
+                    .extended_details()
+                    .await?
+            }
+            ProviderAgent::Moonshot(agent) => {
+                agent
+                    .prompt(prompt)
+                    .multi_turn(max_iterations)
+                    .extended_details()
+                    .await?
+            }
+            ProviderAgent::DeepSeek(agent) => {
+                agent
+                    .prompt(prompt)
+                    .multi_turn(max_iterations)
+                    .extended_details()
+                    .await?
+            }
+            ProviderAgent::Mistral(agent) => {
+                agent
+                    .prompt(prompt)
+                    .multi_turn(max_iterations)
+                    .extended_details()
+                    .await?
+            }
             ProviderAgent::OpenRouter(agent) => {
-                agent.prompt(prompt).multi_turn(max_iterations).await
+                agent
+                    .prompt(prompt)
+                    .multi_turn(max_iterations)
+                    .extended_details()
+                    .await?
             }
             ProviderAgent::Anthropic(agent) => {
-                agent.prompt(prompt).multi_turn(max_iterations).await
-            }
-            ProviderAgent::Gemini(agent) => agent.prompt(prompt).multi_turn(max_iterations).await,
-            ProviderAgent::Ollama(agent) => agent.prompt(prompt).multi_turn(max_iterations).await,
-        }
+                agent
+                    .prompt(prompt)
+                    .multi_turn(max_iterations)
+                    .extended_details()
+                    .await?
+            }
+            ProviderAgent::Gemini(agent) => {
+                agent
+                    .prompt(prompt)
+                    .multi_turn(max_iterations)
+                    .extended_details()
+                    .await?
+            }
+            ProviderAgent::Ollama(agent) => {
+                agent
+                    .prompt(prompt)
+                    .multi_turn(max_iterations)
+                    .extended_details()
+                    .await?
+            }
+        };
+        Ok((response.output, TokenUsage::from(response.total_usage)))
     }
 }
 
@@ -414,47 +621,259 @@ pub enum ProviderExtractor<T>
 where
     T: JsonSchema + for<'a> Deserialize<'a> + Serialize + Send + Sync + 'static,
 {
-    OpenAI(Extractor<rig::providers::openai::CompletionModel, T>),
-    Mistral(Extractor<rig::providers::mistral::CompletionModel, T>),
-    OpenRouter(Extractor<rig::providers::openrouter::CompletionModel, T>),
-    Anthropic(Extractor<rig::providers::anthropic::completion::CompletionModel, T>),
-    Gemini(Extractor<rig::providers::gemini::completion::CompletionModel, T>),
-    Moonshot(Extractor<rig::providers::moonshot::CompletionModel, T>),
-    DeepSeek(Extractor<rig::providers::deepseek::CompletionModel, T>),
-    Ollama(Extractor<rig::providers::ollama::CompletionModel<reqwest::Client>, T>),
+    OpenAI(Extractor<LimitedModel<rig::providers::openai::CompletionModel>, T>),
+    Mistral(Extractor<LimitedModel<rig::providers::mistral::CompletionModel>, T>),
+    OpenRouter(Extractor<LimitedModel<rig::providers::openrouter::CompletionModel>, T>),
+    Anthropic(Extractor<LimitedModel<rig::providers::anthropic::completion::CompletionModel>, T>),
+    Gemini(Extractor<LimitedModel<rig::providers::gemini::completion::CompletionModel>, T>),
+    Moonshot(Extractor<LimitedModel<rig::providers::moonshot::CompletionModel>, T>),
+    DeepSeek(Extractor<LimitedModel<rig::providers::deepseek::CompletionModel>, T>),
+    Ollama(Extractor<LimitedModel<rig::providers::ollama::CompletionModel<reqwest::Client>>, T>),
+    /// 开启provider原生JSON模式的普通prompt调用，回复内容按JSON解析
+    JsonMode(ProviderAgent),
 }
 
 impl<T> ProviderExtractor<T>
 where
     T: JsonSchema + for<'a> Deserialize<'a> + Serialize + Send + Sync + 'static,
 {
-    /// 执行提取
-    pub async fn extract(&self, prompt: &str) -> Result<T> {
+    /// 执行提取，返回提取结果及provider上报的token使用情况
+    pub async fn extract(&self, prompt: &str) -> Result<(T, TokenUsage)> {
         match self {
-            ProviderExtractor::OpenAI(extractor) => {
-                extractor.extract(prompt).await.map_err(|e| e.into())
-            }
-            ProviderExtractor::Moonshot(extractor) => {
-                extractor.extract(prompt).await.map_err(|e| e.into())
-            }
-            ProviderExtractor::DeepSeek(extractor) => {
-                extractor.extract(prompt).await.map_err(|e| e.into())
-            }
-            ProviderExtractor::Mistral(extractor) => {
-                extractor.extract(prompt).await.map_err(|e| e.into())
-            }
-            ProviderExtractor::OpenRouter(extractor) => {
-                extractor.extract(prompt).await.map_err(|e| e.into())
-            }
-            ProviderExtractor::Anthropic(extractor) => {
-                extractor.extract(prompt).await.map_err(|e| e.into())
-            }
-            ProviderExtractor::Gemini(extractor) => {
-                extractor.extract(prompt).await.map_err(|e| e.into())
-            }
-            ProviderExtractor::Ollama(extractor) => {
-                extractor.extract(prompt).await.map_err(|e| e.into())
+            ProviderExtractor::OpenAI(extractor) => extract_with_usage(extractor, prompt).await,
+            ProviderExtractor::Moonshot(extractor) => extract_with_usage(extractor, prompt).await,
+            ProviderExtractor::DeepSeek(extractor) => extract_with_usage(extractor, prompt).await,
+            ProviderExtractor::Mistral(extractor) => extract_with_usage(extractor, prompt).await,
+            ProviderExtractor::OpenRouter(extractor) => extract_with_usage(extractor, prompt).await,
+            ProviderExtractor::Anthropic(extractor) => extract_with_usage(extractor, prompt).await,
+            ProviderExtractor::Gemini(extractor) => extract_with_usage(extractor, prompt).await,
+            ProviderExtractor::Ollama(extractor) => extract_with_usage(extractor, prompt).await,
+            ProviderExtractor::JsonMode(agent) => {
+                let (reply, token_usage) = agent.prompt(prompt).await?;
+                Ok((parse_json_reply(&reply)?, token_usage))
             }
         }
     }
 }
+
+/// 解析JSON模式下的回复，兼容模型用Markdown代码块包裹JSON的情况
+fn parse_json_reply<T: for<'a> Deserialize<'a>>(reply: &str) -> Result<T> {
+    let trimmed = reply.trim();
+    let json = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|body| body.strip_suffix("```"))
+        .unwrap_or(trimmed);
+    serde_json::from_str(json.trim()).map_err(|e| anyhow!("JSON模式返回的内容无法解析: {}", e))
+}
+
+/// 执行一次提取：直接发起extractor内部agent的补全请求，从submit工具调用中解析结构化结果，
+/// 并保留provider上报的token使用情况（rig的`Extractor::extract`不返回usage）
+async fn extract_with_usage<M, T>(
+    extractor: &Extractor<M, T>,
+    prompt: &str,
+) -> Result<(T, TokenUsage)>
+where
+    M: CompletionModel,
+    T: JsonSchema + for<'a> Deserialize<'a> + Serialize + Send + Sync + 'static,
+{
+    let response = extractor
+        .get_inner()
+        .await
+        .completion(prompt, vec![])
+        .await?
+        .send()
+        .await?;
+    let usage = TokenUsage::from(response.usage);
+    let arguments = response
+        .choice
+        .into_iter()
+        .filter_map(|content| match content {
+            AssistantContent::ToolCall(call) if call.function.name == "submit" => {
+                Some(call.function.arguments)
+            }
+            _ => None,
+        })
+        .last()
+        .ok_or_else(|| anyhow!("模型未调用submit工具返回结构化数据"))?;
+    Ok((serde_json::from_value(arguments)?, usage))
+}
+
+/// 统一的Embedder枚举
+pub enum ProviderEmbedder {
+    OpenAI(<rig::providers::openai::Client as EmbeddingsClient>::EmbeddingModel),
+    Gemini(<rig::providers::gemini::Client as EmbeddingsClient>::EmbeddingModel),
+    Ollama(<rig::providers::ollama::Client as EmbeddingsClient>::EmbeddingModel),
+}
+
+impl Embedder for ProviderEmbedder {
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let embeddings = match self {
+            ProviderEmbedder::OpenAI(model) => model.embed_texts(texts).await?,
+            ProviderEmbedder::Gemini(model) => model.embed_texts(texts).await?,
+            ProviderEmbedder::Ollama(model) => model.embed_texts(texts).await?,
+        };
+        Ok(embeddings
+            .into_iter()
+            .map(|embedding| embedding.vec.into_iter().map(|v| v as f32).collect())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_mode_params_set_for_capable_provider() {
+        let config = LLMConfig::default();
+
+        let params = json_mode_params(&LLMProvider::OpenAI, &config).unwrap();
+        assert_eq!(params["response_format"]["type"], "json_object");
+
+        let gemini = json_mode_params(&LLMProvider::Gemini, &config).unwrap();
+        assert!(gemini.to_string().contains("application/json"));
+
+        assert!(json_mode_params(&LLMProvider::Anthropic, &config).is_none());
+        assert!(json_mode_params(&LLMProvider::Ollama, &config).is_none());
+    }
+
+    #[test]
+    fn test_embedder_unsupported_provider_is_rejected() {
+        let config = LLMConfig {
+            provider: LLMProvider::Anthropic,
+            api_key: "test-key".to_string(),
+            ..Default::default()
+        };
+        let client = ProviderClient::new(&config, Arc::new(Semaphore::new(1))).unwrap();
+
+        let Err(err) = client.create_embedder("any-model") else {
+            panic!("anthropic不应支持向量嵌入");
+        };
+        assert!(err.to_string().contains("不支持向量嵌入"));
+    }
+
+    #[tokio::test]
+    async fn test_extra_headers_attached_to_outgoing_requests() {
+        use super::super::mock_server::MockServer;
+
+        let server = MockServer::start().await;
+        let mut config = LLMConfig {
+            provider: LLMProvider::OpenAI,
+            api_key: "test-key".to_string(),
+            api_base_url: server.api_base_url.clone(),
+            ..Default::default()
+        };
+        config
+            .extra_headers
+            .insert("X-Tenant-Id".to_string(), "tenant-42".to_string());
+        config.extra_headers.insert(
+            "Ocp-Apim-Subscription-Key".to_string(),
+            "gateway-key".to_string(),
+        );
+
+        // 经由rig客户端发出的补全请求携带自定义请求头
+        let client = ProviderClient::new(&config, Arc::new(Semaphore::new(1))).unwrap();
+        client
+            .create_agent("mock-model", "system", &config)
+            .prompt("question")
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].headers.contains("x-tenant-id: tenant-42"));
+        assert!(
+            requests[0]
+                .headers
+                .contains("ocp-apim-subscription-key: gateway-key")
+        );
+    }
+
+    #[test]
+    fn test_invalid_extra_header_is_rejected() {
+        let mut config = LLMConfig::default();
+        config
+            .extra_headers
+            .insert("Bad Header".to_string(), "value".to_string());
+        assert!(build_http_client(&config).is_err());
+
+        // 每个provider都基于自定义HTTP客户端构建，不会静默忽略请求头配置
+        for provider in [
+            LLMProvider::OpenAI,
+            LLMProvider::Moonshot,
+            LLMProvider::DeepSeek,
+            LLMProvider::Mistral,
+            LLMProvider::OpenRouter,
+            LLMProvider::Anthropic,
+            LLMProvider::Gemini,
+            LLMProvider::Ollama,
+        ] {
+            config.provider = provider;
+            assert!(ProviderClient::new(&config, Arc::new(Semaphore::new(1))).is_err());
+        }
+    }
+
+    #[test]
+    fn test_json_mode_params_disabled_by_config() {
+        let config = LLMConfig {
+            native_json_mode: false,
+            ..Default::default()
+        };
+        assert!(json_mode_params(&LLMProvider::OpenAI, &config).is_none());
+    }
+
+    #[derive(Debug, Serialize, Deserialize, JsonSchema)]
+    struct JsonProbe {
+        answer: String,
+    }
+
+    #[tokio::test]
+    async fn test_json_mode_is_not_combined_with_extractor_tool_call() {
+        use super::super::mock_server::{MockServer, openai_reply};
+
+        // JSON模式下以普通补全回复JSON文本（附带代码块包裹）
+        let server = MockServer::with_handler(|request| {
+            let mut reply = openai_reply(request);
+            if request.body.get("response_format").is_some() {
+                reply["choices"][0]["message"] =
+                    serde_json::json!({ "role": "assistant", "content": "```json\n{\"answer\":\"ok\"}\n```" });
+            }
+            Some(reply)
+        })
+        .await;
+        let mut config = LLMConfig {
+            provider: LLMProvider::OpenAI,
+            api_key: "test-key".to_string(),
+            api_base_url: server.api_base_url.clone(),
+            ..Default::default()
+        };
+
+        let client = ProviderClient::new(&config, Arc::new(Semaphore::new(1))).unwrap();
+        let (probe, _) = client
+            .create_extractor::<JsonProbe>("mock-model", "system", &config)
+            .extract("question")
+            .await
+            .unwrap();
+        assert_eq!(probe.answer, "ok");
+
+        let body = &server.requests()[0].body;
+        assert_eq!(body["response_format"]["type"], "json_object");
+        assert!(body.get("tools").is_none());
+        assert!(body.get("tool_choice").is_none());
+
+        // 关闭JSON模式时使用强制工具调用的抽取器，且不携带response_format
+        config.native_json_mode = false;
+        let client = ProviderClient::new(&config, Arc::new(Semaphore::new(1))).unwrap();
+        let (probe, _) = client
+            .create_extractor::<JsonProbe>("mock-model", "system", &config)
+            .extract("question")
+            .await
+            .unwrap();
+        assert_eq!(probe.answer, "mock");
+
+        let body = &server.requests()[1].body;
+        assert!(body.get("response_format").is_none());
+        assert_eq!(body["tools"][0]["function"]["name"], "submit");
+    }
+}
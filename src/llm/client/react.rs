@@ -2,6 +2,9 @@
 
 use rig::completion::Message;
 
+use super::types::TokenUsage;
+use crate::config::LLMConfig;
+
 /// ReAct模式配置
 #[derive(Debug, Clone)]
 pub struct ReActConfig {
@@ -26,6 +29,17 @@ impl Default for ReActConfig {
     }
 }
 
+impl From<&LLMConfig> for ReActConfig {
+    fn from(config: &LLMConfig) -> Self {
+        Self {
+            max_iterations: config.react_max_iterations,
+            verbose: config.react_verbose || cfg!(debug_assertions),
+            enable_summary_reasoning: config.enable_summary_reasoning,
+            ..Self::default()
+        }
+    }
+}
+
 /// ReAct响应结果
 #[derive(Debug, Clone)]
 pub struct ReActResponse {
@@ -39,6 +53,8 @@ pub struct ReActResponse {
     pub tool_calls_history: Vec<String>,
     /// 对话历史（仅在达到最大深度时包含）
     pub chat_history: Option<Vec<Message>>,
+    /// provider上报的token使用情况（中断时无法获取）
+    pub token_usage: Option<TokenUsage>,
 }
 
 impl ReActResponse {
@@ -56,9 +72,16 @@ impl ReActResponse {
             stopped_by_max_depth,
             tool_calls_history,
             chat_history,
+            token_usage: None,
         }
     }
 
+    /// 附加token使用情况
+    pub fn with_token_usage(mut self, token_usage: TokenUsage) -> Self {
+        self.token_usage = Some(token_usage);
+        self
+    }
+
     /// 创建成功完成的响应
     pub fn success(content: String, iterations_used: usize) -> Self {
         Self::new(content, iterations_used, false, Vec::new(), None)
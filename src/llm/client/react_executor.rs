@@ -5,6 +5,7 @@ use rig::completion::{AssistantContent, Message, PromptError};
 
 use super::providers::ProviderAgent;
 use super::react::{ReActConfig, ReActResponse};
+use log::{error, info, warn};
 
 /// ReAct执行器
 pub struct ReActExecutor;
@@ -17,7 +18,7 @@ impl ReActExecutor {
         config: &ReActConfig,
     ) -> Result<ReActResponse> {
         if config.verbose {
-            println!(
+            info!(
                 "   ♻️ 激活ReAct Agent模式，最大迭代次数: {}",
                 config.max_iterations
             );
@@ -26,12 +27,13 @@ impl ReActExecutor {
         let mut tool_calls_history = Vec::new();
 
         match agent.multi_turn(user_prompt, config.max_iterations).await {
-            Ok(response) => {
+            Ok((response, token_usage)) => {
                 if config.verbose {
-                    println!("   ✅ ReAct Agent任务完成");
+                    info!("   ✅ ReAct Agent任务完成");
                 }
 
-                Ok(ReActResponse::success(response, config.max_iterations))
+                Ok(ReActResponse::success(response, config.max_iterations)
+                    .with_token_usage(token_usage))
             }
             Err(PromptError::MaxDepthError {
                 max_depth,
@@ -39,7 +41,7 @@ impl ReActExecutor {
                 prompt: _,
             }) => {
                 if config.verbose {
-                    println!("   ⚠️ 达到最大迭代次数 ({}), 触发中断", max_depth);
+                    warn!("   ⚠️ 达到最大迭代次数 ({}), 触发中断", max_depth);
                 }
 
                 if config.return_partial_on_max_depth {
@@ -64,7 +66,7 @@ impl ReActExecutor {
             }
             Err(e) => {
                 if config.verbose {
-                    println!("   ❌ ReAct Agent出错: {:?}", e);
+                    error!("   ❌ ReAct Agent出错: {:?}", e);
                 }
                 Err(anyhow::anyhow!("ReAct Agent任务执行失败: {}", e))
             }
@@ -4,6 +4,7 @@ use anyhow::Result;
 use rig::completion::Message;
 
 use super::providers::ProviderAgent;
+use super::types::TokenUsage;
 
 /// 总结推理器
 pub struct SummaryReasoner;
@@ -16,7 +17,7 @@ impl SummaryReasoner {
         original_user_prompt: &str,
         chat_history: &[Message],
         tool_calls_history: &[String],
-    ) -> Result<String> {
+    ) -> Result<(String, TokenUsage)> {
         // 构建总结推理的提示词
         let summary_prompt = Self::build_summary_prompt(
             original_system_prompt,
@@ -26,9 +27,7 @@ impl SummaryReasoner {
         );
 
         // 使用无工具的agent进行单轮推理
-        let result = agent_without_tools.prompt(&summary_prompt).await?;
-
-        Ok(result)
+        agent_without_tools.prompt(&summary_prompt).await
     }
 
     /// 构建总结推理的提示词
@@ -20,6 +20,11 @@ impl TokenUsage {
         }
     }
 
+    /// 是否没有记录任何token（部分provider不会返回usage信息）
+    pub fn is_empty(&self) -> bool {
+        self.total_tokens == 0
+    }
+
     /// 估算成本（基于不同模型的定价）
     pub fn estimate_cost(&self, _model_name: &str) -> f64 {
         let (input_cost_per_1k, output_cost_per_1k) = (0.00025, 0.002);
@@ -28,3 +33,9 @@ impl TokenUsage {
             + (self.output_tokens as f64 / 1000.0) * output_cost_per_1k
     }
 }
+
+impl From<rig::completion::Usage> for TokenUsage {
+    fn from(usage: rig::completion::Usage) -> Self {
+        Self::new(usage.input_tokens as usize, usage.output_tokens as usize)
+    }
+}
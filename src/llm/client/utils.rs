@@ -30,3 +30,85 @@ pub fn estimate_token_usage(input_text: &str, output_text: &str) -> TokenUsage {
         output_estimate.estimated_tokens,
     )
 }
+
+/// 优先使用provider上报的token使用情况，若provider未返回则基于文本长度估算
+pub fn resolve_token_usage(
+    reported: TokenUsage,
+    input_text: &str,
+    output_text: &str,
+) -> TokenUsage {
+    if reported.is_empty() {
+        estimate_token_usage(input_text, output_text)
+    } else {
+        reported
+    }
+}
+
+/// 输出被截断且无法续写时追加的标记
+pub const TRUNCATION_MARKER: &str = "[output truncated]";
+
+/// 判断回复是否可能因达到max_tokens而被截断
+///
+/// prompt接口不暴露finish_reason，因此以provider上报的输出token数达到上限（即finish_reason=length）
+/// 或Markdown代码块未闭合作为判断依据。
+pub fn is_likely_truncated(content: &str, output_tokens: usize, max_tokens: u32) -> bool {
+    (max_tokens > 0 && output_tokens >= max_tokens as usize) || has_unclosed_code_fence(content)
+}
+
+/// 检查Markdown中是否存在未闭合的代码块围栏（``` 或 ~~~）
+pub fn has_unclosed_code_fence(content: &str) -> bool {
+    let mut open_fence: Option<&str> = None;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        for fence in ["```", "~~~"] {
+            if !trimmed.starts_with(fence) {
+                continue;
+            }
+            match open_fence {
+                None => open_fence = Some(fence),
+                // 只有同类型且不带语言标识的围栏才能闭合代码块
+                Some(open) if open == fence && trimmed.trim_end() == fence => open_fence = None,
+                Some(_) => {}
+            }
+        }
+    }
+    open_fence.is_some()
+}
+
+/// 为截断的回复补全未闭合的代码块并追加截断标记
+pub fn mark_truncated(content: &str) -> String {
+    let mut marked = content.trim_end().to_string();
+    if has_unclosed_code_fence(&marked) {
+        marked.push_str("\n```");
+    }
+    marked.push_str(&format!("\n\n> ⚠️ {}\n", TRUNCATION_MARKER));
+    marked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unbalanced_fence_is_detected_as_truncated() {
+        let truncated = "## 示例\n\n```rust\nfn main() {\n    println!(\"hi\");\n";
+        assert!(has_unclosed_code_fence(truncated));
+        assert!(is_likely_truncated(truncated, 10, 4096));
+
+        let complete = "## 示例\n\n```rust\nfn main() {}\n```\n\n~~~\nplain\n~~~\n";
+        assert!(!has_unclosed_code_fence(complete));
+        assert!(!is_likely_truncated(complete, 10, 4096));
+    }
+
+    #[test]
+    fn test_reaching_max_tokens_is_detected_as_truncated() {
+        assert!(is_likely_truncated("| a | b |\n|---|", 4096, 4096));
+    }
+
+    #[test]
+    fn test_mark_truncated_closes_fence_and_appends_marker() {
+        let marked = mark_truncated("```json\n{\"a\": 1");
+        assert!(!has_unclosed_code_fence(&marked));
+        assert!(marked.contains(TRUNCATION_MARKER));
+    }
+}
@@ -1,6 +1,7 @@
 //! 文件系统探索工具
 
 use anyhow::Result;
+use log::debug;
 use rig::tool::Tool;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -10,8 +11,9 @@ use std::time::Duration;
 use walkdir::WalkDir;
 
 use crate::config::Config;
+use crate::llm::tools::limits::{Deadline, truncation_notice};
 use crate::types::FileInfo;
-use crate::utils::file_utils::is_test_file;
+use crate::utils::file_utils::{is_hidden_path, is_test_file, matches_hidden_whitelist};
 
 /// 文件探索工具
 #[derive(Debug, Clone)]
@@ -39,12 +41,82 @@ pub struct FileExplorerResult {
     pub file_types: HashMap<String, usize>,
 }
 
+impl FileExplorerResult {
+    /// 按序列化后的大小从尾部裁剪文件与目录列表，使结果不超过`max_bytes`字节
+    fn truncate_to(&mut self, max_bytes: usize) {
+        let original_bytes = serialized_len(self);
+        if original_bytes <= max_bytes {
+            return;
+        }
+
+        self.insights.push(format!(
+            "{} 共 {} 个文件、{} 个目录，仅返回前面部分",
+            truncation_notice(original_bytes, max_bytes),
+            self.files.len(),
+            self.directories.len()
+        ));
+        let files = std::mem::take(&mut self.files);
+        let directories = std::mem::take(&mut self.directories);
+
+        // 每个条目额外计入一个分隔逗号
+        let mut remaining = max_bytes.saturating_sub(serialized_len(self));
+        self.files = files
+            .into_iter()
+            .take_while(|file| fits(serialized_len(file) + 1, &mut remaining))
+            .collect();
+        self.directories = directories
+            .into_iter()
+            .take_while(|directory| fits(serialized_len(directory) + 1, &mut remaining))
+            .collect();
+    }
+}
+
+fn limit_notice(max_files: usize) -> String {
+    format!("已达到返回数量上限（{} 个），其余条目未列出", max_files)
+}
+
+fn serialized_len<T: Serialize>(value: &T) -> usize {
+    serde_json::to_string(value).map(|s| s.len()).unwrap_or(0)
+}
+
+fn fits(size: usize, remaining: &mut usize) -> bool {
+    if size > *remaining {
+        return false;
+    }
+    *remaining -= size;
+    true
+}
+
 impl AgentToolFileExplorer {
     pub fn new(config: Config) -> Self {
         Self { config }
     }
 
-    async fn list_directory(&self, args: &FileExplorerArgs) -> Result<FileExplorerResult> {
+    /// 单次返回的条目数量上限：调用方指定的数量不能超过`max_files_per_directory`
+    fn max_files(&self, args: &FileExplorerArgs) -> usize {
+        args.max_files
+            .unwrap_or(100)
+            .min(self.config.max_files_per_directory)
+    }
+
+    /// 执行探索操作，并对结果施加超时与输出大小限制
+    async fn explore(&self, args: &FileExplorerArgs) -> Result<FileExplorerResult> {
+        let deadline = Deadline::after_seconds(self.config.llm.tool_timeout_seconds);
+        let mut result = match args.action.as_str() {
+            "list_directory" => self.list_directory(args, deadline).await?,
+            "find_files" => self.find_files(args, deadline).await?,
+            "get_file_info" => self.get_file_info(args).await?,
+            action => return Err(anyhow::anyhow!("unknown action: {}", action)),
+        };
+        result.truncate_to(self.config.llm.tool_output_max_bytes);
+        Ok(result)
+    }
+
+    async fn list_directory(
+        &self,
+        args: &FileExplorerArgs,
+        deadline: Deadline,
+    ) -> Result<FileExplorerResult> {
         let target_path = if let Some(path) = &args.path {
             self.config.project_path.join(path)
         } else {
@@ -59,15 +131,22 @@ impl AgentToolFileExplorer {
         }
 
         let recursive = args.recursive.unwrap_or(false);
-        let max_files = args.max_files.unwrap_or(100);
+        let max_files = self.max_files(args);
         let mut files = Vec::new();
         let mut directories = Vec::new();
         let mut file_types = HashMap::new();
+        let mut timed_out = false;
+        let mut limit_reached = false;
 
         if recursive {
             // 递归遍历，限制深度为3
             for entry in WalkDir::new(&target_path).max_depth(3) {
-                if files.len() >= max_files {
+                if files.len() >= max_files || directories.len() >= max_files {
+                    limit_reached = true;
+                    break;
+                }
+                if deadline.is_expired() {
+                    timed_out = true;
                     break;
                 }
 
@@ -96,7 +175,12 @@ impl AgentToolFileExplorer {
         } else {
             // 非递归，只列出当前目录
             for entry in std::fs::read_dir(&target_path)? {
-                if files.len() >= max_files {
+                if files.len() >= max_files || directories.len() >= max_files {
+                    limit_reached = true;
+                    break;
+                }
+                if deadline.is_expired() {
+                    timed_out = true;
                     break;
                 }
 
@@ -124,7 +208,13 @@ impl AgentToolFileExplorer {
             }
         }
 
-        let insights = self.generate_insights(&files, &directories, &file_types);
+        let mut insights = self.generate_insights(&files, &directories, &file_types);
+        if timed_out {
+            insights.push(deadline.expired_notice());
+        }
+        if limit_reached {
+            insights.push(limit_notice(max_files));
+        }
 
         Ok(FileExplorerResult {
             total_count: files.len(),
@@ -135,7 +225,11 @@ impl AgentToolFileExplorer {
         })
     }
 
-    async fn find_files(&self, args: &FileExplorerArgs) -> Result<FileExplorerResult> {
+    async fn find_files(
+        &self,
+        args: &FileExplorerArgs,
+        deadline: Deadline,
+    ) -> Result<FileExplorerResult> {
         let pattern = args
             .pattern
             .as_ref()
@@ -154,13 +248,20 @@ impl AgentToolFileExplorer {
             });
         }
 
-        let max_files = args.max_files.unwrap_or(100);
+        let max_files = self.max_files(args);
         let mut files = Vec::new();
         let mut file_types = HashMap::new();
+        let mut timed_out = false;
+        let mut limit_reached = false;
 
         // 使用walkdir递归搜索，限制深度为5
         for entry in WalkDir::new(&search_path).max_depth(5) {
             if files.len() >= max_files {
+                limit_reached = true;
+                break;
+            }
+            if deadline.is_expired() {
+                timed_out = true;
                 break;
             }
 
@@ -183,11 +284,17 @@ impl AgentToolFileExplorer {
             }
         }
 
-        let insights = vec![
+        let mut insights = vec![
             format!("搜索模式: {}", pattern),
             format!("搜索路径: {}", search_path.display()),
             format!("找到 {} 个匹配文件", files.len()),
         ];
+        if timed_out {
+            insights.push(deadline.expired_notice());
+        }
+        if limit_reached {
+            insights.push(limit_notice(max_files));
+        }
 
         Ok(FileExplorerResult {
             total_count: files.len(),
@@ -257,16 +364,23 @@ impl AgentToolFileExplorer {
     }
 
     fn is_ignored(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy().to_lowercase();
         let file_name = path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("")
             .to_lowercase();
 
-        // 检查排除的目录
+        let relative_path = path
+            .strip_prefix(&self.config.project_path)
+            .unwrap_or_else(|_| Path::new(path.file_name().unwrap_or_default()));
+
+        // 检查排除的目录（按路径段匹配，避免`.git`误伤`.github`）
         for excluded_dir in &self.config.excluded_dirs {
-            if path_str.contains(&excluded_dir.to_lowercase()) {
+            let excluded_dir = excluded_dir.to_lowercase();
+            if relative_path
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().to_lowercase() == excluded_dir)
+            {
                 return true;
             }
         }
@@ -314,8 +428,11 @@ impl AgentToolFileExplorer {
             return true;
         }
 
-        // 检查隐藏文件
-        if !self.config.include_hidden && file_name.starts_with('.') {
+        // 检查隐藏文件（白名单模式中的隐藏路径除外）
+        if !self.config.include_hidden
+            && is_hidden_path(relative_path)
+            && !matches_hidden_whitelist(relative_path, &self.config.included_hidden_patterns)
+        {
             return true;
         }
 
@@ -450,7 +567,9 @@ impl AgentToolFileExplorer {
 
         if !file_types.is_empty() {
             let mut type_summary = String::new();
-            for (ext, count) in file_types.iter() {
+            let mut file_types: Vec<_> = file_types.iter().collect();
+            file_types.sort();
+            for (ext, count) in file_types {
                 if !type_summary.is_empty() {
                     type_summary.push_str(", ");
                 }
@@ -512,7 +631,7 @@ impl Tool for AgentToolFileExplorer {
                     },
                     "max_files": {
                         "type": "integer",
-                        "description": "最大返回文件数量（默认100）"
+                        "description": "最大返回文件数量（默认100，不超过max_files_per_directory）"
                     }
                 },
                 "required": ["action"]
@@ -521,25 +640,109 @@ impl Tool for AgentToolFileExplorer {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-        println!("   🔧 tool called...file_reader@{:?}", args);
+        debug!("   🔧 tool called...file_reader@{:?}", args);
 
         #[cfg(debug_assertions)]
         tokio::time::sleep(Duration::from_secs(2)).await;
 
-        match args.action.as_str() {
-            "list_directory" => self
-                .list_directory(&args)
-                .await
-                .map_err(|_e| FileExplorerToolError),
-            "find_files" => self
-                .find_files(&args)
-                .await
-                .map_err(|_e| FileExplorerToolError),
-            "get_file_info" => self
-                .get_file_info(&args)
-                .await
-                .map_err(|_e| FileExplorerToolError),
-            _ => Err(FileExplorerToolError),
+        self.explore(&args)
+            .await
+            .map_err(|_e| FileExplorerToolError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_included_hidden_patterns_whitelist() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join(".github/workflows")).unwrap();
+        std::fs::write(root.join(".github/workflows/ci.yml"), "on: push").unwrap();
+        std::fs::write(root.join(".eslintrc.json"), "{}").unwrap();
+        std::fs::create_dir_all(root.join(".vscode")).unwrap();
+        std::fs::write(root.join(".vscode/settings.json"), "{}").unwrap();
+
+        let config = Config {
+            project_path: root.to_path_buf(),
+            included_hidden_patterns: vec![".github/**".to_string()],
+            ..Default::default()
+        };
+        let explorer = AgentToolFileExplorer::new(config);
+
+        assert!(!explorer.is_ignored(&root.join(".github")));
+        assert!(!explorer.is_ignored(&root.join(".github/workflows")));
+        assert!(!explorer.is_ignored(&root.join(".github/workflows/ci.yml")));
+        assert!(explorer.is_ignored(&root.join(".eslintrc.json")));
+        assert!(explorer.is_ignored(&root.join(".vscode/settings.json")));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_directory_listing_is_truncated_to_cap() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for i in 0..200 {
+            std::fs::write(root.join(format!("module_{:03}.rs", i)), "fn main() {}").unwrap();
+        }
+
+        let mut config = Config {
+            project_path: root.to_path_buf(),
+            ..Default::default()
+        };
+        config.llm.tool_output_max_bytes = 4096;
+        let explorer = AgentToolFileExplorer::new(config);
+        let args = FileExplorerArgs {
+            action: "list_directory".to_string(),
+            path: None,
+            pattern: None,
+            recursive: Some(false),
+            max_files: Some(500),
+        };
+
+        let result = explorer.explore(&args).await.unwrap();
+        let output = serde_json::to_string(&result).unwrap();
+
+        assert!(output.len() <= 4096, "输出 {} 字节超出上限", output.len());
+        assert!(!result.files.is_empty());
+        assert!(result.files.len() < 200);
+        assert_eq!(result.total_count, 200);
+        assert!(
+            result
+                .insights
+                .iter()
+                .any(|insight| insight.contains("输出已截断"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_requested_max_files_is_capped_by_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for i in 0..30 {
+            std::fs::write(root.join(format!("row_{:03}.csv", i)), "a,b").unwrap();
+        }
+
+        let config = Config {
+            project_path: root.to_path_buf(),
+            max_files_per_directory: 10,
+            ..Default::default()
+        };
+        let explorer = AgentToolFileExplorer::new(config);
+        for action in ["list_directory", "find_files"] {
+            let args = FileExplorerArgs {
+                action: action.to_string(),
+                path: None,
+                pattern: Some("*.csv".to_string()),
+                recursive: Some(false),
+                max_files: Some(usize::MAX),
+            };
+
+            let result = explorer.explore(&args).await.unwrap();
+            assert_eq!(result.files.len(), 10);
+            assert!(result.insights.contains(&limit_notice(10)));
         }
     }
 }
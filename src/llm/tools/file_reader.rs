@@ -1,13 +1,17 @@
 //! 文件读取工具
 
-#[cfg(debug_assertions)]
 use std::time::Duration;
 
 use anyhow::Result;
 use rig::tool::Tool;
 use serde::{Deserialize, Serialize};
 
-use crate::{config::Config, utils::file_utils::is_binary_file_path};
+use crate::{
+    config::Config,
+    llm::tools::limits::truncate_text,
+    utils::{file_read_limiter::FileReadLimiter, file_utils::is_binary_file_path},
+};
+use log::debug;
 
 /// 文件读取工具
 #[derive(Debug, Clone)]
@@ -59,7 +63,9 @@ impl AgentToolFileReader {
         }
 
         let metadata = tokio::fs::metadata(&file_path).await?;
-        let full_content = tokio::fs::read_to_string(&file_path).await?;
+        let full_content = FileReadLimiter::global()
+            .run(tokio::fs::read_to_string(&file_path))
+            .await?;
         let lines: Vec<&str> = full_content.lines().collect();
         let total_lines = lines.len();
 
@@ -98,7 +104,7 @@ impl AgentToolFileReader {
             };
 
         Ok(FileReaderResult {
-            content,
+            content: truncate_text(&content, self.config.llm.tool_output_max_bytes),
             file_path: args.file_path.clone(),
             total_lines,
             read_lines,
@@ -150,13 +156,22 @@ impl Tool for AgentToolFileReader {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-        println!("   🔧 tool called...file_reader@{:?}", args);
+        debug!("   🔧 tool called...file_reader@{:?}", args);
 
         #[cfg(debug_assertions)]
         tokio::time::sleep(Duration::from_secs(2)).await;
 
-        self.read_file_content(&args)
-            .await
-            .map_err(|_e| FileReaderToolError)
+        let timeout = Duration::from_secs(self.config.llm.tool_timeout_seconds);
+        match tokio::time::timeout(timeout, self.read_file_content(&args)).await {
+            Ok(result) => result.map_err(|_e| FileReaderToolError),
+            Err(_) => {
+                debug!(
+                    "   ⚠️ file_reader调用超过 {} 秒时限: {}",
+                    timeout.as_secs(),
+                    args.file_path
+                );
+                Err(FileReaderToolError)
+            }
+        }
     }
 }
@@ -0,0 +1,88 @@
+//! 工具调用的输出大小与耗时限制
+
+use std::time::{Duration, Instant};
+
+/// 工具调用的截止时间，遍历类操作在循环中检查，避免超大或异常的目录结构导致长时间阻塞
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    expires_at: Instant,
+    seconds: u64,
+}
+
+impl Deadline {
+    pub fn after_seconds(seconds: u64) -> Self {
+        Self {
+            expires_at: Instant::now() + Duration::from_secs(seconds),
+            seconds,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+
+    /// 超时后附加到工具结果中的说明
+    pub fn expired_notice(&self) -> String {
+        format!(
+            "工具调用超过 {} 秒时限，遍历已提前结束，结果不完整",
+            self.seconds
+        )
+    }
+}
+
+/// 截断说明，明确告知调用方结果已被截断
+pub fn truncation_notice(original_bytes: usize, max_bytes: usize) -> String {
+    format!(
+        "[输出已截断：原始 {} 字节，超出单次工具调用上限 {} 字节]",
+        original_bytes, max_bytes
+    )
+}
+
+/// 将文本截断到不超过`max_bytes`字节（含截断说明），截断位置回退到字符边界
+pub fn truncate_text(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+
+    let notice = format!("\n\n{}", truncation_notice(text.len(), max_bytes));
+    // 上限小于截断说明本身时只保留说明的前缀，保证结果不超过上限
+    let notice = &notice[..char_boundary_at_or_before(&notice, max_bytes)];
+    let end = char_boundary_at_or_before(text, max_bytes - notice.len());
+    format!("{}{}", &text[..end], notice)
+}
+
+/// 不超过`index`的最近字符边界
+fn char_boundary_at_or_before(text: &str, index: usize) -> usize {
+    let mut end = index.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_text_respects_limit_and_char_boundary() {
+        assert_eq!(truncate_text("short", 100), "short");
+
+        let text = "中文内容".repeat(100);
+        let truncated = truncate_text(&text, 200);
+        assert!(truncated.len() <= 200);
+        assert!(truncated.ends_with(&truncation_notice(text.len(), 200)));
+        assert!(truncated.starts_with("中文"));
+
+        // 上限小于截断说明时结果同样不超过上限
+        for max_bytes in [0, 1, 10, 40] {
+            let truncated = truncate_text(&text, max_bytes);
+            assert!(
+                truncated.len() <= max_bytes,
+                "{} 超出上限 {}",
+                truncated.len(),
+                max_bytes
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod file_explorer;
 pub mod file_reader;
+pub mod limits;
 pub mod time;
@@ -1,6 +1,8 @@
 //! 时间查询工具
 
 use anyhow::Result;
+use chrono_tz::Tz;
+use log::debug;
 use rig::tool::Tool;
 use serde::{Deserialize, Serialize};
 #[cfg(debug_assertions)]
@@ -9,7 +11,10 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 /// 时间工具
 #[derive(Debug, Clone)]
-pub struct AgentToolTime;
+pub struct AgentToolTime {
+    /// 返回`current_time`所使用的时区
+    timezone: Tz,
+}
 
 /// 时间查询参数
 #[derive(Debug, Deserialize)]
@@ -21,7 +26,9 @@ pub struct TimeArgs {
 /// 时间查询结果
 #[derive(Debug, Serialize)]
 pub struct TimeResult {
+    /// 配置时区下的当前时间
     pub current_time: String,
+    pub timezone: String,
     pub timestamp: u64,
     pub utc_time: String,
 }
@@ -40,13 +47,13 @@ impl std::error::Error for TimeToolError {}
 
 impl Default for AgentToolTime {
     fn default() -> Self {
-        Self::new()
+        Self::new(Tz::UTC)
     }
 }
 
 impl AgentToolTime {
-    pub fn new() -> Self {
-        Self
+    pub fn new(timezone: Tz) -> Self {
+        Self { timezone }
     }
 
     async fn get_current_time(&self, args: &TimeArgs) -> Result<TimeResult> {
@@ -57,16 +64,19 @@ impl AgentToolTime {
         // 格式化时间
         let format = args.format.as_deref().unwrap_or("%Y-%m-%d %H:%M:%S");
 
-        // 本地时间
-        let datetime: chrono::DateTime<chrono::Local> = now.into();
-        let current_time = datetime.format(format).to_string();
-
         // UTC时间
         let utc_datetime: chrono::DateTime<chrono::Utc> = now.into();
         let utc_time = utc_datetime.format(format).to_string();
 
+        // 配置时区的时间
+        let current_time = utc_datetime
+            .with_timezone(&self.timezone)
+            .format(format)
+            .to_string();
+
         Ok(TimeResult {
             current_time,
+            timezone: self.timezone.to_string(),
             timestamp,
             utc_time,
         })
@@ -83,7 +93,8 @@ impl Tool for AgentToolTime {
     async fn definition(&self, _prompt: String) -> rig::completion::ToolDefinition {
         rig::completion::ToolDefinition {
             name: Self::NAME.to_string(),
-            description: "获取当前日期和时间信息，包括本地时间和UTC时间以及时间戳。".to_string(),
+            description: "获取当前日期和时间信息，包括配置时区的时间、UTC时间以及时间戳。"
+                .to_string(),
             parameters: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -98,7 +109,7 @@ impl Tool for AgentToolTime {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-        println!("   🔧 tool called...time@{:?}", args);
+        debug!("   🔧 tool called...time@{:?}", args);
 
         #[cfg(debug_assertions)]
         tokio::time::sleep(Duration::from_secs(2)).await;
@@ -108,3 +119,27 @@ impl Tool for AgentToolTime {
             .map_err(|_e| TimeToolError)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_configured_timezone_changes_formatted_time() {
+        let args = TimeArgs {
+            format: Some("%H:%M %z".to_string()),
+        };
+        let utc = AgentToolTime::default()
+            .get_current_time(&args)
+            .await
+            .unwrap();
+        let shanghai = AgentToolTime::new(Tz::Asia__Shanghai)
+            .get_current_time(&args)
+            .await
+            .unwrap();
+
+        assert!(utc.current_time.ends_with("+0000"));
+        assert!(shanghai.current_time.ends_with("+0800"));
+        assert_eq!(shanghai.timezone, "Asia/Shanghai");
+    }
+}
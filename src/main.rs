@@ -1,6 +1,7 @@
-use crate::generator::workflow::launch;
+use crate::generator::workflow::launch_with_listener;
 use anyhow::Result;
 use clap::Parser;
+use std::sync::Arc;
 
 mod cache;
 mod cli;
@@ -15,7 +16,25 @@ mod utils;
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = cli::Args::parse();
+    let command = args.command.clone();
     let config = args.into_config();
+    utils::logger::init(
+        config.effective_log_level().to_level_filter(),
+        config.plain_status_output(),
+    );
 
-    launch(&config).await
+    match command {
+        Some(cli::Command::Check) => {
+            let report = cli::check::run(&config).await;
+            std::process::exit(report.exit_code());
+        }
+        Some(cli::Command::AnalyzeSelf) => {
+            let report = cli::self_test::run(&config).await;
+            std::process::exit(report.exit_code());
+        }
+        None => {}
+    }
+
+    let progress = Arc::new(cli::progress::ProgressReporter::default());
+    launch_with_listener(&config, progress).await
 }
@@ -2,7 +2,9 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
 
 /// Memory元数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,10 +34,56 @@ impl MemoryMetadata {
     }
 }
 
+/// 单个内存条目的导出内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryEntryDump {
+    /// 序列化后的字节数
+    pub size: usize,
+    pub value: Value,
+}
+
+/// 单个作用域的导出内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryScopeDump {
+    /// 作用域内全部数据的字节数
+    pub total_size: usize,
+    pub entries: BTreeMap<String, MemoryEntryDump>,
+}
+
+/// 内存导出快照，用于调试时查看各阶段实际写入了哪些数据，也作为`--resume`恢复运行时的内存快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryDump {
+    pub created_at: DateTime<Utc>,
+    pub last_updated: DateTime<Utc>,
+    pub total_size: usize,
+    pub scopes: BTreeMap<String, MemoryScopeDump>,
+}
+
+impl MemoryDump {
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
 /// 统一内存管理器
+///
+/// 数据只保存在当前进程内，进程退出即丢失；跨运行复用结果依赖每个阶段结束后写入内部目录的快照
+///（通过`--resume`恢复），快照只包含各键的当前值，不包含历史版本。
 #[derive(Debug)]
 pub struct Memory {
     data: HashMap<String, Value>,
+    /// 被覆盖的历史值，按从新到旧排列
+    history: HashMap<String, VecDeque<Value>>,
+    /// 每个键保留的历史版本数量，为0时不保留历史
+    max_versions: usize,
     metadata: MemoryMetadata,
 }
 
@@ -47,8 +95,15 @@ impl Default for Memory {
 
 impl Memory {
     pub fn new() -> Self {
+        Self::with_max_versions(0)
+    }
+
+    /// 创建保留历史版本的内存管理器，覆盖写入时每个键最多保留最近`max_versions`个旧值
+    pub fn with_max_versions(max_versions: usize) -> Self {
         Self {
             data: HashMap::new(),
+            history: HashMap::new(),
+            max_versions,
             metadata: MemoryMetadata::new(),
         }
     }
@@ -72,10 +127,41 @@ impl Memory {
         self.metadata.total_size += data_size;
         self.metadata.last_updated = Utc::now();
 
-        self.data.insert(full_key, serialized);
+        if let Some(previous) = self.data.insert(full_key.clone(), serialized)
+            && self.max_versions > 0
+        {
+            let versions = self.history.entry(full_key).or_default();
+            versions.push_front(previous);
+            versions.truncate(self.max_versions);
+        }
         Ok(())
     }
 
+    /// 仅在指定键不存在时存储数据，返回是否写入；用于可恢复的运行中保留已有的有效结果
+    pub fn store_if_absent<T>(&mut self, scope: &str, key: &str, data: T) -> Result<bool>
+    where
+        T: Serialize,
+    {
+        if self.has_data(scope, key) {
+            return Ok(false);
+        }
+        self.store(scope, key, data)?;
+        Ok(true)
+    }
+
+    /// 获取指定版本的数据：0为当前值，1为上一个版本，依此类推；超出保留范围时返回None
+    pub fn get_versioned<T>(&self, scope: &str, key: &str, version: usize) -> Option<T>
+    where
+        T: for<'a> Deserialize<'a>,
+    {
+        let full_key = format!("{}:{}", scope, key);
+        let value = match version {
+            0 => self.data.get(&full_key),
+            _ => self.history.get(&full_key)?.get(version - 1),
+        }?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
     /// 从指定作用域和键获取数据
     pub fn get<T>(&mut self, scope: &str, key: &str) -> Option<T>
     where
@@ -122,4 +208,168 @@ impl Memory {
 
         stats
     }
+
+    /// 导出所有作用域下的键、数据大小及数据内容（不计入访问次数），历史版本不包含在内
+    pub fn dump(&self) -> MemoryDump {
+        let scopes = self
+            .get_usage_stats()
+            .into_iter()
+            .map(|(scope, total_size)| {
+                let entries = self
+                    .list_keys(&scope)
+                    .into_iter()
+                    .filter_map(|key| {
+                        let full_key = format!("{}:{}", scope, key);
+                        let value = self.data.get(&full_key)?.clone();
+                        let size = self
+                            .metadata
+                            .data_sizes
+                            .get(&full_key)
+                            .copied()
+                            .unwrap_or_default();
+                        Some((key, MemoryEntryDump { size, value }))
+                    })
+                    .collect();
+                (
+                    scope,
+                    MemoryScopeDump {
+                        total_size,
+                        entries,
+                    },
+                )
+            })
+            .collect();
+
+        MemoryDump {
+            created_at: self.metadata.created_at,
+            last_updated: self.metadata.last_updated,
+            total_size: self.metadata.total_size,
+            scopes,
+        }
+    }
+
+    /// 将导出的快照写回内存，返回恢复的条目数量
+    pub fn restore(&mut self, dump: MemoryDump) -> Result<usize> {
+        let mut restored = 0;
+        for (scope, scope_dump) in dump.scopes {
+            for (key, entry) in scope_dump.entries {
+                self.store(&scope, &key, entry.value)?;
+                restored += 1;
+            }
+        }
+        Ok(restored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::preprocess::memory::{MemoryScope, ScopedKeys};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dump_contains_preprocess_scope_keys() {
+        let mut memory = Memory::new();
+        memory
+            .store(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::PROJECT_STRUCTURE,
+                "structure",
+            )
+            .unwrap();
+        memory
+            .store(
+                MemoryScope::PREPROCESS,
+                ScopedKeys::CODE_INSIGHTS,
+                vec![1, 2, 3],
+            )
+            .unwrap();
+        memory
+            .store("studies_research", "SystemContextResearcher", 42)
+            .unwrap();
+
+        let temp_dir = TempDir::new().unwrap();
+        let dump_path = temp_dir.path().join("memory-dump.json");
+        memory.dump().save(&dump_path).unwrap();
+        let dump: MemoryDump =
+            serde_json::from_str(&fs::read_to_string(&dump_path).unwrap()).unwrap();
+
+        let preprocess = &dump.scopes[MemoryScope::PREPROCESS];
+        assert!(
+            preprocess
+                .entries
+                .contains_key(ScopedKeys::PROJECT_STRUCTURE)
+        );
+        assert_eq!(
+            preprocess.entries[ScopedKeys::CODE_INSIGHTS].value,
+            serde_json::json!([1, 2, 3])
+        );
+        assert_eq!(
+            preprocess.total_size,
+            preprocess
+                .entries
+                .values()
+                .map(|entry| entry.size)
+                .sum::<usize>()
+        );
+        assert_eq!(dump.scopes.len(), 2);
+
+        // 快照可以恢复到新的内存中
+        let mut restored = Memory::new();
+        assert_eq!(
+            restored
+                .restore(MemoryDump::load(&dump_path).unwrap())
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            restored.get::<Vec<i32>>(MemoryScope::PREPROCESS, ScopedKeys::CODE_INSIGHTS),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(
+            restored.get::<i32>("studies_research", "SystemContextResearcher"),
+            Some(42)
+        );
+    }
+
+    #[test]
+    fn test_store_keeps_previous_versions() {
+        let mut memory = Memory::with_max_versions(2);
+        memory
+            .store("studies_research", "ArchitectureResearcher", "v1")
+            .unwrap();
+        memory
+            .store("studies_research", "ArchitectureResearcher", "v2")
+            .unwrap();
+        memory
+            .store("studies_research", "ArchitectureResearcher", "v3")
+            .unwrap();
+
+        let at = |memory: &Memory, version| {
+            memory.get_versioned::<String>("studies_research", "ArchitectureResearcher", version)
+        };
+        assert_eq!(at(&memory, 0).as_deref(), Some("v3"));
+        assert_eq!(at(&memory, 1).as_deref(), Some("v2"));
+        assert_eq!(at(&memory, 2).as_deref(), Some("v1"));
+        assert_eq!(at(&memory, 3), None);
+
+        // 已存在的结果不会被覆盖，也不会产生新的历史版本
+        assert!(
+            !memory
+                .store_if_absent("studies_research", "ArchitectureResearcher", "partial")
+                .unwrap()
+        );
+        assert_eq!(at(&memory, 0).as_deref(), Some("v3"));
+        assert!(
+            memory
+                .store_if_absent("studies_research", "WorkflowResearcher", "w1")
+                .unwrap()
+        );
+
+        // 默认不保留历史版本
+        let mut memory = Memory::new();
+        memory.store("scope", "key", 1).unwrap();
+        memory.store("scope", "key", 2).unwrap();
+        assert_eq!(memory.get_versioned::<i32>("scope", "key", 1), None);
+    }
 }
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 分析报告文件名（位于`.litho`目录下）
+const ANALYSIS_REPORT_FILE_NAME: &str = "analysis-report.json";
+
+/// 文件的分析状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileAnalysisStatus {
+    /// 作为核心文件参与了代码分析
+    Analyzed,
+    /// 被排除规则（目录、文件名、扩展名、测试/隐藏文件等）过滤
+    SkippedExcluded,
+    /// 超出文件大小限制
+    SkippedTooLarge,
+    /// 二进制文件
+    SkippedBinary,
+    /// 已发现但未被选为核心文件
+    SkippedNotCore,
+    /// 大型数据目录，文件数量超出单目录上限，仅抽样部分文件
+    SkippedLargeDirectory,
+}
+
+/// 单个文件（或被整体排除的目录）的分析状态及原因
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileAnalysisEntry {
+    /// 相对于项目根目录的路径，被排除的目录以`/`结尾
+    pub path: String,
+    pub status: FileAnalysisStatus,
+    pub reason: String,
+}
+
+impl FileAnalysisEntry {
+    pub fn new(path: &Path, status: FileAnalysisStatus, reason: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string_lossy().replace('\\', "/"),
+            status,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// 已分析与被跳过文件的清单，用于排查“某个文件为什么没有出现在文档中”
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AnalysisReport {
+    pub files: Vec<FileAnalysisEntry>,
+}
+
+impl AnalysisReport {
+    /// 报告文件路径
+    pub fn path(internal_path: &Path) -> PathBuf {
+        internal_path.join(ANALYSIS_REPORT_FILE_NAME)
+    }
+
+    /// 指定状态的文件数量
+    pub fn count(&self, status: FileAnalysisStatus) -> usize {
+        self.files
+            .iter()
+            .filter(|entry| entry.status == status)
+            .count()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
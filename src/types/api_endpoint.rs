@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::code::ParameterInfo;
+
+/// API 端点信息
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiEndpoint {
+    pub method: String,                 // GET, POST, etc.
+    pub path: String,                   // /api/users/:id
+    pub handler: String,                // 处理函数名
+    pub file_path: String,              // 定义位置
+    pub line_number: usize,             // 行号
+    pub parameters: Vec<ParameterInfo>, // 参数列表
+    pub return_type: Option<String>,    // 返回类型
+    pub description: Option<String>,    // 端点描述（来自接口规范）
+    pub framework: Option<String>,      // 框架类型 (Actix, Axum, Rocket, OpenAPI等)
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// 公开接口的稳定性标记
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiStability {
+    /// 已弃用：`#[deprecated]`、`@deprecated`、`@Deprecated`
+    Deprecated,
+    /// 仅供内部使用：`@internal`、`#[doc(hidden)]`
+    Internal,
+    /// 实验性/不稳定：`#[unstable]`、`@experimental`、`@beta`、`@alpha`
+    Unstable,
+}
+
+/// 被标记为弃用或不稳定的公开接口
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ApiSurfaceItem {
+    pub name: String,
+    pub interface_type: String,
+    /// 所在文件（相对于项目根目录）
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub stability: ApiStability,
+    /// 标记所在的源码行，如`#[deprecated(note = "...")]`
+    pub marker: String,
+}
+
+/// 公开API表面及其稳定性提示
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ApiSurfaceReport {
+    /// 公开接口总数
+    pub public_items: usize,
+    /// 被标记为弃用、内部或不稳定的公开接口
+    pub flagged: Vec<ApiSurfaceItem>,
+}
+
+impl ApiSurfaceReport {
+    pub fn is_empty(&self) -> bool {
+        self.public_items == 0
+    }
+
+    /// 指定稳定性标记的接口数量
+    pub fn count(&self, stability: ApiStability) -> usize {
+        self.flagged
+            .iter()
+            .filter(|item| item.stability == stability)
+            .count()
+    }
+}
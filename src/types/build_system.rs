@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// 识别到的构建/CI配置文件
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BuildConfigFile {
+    /// 工具名称，如GitHub Actions、Makefile、Dockerfile
+    pub tool: String,
+    /// 配置文件路径（相对项目根目录）
+    pub file_path: String,
+    /// 从配置中提取的要点，如工作流任务、构建目标、基础镜像
+    pub details: Vec<String>,
+}
+
+/// 包管理器中定义的脚本命令
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PackageScript {
+    pub name: String,
+    pub command: String,
+    /// 定义脚本的清单文件
+    pub manifest_path: String,
+}
+
+/// 项目的构建系统与持续集成配置
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BuildSystemReport {
+    /// 持续集成流水线配置
+    pub ci: Vec<BuildConfigFile>,
+    /// 构建与容器化工具配置
+    pub build_tools: Vec<BuildConfigFile>,
+    /// 包管理器脚本
+    pub package_scripts: Vec<PackageScript>,
+}
+
+impl BuildSystemReport {
+    pub fn is_empty(&self) -> bool {
+        self.ci.is_empty() && self.build_tools.is_empty() && self.package_scripts.is_empty()
+    }
+}
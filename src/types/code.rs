@@ -6,6 +6,8 @@ use std::{
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::config::CustomCodePurpose;
+
 /// 代码基本信息
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct CodeDossier {
@@ -39,7 +41,9 @@ pub struct CodeInsight {
     pub interfaces: Vec<InterfaceInfo>,
     /// 依赖信息
     pub dependencies: Vec<Dependency>,
-    pub complexity_metrics: CodeComplexity,
+    /// 复杂度指标，文件超出复杂度计算阈值而跳过计算时为None
+    #[serde(default)]
+    pub complexity_metrics: Option<CodeComplexity>,
 }
 
 /// 接口信息
@@ -68,6 +72,10 @@ pub struct InterfaceInfo {
     /// 原始代码片段
     #[serde(default)]
     pub source_code: Option<String>,
+    /// 从测试代码中挖掘的调用示例
+    #[schemars(skip)]
+    #[serde(default)]
+    pub usage_examples: Vec<UsageExample>,
 }
 
 impl InterfaceInfo {
@@ -92,10 +100,22 @@ impl InterfaceInfo {
             fields: Vec::new(),
             variants: Vec::new(),
             source_code: None,
+            usage_examples: Vec::new(),
         }
     }
 }
 
+/// 接口的使用示例
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+pub struct UsageExample {
+    /// 调用代码片段
+    pub snippet: String,
+    /// 示例所在的测试文件
+    pub file_path: String,
+    /// 示例所在行号
+    pub line_number: usize,
+}
+
 /// 参数信息
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ParameterInfo {
@@ -210,6 +230,9 @@ pub enum CodePurpose {
     /// 数据库组件
     #[serde(alias = "数据库组件")]
     Database,
+    /// 数据库迁移脚本（SQL、Diesel、Prisma、Flyway等），描述数据库结构的演进
+    #[serde(alias = "数据库迁移脚本", alias = "数据库迁移")]
+    Migration,
     /// 供外部调用的服务API，提供基于HTTP、RPC、IPC等协议等调用能力。
     #[serde(alias = "供外部调用的服务API", alias = "各类接口定义", alias = "供外部调用的服务API，提供基于HTTP、RPC、IPC等协议等调用能力。")]
     Api,
@@ -235,11 +258,14 @@ pub enum CodePurpose {
     #[default]
     #[serde(alias = "其他未归类", alias = "其他组件", alias = "其他未归类或未知")]
     Other,
+    /// 配置中自定义的分类，序列化为分类名称本身
+    #[serde(untagged)]
+    Custom(String),
 }
 
 impl CodePurpose {
     /// 获取组件类型的显示名称
-    pub fn display_name(&self) -> &'static str {
+    pub fn display_name(&self) -> &str {
         match self {
             CodePurpose::Entry => "项目执行入口",
             CodePurpose::Agent => "智能Agent",
@@ -254,6 +280,7 @@ impl CodePurpose {
             CodePurpose::Plugin => "插件",
             CodePurpose::Router => "路由组件",
             CodePurpose::Database => "数据库组件",
+            CodePurpose::Migration => "数据库迁移脚本",
             CodePurpose::Api => "各类接口定义",
             CodePurpose::Controller => "Controller组件",
             CodePurpose::Service => "Service组件",
@@ -263,8 +290,34 @@ impl CodePurpose {
             CodePurpose::Doc => "文档组件",
             CodePurpose::Other => "其他组件",
             CodePurpose::Types => "程序接口定义",
+            CodePurpose::Custom(name) => name,
         }
     }
+
+    /// 文档编排时的展示优先级，数值越小越靠前：
+    /// 入口 > API/Controller/Service > 模块 > 工具 > 其他
+    pub fn compose_priority(&self) -> u8 {
+        match self {
+            CodePurpose::Entry => 0,
+            CodePurpose::Api | CodePurpose::Controller | CodePurpose::Service => 1,
+            CodePurpose::Module => 2,
+            CodePurpose::Util => 3,
+            _ => 4,
+        }
+    }
+
+    /// 规范化反序列化得到的分类：自定义分类统一为配置中的名称，未配置的未知分类归为Other。
+    /// `Custom`会接受任意字符串，因此LLM结果与缓存结果进入流程前都需要经过这里
+    pub fn resolve_custom(self, custom_purposes: &[CustomCodePurpose]) -> CodePurpose {
+        let CodePurpose::Custom(name) = &self else {
+            return self;
+        };
+        custom_purposes
+            .iter()
+            .find(|custom| custom.name.eq_ignore_ascii_case(name.trim()))
+            .map(|custom| CodePurpose::Custom(custom.name.clone()))
+            .unwrap_or(CodePurpose::Other)
+    }
 }
 
 impl Display for CodePurpose {
@@ -282,6 +335,11 @@ impl CodePurposeMapper {
         let path_lower = file_path.to_lowercase();
         let name_lower = file_name.to_lowercase();
 
+        // 数据库迁移脚本优先识别，避免被归入数据库组件或其他类别
+        if Self::is_migration_path(&path_lower, &name_lower) {
+            return CodePurpose::Migration;
+        }
+
         // 基于路径的映射
         if path_lower.contains("/pages/")
             || path_lower.contains("/views/")
@@ -400,6 +458,36 @@ impl CodePurposeMapper {
 
         CodePurpose::Other
     }
+
+    /// 判断文件是否为数据库迁移脚本（覆盖SQL目录约定、Diesel、Prisma、Flyway、Rails、Alembic等）
+    pub fn is_migration_path(path_lower: &str, name_lower: &str) -> bool {
+        let normalized_path = path_lower.replace('\\', "/");
+        let in_migration_dir = normalized_path
+            .split('/')
+            .rev()
+            .skip(1) // 跳过文件名本身
+            .any(|segment| matches!(segment, "migrations" | "migration" | "migrate"))
+            || normalized_path.contains("alembic/versions/");
+        if in_migration_dir {
+            return true;
+        }
+
+        // Flyway命名约定：V1__init.sql / R__views.sql
+        let is_flyway = name_lower.ends_with(".sql")
+            && (name_lower.starts_with('v') || name_lower.starts_with('r'))
+            && name_lower[1..]
+                .split_once("__")
+                .is_some_and(|(version, _)| {
+                    version
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || c == '.' || c == '_')
+                });
+
+        is_flyway
+            || name_lower.ends_with(".up.sql")
+            || name_lower.ends_with(".down.sql")
+            || name_lower.contains("migration")
+    }
 }
 
 #[cfg(test)]
@@ -576,4 +664,26 @@ mod tests {
         assert_eq!(info.parameters.len(), 2);
         assert_eq!(info.return_type, Some("Result<User>".to_string()));
     }
+
+    #[test]
+    fn test_migration_file_classified_as_migration() {
+        assert_eq!(
+            CodePurposeMapper::map_by_path_and_name("migrations/001_init.sql", "001_init.sql"),
+            CodePurpose::Migration
+        );
+        assert_eq!(
+            CodePurposeMapper::map_by_path_and_name(
+                "src/main/resources/db/migration/V1__create_users.sql",
+                "V1__create_users.sql"
+            ),
+            CodePurpose::Migration
+        );
+        assert_eq!(
+            CodePurposeMapper::map_by_path_and_name("src/db/pool.rs", "pool.rs"),
+            CodePurpose::Database
+        );
+
+        let purpose: CodePurpose = serde_json::from_str("\"migration\"").unwrap();
+        assert_eq!(purpose, CodePurpose::Migration);
+    }
 }
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 源码中识别出的并发原语，如异步运行时、线程、通道与锁
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConcurrencySignal {
+    /// 并发模式，如 `async/tokio`、`goroutine`、`channel`、`lock`
+    pub pattern: String,
+    /// 所在文件
+    pub file_path: String,
+    /// 文件中首次出现的行号
+    pub line_number: usize,
+}
+
+/// 项目的并发模型概况
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ConcurrencyReport {
+    pub signals: Vec<ConcurrencySignal>,
+}
+
+impl ConcurrencyReport {
+    pub fn is_empty(&self) -> bool {
+        self.signals.is_empty()
+    }
+
+    /// 按并发模式汇总出现的位置，模式按名称排序
+    pub fn by_pattern(&self) -> BTreeMap<&str, Vec<&ConcurrencySignal>> {
+        let mut patterns: BTreeMap<&str, Vec<&ConcurrencySignal>> = BTreeMap::new();
+        for signal in &self.signals {
+            patterns.entry(&signal.pattern).or_default().push(signal);
+        }
+        patterns
+    }
+}
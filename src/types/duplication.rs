@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// 代码摘要高度相似的一组文件
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DuplicateGroup {
+    /// 组内文件（相对于项目根目录）
+    pub files: Vec<String>,
+    /// 组内连接各文件的最低余弦相似度
+    pub similarity: f32,
+}
+
+/// 疑似重复代码的检测结果
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DuplicationReport {
+    /// 判定疑似重复使用的相似度阈值
+    pub threshold: f32,
+    /// 按组内最低相似度降序排列的疑似重复文件组
+    pub groups: Vec<DuplicateGroup>,
+}
+
+impl DuplicationReport {
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
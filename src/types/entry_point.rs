@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::types::code_releationship::CoreDependency;
+
+/// 程序入口，以及从入口出发沿调用/导入关系追踪到的运行时调用链
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EntryPoint {
+    /// 入口文件路径（相对于项目根目录）
+    pub file_path: String,
+    /// 入口文件的语言
+    pub language: String,
+    /// 从入口向外追踪到的依赖边，按广度优先顺序排列
+    #[serde(default)]
+    pub flow: Vec<CoreDependency>,
+}
+
+impl EntryPoint {
+    /// 以入口为起点的Mermaid流程图
+    pub fn to_mermaid(&self) -> String {
+        let mut node_ids: HashMap<&str, String> = HashMap::new();
+        let mut nodes = vec![self.file_path.as_str()];
+        node_ids.insert(&self.file_path, "N0".to_string());
+        for dependency in &self.flow {
+            for path in [dependency.from.as_str(), dependency.to.as_str()] {
+                if !node_ids.contains_key(path) {
+                    node_ids.insert(path, format!("N{}", nodes.len()));
+                    nodes.push(path);
+                }
+            }
+        }
+
+        let mut mermaid = String::from("flowchart TD\n");
+        for path in &nodes {
+            mermaid.push_str(&format!("    {}[\"{}\"]\n", node_ids[path], path));
+        }
+        for dependency in &self.flow {
+            mermaid.push_str(&format!(
+                "    {} -->|{}| {}\n",
+                node_ids[dependency.from.as_str()],
+                dependency.dependency_type.as_str(),
+                node_ids[dependency.to.as_str()]
+            ));
+        }
+        mermaid
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Cargo `[features]` 中声明的特性
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CargoFeature {
+    /// 特性名称
+    pub name: String,
+    /// 启用该特性时连带启用的特性或可选依赖
+    pub enables: Vec<String>,
+    /// 是否包含在default特性中
+    pub is_default: bool,
+    /// 声明该特性的Cargo.toml路径
+    pub manifest_path: String,
+}
+
+/// 代码中受特性开关或环境变量控制的位置
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FeatureGate {
+    /// 开关名称，如特性名或环境变量名
+    pub name: String,
+    /// 开关类型：cfg_feature（条件编译特性）或 env（环境变量）
+    pub gate_type: String,
+    /// 所在文件
+    pub file_path: String,
+    /// 所在行号
+    pub line_number: usize,
+}
+
+/// 项目中可配置的构建/运行时特性
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FeatureFlagReport {
+    pub cargo_features: Vec<CargoFeature>,
+    pub gates: Vec<FeatureGate>,
+}
+
+impl FeatureFlagReport {
+    pub fn is_empty(&self) -> bool {
+        self.cargo_features.is_empty() && self.gates.is_empty()
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// 无对应语言处理器的文件归入的语言名称
+pub const OTHER_LANGUAGE: &str = "other";
+
+/// 单一语言的规模统计
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LanguageStat {
+    /// 语言名称
+    pub language: String,
+    /// 文件数量
+    pub files: usize,
+    /// 代码行数
+    pub lines: usize,
+    /// 占项目总行数的百分比（项目无可统计行数时按文件数计算）
+    pub percentage: f64,
+}
+
+/// 项目的语言构成，按占比从高到低排列
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LanguageBreakdown {
+    pub languages: Vec<LanguageStat>,
+    pub total_files: usize,
+    pub total_lines: usize,
+}
+
+impl LanguageBreakdown {
+    pub fn is_empty(&self) -> bool {
+        self.languages.is_empty()
+    }
+
+    /// 主要语言（占比最高且不属于other的语言）
+    pub fn primary_language(&self) -> Option<&LanguageStat> {
+        self.languages
+            .iter()
+            .find(|stat| stat.language != OTHER_LANGUAGE)
+    }
+}
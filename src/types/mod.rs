@@ -1,5 +1,14 @@
+pub mod analysis_report;
+pub mod api_endpoint;
+pub mod api_surface;
+pub mod build_system;
 pub mod code;
 pub mod code_releationship;
+pub mod concurrency;
+pub mod duplication;
+pub mod entry_point;
+pub mod feature_flag;
+pub mod language_breakdown;
 pub mod original_document;
 pub mod project_structure;
 
@@ -28,4 +37,7 @@ pub struct DirectoryInfo {
     pub subdirectory_count: usize,
     pub total_size: u64,
     pub importance_score: f64,
+    /// 大型数据目录的实际文件数量（超过`max_files_per_directory`时仅抽样部分文件，`file_count`为抽样后的数量）
+    #[serde(default)]
+    pub large_data_file_count: Option<usize>,
 }
@@ -0,0 +1,330 @@
+use anyhow::{Context, Result, bail};
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use tempfile::TempDir;
+
+use crate::config::Config;
+
+/// 解压后的总大小上限，防止压缩炸弹耗尽磁盘
+const MAX_EXTRACTED_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// 支持作为项目源码输入的归档格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveKind {
+    Zip,
+    TarGz,
+}
+
+impl ArchiveKind {
+    /// 根据文件扩展名识别归档格式，目录或不支持的文件返回None
+    pub fn detect(path: &Path) -> Option<Self> {
+        if !path.is_file() {
+            return None;
+        }
+        let file_name = path.file_name()?.to_string_lossy().to_lowercase();
+        if file_name.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// 解压到临时目录的项目归档，离开作用域时自动清理临时目录
+pub struct ExtractedArchive {
+    temp_dir: TempDir,
+    root: PathBuf,
+    /// 因超过单文件大小限制而跳过的文件数
+    pub skipped_files: usize,
+}
+
+impl ExtractedArchive {
+    /// 解压后的项目根目录，归档内只有一个顶层目录时指向该目录
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 归档内容是否被直接解压在临时目录根部（没有统一的顶层目录）
+    fn is_flat(&self) -> bool {
+        self.root == self.temp_dir.path()
+    }
+}
+
+/// 解压过程中的大小限制
+struct ExtractLimits {
+    max_file_size: u64,
+    remaining_bytes: u64,
+    skipped_files: usize,
+}
+
+/// 将归档解压到临时目录，超过`max_file_size`的文件会被跳过
+pub fn extract(
+    archive_path: &Path,
+    kind: ArchiveKind,
+    max_file_size: u64,
+) -> Result<ExtractedArchive> {
+    let temp_dir = tempfile::Builder::new()
+        .prefix("litho-archive-")
+        .tempdir()
+        .context("无法创建归档解压临时目录")?;
+    let mut limits = ExtractLimits {
+        max_file_size,
+        remaining_bytes: MAX_EXTRACTED_BYTES,
+        skipped_files: 0,
+    };
+
+    match kind {
+        ArchiveKind::Zip => extract_zip(archive_path, temp_dir.path(), &mut limits),
+        ArchiveKind::TarGz => extract_tar_gz(archive_path, temp_dir.path(), &mut limits),
+    }
+    .with_context(|| format!("解压归档失败: {}", archive_path.display()))?;
+
+    let root =
+        single_top_level_dir(temp_dir.path()).unwrap_or_else(|| temp_dir.path().to_path_buf());
+    Ok(ExtractedArchive {
+        temp_dir,
+        root,
+        skipped_files: limits.skipped_files,
+    })
+}
+
+/// 项目路径指向归档文件时将其解压，返回指向解压目录的配置；项目路径为目录时原样返回
+pub fn prepare_project(config: &Config) -> Result<(Config, Option<ExtractedArchive>)> {
+    let Some(kind) = ArchiveKind::detect(&config.project_path) else {
+        return Ok((config.clone(), None));
+    };
+
+    let archive = extract(&config.project_path, kind, config.max_file_size)?;
+    let mut prepared = config.clone();
+    prepared.project_path = archive.root().to_path_buf();
+    // 默认的内部目录位于归档文件路径之下，改为放在归档文件所在目录中；
+    // 解压目录在运行结束时即被清理，不能用于保存需要跨运行保留的内部数据
+    if config.internal_path.starts_with(&config.project_path) {
+        let archive_dir = config
+            .project_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."));
+        prepared.internal_path = archive_dir.join(".litho");
+    }
+    // 没有统一顶层目录时，临时目录名没有意义，改用归档文件名作为项目名称的兜底
+    if prepared.project_name.is_none() && archive.is_flat() {
+        prepared.project_name = Some(archive_stem(&config.project_path));
+    }
+    Ok((prepared, Some(archive)))
+}
+
+fn extract_zip(archive_path: &Path, dest: &Path, limits: &mut ExtractLimits) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(File::open(archive_path)?)?;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        // enclosed_name会拒绝绝对路径及跳出解压目录的路径
+        let Some(relative_path) = entry.enclosed_name() else {
+            continue;
+        };
+        let target = dest.join(relative_path);
+        if entry.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        let size = entry.size();
+        write_entry(&mut entry, size, &target, limits)?;
+    }
+    Ok(())
+}
+
+fn extract_tar_gz(archive_path: &Path, dest: &Path, limits: &mut ExtractLimits) -> Result<()> {
+    let mut archive = tar::Archive::new(GzDecoder::new(File::open(archive_path)?));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let Some(relative_path) = sanitize_entry_path(&entry.path()?) else {
+            continue;
+        };
+        let target = dest.join(relative_path);
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        // 符号链接等特殊条目不参与分析
+        if !entry_type.is_file() {
+            continue;
+        }
+        let size = entry.size();
+        write_entry(&mut entry, size, &target, limits)?;
+    }
+    Ok(())
+}
+
+/// 写出单个文件条目，按声明大小限制读取量以防止条目头信息造假
+fn write_entry(
+    reader: &mut impl Read,
+    size: u64,
+    target: &Path,
+    limits: &mut ExtractLimits,
+) -> Result<()> {
+    if size > limits.max_file_size {
+        limits.skipped_files += 1;
+        return Ok(());
+    }
+    if size > limits.remaining_bytes {
+        bail!("归档解压后的总大小超过上限 {} 字节", MAX_EXTRACTED_BYTES);
+    }
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = File::create(target)?;
+    let written = io::copy(&mut reader.take(size), &mut file)?;
+    limits.remaining_bytes -= written;
+    Ok(())
+}
+
+/// 只保留普通路径组件，包含`..`、根目录等组件的路径视为不安全并返回None
+fn sanitize_entry_path(path: &Path) -> Option<PathBuf> {
+    let mut sanitized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => sanitized.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!sanitized.as_os_str().is_empty()).then_some(sanitized)
+}
+
+/// 目录下只有一个子目录且没有其他文件时返回该子目录
+fn single_top_level_dir(dir: &Path) -> Option<PathBuf> {
+    let mut entries = fs::read_dir(dir).ok()?.filter_map(|entry| entry.ok());
+    let first = entries.next()?;
+    if entries.next().is_some() || !first.path().is_dir() {
+        return None;
+    }
+    Some(first.path())
+}
+
+/// 去掉归档扩展名后的文件名
+fn archive_stem(archive_path: &Path) -> String {
+    let file_name = archive_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let lower = file_name.to_lowercase();
+    [".tar.gz", ".tgz", ".zip"]
+        .iter()
+        .find(|extension| lower.ends_with(*extension))
+        .map(|extension| file_name[..file_name.len() - extension.len()].to_string())
+        .unwrap_or(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::context::GeneratorContext;
+    use crate::generator::preprocess::extractors::structure_extractor::StructureExtractor;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn write_zip(path: &Path, files: &[(&str, &str)]) {
+        let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_analyze_zipped_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("demo.zip");
+        write_zip(
+            &archive_path,
+            &[
+                ("demo/Cargo.toml", "[package]\nname = \"demo\"\n"),
+                (
+                    "demo/src/main.rs",
+                    "fn main() {\n    println!(\"hi\");\n}\n",
+                ),
+                (
+                    "demo/src/lib.rs",
+                    "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+                ),
+            ],
+        );
+
+        let mut config = Config {
+            project_path: archive_path.clone(),
+            output_path: temp_dir.path().join("output"),
+            internal_path: archive_path.join(".litho"),
+            ..Default::default()
+        };
+        config.cache.enabled = false;
+        let (prepared, archive) = prepare_project(&config).unwrap();
+        let archive = archive.expect("zip archive should be extracted");
+        let extracted_root = archive.root().to_path_buf();
+
+        assert!(extracted_root.ends_with("demo"));
+        assert_eq!(prepared.project_path, extracted_root);
+        assert_eq!(prepared.internal_path, temp_dir.path().join(".litho"));
+        assert_eq!(prepared.get_project_name(), "demo");
+
+        let context = GeneratorContext::new(prepared.clone()).unwrap();
+        let structure = StructureExtractor::new(context)
+            .extract_structure(&prepared.project_path)
+            .await
+            .unwrap();
+        let analyzed: Vec<String> = structure
+            .files
+            .iter()
+            .map(|file| file.path.to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert!(analyzed.contains(&"src/main.rs".to_string()));
+        assert!(analyzed.contains(&"src/lib.rs".to_string()));
+        assert_eq!(structure.project_name, "demo");
+
+        // 分析结束后释放归档即清理临时目录
+        drop(archive);
+        assert!(!extracted_root.exists());
+    }
+
+    #[test]
+    fn test_tar_gz_skips_oversized_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("release.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&archive_path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        for (name, size) in [("main.py", 16), ("data/blob.bin", 4096)] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(size);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, io::repeat(b'a').take(size))
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let config = Config {
+            project_path: archive_path,
+            max_file_size: 1024,
+            ..Default::default()
+        };
+        let (prepared, archive) = prepare_project(&config).unwrap();
+        let archive = archive.unwrap();
+
+        assert_eq!(archive.skipped_files, 1);
+        assert!(prepared.project_path.join("main.py").is_file());
+        assert!(!prepared.project_path.join("data/blob.bin").exists());
+        assert_eq!(prepared.project_name.as_deref(), Some("release"));
+    }
+}
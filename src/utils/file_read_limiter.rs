@@ -0,0 +1,130 @@
+//! 文件读取并发限制 - 并发读取大量文件时可能耗尽进程的文件描述符（`too many open files`），
+//! 所有并发读取路径共享同一个信号量，上限默认取文件描述符上限的一部分
+
+use std::future::Future;
+use std::sync::OnceLock;
+use tokio::sync::Semaphore;
+
+/// 未配置时使用文件描述符上限的 1/N 作为并发读取上限，其余留给网络连接、缓存写入等
+const FD_LIMIT_DIVISOR: u64 = 4;
+/// 无法查询文件描述符上限时的并发读取上限
+const DEFAULT_CONCURRENT_FILE_READS: usize = 64;
+/// 自动计算的并发读取上限的取值范围
+const MIN_CONCURRENT_FILE_READS: usize = 4;
+const MAX_CONCURRENT_FILE_READS: usize = 1024;
+
+static GLOBAL_LIMITER: OnceLock<FileReadLimiter> = OnceLock::new();
+
+/// 文件读取并发限制器
+#[derive(Debug)]
+pub struct FileReadLimiter {
+    semaphore: Semaphore,
+    limit: usize,
+}
+
+impl FileReadLimiter {
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            semaphore: Semaphore::new(limit),
+            limit,
+        }
+    }
+
+    /// 按配置初始化全局限制器，未配置时根据文件描述符上限自动计算；已初始化时保持原有上限
+    pub fn init_global(configured: Option<usize>) -> &'static Self {
+        GLOBAL_LIMITER.get_or_init(|| Self::new(configured.unwrap_or_else(auto_limit)))
+    }
+
+    /// 全局限制器，未显式初始化时按文件描述符上限自动计算
+    pub fn global() -> &'static Self {
+        Self::init_global(None)
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// 占用一个读取许可执行文件操作，超出上限的操作排队等待
+    pub async fn run<T>(&self, operation: impl Future<Output = T>) -> T {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("文件读取信号量不会被关闭");
+        operation.await
+    }
+}
+
+/// 根据文件描述符上限计算安全的并发读取数量
+fn auto_limit() -> usize {
+    open_file_limit()
+        .map(|limit| {
+            ((limit / FD_LIMIT_DIVISOR) as usize)
+                .clamp(MIN_CONCURRENT_FILE_READS, MAX_CONCURRENT_FILE_READS)
+        })
+        .unwrap_or(DEFAULT_CONCURRENT_FILE_READS)
+}
+
+/// 当前进程的文件描述符软上限（`ulimit -n`）
+#[cfg(unix)]
+fn open_file_limit() -> Option<u64> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: getrlimit只写入传入的rlimit结构体
+    let result = unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) };
+    (result == 0 && limit.rlim_cur != libc::RLIM_INFINITY).then_some(limit.rlim_cur)
+}
+
+#[cfg(not(unix))]
+fn open_file_limit() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_many_reads_stay_under_concurrency_bound() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths: Vec<_> = (0..64)
+            .map(|index| {
+                let path = temp_dir.path().join(format!("file_{}.txt", index));
+                std::fs::write(&path, format!("content {}", index)).unwrap();
+                path
+            })
+            .collect();
+        let limiter = FileReadLimiter::new(3);
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let (in_flight, peak) = (&in_flight, &peak);
+        let reads = paths.iter().map(|path| {
+            limiter.run(async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(current, Ordering::SeqCst);
+                let content = tokio::fs::read_to_string(path).await;
+                tokio::task::yield_now().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                content
+            })
+        });
+        let contents = futures::future::join_all(reads).await;
+
+        assert_eq!(contents.len(), 64);
+        assert!(contents.iter().all(|content| content.is_ok()));
+        assert!(peak.load(Ordering::SeqCst) <= limiter.limit());
+        assert!(peak.load(Ordering::SeqCst) > 1);
+    }
+
+    #[test]
+    fn test_auto_limit_within_bounds() {
+        let limit = auto_limit();
+        assert!((MIN_CONCURRENT_FILE_READS..=MAX_CONCURRENT_FILE_READS).contains(&limit));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::file_read_limiter::FileReadLimiter;
 use std::path::Path;
 
 /// 检查文件是否为测试文件
@@ -153,3 +154,197 @@ pub fn is_binary_file_path(path: &Path) -> bool {
         false
     }
 }
+
+/// 判断二进制内容时检查的前缀字节数
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+/// 根据内容判断是否为二进制数据：前若干字节中出现空字节即视为二进制（与git的判断方式一致）
+pub fn is_binary_content(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_SNIFF_BYTES).any(|byte| *byte == 0)
+}
+
+/// 读取文件开头部分判断其是否为二进制文件，不依赖扩展名；无法读取时返回false
+pub fn is_binary_file_content(path: &Path) -> bool {
+    use std::io::Read;
+
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut buffer = Vec::with_capacity(BINARY_SNIFF_BYTES);
+    if file
+        .take(BINARY_SNIFF_BYTES as u64)
+        .read_to_end(&mut buffer)
+        .is_err()
+    {
+        return false;
+    }
+    is_binary_content(&buffer)
+}
+
+/// 检查相对路径中是否包含隐藏的路径段（以`.`开头的文件或目录）
+pub fn is_hidden_path(relative_path: &Path) -> bool {
+    relative_path.components().any(|component| {
+        component
+            .as_os_str()
+            .to_str()
+            .map(|s| s.starts_with('.') && s != "." && s != "..")
+            .unwrap_or(false)
+    })
+}
+
+/// 检查隐藏路径是否命中白名单模式（如`.github/**`）
+///
+/// 模式基于项目根目录的相对路径进行glob匹配；对于目录，只要其可能包含匹配的文件即视为命中，
+/// 以便扫描时能够进入`.github`这类隐藏目录。
+pub fn matches_hidden_whitelist(relative_path: &Path, patterns: &[String]) -> bool {
+    let segments: Vec<String> = relative_path
+        .components()
+        .filter_map(|c| c.as_os_str().to_str().map(|s| s.to_string()))
+        .filter(|s| s != ".")
+        .collect();
+    if segments.is_empty() {
+        return false;
+    }
+    let normalized = segments.join("/");
+    let options = glob::MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    patterns.iter().any(|pattern| {
+        let pattern = pattern
+            .trim()
+            .trim_start_matches("./")
+            .trim_end_matches('/');
+        if pattern.is_empty() {
+            return false;
+        }
+
+        if let Ok(glob_pattern) = glob::Pattern::new(pattern)
+            && glob_pattern.matches_with(&normalized, options)
+        {
+            return true;
+        }
+
+        // 前缀匹配：判断当前路径是否为模式所覆盖路径的祖先目录
+        let pattern_segments: Vec<&str> = pattern.split('/').collect();
+        for (pattern_segment, segment) in pattern_segments.iter().zip(segments.iter()) {
+            if *pattern_segment == "**" {
+                return true;
+            }
+            let segment_matches = glob::Pattern::new(pattern_segment)
+                .map(|p| p.matches_with(segment, options))
+                .unwrap_or(false);
+            if !segment_matches {
+                return false;
+            }
+        }
+        segments.len() < pattern_segments.len()
+    })
+}
+
+/// 有界读取文本文件：最多读取`max_bytes`字节，返回内容以及是否发生了截断
+///
+/// 截断位置会回退到最近的UTF-8字符边界，避免产生非法字符；内容为二进制时返回`Ok(None)`，由调用方跳过该文件。
+pub async fn read_text_with_limit(
+    path: &Path,
+    max_bytes: u64,
+) -> std::io::Result<Option<(String, bool)>> {
+    use tokio::io::AsyncReadExt;
+
+    let mut buffer = FileReadLimiter::global()
+        .run(async {
+            let file = tokio::fs::File::open(path).await?;
+            let mut buffer = Vec::new();
+            // 多读取一个字节，用于判断文件是否超出限制
+            file.take(max_bytes.saturating_add(1))
+                .read_to_end(&mut buffer)
+                .await?;
+            std::io::Result::Ok(buffer)
+        })
+        .await?;
+
+    if is_binary_content(&buffer) {
+        return Ok(None);
+    }
+
+    let truncated = buffer.len() as u64 > max_bytes;
+    if truncated {
+        buffer.truncate(max_bytes as usize);
+        if let Err(e) = std::str::from_utf8(&buffer)
+            && e.error_len().is_none()
+        {
+            buffer.truncate(e.valid_up_to());
+        }
+    }
+
+    Ok(Some((
+        String::from_utf8_lossy(&buffer).into_owned(),
+        truncated,
+    )))
+}
+
+/// 并发有界读取多个文本文件，并发数受全局文件读取限制器约束；结果与输入顺序一致，
+/// 读取失败或内容为二进制的文件返回None
+pub async fn read_texts_with_limit<P: AsRef<Path>>(
+    paths: &[P],
+    max_bytes: u64,
+) -> Vec<Option<String>> {
+    let reads = paths.iter().map(|path| async move {
+        read_text_with_limit(path.as_ref(), max_bytes)
+            .await
+            .ok()
+            .flatten()
+            .map(|(content, _)| content)
+    });
+    futures::future::join_all(reads).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_read_text_with_limit_stops_at_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("huge.md");
+        std::fs::write(&path, "a".repeat(10 * 1024)).unwrap();
+
+        let (content, truncated) = read_text_with_limit(&path, 1024).await.unwrap().unwrap();
+        assert!(truncated);
+        assert_eq!(content.len(), 1024);
+
+        let (content, truncated) = read_text_with_limit(&path, 64 * 1024)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!truncated);
+        assert_eq!(content.len(), 10 * 1024);
+    }
+
+    #[tokio::test]
+    async fn test_read_text_with_limit_keeps_char_boundary() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("zh.md");
+        std::fs::write(&path, "文档".repeat(100)).unwrap();
+
+        // 每个汉字3字节，限制为4字节时只能保留一个完整字符
+        let (content, truncated) = read_text_with_limit(&path, 4).await.unwrap().unwrap();
+        assert!(truncated);
+        assert_eq!(content, "文");
+    }
+
+    #[tokio::test]
+    async fn test_read_text_with_limit_skips_binary_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("font");
+        std::fs::write(&path, b"OTTO\x00\x0b\x00\x80").unwrap();
+
+        assert!(read_text_with_limit(&path, 1024).await.unwrap().is_none());
+        assert_eq!(read_texts_with_limit(&[&path], 1024).await, vec![None]);
+        assert!(is_binary_file_content(&path));
+        assert!(!is_binary_content("纯文本\n".as_bytes()));
+    }
+}
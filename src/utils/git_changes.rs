@@ -0,0 +1,82 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 获取自指定git引用以来变更的文件（包括已提交、未提交的修改以及未跟踪的新文件），
+/// 路径相对于项目目录，已删除的文件不计入
+pub fn changed_files_since(project_path: &Path, git_ref: &str) -> Result<HashSet<PathBuf>> {
+    let inside_work_tree = run_git(project_path, &["rev-parse", "--is-inside-work-tree"])
+        .is_ok_and(|output| output.trim() == "true");
+    if !inside_work_tree {
+        return Err(anyhow!(
+            "--since 需要git仓库，但项目目录不是git工作区: {}",
+            project_path.display()
+        ));
+    }
+
+    run_git(
+        project_path,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", git_ref),
+        ],
+    )
+    .map_err(|_| anyhow!("无法解析git引用: {}", git_ref))?;
+
+    let changed = run_git(
+        project_path,
+        &[
+            "diff",
+            "--name-only",
+            "--relative",
+            "--diff-filter=d",
+            git_ref,
+            "--",
+        ],
+    )?;
+    let untracked = run_git(
+        project_path,
+        &["ls-files", "--others", "--exclude-standard"],
+    )?;
+
+    Ok(changed
+        .lines()
+        .chain(untracked.lines())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+fn run_git(project_path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(project_path)
+        .args(args)
+        .output()
+        .map_err(|e| anyhow!("无法执行git命令: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} 执行失败: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_non_git_project_errors_clearly() {
+        let temp_dir = TempDir::new().unwrap();
+        let error = changed_files_since(temp_dir.path(), "v1.0").unwrap_err();
+        assert!(error.to_string().contains("不是git工作区"));
+    }
+}
@@ -0,0 +1,94 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::utils::status_text;
+
+/// 控制台日志输出器，保留原有的emoji状态行格式：
+/// info/debug输出到stdout，warn/error输出到stderr
+pub struct ConsoleLogger {
+    level: LevelFilter,
+    /// 将状态行中的emoji替换为纯ASCII标记
+    plain_symbols: bool,
+}
+
+impl ConsoleLogger {
+    pub fn new(level: LevelFilter) -> Self {
+        Self {
+            level,
+            plain_symbols: false,
+        }
+    }
+
+    pub fn with_plain_symbols(mut self, plain_symbols: bool) -> Self {
+        self.plain_symbols = plain_symbols;
+        self
+    }
+
+    fn format(&self, record: &Record) -> String {
+        status_text::render(&record.args().to_string(), self.plain_symbols)
+    }
+}
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let message = self.format(record);
+        match record.level() {
+            Level::Error | Level::Warn => eprintln!("{}", message),
+            Level::Info | Level::Debug | Level::Trace => println!("{}", message),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// 初始化全局日志输出器。全局日志输出器只能安装一次，重复调用不生效，保持首次初始化的级别与符号设置
+pub fn init(level: LevelFilter, plain_symbols: bool) {
+    let logger = ConsoleLogger::new(level).with_plain_symbols(plain_symbols);
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LogLevel;
+
+    fn metadata(level: Level) -> Metadata<'static> {
+        Metadata::builder().level(level).target("litho").build()
+    }
+
+    #[test]
+    fn test_quiet_suppresses_info_messages() {
+        let logger = ConsoleLogger::new(LogLevel::Warn.to_level_filter());
+
+        assert!(!logger.enabled(&metadata(Level::Info)));
+        assert!(!logger.enabled(&metadata(Level::Debug)));
+        assert!(logger.enabled(&metadata(Level::Warn)));
+        assert!(logger.enabled(&metadata(Level::Error)));
+    }
+
+    #[test]
+    fn test_default_level_shows_info_but_not_debug() {
+        let logger = ConsoleLogger::new(LogLevel::default().to_level_filter());
+
+        assert!(logger.enabled(&metadata(Level::Info)));
+        assert!(!logger.enabled(&metadata(Level::Debug)));
+    }
+
+    #[test]
+    fn test_plain_symbols_formats_status_lines_as_ascii() {
+        let logger = ConsoleLogger::new(LevelFilter::Info).with_plain_symbols(true);
+        let args = format_args!("✅ 分析完成: {}", "main.rs");
+        let record = Record::builder().args(args).level(Level::Info).build();
+
+        assert_eq!(logger.format(&record), "[OK] 分析完成: main.rs");
+    }
+}
@@ -1,6 +1,11 @@
+pub mod archive;
+pub mod file_read_limiter;
 pub mod file_utils;
+pub mod git_changes;
+pub mod logger;
 pub mod project_structure_formatter;
 pub mod prompt_compressor;
 pub mod sources;
+pub mod status_text;
 pub mod threads;
 pub mod token_estimator;
@@ -1,6 +1,7 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
+use crate::types::code::CodeInsight;
 use crate::types::project_structure::ProjectStructure;
 
 /// 项目结构格式化器 - 负责将项目结构数据转换为树形字符串表示
@@ -9,6 +10,33 @@ pub struct ProjectStructureFormatter;
 impl ProjectStructureFormatter {
     /// 格式化项目结构信息为树形结构
     pub fn format_as_tree(structure: &ProjectStructure) -> String {
+        Self::format_tree_with_annotations(structure, &HashMap::new())
+    }
+
+    /// 格式化项目结构信息为树形结构，并在文件节点上标注代码洞察中的用途类型与重要性
+    pub fn format_as_annotated_tree(
+        structure: &ProjectStructure,
+        insights: &[CodeInsight],
+    ) -> String {
+        let annotations: HashMap<PathBuf, String> = insights
+            .iter()
+            .map(|insight| {
+                (
+                    Self::normalize_path(&insight.code_dossier.file_path),
+                    format!(
+                        "[{} | 重要性 {:.2}]",
+                        insight.code_dossier.code_purpose, insight.code_dossier.importance_score
+                    ),
+                )
+            })
+            .collect();
+        Self::format_tree_with_annotations(structure, &annotations)
+    }
+
+    fn format_tree_with_annotations(
+        structure: &ProjectStructure,
+        annotations: &HashMap<PathBuf, String>,
+    ) -> String {
         let mut result = format!(
             "### 项目结构信息\n项目名称: {}\n根目录: {}\n\n项目目录结构：\n```\n",
             structure.project_name,
@@ -21,7 +49,12 @@ impl ProjectStructureFormatter {
         // 先插入所有文件（这些是确定的文件）
         for file in &structure.files {
             let normalized_path = Self::normalize_path(&file.path);
-            tree.insert_file(&normalized_path);
+            tree.insert_file(&normalized_path, annotations.get(&normalized_path).cloned());
+        }
+
+        // 大型数据目录只包含抽样文件，标注实际文件数量
+        for (path, annotation) in Self::large_directory_annotations(structure) {
+            tree.insert_file(&path, Some(annotation));
         }
 
         // 生成树形字符串
@@ -50,6 +83,10 @@ impl ProjectStructureFormatter {
                 dir_tree.insert_directory(parent_dir);
             }
         }
+        for (path, annotation) in Self::large_directory_annotations(structure) {
+            dir_tree.insert_directory(&path);
+            dir_tree.annotate_directory(&path, annotation);
+        }
 
         // 生成目录树字符串
         let tree_output = dir_tree.to_tree_string();
@@ -59,6 +96,28 @@ impl ProjectStructureFormatter {
         result
     }
 
+    /// 大型数据目录的相对路径及其标注（实际文件数量与抽样数量）
+    fn large_directory_annotations(structure: &ProjectStructure) -> Vec<(PathBuf, String)> {
+        structure
+            .directories
+            .iter()
+            .filter_map(|dir| {
+                let total_files = dir.large_data_file_count?;
+                let relative_path = dir
+                    .path
+                    .strip_prefix(&structure.root_path)
+                    .unwrap_or(&dir.path);
+                Some((
+                    Self::normalize_path(relative_path),
+                    format!(
+                        "[大型数据目录：共 {} 个文件，仅列出抽样的 {} 个]",
+                        total_files, dir.file_count
+                    ),
+                ))
+            })
+            .collect()
+    }
+
     /// 标准化路径格式，移除 "./" 前缀
     fn normalize_path(path: &Path) -> PathBuf {
         let path_str = path.to_string_lossy();
@@ -74,6 +133,8 @@ impl ProjectStructureFormatter {
 #[derive(Debug)]
 struct PathNode {
     name: String,
+    /// 文件节点的附加标注（用途类型、重要性等）
+    annotation: Option<String>,
     children: BTreeMap<String, PathNode>,
 }
 
@@ -81,6 +142,7 @@ impl PathNode {
     fn new(name: String) -> Self {
         Self {
             name,
+            annotation: None,
             children: BTreeMap::new(),
         }
     }
@@ -96,6 +158,8 @@ struct PathTree {
 #[derive(Debug)]
 struct DirectoryNode {
     name: String,
+    /// 目录节点的附加标注（如大型数据目录的文件数量）
+    annotation: Option<String>,
     children: BTreeMap<String, DirectoryNode>,
 }
 
@@ -103,6 +167,7 @@ impl DirectoryNode {
     fn new(name: String) -> Self {
         Self {
             name,
+            annotation: None,
             children: BTreeMap::new(),
         }
     }
@@ -144,6 +209,18 @@ impl DirectoryTree {
         }
     }
 
+    /// 为已插入的目录添加标注
+    fn annotate_directory(&mut self, path: &Path, annotation: String) {
+        let mut current = &mut self.root;
+        for component in path.components().filter_map(|c| c.as_os_str().to_str()) {
+            match current.children.get_mut(component) {
+                Some(child) => current = child,
+                None => return,
+            }
+        }
+        current.annotation = Some(annotation);
+    }
+
     /// 生成目录树字符串表示
     fn to_tree_string(&self) -> String {
         let mut result = String::new();
@@ -160,7 +237,13 @@ impl DirectoryTree {
     ) {
         if !node.name.is_empty() {
             let connector = if is_last { "└── " } else { "├── " };
-            result.push_str(&format!("{}{}{}/\n", prefix, connector, node.name));
+            match &node.annotation {
+                Some(annotation) => result.push_str(&format!(
+                    "{}{}{}/  {}\n",
+                    prefix, connector, node.name, annotation
+                )),
+                None => result.push_str(&format!("{}{}{}/\n", prefix, connector, node.name)),
+            }
         }
 
         let children: Vec<_> = node.children.values().collect();
@@ -187,19 +270,21 @@ impl PathTree {
     }
 
     /// 插入文件路径到树中
-    fn insert_file(&mut self, path: &Path) {
-        self.insert_path(path);
+    fn insert_file(&mut self, path: &Path, annotation: Option<String>) {
+        if let Some(node) = self.insert_path(path) {
+            node.annotation = annotation;
+        }
     }
 
-    /// 插入路径到树中
-    fn insert_path(&mut self, path: &Path) {
+    /// 插入路径到树中，返回路径末端的节点
+    fn insert_path(&mut self, path: &Path) -> Option<&mut PathNode> {
         let components: Vec<&str> = path
             .components()
             .filter_map(|c| c.as_os_str().to_str())
             .collect();
 
         if components.is_empty() {
-            return;
+            return None;
         }
 
         let mut current = &mut self.root;
@@ -212,6 +297,7 @@ impl PathTree {
 
             current = current.children.get_mut(*component).unwrap();
         }
+        Some(current)
     }
 
     /// 生成树形字符串表示
@@ -225,7 +311,13 @@ impl PathTree {
     fn render_node(node: &PathNode, prefix: &str, is_last: bool, result: &mut String) {
         if !node.name.is_empty() {
             let connector = if is_last { "└── " } else { "├── " };
-            result.push_str(&format!("{}{}{}\n", prefix, connector, node.name));
+            match &node.annotation {
+                Some(annotation) => result.push_str(&format!(
+                    "{}{}{}  {}\n",
+                    prefix, connector, node.name, annotation
+                )),
+                None => result.push_str(&format!("{}{}{}\n", prefix, connector, node.name)),
+            }
         }
 
         let children: Vec<_> = node.children.values().collect();
@@ -360,4 +452,60 @@ mod tests {
 
         println!("Tree structure:\n{}", result);
     }
+
+    #[test]
+    fn test_annotated_tree_includes_file_purpose() {
+        use crate::types::code::{CodeComplexity, CodeDossier, CodePurpose};
+
+        let file = |path: &str| FileInfo {
+            path: PathBuf::from(path),
+            name: path.rsplit('/').next().unwrap().to_string(),
+            size: 100,
+            extension: Some("rs".to_string()),
+            is_core: true,
+            importance_score: 0.5,
+            complexity_score: 0.5,
+            last_modified: None,
+        };
+        let structure = ProjectStructure {
+            project_name: "test_project".to_string(),
+            root_path: PathBuf::from("/test"),
+            files: vec![file("src/main.rs"), file("src/utils/mod.rs")],
+            directories: vec![],
+            total_files: 2,
+            total_directories: 2,
+            file_types: std::collections::HashMap::new(),
+            size_distribution: std::collections::HashMap::new(),
+        };
+        let insight = CodeInsight {
+            code_dossier: CodeDossier {
+                name: "main.rs".to_string(),
+                file_path: PathBuf::from("./src/main.rs"),
+                source_summary: String::new(),
+                code_purpose: CodePurpose::Entry,
+                importance_score: 0.9,
+                description: None,
+                functions: vec![],
+                interfaces: vec![],
+            },
+            detailed_description: String::new(),
+            responsibilities: vec![],
+            interfaces: vec![],
+            dependencies: vec![],
+            complexity_metrics: Some(CodeComplexity {
+                cyclomatic_complexity: 0.0,
+                lines_of_code: 0,
+                number_of_functions: 0,
+                number_of_classes: 0,
+            }),
+        };
+
+        let result = ProjectStructureFormatter::format_as_annotated_tree(&structure, &[insight]);
+
+        assert!(result.contains("main.rs  [项目执行入口 | 重要性 0.90]"));
+        // 没有洞察的文件不加标注
+        assert!(result.contains("mod.rs\n"));
+        // 普通树形格式不受影响
+        assert!(!ProjectStructureFormatter::format_as_tree(&structure).contains("项目执行入口"));
+    }
 }
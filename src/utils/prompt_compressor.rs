@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::generator::agent_executor::{AgentExecuteParams, prompt};
 use crate::generator::context::GeneratorContext;
+use crate::llm::client::types::TokenUsage;
 use crate::utils::token_estimator::{TokenEstimation, TokenEstimator};
+use log::info;
 
 /// Prompt压缩器，用于压缩过长的prompt内容
 pub struct PromptCompressor {
@@ -83,6 +85,16 @@ impl PromptCompressor {
         }
     }
 
+    /// 内容是否超出压缩阈值，未超出时无需任何压缩调用
+    pub fn exceeds_threshold(&self, content: &str) -> bool {
+        self.compression_config.enabled
+            && self
+                .token_estimator
+                .estimate_tokens(content)
+                .estimated_tokens
+                > self.compression_config.compression_threshold
+    }
+
     /// 检查并压缩prompt内容
     pub async fn compress_if_needed(
         &self,
@@ -106,7 +118,7 @@ impl PromptCompressor {
             .get_compression_cache(content, content_type)
             .await
         {
-            println!("   💾 使用缓存的压缩结果 [{}]", content_type);
+            info!("   💾 使用缓存的压缩结果 [{}]", content_type);
             let compressed_estimation = self.token_estimator.estimate_tokens(&cached_result);
             let actual_ratio =
                 compressed_estimation.estimated_tokens as f64 / estimation.estimated_tokens as f64;
@@ -127,7 +139,7 @@ impl PromptCompressor {
         }
         drop(cache_manager);
 
-        println!(
+        info!(
             "   🗜️  检测到超长内容 [{}]: {} tokens，开始智能压缩...",
             content_type, estimation.estimated_tokens
         );
@@ -139,8 +151,14 @@ impl PromptCompressor {
         // 缓存压缩结果
         if result.was_compressed {
             let cache_manager = context.cache_manager.write().await;
+            let token_usage = TokenUsage::new(result.original_tokens, result.compressed_tokens);
             let _ = cache_manager
-                .set_compression_cache(content, content_type, result.compressed_content.clone())
+                .set_compression_cache(
+                    content,
+                    content_type,
+                    result.compressed_content.clone(),
+                    token_usage,
+                )
                 .await;
         }
 
@@ -178,7 +196,7 @@ impl PromptCompressor {
         let actual_ratio = compressed_estimation.estimated_tokens as f64
             / original_estimation.estimated_tokens as f64;
 
-        println!(
+        info!(
             "   ✅ 压缩完成: {} tokens -> {} tokens (压缩比: {:.1}%)",
             original_estimation.estimated_tokens,
             compressed_estimation.estimated_tokens,
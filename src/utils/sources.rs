@@ -7,6 +7,7 @@ use crate::{
         LanguageProcessor, LanguageProcessorManager,
     },
     types::code::CodeInsight,
+    utils::file_utils::is_binary_content,
 };
 
 pub fn read_code_source(
@@ -18,11 +19,13 @@ pub fn read_code_source(
     let full_path = project_path.join(file_path);
 
     // 读取源代码
-    if let Ok(content) = std::fs::read_to_string(&full_path) {
+    match std::fs::read_to_string(&full_path) {
+        Ok(content) if is_binary_content(content.as_bytes()) => {
+            format!("二进制文件，已跳过: {}", full_path.display())
+        }
         // 如果代码太长，进行智能截取
-        truncate_source_code(language_processor, &full_path, &content, 8_1024)
-    } else {
-        format!("无法读取文件: {}", full_path.display())
+        Ok(content) => truncate_source_code(language_processor, &full_path, &content, 8_1024),
+        Err(_) => format!("无法读取文件: {}", full_path.display()),
     }
 }
 
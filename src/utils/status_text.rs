@@ -0,0 +1,117 @@
+/// 状态符号及其纯ASCII替代文本，未列出的符号在纯文本模式下直接去除
+const STATUS_SYMBOLS: &[(&str, &str)] = &[
+    ("✅", "[OK]"),
+    ("✓", "[OK]"),
+    ("⚠️", "[WARN]"),
+    ("⚠", "[WARN]"),
+    ("❌", "[ERROR]"),
+    ("🔴", "[ERROR]"),
+    ("🚨", "[ERROR]"),
+    ("💡", "[TIP]"),
+    ("🚀", "[START]"),
+    ("🤖", "[AI]"),
+    ("💾", "[SAVE]"),
+    ("📊", "[STAT]"),
+    ("🔄", "[RETRY]"),
+    ("♻️", "[CACHE]"),
+    ("🗑️", "[CLEAN]"),
+    ("🧹", "[CLEAN]"),
+    ("💰", "[COST]"),
+    ("💸", "[COST]"),
+    ("⏭️", "[SKIP]"),
+];
+
+/// 是否为状态行中使用的emoji/图形符号（不包括中文等正常文字）
+pub fn is_status_symbol(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x2190..=0x21FF // 箭头
+            | 0x2300..=0x23FF // 技术符号（⌛ ⏭ ⏹ 等）
+            | 0x25A0..=0x25FF // 几何图形（▶ 等）
+            | 0x2600..=0x27BF // 杂项符号与装饰符号（⚠ ✅ ✓ ❌ 等）
+            | 0x2B00..=0x2BFF // 杂项符号与箭头
+            | 0x1F000..=0x1FAFF // emoji
+            | 0xFE0F // emoji变体选择符
+            | 0x200D // 零宽连接符
+    )
+}
+
+/// 用户是否通过`NO_COLOR`环境变量要求纯文本输出（变量存在且非空）
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// 将状态文本中的emoji替换为纯ASCII标记，无对应标记的符号连同其后的一个空格一起去除
+pub fn to_plain(text: &str) -> String {
+    let mut replaced = text.to_string();
+    for (symbol, plain) in STATUS_SYMBOLS {
+        if replaced.contains(symbol) {
+            replaced = replaced.replace(symbol, plain);
+        }
+    }
+
+    let mut result = String::with_capacity(replaced.len());
+    let mut chars = replaced.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !is_status_symbol(c) {
+            result.push(c);
+            continue;
+        }
+        while chars.peek().is_some_and(|next| is_status_symbol(*next)) {
+            chars.next();
+        }
+        if chars.peek() == Some(&' ') {
+            chars.next();
+        }
+    }
+    result
+}
+
+/// 按需转换状态文本
+pub fn render(text: &str, plain: bool) -> String {
+    if plain {
+        to_plain(text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_emoji_mode_has_no_status_symbols() {
+        let lines = [
+            "   ✅ 使用缓存的AI分析结果: overview",
+            "⚠️ 警告: 未知的provider: foo，使用默认provider",
+            "✓ Litho Studies Research流程执行完毕",
+            "   🔗 分析组件关系...",
+            "🗑️ 已清除全部缓存",
+            "📊 [preprocess] 阶段完成，耗时 1.2秒",
+            "   ▶️ 智能体开始: 项目概述",
+            "🧜 修复Mermaid图表",
+        ];
+
+        for line in lines {
+            let plain = to_plain(line);
+            assert!(
+                !plain.chars().any(is_status_symbol),
+                "仍包含状态符号: {}",
+                plain
+            );
+        }
+        assert_eq!(
+            to_plain("   ✅ 使用缓存的AI分析结果: overview"),
+            "   [OK] 使用缓存的AI分析结果: overview"
+        );
+        assert_eq!(
+            to_plain("⚠️ 警告: 未知的目标语言"),
+            "[WARN] 警告: 未知的目标语言"
+        );
+        assert_eq!(to_plain("   🔗 分析组件关系..."), "   分析组件关系...");
+        // 普通ASCII文本与中文内容保持不变
+        assert_eq!(to_plain("done -> 100%"), "done -> 100%");
+        assert_eq!(render("🚀 开始", false), "🚀 开始");
+    }
+}